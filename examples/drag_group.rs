@@ -0,0 +1,66 @@
+#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
+
+use eframe::{egui, NativeOptions};
+use egui::{Id, SidePanel};
+
+use egui_dock::{DockArea, DockState, Style};
+
+fn main() -> eframe::Result<()> {
+    let options = NativeOptions::default();
+    eframe::run_native(
+        "Drag group example",
+        options,
+        Box::new(|_cc| Ok(Box::<MyApp>::default())),
+    )
+}
+
+struct TabViewer {}
+
+impl egui_dock::TabViewer for TabViewer {
+    type Tab = String;
+
+    fn title(&mut self, tab: &mut Self::Tab) -> egui::WidgetText {
+        (&*tab).into()
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab) {
+        ui.label(format!("Content of {tab}"));
+    }
+}
+
+struct MyApp {
+    side: DockState<String>,
+    main: DockState<String>,
+}
+
+impl Default for MyApp {
+    fn default() -> Self {
+        Self {
+            side: DockState::new(vec!["Outline".to_owned(), "Search".to_owned()]),
+            main: DockState::new(vec!["main.rs".to_owned(), "lib.rs".to_owned()]),
+        }
+    }
+}
+
+impl eframe::App for MyApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Tabs can be dragged between areas sharing the same drag group.
+        let group = Id::new("tabs");
+
+        SidePanel::left("side_panel")
+            .default_width(250.0)
+            .show(ctx, |ui| {
+                DockArea::new(&mut self.side)
+                    .id(Id::new("side_dock_area"))
+                    .drag_group(group)
+                    .style(Style::from_egui(ctx.style().as_ref()))
+                    .show_inside(ui, &mut TabViewer {});
+            });
+
+        DockArea::new(&mut self.main)
+            .id(Id::new("main_dock_area"))
+            .drag_group(group)
+            .style(Style::from_egui(ctx.style().as_ref()))
+            .show(ctx, &mut TabViewer {});
+    }
+}
@@ -10,8 +10,9 @@ use egui::{
 };
 
 use egui_dock::{
-    AllowedSplits, DockArea, DockState, NodeIndex, OverlayType, Style, SurfaceIndex,
-    TabInteractionStyle, TabViewer,
+    AllowedSplits, CloseActivationPolicy, DockArea, DockState, DropPreviewFill, LayoutDirection,
+    NodeIndex, OnCloseResponse, OverlayType, Style, SurfaceIndex, TabBarHeightMode, TabBarPosition,
+    TabInteractionStyle, TabViewer, TitleTruncation,
 };
 
 /// Adds a widget with a label next to it, can be given an extra parameter in order to show a hover text
@@ -71,14 +72,20 @@ struct MyContext {
 
     show_close_buttons: bool,
     show_add_buttons: bool,
+    show_tab_search_buttons: bool,
     draggable_tabs: bool,
-    show_tab_name_on_hover: bool,
+    locked: bool,
+    show_tab_tooltips: bool,
     allowed_splits: AllowedSplits,
     show_leaf_close_all: bool,
     show_leaf_collapse: bool,
+    collapse_into_side_strips: bool,
     show_secondary_button_hint: bool,
     secondary_button_on_modifier: bool,
     secondary_button_context_menu: bool,
+    native_windows: bool,
+    layout_direction: LayoutDirection,
+    close_activation_policy: CloseActivationPolicy,
 }
 
 struct MyApp {
@@ -123,9 +130,9 @@ impl TabViewer for MyContext {
         ["Inspector", "Style Editor"].contains(&tab.as_str())
     }
 
-    fn on_close(&mut self, tab: &mut Self::Tab) -> bool {
+    fn on_close(&mut self, tab: &mut Self::Tab) -> OnCloseResponse {
         self.open_tabs.remove(tab);
-        true
+        OnCloseResponse::Close
     }
 }
 
@@ -157,8 +164,10 @@ impl MyContext {
         ui.collapsing("DockArea Options", |ui| {
             ui.checkbox(&mut self.show_close_buttons, "Show close buttons");
             ui.checkbox(&mut self.show_add_buttons, "Show add buttons");
+            ui.checkbox(&mut self.show_tab_search_buttons, "Show tab search buttons");
             ui.checkbox(&mut self.draggable_tabs, "Draggable tabs");
-            ui.checkbox(&mut self.show_tab_name_on_hover, "Show tab name on hover");
+            ui.checkbox(&mut self.locked, "Lock the layout");
+            ui.checkbox(&mut self.show_tab_tooltips, "Show tab tooltips");
             ui.checkbox(
                 &mut self.show_leaf_close_all,
                 "Show close all button on tab bars",
@@ -167,6 +176,10 @@ impl MyContext {
                 &mut self.show_leaf_collapse,
                 "Show collaspse button on tab bars",
             );
+            ui.checkbox(
+                &mut self.collapse_into_side_strips,
+                "Collapse leaves into side strips",
+            );
             ui.checkbox(
                 &mut self.secondary_button_on_modifier,
                 "Enable secondary buttons when modifiers (Shift by default) are pressed",
@@ -179,6 +192,37 @@ impl MyContext {
                 &mut self.show_secondary_button_hint,
                 "Show tooltip hints for secondary buttons",
             );
+            ui.checkbox(&mut self.native_windows, "Show windows as native windows");
+            ComboBox::new("cbox:layout_direction", "Layout direction")
+                .selected_text(format!("{:?}", self.layout_direction))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(
+                        &mut self.layout_direction,
+                        LayoutDirection::LeftToRight,
+                        "Left to right",
+                    );
+                    ui.selectable_value(
+                        &mut self.layout_direction,
+                        LayoutDirection::RightToLeft,
+                        "Right to left",
+                    );
+                });
+            ComboBox::new("cbox:close_activation_policy", "Activate after closing")
+                .selected_text(format!("{:?}", self.close_activation_policy))
+                .show_ui(ui, |ui| {
+                    for policy in [
+                        CloseActivationPolicy::MostRecentlyUsed,
+                        CloseActivationPolicy::NextRight,
+                        CloseActivationPolicy::NextLeft,
+                        CloseActivationPolicy::KeepIndex,
+                    ] {
+                        ui.selectable_value(
+                            &mut self.close_activation_policy,
+                            policy,
+                            format!("{policy:?}"),
+                        );
+                    }
+                });
             ComboBox::new("cbox:allowed_splits", "Split direction(s)")
                 .selected_text(format!("{:?}", self.allowed_splits))
                 .show_ui(ui, |ui| {
@@ -222,6 +266,44 @@ impl MyContext {
             });
         });
 
+        ui.collapsing("Focus highlight", |ui| {
+            egui::Grid::new("focus highlight").show(ui, |ui| {
+                ui.label("Fill color:");
+                color_edit_button_srgba(
+                    ui,
+                    &mut style.focused_leaf_highlight.color,
+                    Alpha::OnlyBlend,
+                );
+                ui.end_row();
+
+                ui.label("Stroke color:");
+                color_edit_button_srgba(
+                    ui,
+                    &mut style.focused_leaf_highlight.stroke.color,
+                    Alpha::OnlyBlend,
+                );
+                ui.end_row();
+
+                ui.label("Stroke width:");
+                ui.add(Slider::new(
+                    &mut style.focused_leaf_highlight.stroke.width,
+                    0.0..=50.0,
+                ));
+                ui.end_row();
+
+                ui.label("Expansion:");
+                ui.add(Slider::new(
+                    &mut style.focused_leaf_highlight.expansion,
+                    -50.0..=50.0,
+                ));
+                ui.end_row();
+
+                ui.label("Rounding:");
+                rounding_ui(ui, &mut style.focused_leaf_highlight.rounding);
+                ui.end_row();
+            });
+        });
+
         ui.collapsing("Separator", |ui| {
             egui::Grid::new("separator").show(ui, |ui| {
                 ui.label("Width:");
@@ -235,8 +317,11 @@ impl MyContext {
                 ));
                 ui.end_row();
 
-                ui.label("Offset limit:");
-                ui.add(Slider::new(&mut style.separator.extra, 1.0..=300.0));
+                ui.label("Min panel size:");
+                ui.add(Slider::new(
+                    &mut style.separator.min_panel_size,
+                    0.0..=300.0,
+                ));
                 ui.end_row();
 
                 ui.label("Idle color:");
@@ -265,11 +350,45 @@ impl MyContext {
                 &mut style.tab.hline_below_active_tab_name,
                 "Show a line below the active tab name",
             );
+            ui.checkbox(
+                &mut style.tab_bar.hide_when_single_tab,
+                "Hide the tab bar in leaves with a single tab",
+            );
             ui.horizontal(|ui| {
-                ui.add(Slider::new(&mut style.tab_bar.height, 20.0..=50.0));
+                let mut auto = style.tab_bar.height_mode == TabBarHeightMode::AutoFromText;
+                ui.add_enabled(!auto, Slider::new(&mut style.tab_bar.height, 20.0..=50.0));
                 ui.label("Tab bar height");
+                ui.checkbox(&mut auto, "Fit to text");
+                style.tab_bar.height_mode = if auto {
+                    TabBarHeightMode::AutoFromText
+                } else {
+                    TabBarHeightMode::Fixed
+                };
+            });
+            ui.horizontal(|ui| {
+                let mut limited = style.tab.maximum_width.is_some();
+                ui.checkbox(&mut limited, "Maximum tab width");
+                let mut width = style.tab.maximum_width.unwrap_or(150.0);
+                ui.add_enabled(limited, Slider::new(&mut width, 50.0..=400.0));
+                style.tab.maximum_width = limited.then_some(width);
             });
 
+            ComboBox::new("title_truncation", "Title truncation")
+                .selected_text(format!("{:?}", style.tab.title_truncation))
+                .show_ui(ui, |ui| {
+                    for truncation in [
+                        TitleTruncation::Truncate,
+                        TitleTruncation::MiddleEllipsis,
+                        TitleTruncation::Wrap,
+                    ] {
+                        ui.selectable_value(
+                            &mut style.tab.title_truncation,
+                            truncation,
+                            format!("{:?}", truncation),
+                        );
+                    }
+                });
+
             ComboBox::new("add_button_align", "Add button align")
                 .selected_text(format!("{:?}", style.buttons.add_tab_align))
                 .show_ui(ui, |ui| {
@@ -282,6 +401,23 @@ impl MyContext {
                     }
                 });
 
+            ComboBox::new("tab_bar_position", "Tab bar position")
+                .selected_text(format!("{:?}", style.tab_bar.position))
+                .show_ui(ui, |ui| {
+                    for position in [
+                        TabBarPosition::Top,
+                        TabBarPosition::Bottom,
+                        TabBarPosition::Left,
+                        TabBarPosition::Right,
+                    ] {
+                        ui.selectable_value(
+                            &mut style.tab_bar.position,
+                            position,
+                            format!("{:?}", position),
+                        );
+                    }
+                });
+
             ui.separator();
 
             fn tab_style_editor_ui(ui: &mut Ui, tab_style: &mut TabInteractionStyle) {
@@ -518,6 +654,37 @@ impl MyContext {
                 });
                 ui.label("Rounding:");
                 rounding_ui(ui, &mut style.overlay.hovered_leaf_highlight.rounding);
+            });
+
+            ui.collapsing("Tab insert indicator", |ui| {
+                egui::Grid::new("tab insert indicator prefs").show(ui, |ui| {
+                    ui.label("Color:");
+                    color_edit_button_srgba(ui, &mut style.overlay.tab_insert_indicator.color, Alpha::OnlyBlend);
+                    ui.end_row();
+
+                    ui.label("Width:");
+                    ui.add(Slider::new(&mut style.overlay.tab_insert_indicator.width, 0.0..=10.0));
+                    ui.end_row();
+                });
+                ui.label("Rounding:");
+                rounding_ui(ui, &mut style.overlay.tab_insert_indicator.rounding);
+            });
+
+            ui.collapsing("Drop preview", |ui| {
+                let preview = &mut style.overlay.drop_preview;
+                for (label, fill) in [
+                    ("Append", &mut preview.append),
+                    ("Split", &mut preview.split),
+                    ("Window", &mut preview.window),
+                ] {
+                    ComboBox::new(("drop_preview", label), label)
+                        .selected_text(format!("{fill:?}"))
+                        .show_ui(ui, |ui| {
+                            for value in [DropPreviewFill::Filled, DropPreviewFill::Stroked] {
+                                ui.selectable_value(fill, value, format!("{value:?}"));
+                            }
+                        });
+                }
             })
         });
     }
@@ -528,6 +695,7 @@ impl Default for MyApp {
         let mut dock_state =
             DockState::new(vec!["Simple Demo".to_owned(), "Style Editor".to_owned()]);
         "Undock".clone_into(&mut dock_state.translations.tab_context_menu.eject_button);
+        dock_state.enable_history(100);
         let [a, b] = dock_state.main_surface_mut().split_left(
             NodeIndex::root(),
             0.3,
@@ -560,14 +728,20 @@ impl Default for MyApp {
 
             show_leaf_close_all: true,
             show_leaf_collapse: true,
+            collapse_into_side_strips: false,
             show_secondary_button_hint: true,
             secondary_button_on_modifier: true,
             secondary_button_context_menu: true,
             show_close_buttons: true,
             show_add_buttons: false,
+            show_tab_search_buttons: false,
             draggable_tabs: true,
-            show_tab_name_on_hover: false,
+            locked: false,
+            show_tab_tooltips: false,
             allowed_splits: AllowedSplits::default(),
+            native_windows: false,
+            layout_direction: LayoutDirection::default(),
+            close_activation_policy: CloseActivationPolicy::default(),
         };
 
         Self {
@@ -581,6 +755,28 @@ impl eframe::App for MyApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         TopBottomPanel::top("egui_dock::MenuBar").show(ctx, |ui| {
             egui::menu::bar(ui, |ui| {
+                ui.menu_button("Edit", |ui| {
+                    if ui
+                        .add_enabled(
+                            self.tree.can_undo(),
+                            egui::Button::new("Undo layout change"),
+                        )
+                        .clicked()
+                    {
+                        self.tree.undo();
+                        ui.close_menu();
+                    }
+                    if ui
+                        .add_enabled(
+                            self.tree.can_redo(),
+                            egui::Button::new("Redo layout change"),
+                        )
+                        .clicked()
+                    {
+                        self.tree.redo();
+                        ui.close_menu();
+                    }
+                });
                 ui.menu_button("View", |ui| {
                     // allow certain tabs to be toggled
                     for tab in &["File Browser", "Asset Manager"] {
@@ -617,14 +813,20 @@ impl eframe::App for MyApp {
                     .style(style)
                     .show_close_buttons(self.context.show_close_buttons)
                     .show_add_buttons(self.context.show_add_buttons)
+                    .show_tab_search_buttons(self.context.show_tab_search_buttons)
                     .draggable_tabs(self.context.draggable_tabs)
-                    .show_tab_name_on_hover(self.context.show_tab_name_on_hover)
+                    .locked(self.context.locked)
+                    .show_tab_tooltips(self.context.show_tab_tooltips)
                     .allowed_splits(self.context.allowed_splits)
                     .show_leaf_close_all_buttons(self.context.show_leaf_close_all)
                     .show_leaf_collapse_buttons(self.context.show_leaf_collapse)
+                    .collapse_into_side_strips(self.context.collapse_into_side_strips)
                     .show_secondary_button_hint(self.context.show_secondary_button_hint)
                     .secondary_button_on_modifier(self.context.secondary_button_on_modifier)
                     .secondary_button_context_menu(self.context.secondary_button_context_menu)
+                    .native_windows(self.context.native_windows)
+                    .layout_direction(self.context.layout_direction)
+                    .on_close_activate(self.context.close_activation_policy)
                     .show_inside(ui, &mut self.context);
             });
     }
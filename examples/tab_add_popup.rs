@@ -80,8 +80,12 @@ impl egui_dock::TabViewer for TabViewer<'_> {
         ui.label(tab.content());
     }
 
-    fn add_popup(&mut self, ui: &mut egui::Ui, surface: SurfaceIndex, node: NodeIndex) {
-        ui.set_min_width(120.0);
+    fn add_popup(
+        &mut self,
+        ui: &mut egui::Ui,
+        surface: SurfaceIndex,
+        node: NodeIndex,
+    ) -> Option<egui::Vec2> {
         ui.style_mut().visuals.button_frame = false;
 
         if ui.button("Regular tab").clicked() {
@@ -91,6 +95,9 @@ impl egui_dock::TabViewer for TabViewer<'_> {
         if ui.button("Fancy tab").clicked() {
             self.added_nodes.push(MyTab::fancy(surface, node));
         }
+
+        // 120 points wide, scrolled if it ever gets taller than 200 points.
+        Some(egui::vec2(120.0, 200.0))
     }
 }
 
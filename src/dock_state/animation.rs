@@ -0,0 +1,34 @@
+use egui::emath::{easing, lerp};
+
+use crate::{NodeIndex, SurfaceIndex};
+
+/// A transition of the fraction of a parent node towards a target value, started with
+/// [`DockState::animate_fraction`](crate::DockState::animate_fraction).
+#[derive(Clone, Debug)]
+pub(crate) struct FractionAnimation {
+    pub(crate) surface: SurfaceIndex,
+    pub(crate) node: NodeIndex,
+    pub(crate) target: f32,
+
+    /// Duration of the animation in seconds.
+    pub(crate) duration: f32,
+
+    /// Time and fraction at the first frame of the animation, known only once it is shown.
+    pub(crate) start: Option<(f64, f32)>,
+}
+
+impl FractionAnimation {
+    /// Returns the fraction at the given `time` and whether the animation has finished.
+    pub(crate) fn step(&mut self, time: f64, current: f32) -> (f32, bool) {
+        let (start_time, start_fraction) = *self.start.get_or_insert((time, current));
+        let t = if self.duration > 0.0 {
+            ((time - start_time) as f32 / self.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        (
+            lerp(start_fraction..=self.target, easing::cubic_in_out(t)),
+            t >= 1.0,
+        )
+    }
+}
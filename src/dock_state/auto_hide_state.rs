@@ -0,0 +1,92 @@
+use crate::{Split, SurfaceId};
+
+/// Edge of the main surface along which auto-hidden leaves are kept, see
+/// [`DockState::set_auto_hide`](crate::DockState::set_auto_hide).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[allow(missing_docs)]
+pub enum Edge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl Edge {
+    /// Returns `true` if the edge is [`Left`](Self::Left) or [`Right`](Self::Right), i.e. the strip along it is
+    /// vertical.
+    #[inline(always)]
+    pub const fn is_vertical(self) -> bool {
+        matches!(self, Self::Left | Self::Right)
+    }
+
+    /// The side of the main surface on which a leaf along this edge is docked again.
+    #[inline(always)]
+    pub const fn split(self) -> Split {
+        match self {
+            Self::Left => Split::Left,
+            Self::Right => Split::Right,
+            Self::Top => Split::Above,
+            Self::Bottom => Split::Below,
+        }
+    }
+}
+
+/// The state of a [`Surface::AutoHidden`](crate::Surface::AutoHidden).
+///
+/// Doubles as a handle for the surface, allowing the user to move it to another edge or change its size.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct AutoHideState {
+    #[cfg_attr(feature = "serde", serde(skip, default = "SurfaceId::next"))]
+    id: SurfaceId,
+
+    /// The edge of the main surface along which the leaf is kept.
+    edge: Edge,
+
+    /// The width of the leaf when it's shown along a vertical edge, or its height along a horizontal one.
+    size: f32,
+}
+
+impl AutoHideState {
+    /// Creates a new `AutoHideState` of a leaf kept along the `edge`, which is `size` points wide (or tall, for the
+    /// [`Top`](Edge::Top) and [`Bottom`](Edge::Bottom) edges) when it's shown.
+    pub fn new(edge: Edge, size: f32) -> Self {
+        Self {
+            id: SurfaceId::next(),
+            edge,
+            size,
+        }
+    }
+
+    /// Returns the id of the auto-hidden leaf, which stays the same while its
+    /// [`SurfaceIndex`](crate::SurfaceIndex) may be reused by other surfaces after it's removed.
+    #[inline(always)]
+    pub const fn id(&self) -> SurfaceId {
+        self.id
+    }
+
+    /// The edge of the main surface along which the leaf is kept.
+    #[inline(always)]
+    pub const fn edge(&self) -> Edge {
+        self.edge
+    }
+
+    /// Moves the leaf to another edge of the main surface.
+    #[inline(always)]
+    pub fn set_edge(&mut self, edge: Edge) {
+        self.edge = edge;
+    }
+
+    /// The width of the leaf when it's shown along a vertical edge, or its height along a horizontal one.
+    #[inline(always)]
+    pub const fn size(&self) -> f32 {
+        self.size
+    }
+
+    /// Sets the width of the leaf when it's shown along a vertical edge, or its height along a horizontal one.
+    #[inline(always)]
+    pub fn set_size(&mut self, size: f32) {
+        self.size = size;
+    }
+}
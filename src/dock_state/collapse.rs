@@ -0,0 +1,90 @@
+use crate::{DockError, DockState, NodeIndex, SurfaceIndex};
+
+impl<Tab> DockState<Tab> {
+    /// Collapses a leaf into its tab bar, or expands it back, like its collapse button would, but without animating
+    /// the change.
+    ///
+    /// Windows whose leaves are all collapsed shrink to their title bars, and grow back to their previous height when
+    /// one of them is expanded. Does nothing if the leaf already is in the requested state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node isn't a leaf.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex};
+    /// let mut dock_state = DockState::new(vec!["a"]);
+    /// let [_, below] = dock_state.main_surface_mut().split_below(NodeIndex::root(), 0.5, vec!["b"]);
+    ///
+    /// dock_state.set_leaf_collapsed((SurfaceIndex::main(), below), true);
+    /// assert!(dock_state.main_surface()[below].is_collapsed());
+    /// assert!(!dock_state.main_surface()[NodeIndex::root()].is_collapsed());
+    /// ```
+    #[inline]
+    pub fn set_leaf_collapsed(&mut self, leaf: (SurfaceIndex, NodeIndex), collapsed: bool) {
+        self.change_leaf_collapsed(leaf, collapsed, false);
+    }
+
+    /// Collapses or expands a leaf like [`set_leaf_collapsed`](Self::set_leaf_collapsed), but returns an error
+    /// instead of panicking when the leaf doesn't exist.
+    pub fn try_set_leaf_collapsed(
+        &mut self,
+        (surface, node): (SurfaceIndex, NodeIndex),
+        collapsed: bool,
+    ) -> Result<(), DockError> {
+        self.check_leaf(surface, node)?;
+        self.set_leaf_collapsed((surface, node), collapsed);
+        Ok(())
+    }
+
+    /// Collapses a leaf into its tab bar, or expands it back, like [`set_leaf_collapsed`](Self::set_leaf_collapsed),
+    /// while animating the change the same way clicking its collapse button does.
+    ///
+    /// The transition takes [`egui::Style::animation_time`] and is shown by the [`DockArea`](crate::DockArea) showing
+    /// this [`DockState`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node isn't a leaf.
+    #[inline]
+    pub fn animate_leaf_collapsed(&mut self, leaf: (SurfaceIndex, NodeIndex), collapsed: bool) {
+        self.change_leaf_collapsed(leaf, collapsed, true);
+    }
+
+    fn change_leaf_collapsed(
+        &mut self,
+        (surface, node): (SurfaceIndex, NodeIndex),
+        collapsed: bool,
+        animated: bool,
+    ) {
+        assert!(self[surface][node].is_leaf(), "the node isn't a leaf");
+        if self[surface][node].is_collapsed() == collapsed {
+            return;
+        }
+        let tree = &mut self[surface];
+        tree[node].set_collapsed(collapsed);
+        tree.node_update_collapsed(node);
+
+        // Windows shrink to their title bars when everything inside them is collapsed.
+        let expanded_height = tree
+            .root_node()
+            .filter(|root| root.is_collapsed())
+            .map(|root| root.rect().map_or(0.0, |rect| rect.height()));
+        if let Some(window_state) = self.get_window_state_mut(surface) {
+            if !collapsed {
+                window_state.set_new(true);
+            } else if let Some(height) = expanded_height {
+                window_state.set_expanded_height(height);
+            }
+        }
+        self.collapse_changes.push((surface, node, animated));
+    }
+
+    /// Returns the leaves which have been collapsed or expanded since the last call, and whether the change should be
+    /// animated.
+    pub(crate) fn take_collapse_changes(&mut self) -> Vec<(SurfaceIndex, NodeIndex, bool)> {
+        std::mem::take(&mut self.collapse_changes)
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{Direction, DockState, NodeIndex, SurfaceIndex, TabIndex, TabInsert};
+
+impl<Tab> DockState<Tab> {
+    /// Moves the active tab of the focused leaf to the neighboring leaf in the given `direction` (see
+    /// [`Tree::neighbor_in_direction`](crate::Tree::neighbor_in_direction)), like dropping it on that leaf's tab bar
+    /// would. If there's no leaf in that direction, the focused leaf is split instead and the
+    /// tab is put in the new leaf.
+    ///
+    /// The moved tab stays focused. Returns its new location, or [`None`] if there's no focused tab, or it's the only
+    /// tab of a leaf which has no neighbor in that direction.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{Direction, DockState, NodeIndex, SurfaceIndex, TabIndex};
+    /// let mut dock_state = DockState::new(vec!["a", "b"]);
+    /// let main = SurfaceIndex::main();
+    /// dock_state.set_focused_node_and_surface((main, NodeIndex::root()));
+    /// dock_state.set_active_tab((main, NodeIndex::root(), TabIndex(1)));
+    ///
+    /// // There's nothing to the right yet, so the leaf is split.
+    /// let moved = dock_state.move_active_tab(Direction::Right);
+    /// assert_eq!(moved, Some((main, NodeIndex::root().right(), TabIndex(0))));
+    ///
+    /// // Moving it back to the left merges it into the other leaf again.
+    /// let moved = dock_state.move_active_tab(Direction::Left);
+    /// assert_eq!(moved, Some((main, NodeIndex::root(), TabIndex(1))));
+    /// assert_eq!(dock_state.main_surface().num_tabs(), 2);
+    /// ```
+    pub fn move_active_tab(
+        &mut self,
+        direction: Direction,
+    ) -> Option<(SurfaceIndex, NodeIndex, TabIndex)> {
+        let (surface, node) = self.focused_leaf()?;
+        let tabs_count = self[surface][node].tabs_count();
+        if tabs_count == 0 {
+            return None;
+        }
+        let tab = self[surface][node].active_tab()?;
+        let removes_source = tabs_count == 1 && !self[surface][node].is_central();
+
+        let destination = match self[surface].neighbor_in_direction(node, direction) {
+            Some(neighbor) => {
+                let tab_index = TabIndex(self[surface][neighbor].tabs_count());
+                self.move_tab((surface, node, tab), (surface, neighbor, TabInsert::Append));
+                // The source leaf is removed once it's emptied, which moves its sibling's subtree up a level.
+                let neighbor = if removes_source {
+                    neighbor.after_removal_of(node)
+                } else {
+                    neighbor
+                };
+                (surface, neighbor, tab_index)
+            }
+            None if tabs_count > 1 => {
+                self.move_tab(
+                    (surface, node, tab),
+                    (surface, node, TabInsert::Split(direction.split())),
+                );
+                let new = match direction {
+                    Direction::Left | Direction::Up => node.left(),
+                    Direction::Right | Direction::Down => node.right(),
+                };
+                (surface, new, TabIndex(0))
+            }
+            None => return None,
+        };
+        self.focus_tab(destination);
+        Some(destination)
+    }
+}
@@ -0,0 +1,318 @@
+use crate::{DockError, DockState, Node, NodeIndex, Split, SurfaceIndex, TabDestination, TabIndex};
+
+/// Makes changes to a [`DockState`] inside of a [`DockState::transaction`].
+///
+/// Unlike the methods of [`DockState`] and [`Tree`](crate::Tree), these never panic, and return a [`DockError`] when given an
+/// invalid index instead.
+///
+/// Leaves and windows left without tabs aren't removed until the transaction ends, so indices of nodes and surfaces
+/// stay the same in between edits, except for the ones which are explicitly removed.
+pub struct DockEdit<'a, Tab> {
+    dock_state: &'a mut DockState<Tab>,
+}
+
+impl<Tab> std::fmt::Debug for DockEdit<'_, Tab> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DockEdit").finish_non_exhaustive()
+    }
+}
+
+impl<'a, Tab> DockEdit<'a, Tab> {
+    pub(crate) fn new(dock_state: &'a mut DockState<Tab>) -> Self {
+        Self { dock_state }
+    }
+
+    /// Returns the [`DockState`] being edited, as it is after the edits made so far.
+    pub fn dock_state(&self) -> &DockState<Tab> {
+        self.dock_state
+    }
+
+    /// Creates two new nodes by splitting the `parent` node, like [`Tree::split`](crate::Tree::split), and puts
+    /// `tabs` in the new one.
+    ///
+    /// Returns the indices of the old node and the new node.
+    pub fn split(
+        &mut self,
+        (surface, parent): (SurfaceIndex, NodeIndex),
+        split: Split,
+        fraction: f32,
+        tabs: Vec<Tab>,
+    ) -> Result<[NodeIndex; 2], DockError> {
+        if tabs.is_empty() {
+            return Err(DockError::NoTabs);
+        }
+        self.dock_state
+            .try_split((surface, parent), split, fraction, Node::leaf_with(tabs))
+    }
+
+    /// Adds a window with its own list of tabs.
+    ///
+    /// Returns the surface index of the new window.
+    pub fn add_window(&mut self, tabs: Vec<Tab>) -> Result<SurfaceIndex, DockError> {
+        if tabs.is_empty() {
+            return Err(DockError::NoTabs);
+        }
+        Ok(self.dock_state.add_window(tabs))
+    }
+
+    /// Removes a window together with all of its tabs.
+    pub fn remove_window(&mut self, surface: SurfaceIndex) -> Result<(), DockError> {
+        if surface.is_main() {
+            return Err(DockError::MainSurface);
+        }
+        self.dock_state.try_surface(surface)?;
+        self.dock_state.remove_surface(surface);
+        Ok(())
+    }
+
+    /// Removes all tabs from a leaf. The leaf itself is removed when the transaction ends, unless it's the
+    /// [central node](crate::Tree::set_central_node) or gets new tabs before then.
+    pub fn remove_leaf(
+        &mut self,
+        (surface, node): (SurfaceIndex, NodeIndex),
+    ) -> Result<(), DockError> {
+        self.dock_state.check_leaf(surface, node)?;
+        if let Node::Leaf {
+            tabs,
+            active,
+            history,
+            ..
+        } = &mut self.dock_state[surface][node]
+        {
+            tabs.clear();
+            history.clear();
+            *active = TabIndex(0);
+        }
+        Ok(())
+    }
+
+    /// Removes the tab at the given index and returns it.
+    pub fn remove_tab(
+        &mut self,
+        (surface, node, tab): (SurfaceIndex, NodeIndex, TabIndex),
+    ) -> Result<Tab, DockError> {
+        self.dock_state.check_tab(surface, node, tab)?;
+        Ok(self.dock_state[surface][node].remove_tab(tab).unwrap())
+    }
+
+    /// Puts a new `tab` at the given destination, like [`DockState::insert_tab`].
+    ///
+    /// Returns the surface index the tab has ended up in.
+    pub fn insert_tab(
+        &mut self,
+        tab: Tab,
+        destination: impl Into<TabDestination>,
+    ) -> Result<SurfaceIndex, DockError> {
+        let destination = destination.into();
+        self.dock_state.check_destination(destination)?;
+        Ok(self.dock_state.insert_tab(tab, destination))
+    }
+
+    /// Moves a tab to the given destination, like [`DockState::move_tab`].
+    ///
+    /// A tab inserted at a [`TabIndex`] of the node it's moved out of ends up at that index after it's been removed.
+    pub fn move_tab(
+        &mut self,
+        (surface, node, tab): (SurfaceIndex, NodeIndex, TabIndex),
+        destination: impl Into<TabDestination>,
+    ) -> Result<SurfaceIndex, DockError> {
+        let destination = destination.into();
+        self.dock_state
+            .check_move((surface, node, tab), destination)?;
+        let tab = self.dock_state[surface][node].remove_tab(tab).unwrap();
+        Ok(self.dock_state.insert_tab(tab, destination))
+    }
+
+    /// Sets which tab is active in its leaf.
+    pub fn set_active_tab(
+        &mut self,
+        (surface, node, tab): (SurfaceIndex, NodeIndex, TabIndex),
+    ) -> Result<(), DockError> {
+        self.dock_state.check_tab(surface, node, tab)?;
+        self.dock_state[surface].set_active_tab(node, tab);
+        Ok(())
+    }
+
+    /// Sets which leaf is focused.
+    pub fn set_focused_leaf(
+        &mut self,
+        (surface, node): (SurfaceIndex, NodeIndex),
+    ) -> Result<(), DockError> {
+        self.dock_state.check_leaf(surface, node)?;
+        self.dock_state
+            .set_focused_node_and_surface((surface, node));
+        Ok(())
+    }
+
+    /// Makes a leaf of the main surface its [central node](crate::Tree::set_central_node).
+    pub fn set_central_node(&mut self, node: NodeIndex) -> Result<(), DockError> {
+        self.dock_state.check_leaf(SurfaceIndex::main(), node)?;
+        self.dock_state.set_central_node(node);
+        Ok(())
+    }
+}
+
+impl<Tab> DockState<Tab> {
+    /// Makes a number of changes to the layout at once through a [`DockEdit`], which returns errors instead of
+    /// panicking on invalid indices.
+    ///
+    /// Once `edit` returns, leaves and windows left without tabs are removed, and the focus is moved to a leaf that
+    /// still exists. This also happens when `edit` returns an error, in which case the changes made before the error
+    /// are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockError, DockState, NodeIndex, Split, SurfaceIndex, TabIndex};
+    /// let mut dock_state = DockState::new(vec!["editor"]);
+    /// let main = SurfaceIndex::main();
+    ///
+    /// dock_state
+    ///     .transaction(|edit| {
+    ///         let [editor, files] = edit.split((main, NodeIndex::root()), Split::Left, 0.8, vec!["files"])?;
+    ///         edit.split((main, editor), Split::Below, 0.7, vec!["console", "output"])?;
+    ///         edit.remove_leaf((main, files))?;
+    ///         Ok(())
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(dock_state.main_surface().num_tabs(), 3);
+    ///
+    /// let result = dock_state.transaction(|edit| edit.remove_tab((main, NodeIndex(7), TabIndex(0))));
+    /// assert_eq!(result, Err(DockError::InvalidNode(main, NodeIndex(7))));
+    /// ```
+    pub fn transaction<R>(
+        &mut self,
+        edit: impl FnOnce(&mut DockEdit<'_, Tab>) -> Result<R, DockError>,
+    ) -> Result<R, DockError> {
+        let result = edit(&mut DockEdit::new(self));
+        self.remove_empty_nodes();
+        result
+    }
+
+    /// Removes leaves without tabs (except central ones) and windows without tabs, then makes sure a leaf which
+    /// exists is focused and updates the collapsed state of all surfaces.
+    fn remove_empty_nodes(&mut self) {
+        for surface in self.valid_surface_indices().iter().copied() {
+            // Go from the back so that removing a leaf doesn't move the ones left to check.
+            while let Some(index) = self[surface]
+                .iter()
+                .rposition(|node| node.is_leaf() && node.tabs_count() == 0 && !node.is_central())
+            {
+                self.remove_leaf_following_recent((surface, NodeIndex(index)));
+            }
+            let tree = &mut self[surface];
+            let focused = tree
+                .focused_leaf()
+                .filter(|node| tree.iter().nth(node.0).is_some_and(Node::is_leaf))
+                .or_else(|| tree.iter().position(Node::is_leaf).map(NodeIndex));
+            tree.set_focused_leaf(focused);
+            let leaves: Vec<_> = (0..tree.len())
+                .filter(|&index| tree[NodeIndex(index)].is_leaf())
+                .collect();
+            for leaf in leaves {
+                tree.node_update_collapsed(NodeIndex(leaf));
+            }
+            if !surface.is_main() && tree.is_empty() {
+                self.remove_surface(surface);
+            }
+        }
+        if !self
+            .focused_leaf()
+            .is_some_and(|(surface, node)| self.check_leaf(surface, node).is_ok())
+        {
+            self.focused_surface = Some(SurfaceIndex::main());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        dock_state::fixtures::dock_state, DockError, NodeIndex, Split, SurfaceIndex,
+        TabDestination, TabIndex, TabInsert,
+    };
+
+    const MAIN: SurfaceIndex = SurfaceIndex::main();
+
+    #[test]
+    fn edits_return_errors() {
+        let (mut dock_state, window, _) = dock_state();
+        let root = NodeIndex::root();
+        let result = dock_state.transaction(|edit| {
+            assert_eq!(
+                edit.split((MAIN, root.left()), Split::Left, 0.5, Vec::new()),
+                Err(DockError::NoTabs)
+            );
+            assert_eq!(edit.add_window(Vec::new()), Err(DockError::NoTabs));
+            assert_eq!(edit.remove_window(MAIN), Err(DockError::MainSurface));
+            assert_eq!(
+                edit.remove_window(SurfaceIndex(9)),
+                Err(DockError::InvalidSurface(SurfaceIndex(9)))
+            );
+            assert_eq!(
+                edit.remove_leaf((MAIN, root)),
+                Err(DockError::NotALeaf(MAIN, root))
+            );
+            assert_eq!(
+                edit.remove_tab((window, root, TabIndex(1))),
+                Err(DockError::InvalidTab(window, root, TabIndex(1)))
+            );
+            assert_eq!(
+                edit.insert_tab("x", TabDestination::EmptySurface(window)),
+                Err(DockError::SurfaceNotEmpty(window))
+            );
+            assert_eq!(
+                edit.move_tab(
+                    (MAIN, root.left(), TabIndex(0)),
+                    (window, NodeIndex(3), TabInsert::Append)
+                ),
+                Err(DockError::InvalidNode(window, NodeIndex(3)))
+            );
+            assert_eq!(
+                edit.set_active_tab((MAIN, root.right(), TabIndex(1))),
+                Err(DockError::InvalidTab(MAIN, root.right(), TabIndex(1)))
+            );
+            assert_eq!(
+                edit.set_focused_leaf((window, NodeIndex(1))),
+                Err(DockError::InvalidNode(window, NodeIndex(1)))
+            );
+            assert_eq!(
+                edit.set_central_node(root),
+                Err(DockError::NotALeaf(MAIN, root))
+            );
+            Ok(())
+        });
+        assert_eq!(result, Ok(()));
+        assert_eq!(dock_state.main_surface().num_tabs(), 3);
+        assert_eq!(*dock_state.valid_surface_indices(), [MAIN, window]);
+    }
+
+    #[test]
+    fn failed_transaction_keeps_earlier_edits_and_cleans_up() {
+        let (mut dock_state, window, _) = dock_state();
+        let root = NodeIndex::root();
+        dock_state.set_focused_node_and_surface((MAIN, root.right()));
+
+        let result: Result<(), _> = dock_state.transaction(|edit| {
+            // Emptied leaves and windows stay until the transaction ends, so indices don't change in between.
+            edit.remove_tab((window, root, TabIndex(0)))?;
+            edit.remove_leaf((MAIN, root.right()))?;
+            assert_eq!(edit.dock_state()[window].num_tabs(), 0);
+            assert!(edit.dock_state()[MAIN][root.right()].is_leaf());
+            edit.set_active_tab((MAIN, root.left(), TabIndex(1)))?;
+            edit.remove_tab((MAIN, root.right(), TabIndex(0)))?;
+            unreachable!("the leaf has no tabs left to remove")
+        });
+
+        assert_eq!(
+            result,
+            Err(DockError::InvalidTab(MAIN, root.right(), TabIndex(0)))
+        );
+        assert!(!dock_state.is_surface_valid(window));
+        assert_eq!(*dock_state.valid_surface_indices(), [MAIN]);
+        assert_eq!(dock_state[MAIN][root].tabs(), Some(&["a", "b"][..]));
+        assert_eq!(dock_state[MAIN][root].active_tab(), Some(TabIndex(1)));
+        // The focused leaf is gone, so the focus moves to one which is left.
+        assert_eq!(dock_state.focused_leaf(), Some((MAIN, root)));
+    }
+}
@@ -0,0 +1,354 @@
+use std::fmt;
+
+use egui::Id;
+
+use crate::{NodeIndex, SurfaceIndex, TabIndex};
+
+/// Describes why an operation on a [`DockState`](crate::DockState) couldn't be carried out.
+///
+/// Returned from a [`DockState::transaction`](crate::DockState::transaction) and from the `try_` methods of
+/// [`DockState`](crate::DockState), such as [`try_move_tab`](crate::DockState::try_move_tab), instead of panicking on
+/// invalid input.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DockError {
+    /// There's no surface at this index, or it's been removed.
+    InvalidSurface(SurfaceIndex),
+
+    /// There's no node at this index, or it's [`Node::Empty`](crate::Node::Empty).
+    InvalidNode(SurfaceIndex, NodeIndex),
+
+    /// The node at this index isn't a [`Node::Leaf`](crate::Node::Leaf).
+    NotALeaf(SurfaceIndex, NodeIndex),
+
+    /// There's no tab at this index.
+    InvalidTab(SurfaceIndex, NodeIndex, TabIndex),
+
+    /// There's no tab with this [`TabViewer::id`](crate::TabViewer::id).
+    TabNotFound(Id),
+
+    /// The surface was expected to be empty, but it has nodes in it.
+    SurfaceNotEmpty(SurfaceIndex),
+
+    /// The operation can't be done on the main surface, e.g. removing it.
+    MainSurface,
+
+    /// A new leaf or window would have no tabs in it.
+    NoTabs,
+
+    /// A fraction wasn't in the range `0.0..=1.0`.
+    InvalidFraction(f32),
+}
+
+impl fmt::Display for DockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DockError::InvalidSurface(surface) => {
+                write!(f, "there's no surface at index {}", surface.0)
+            }
+            DockError::InvalidNode(surface, node) => {
+                write!(
+                    f,
+                    "there's no node at index {} in surface {}",
+                    node.0, surface.0
+                )
+            }
+            DockError::NotALeaf(surface, node) => {
+                write!(f, "node {} in surface {} is not a leaf", node.0, surface.0)
+            }
+            DockError::InvalidTab(surface, node, tab) => write!(
+                f,
+                "there's no tab at index {} in node {} of surface {}",
+                tab.0, node.0, surface.0
+            ),
+            DockError::TabNotFound(id) => write!(f, "there's no tab with id {id:?}"),
+            DockError::SurfaceNotEmpty(surface) => write!(f, "surface {} is not empty", surface.0),
+            DockError::MainSurface => write!(f, "the operation can't be done on the main surface"),
+            DockError::NoTabs => write!(f, "no tabs were given"),
+            DockError::InvalidFraction(fraction) => {
+                write!(f, "fraction {fraction} is not in the range 0.0..=1.0")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DockError {}
+
+#[cfg(test)]
+mod tests {
+    use egui::{Id, Rect, Ui, WidgetText};
+
+    use crate::{
+        dock_state::fixtures::dock_state, DockError, DockState, Edge, Node, NodeIndex, Split,
+        SurfaceIndex, TabDestination, TabIndex, TabInsert, TabViewer,
+    };
+
+    const MAIN: SurfaceIndex = SurfaceIndex::main();
+
+    struct Viewer;
+
+    impl TabViewer for Viewer {
+        type Tab = &'static str;
+
+        fn title(&mut self, tab: &mut Self::Tab) -> WidgetText {
+            (*tab).into()
+        }
+
+        fn ui(&mut self, _ui: &mut Ui, _tab: &mut Self::Tab) {}
+    }
+
+    /// Asserts that `dock_state` still looks exactly like it did when `before` was taken.
+    fn assert_unchanged(dock_state: &DockState<&'static str>, before: &str) {
+        assert_eq!(format!("{dock_state:?}"), before);
+    }
+
+    #[test]
+    fn invalid_surface() {
+        let (mut dock_state, _, removed) = dock_state();
+        let before = format!("{dock_state:?}");
+        let root = NodeIndex::root();
+        for surface in [removed, SurfaceIndex(9)] {
+            let expected = Some(DockError::InvalidSurface(surface));
+            let tab = (surface, root, TabIndex(0));
+            assert_eq!(dock_state.try_surface(surface).err(), expected);
+            assert_eq!(dock_state.try_surface_mut(surface).err(), expected);
+            assert_eq!(dock_state.try_remove_surface(surface).err(), expected);
+            assert_eq!(dock_state.try_set_active_tab(tab).err(), expected);
+            assert_eq!(dock_state.try_focus_tab(tab).err(), expected);
+            assert_eq!(dock_state.try_remove_tab(tab).err(), expected);
+            assert_eq!(dock_state.try_detach_tab(tab, Rect::ZERO).err(), expected);
+            assert_eq!(
+                dock_state.try_move_tab(tab, (MAIN, root.left(), TabInsert::Append)),
+                Err(DockError::InvalidSurface(surface))
+            );
+            assert_eq!(
+                dock_state
+                    .try_insert_tab("x", (surface, root, TabInsert::Append))
+                    .err(),
+                expected
+            );
+            assert_eq!(
+                dock_state
+                    .try_insert_tab("x", TabDestination::EmptySurface(surface))
+                    .err(),
+                expected
+            );
+            assert_eq!(dock_state.try_remove_leaf((surface, root)).err(), expected);
+            assert_eq!(
+                dock_state
+                    .try_set_leaf_collapsed((surface, root), true)
+                    .err(),
+                expected
+            );
+            assert_eq!(
+                dock_state
+                    .try_split((surface, root), Split::Left, 0.5, Node::leaf("x"))
+                    .err(),
+                expected
+            );
+        }
+        assert_unchanged(&dock_state, &before);
+    }
+
+    #[test]
+    fn invalid_node() {
+        let (mut dock_state, window, _) = dock_state();
+        let before = format!("{dock_state:?}");
+        for (surface, node) in [(MAIN, NodeIndex(9)), (window, NodeIndex(1))] {
+            let expected = Some(DockError::InvalidNode(surface, node));
+            let tab = (surface, node, TabIndex(0));
+            assert_eq!(dock_state.try_set_active_tab(tab).err(), expected);
+            assert_eq!(dock_state.try_remove_tab(tab).err(), expected);
+            assert_eq!(
+                dock_state
+                    .try_move_tab(tab, (MAIN, NodeIndex::root().left(), TabInsert::Append))
+                    .err(),
+                expected
+            );
+            assert_eq!(
+                dock_state
+                    .try_move_tab(
+                        (MAIN, NodeIndex::root().left(), TabIndex(0)),
+                        (surface, node, TabInsert::Append)
+                    )
+                    .err(),
+                expected
+            );
+            assert_eq!(dock_state.try_remove_leaf((surface, node)).err(), expected);
+            assert_eq!(
+                dock_state
+                    .try_set_tab_bar_hidden((surface, node), true)
+                    .err(),
+                expected
+            );
+            assert_eq!(
+                dock_state
+                    .try_set_tab_bar_style((surface, node), None)
+                    .err(),
+                expected
+            );
+            assert_eq!(
+                dock_state
+                    .try_set_tab_bar_scroll((surface, node), 10.0)
+                    .err(),
+                expected
+            );
+            assert_eq!(
+                dock_state
+                    .try_detach_leaf((surface, node), Rect::ZERO)
+                    .err(),
+                expected
+            );
+            assert_eq!(
+                dock_state
+                    .try_split((surface, node), Split::Below, 0.5, Node::leaf("x"))
+                    .err(),
+                expected
+            );
+        }
+        assert_eq!(
+            dock_state.try_set_central_node(NodeIndex(9)).err(),
+            Some(DockError::InvalidNode(MAIN, NodeIndex(9)))
+        );
+        assert_unchanged(&dock_state, &before);
+    }
+
+    #[test]
+    fn not_a_leaf() {
+        let (mut dock_state, _, _) = dock_state();
+        let before = format!("{dock_state:?}");
+        let root = NodeIndex::root();
+        let expected = Some(DockError::NotALeaf(MAIN, root));
+        assert_eq!(
+            dock_state
+                .try_set_active_tab((MAIN, root, TabIndex(0)))
+                .err(),
+            expected
+        );
+        assert_eq!(dock_state.try_remove_leaf((MAIN, root)).err(), expected);
+        assert_eq!(dock_state.try_set_central_node(root).err(), expected);
+        assert_eq!(
+            dock_state.try_set_auto_hide((MAIN, root), Edge::Left).err(),
+            expected
+        );
+        assert_eq!(
+            dock_state.try_detach_leaf((MAIN, root), Rect::ZERO).err(),
+            expected
+        );
+        assert_eq!(
+            dock_state
+                .try_insert_tab("x", (MAIN, root, TabInsert::Append))
+                .err(),
+            expected
+        );
+        assert_unchanged(&dock_state, &before);
+    }
+
+    #[test]
+    fn invalid_tab() {
+        let (mut dock_state, _, _) = dock_state();
+        let before = format!("{dock_state:?}");
+        let [left, right] = [NodeIndex::root().left(), NodeIndex::root().right()];
+        let tab = (MAIN, right, TabIndex(1));
+        let expected = Some(DockError::InvalidTab(MAIN, right, TabIndex(1)));
+        assert_eq!(dock_state.try_set_active_tab(tab).err(), expected);
+        assert_eq!(dock_state.try_focus_tab(tab).err(), expected);
+        assert_eq!(dock_state.try_remove_tab(tab).err(), expected);
+        assert_eq!(dock_state.try_detach_tab(tab, Rect::ZERO).err(), expected);
+        assert_eq!(
+            dock_state.try_move_tab(tab, (MAIN, left, TabInsert::Append)),
+            Err(DockError::InvalidTab(MAIN, right, TabIndex(1)))
+        );
+
+        // Tabs can be inserted after the last one, but not past it.
+        assert_eq!(
+            dock_state
+                .try_insert_tab("x", (MAIN, left, TabInsert::Insert(TabIndex(3))))
+                .err(),
+            Some(DockError::InvalidTab(MAIN, left, TabIndex(3)))
+        );
+        // A tab moved within its own leaf leaves one place fewer.
+        assert_eq!(
+            dock_state.try_move_tab(
+                (MAIN, left, TabIndex(0)),
+                (MAIN, left, TabInsert::Insert(TabIndex(2)))
+            ),
+            Err(DockError::InvalidTab(MAIN, left, TabIndex(2)))
+        );
+        assert_unchanged(&dock_state, &before);
+    }
+
+    #[test]
+    fn tab_not_found() {
+        let (mut dock_state, window, _) = dock_state();
+        let before = format!("{dock_state:?}");
+        let id = Id::new("missing");
+        assert_eq!(
+            dock_state.move_tab_by_id(
+                id,
+                (window, NodeIndex::root(), TabInsert::Append),
+                &mut Viewer
+            ),
+            Err(DockError::TabNotFound(id))
+        );
+        assert_unchanged(&dock_state, &before);
+    }
+
+    #[test]
+    fn surface_not_empty() {
+        let (mut dock_state, window, _) = dock_state();
+        let before = format!("{dock_state:?}");
+        for surface in [MAIN, window] {
+            assert_eq!(
+                dock_state
+                    .try_insert_tab("x", TabDestination::EmptySurface(surface))
+                    .err(),
+                Some(DockError::SurfaceNotEmpty(surface))
+            );
+            assert_eq!(
+                dock_state.try_move_tab(
+                    (MAIN, NodeIndex::root().left(), TabIndex(0)),
+                    TabDestination::EmptySurface(surface)
+                ),
+                Err(DockError::SurfaceNotEmpty(surface))
+            );
+        }
+        assert_unchanged(&dock_state, &before);
+    }
+
+    #[test]
+    fn main_surface() {
+        let (mut dock_state, _, _) = dock_state();
+        let before = format!("{dock_state:?}");
+        assert_eq!(
+            dock_state.try_remove_surface(MAIN).err(),
+            Some(DockError::MainSurface)
+        );
+        assert_unchanged(&dock_state, &before);
+    }
+
+    #[test]
+    fn no_tabs_and_invalid_fraction() {
+        let (mut dock_state, _, _) = dock_state();
+        let before = format!("{dock_state:?}");
+        let leaf = (MAIN, NodeIndex::root().right());
+        assert_eq!(
+            dock_state
+                .try_split(leaf, Split::Above, 0.5, Node::leaf_with(Vec::new()))
+                .err(),
+            Some(DockError::NoTabs)
+        );
+        for fraction in [-0.1, 1.5] {
+            assert_eq!(
+                dock_state
+                    .try_split(leaf, Split::Above, fraction, Node::leaf("x"))
+                    .err(),
+                Some(DockError::InvalidFraction(fraction))
+            );
+        }
+        assert!(matches!(
+            dock_state.try_split(leaf, Split::Above, f32::NAN, Node::leaf("x")),
+            Err(DockError::InvalidFraction(fraction)) if fraction.is_nan()
+        ));
+        assert_unchanged(&dock_state, &before);
+    }
+}
@@ -0,0 +1,14 @@
+use crate::{DockState, NodeIndex, SurfaceIndex};
+
+/// Returns a main surface split into a leaf with `a` and `b` and another with `c`, a window with `w`, and the index
+/// of a window which has been removed.
+pub(crate) fn dock_state() -> (DockState<&'static str>, SurfaceIndex, SurfaceIndex) {
+    let mut dock_state = DockState::new(vec!["a", "b"]);
+    dock_state
+        .main_surface_mut()
+        .split_right(NodeIndex::root(), 0.5, vec!["c"]);
+    let window = dock_state.add_window(vec!["w"]);
+    let removed = dock_state.add_window(vec!["r"]);
+    dock_state.remove_surface(removed);
+    (dock_state, window, removed)
+}
@@ -0,0 +1,145 @@
+use std::collections::VecDeque;
+
+use crate::{DockState, LayoutSnapshot};
+
+/// Snapshots of the layout taken before changes made through a [`DockArea`](crate::DockArea), which can be undone
+/// and redone.
+#[derive(Clone, Debug)]
+pub(crate) struct LayoutHistory {
+    capacity: usize,
+    undo: VecDeque<LayoutSnapshot>,
+    redo: Vec<LayoutSnapshot>,
+
+    /// Steps requested since the dock area was last shown.
+    pending: Vec<HistoryStep>,
+}
+
+/// A step through the [`LayoutHistory`], requested with [`DockState::undo`] or [`DockState::redo`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum HistoryStep {
+    Undo,
+    Redo,
+}
+
+impl<Tab> DockState<Tab> {
+    /// Starts recording the layout before changes made by the user through a [`DockArea`](crate::DockArea), so they
+    /// can be reverted with [`undo`](Self::undo) and [`redo`](Self::redo).
+    ///
+    /// The layout is recorded when a tab is dragged to another place (which may split a leaf or create a window),
+    /// ejected into a window, or when a whole leaf or window is closed. At most `capacity` changes can be undone.
+    /// Calling this again only changes the capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::DockState;
+    /// let mut dock_state = DockState::new(vec!["a", "b"]);
+    /// dock_state.enable_history(50);
+    /// assert!(dock_state.is_history_enabled());
+    ///
+    /// // Nothing has been changed through a dock area yet.
+    /// assert!(!dock_state.can_undo());
+    /// ```
+    pub fn enable_history(&mut self, capacity: usize) {
+        let history = self.history.get_or_insert_with(|| LayoutHistory {
+            capacity,
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            pending: Vec::new(),
+        });
+        history.capacity = capacity;
+        while history.undo.len() > capacity {
+            history.undo.pop_front();
+        }
+    }
+
+    /// Stops recording the layout and forgets all recorded changes.
+    pub fn disable_history(&mut self) {
+        self.history = None;
+    }
+
+    /// Returns `true` if changes of the layout are being recorded, see [`enable_history`](Self::enable_history).
+    #[inline]
+    pub fn is_history_enabled(&self) -> bool {
+        self.history.is_some()
+    }
+
+    /// Returns `true` if there is a recorded change which can be undone.
+    #[inline]
+    pub fn can_undo(&self) -> bool {
+        self.history
+            .as_ref()
+            .is_some_and(|history| !history.undo.is_empty())
+    }
+
+    /// Returns `true` if there is an undone change which can be redone.
+    #[inline]
+    pub fn can_redo(&self) -> bool {
+        self.history
+            .as_ref()
+            .is_some_and(|history| !history.redo.is_empty())
+    }
+
+    /// Reverts the last recorded change of the layout.
+    ///
+    /// Tabs are identified with [`TabViewer::id`](crate::TabViewer::id), so the layout is restored the next time the
+    /// [`DockArea`](crate::DockArea) is shown. Tabs closed or added since then are handled as described in
+    /// [`apply_snapshot`](Self::apply_snapshot). Does nothing if history isn't enabled.
+    pub fn undo(&mut self) {
+        if let Some(history) = &mut self.history {
+            history.pending.push(HistoryStep::Undo);
+        }
+    }
+
+    /// Restores the last change reverted with [`undo`](Self::undo), unless the layout has been changed since.
+    ///
+    /// Like [`undo`](Self::undo), this takes effect the next time the [`DockArea`](crate::DockArea) is shown.
+    pub fn redo(&mut self) {
+        if let Some(history) = &mut self.history {
+            history.pending.push(HistoryStep::Redo);
+        }
+    }
+
+    /// Records the layout before a change, if history is enabled.
+    pub(crate) fn record_history(&mut self, snapshot: LayoutSnapshot) {
+        let Some(history) = self.history.as_mut().filter(|history| history.capacity > 0) else {
+            return;
+        };
+        if history.undo.len() == history.capacity {
+            history.undo.pop_front();
+        }
+        history.undo.push_back(snapshot);
+        history.redo.clear();
+    }
+
+    /// Returns the steps requested with [`undo`](Self::undo) and [`redo`](Self::redo) since the last call.
+    pub(crate) fn take_history_steps(&mut self) -> Vec<HistoryStep> {
+        self.history
+            .as_mut()
+            .map(|history| std::mem::take(&mut history.pending))
+            .unwrap_or_default()
+    }
+
+    /// Moves through the history, storing the `current` layout on the opposite stack.
+    ///
+    /// Returns the layout to restore, or [`None`] if there's nothing to undo or redo.
+    pub(crate) fn step_history(
+        &mut self,
+        step: HistoryStep,
+        current: LayoutSnapshot,
+    ) -> Option<LayoutSnapshot> {
+        let history = self.history.as_mut()?;
+        match step {
+            HistoryStep::Undo => {
+                let snapshot = history.undo.pop_back()?;
+                history.redo.push(current);
+                Some(snapshot)
+            }
+            HistoryStep::Redo => {
+                let snapshot = history.redo.pop()?;
+                history.undo.push_back(current);
+                Some(snapshot)
+            }
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use egui::Pos2;
+
+use crate::{DockState, Node, NodeIndex, Surface, SurfaceIndex, TabIndex, Tree};
+
+/// The part of a [`DockState`] found under a position by [`DockState::hit_test`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DockHit {
+    /// The surface containing the position.
+    pub surface: SurfaceIndex,
+
+    /// The leaf containing the position, or the parent node of the separator it's on.
+    pub node: NodeIndex,
+
+    /// The tab whose title or body contains the position.
+    pub tab: Option<TabIndex>,
+
+    /// What kind of element is under the position.
+    pub element: DockElement,
+}
+
+/// Kinds of elements of a [`DockArea`](crate::DockArea) which can be found with [`DockState::hit_test`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DockElement {
+    /// The title of a tab in a tab bar.
+    TabTitle,
+
+    /// The body of the active tab of a leaf.
+    TabBody,
+
+    /// Any other part of a tab bar: its background, buttons and scroll bar.
+    TabBar,
+
+    /// The separator between the two children of a node.
+    Separator,
+}
+
+impl<Tab> DockState<Tab> {
+    /// Finds what is under `pos`, using the rectangles stored when the [`DockArea`](crate::DockArea) was last shown.
+    ///
+    /// Windows and the open auto-hidden leaf are checked before the main surface, from the one shown on top the last
+    /// time the dock area was shown. Windows which haven't been shown since they were created are checked after the
+    /// ones which have, from the most recently created one. Returns [`None`] if there's nothing under `pos`, or if
+    /// `pos` is in a part of a window which doesn't belong to any node.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockElement, DockState};
+    /// # use egui::pos2;
+    /// let dock_state = DockState::new(vec!["tab"]);
+    ///
+    /// // Nothing has been shown yet, so nothing can be found.
+    /// assert_eq!(dock_state.hit_test(pos2(10.0, 10.0)), None);
+    ///
+    /// // After the dock area has been shown, e.g. in a context menu of your own:
+    /// if let Some(hit) = dock_state.hit_test(pos2(10.0, 10.0)) {
+    ///     if hit.element == DockElement::TabTitle {
+    ///         println!("{:?} is under the pointer", hit.tab);
+    ///     }
+    /// }
+    /// ```
+    pub fn hit_test(&self, pos: Pos2) -> Option<DockHit> {
+        let unstacked = (0..self.surfaces.len())
+            .rev()
+            .map(SurfaceIndex)
+            .filter(|surface| !self.surface_stack.contains(surface));
+        for surface in self.surface_stack.iter().copied().chain(unstacked) {
+            let hit = match self.surfaces.get(surface.0) {
+                Some(Surface::Window(tree, state)) if !state.is_minimized() => {
+                    if !state.rect().contains(pos) {
+                        continue;
+                    }
+                    hit_test_tree(tree, pos)
+                }
+                // Only the open auto-hidden leaf has been shown, and the rect of the others is where it was shown.
+                Some(Surface::AutoHidden(tree, _)) if self.surface_stack.contains(&surface) => {
+                    match hit_test_tree(tree, pos) {
+                        Some(hit) => Some(hit),
+                        None => continue,
+                    }
+                }
+                _ => continue,
+            };
+            return hit.map(|(node, tab, element)| DockHit {
+                surface,
+                node,
+                tab,
+                element,
+            });
+        }
+
+        hit_test_tree(self.main_surface(), pos).map(|(node, tab, element)| DockHit {
+            surface: SurfaceIndex::main(),
+            node,
+            tab,
+            element,
+        })
+    }
+
+    /// Stores the surfaces shown in layers of their own, from the topmost one, see [`hit_test`](Self::hit_test).
+    pub(crate) fn set_surface_stack(&mut self, stack: Vec<SurfaceIndex>) {
+        self.surface_stack = stack;
+    }
+}
+
+fn hit_test_tree<Tab>(
+    tree: &Tree<Tab>,
+    pos: Pos2,
+) -> Option<(NodeIndex, Option<TabIndex>, DockElement)> {
+    let mut node_index = NodeIndex::root();
+    loop {
+        if node_index.0 >= tree.len() {
+            return None;
+        }
+        let node = &tree[node_index];
+        if !node.rect().is_some_and(|rect| rect.contains(pos)) {
+            return None;
+        }
+        match node {
+            Node::Empty => return None,
+            Node::Leaf {
+                viewport,
+                tabs,
+                active,
+                collapsed,
+                ..
+            } => {
+                let title = (0..tabs.len())
+                    .map(TabIndex)
+                    .find(|&tab| node.tab_rect(tab).is_some_and(|rect| rect.contains(pos)));
+                return Some(match title {
+                    Some(tab) => (node_index, Some(tab), DockElement::TabTitle),
+                    None if !collapsed && active.0 < tabs.len() && viewport.contains(pos) => {
+                        (node_index, Some(*active), DockElement::TabBody)
+                    }
+                    None => (node_index, None, DockElement::TabBar),
+                });
+            }
+            Node::Vertical { .. } | Node::Horizontal { .. } => {
+                let child_contains = |child: NodeIndex| {
+                    child.0 < tree.len()
+                        && tree[child].rect().is_some_and(|rect| rect.contains(pos))
+                };
+                if child_contains(node_index.left()) {
+                    node_index = node_index.left();
+                } else if child_contains(node_index.right()) {
+                    node_index = node_index.right();
+                } else {
+                    return Some((node_index, None, DockElement::Separator));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use egui::{Pos2, Rect};
+
+use crate::{DockHit, DockState, Node, NodeIndex, SurfaceIndex, TabDestination, TabIndex};
+
+/// Measurements of a [`DockArea`](crate::DockArea) taken while it was shown, when enabled with
+/// [`DockArea::collect_metrics`](crate::DockArea::collect_metrics).
+///
+/// Indices refer to the layout as it was shown, before tabs closed or moved during that frame were removed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LayoutMetrics {
+    /// Every non-empty node of every surface, in the order of surfaces and then nodes.
+    pub nodes: Vec<NodeMetrics>,
+
+    /// Every tab whose title was visible in a tab bar.
+    pub tabs: Vec<TabMetrics>,
+
+    /// The tab being dragged, if any.
+    pub drag: Option<DragMetrics>,
+
+    /// The position of the pointer, if it was over the screen.
+    pub pointer: Option<Pos2>,
+
+    /// The part of the dock area under the pointer.
+    pub hovered: Option<DockHit>,
+
+    /// The auto-hidden leaf shown over the main surface, if any.
+    pub open_auto_hidden: Option<SurfaceIndex>,
+}
+
+/// The layout of a node, see [`LayoutMetrics::nodes`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NodeMetrics {
+    /// The surface containing the node.
+    pub surface: SurfaceIndex,
+
+    /// The index of the node in its surface.
+    pub node: NodeIndex,
+
+    /// The rect occupied by the node.
+    pub rect: Rect,
+
+    /// Whether the node is a [`Leaf`](Node::Leaf).
+    pub is_leaf: bool,
+
+    /// Whether the node is collapsed.
+    pub collapsed: bool,
+}
+
+/// The title of a tab in a tab bar, see [`LayoutMetrics::tabs`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TabMetrics {
+    /// The surface containing the tab.
+    pub surface: SurfaceIndex,
+
+    /// The leaf containing the tab.
+    pub node: NodeIndex,
+
+    /// The index of the tab in its leaf.
+    pub tab: TabIndex,
+
+    /// The visible part of the tab's title.
+    pub title_rect: Rect,
+
+    /// The close button of the tab, if it was shown.
+    pub close_button_rect: Option<Rect>,
+
+    /// Whether the tab is the active tab of its leaf.
+    pub active: bool,
+}
+
+/// The state of dragging a tab, see [`LayoutMetrics::drag`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DragMetrics {
+    /// The tab being dragged, or [`None`] if it's dragged from another dock area.
+    pub tab: Option<(SurfaceIndex, NodeIndex, TabIndex)>,
+
+    /// The rect of the node or surface the tab is dragged over.
+    pub hovered_rect: Rect,
+
+    /// Where the tab would go if it was released, or [`None`] if the overlay showed no target under the pointer.
+    pub destination: Option<TabDestination>,
+}
+
+/// The tab currently dragged in a [`DockArea`](crate::DockArea), see [`DockState::current_drag`].
+///
+/// Indices refer to the layout as it's shown in the frame the tab is dragged in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DragInfo {
+    /// The tab being dragged, or [`None`] if it's dragged from another dock area.
+    pub source: Option<(SurfaceIndex, NodeIndex, TabIndex)>,
+
+    /// The position of the pointer dragging the tab.
+    pub pointer: Pos2,
+
+    /// The rect of the node or surface the tab is dragged over, or [`None`] if the pointer isn't over the dock area.
+    pub hovered_rect: Option<Rect>,
+
+    /// Where the tab would go if it was released, or [`None`] if the overlay shows no target under the pointer.
+    pub destination: Option<TabDestination>,
+
+    /// Whether releasing the tab would drop it at the [`destination`](Self::destination).
+    ///
+    /// The drop may still be cancelled or redirected by [`TabViewer::on_tab_move`](crate::TabViewer::on_tab_move).
+    pub valid: bool,
+}
+
+impl LayoutMetrics {
+    /// Measures the rects of nodes and tab titles of `dock_state` stored when it was last shown.
+    pub(crate) fn measure<Tab>(dock_state: &DockState<Tab>) -> Self {
+        let mut metrics = Self::default();
+        for surface in dock_state.valid_surface_index_iter() {
+            let Some(tree) = dock_state.get_surface(surface).and_then(|s| s.node_tree()) else {
+                continue;
+            };
+            for (index, node) in tree.iter().enumerate() {
+                let Some(rect) = node.rect() else {
+                    continue;
+                };
+                let node_index = NodeIndex(index);
+                metrics.nodes.push(NodeMetrics {
+                    surface,
+                    node: node_index,
+                    rect,
+                    is_leaf: node.is_leaf(),
+                    collapsed: node.is_collapsed(),
+                });
+                if let Node::Leaf { tabs, active, .. } = node {
+                    metrics.tabs.extend((0..tabs.len()).filter_map(|tab| {
+                        let tab = TabIndex(tab);
+                        Some(TabMetrics {
+                            surface,
+                            node: node_index,
+                            tab,
+                            title_rect: node.tab_rect(tab)?,
+                            close_button_rect: None,
+                            active: tab == *active,
+                        })
+                    }));
+                }
+            }
+        }
+        metrics
+    }
+
+    /// Returns the metrics of the node at `node` of `surface`, if it was shown.
+    pub fn node(&self, surface: SurfaceIndex, node: NodeIndex) -> Option<&NodeMetrics> {
+        self.nodes
+            .iter()
+            .find(|metrics| metrics.surface == surface && metrics.node == node)
+    }
+
+    /// Returns the metrics of the tab at `tab` of the leaf at `node` of `surface`, if its title was visible.
+    pub fn tab(
+        &self,
+        surface: SurfaceIndex,
+        node: NodeIndex,
+        tab: TabIndex,
+    ) -> Option<&TabMetrics> {
+        self.tabs.iter().find(|metrics| {
+            metrics.surface == surface && metrics.node == node && metrics.tab == tab
+        })
+    }
+}
+
+impl<Tab> DockState<Tab> {
+    /// Returns the metrics collected the last time the [`DockArea`](crate::DockArea) was shown with
+    /// [`DockArea::collect_metrics`](crate::DockArea::collect_metrics) enabled.
+    ///
+    /// Returns [`None`] if the dock area hasn't been shown yet, or was shown without collecting metrics.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockArea, DockState, NodeIndex, SurfaceIndex, TabIndex, TabViewer};
+    /// # use egui::{Ui, WidgetText};
+    /// # struct MyTabViewer;
+    /// # impl TabViewer for MyTabViewer {
+    /// #     type Tab = String;
+    /// #     fn title(&mut self, tab: &mut Self::Tab) -> WidgetText { tab.as_str().into() }
+    /// #     fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {}
+    /// # }
+    /// let mut dock_state = DockState::new(vec!["first".to_owned(), "second".to_owned()]);
+    /// let ctx = egui::Context::default();
+    /// let _ = ctx.run(Default::default(), |ctx| {
+    ///     egui::CentralPanel::default().show(ctx, |ui| {
+    ///         DockArea::new(&mut dock_state)
+    ///             .collect_metrics(true)
+    ///             .show_inside(ui, &mut MyTabViewer);
+    ///     });
+    /// });
+    ///
+    /// let metrics = dock_state.last_metrics().unwrap();
+    /// let root = metrics.node(SurfaceIndex::main(), NodeIndex::root()).unwrap();
+    /// let title = metrics
+    ///     .tab(SurfaceIndex::main(), NodeIndex::root(), TabIndex(0))
+    ///     .unwrap();
+    /// assert!(root.rect.contains_rect(title.title_rect));
+    /// ```
+    #[inline(always)]
+    pub fn last_metrics(&self) -> Option<&LayoutMetrics> {
+        self.last_metrics.as_ref()
+    }
+
+    /// Stores the metrics collected while the dock area was shown.
+    pub(crate) fn set_last_metrics(&mut self, metrics: Option<LayoutMetrics>) {
+        self.last_metrics = metrics;
+    }
+
+    /// Returns the tab dragged in the [`DockArea`](crate::DockArea) the last time it was shown, along with where it
+    /// would be dropped, or [`None`] if no tab is being dragged.
+    ///
+    /// It's available right after the dock area is shown, e.g. to describe the drop in a status bar or to change the
+    /// cursor, and it's kept until the dock area is shown again.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, Split, TabDestination, TabInsert};
+    /// # let dock_state = DockState::new(vec!["tab"]);
+    /// let hint = dock_state.current_drag().and_then(|drag| match drag.destination {
+    ///     Some(TabDestination::Node(_, _, TabInsert::Split(Split::Right))) if drag.valid => {
+    ///         Some("Drop to split right")
+    ///     }
+    ///     _ => None,
+    /// });
+    /// assert_eq!(hint, None);
+    /// ```
+    #[inline(always)]
+    pub fn current_drag(&self) -> Option<&DragInfo> {
+        self.current_drag.as_ref()
+    }
+
+    /// Stores the tab dragged while the dock area was shown.
+    pub(crate) fn set_current_drag(&mut self, drag: Option<DragInfo>) {
+        self.current_drag = drag;
+    }
+}
@@ -1,22 +1,70 @@
 /// Wrapper around indices to the collection of surfaces inside a [`DockState`].
 pub mod surface_index;
 
+mod animation;
+mod collapse;
+mod directional;
+mod history;
+mod recent;
+mod selection;
+mod tab_id;
+
+#[cfg(test)]
+mod fixtures;
+
+/// States of leaves hidden along the edges of the main surface.
+pub mod auto_hide_state;
+
+/// Making many changes to a [`DockState`] at once.
+pub mod edit;
+
+/// Errors returned instead of panicking on invalid input.
+pub mod error;
 pub mod tree;
 
+/// Finding out which part of a [`DockState`] is under a position.
+pub mod hit_test;
+
+/// Measurements of the layout taken while a [`DockArea`](crate::DockArea) was shown.
+pub mod metrics;
+
+/// Saving a [`DockState`] whose tabs can't be serialized.
+pub mod portable;
+
+/// Snapshots of the arrangement of tabs, which don't hold the tabs themselves.
+pub mod snapshot;
+
 /// Represents an area in which a dock tree is rendered.
 pub mod surface;
+
+/// Writing layouts into text which can be edited by hand, and reading them back.
+pub mod text_layout;
 /// Specifies text displayed in different elements of the [`DockArea`](crate::DockArea).
 pub mod translations;
 /// Window states which tells floating tabs how to be displayed inside their window,
 pub mod window_state;
 
+pub use auto_hide_state::{AutoHideState, Edge};
+pub use edit::DockEdit;
+pub use error::DockError;
+pub use hit_test::{DockElement, DockHit};
+pub use metrics::{DragInfo, DragMetrics, LayoutMetrics, NodeMetrics, TabMetrics};
+pub use portable::{PortableDockState, TabCodec, TabPayload};
+pub use snapshot::LayoutSnapshot;
 pub use surface::Surface;
-pub use surface_index::SurfaceIndex;
-pub use window_state::WindowState;
+pub use surface_index::{SurfaceId, SurfaceIndex};
+pub use text_layout::LayoutParseError;
+pub use window_state::{WindowAnchor, WindowState};
+
+use egui::{Id, Rect};
 
-use egui::Rect;
+use animation::FractionAnimation;
+use history::LayoutHistory;
 
-use crate::{Node, NodeIndex, Split, TabDestination, TabIndex, TabInsert, Translations, Tree};
+use crate::{
+    ComputedLayout, LayoutDescription, Node, NodeIndex, Split, Style, TabBarStyle, TabDestination,
+    TabIndex, TabInsert, Translations, Tree,
+};
 
 /// The heart of `egui_dock`.
 ///
@@ -31,6 +79,50 @@ pub struct DockState<Tab> {
     surfaces: Vec<Surface<Tab>>,
     focused_surface: Option<SurfaceIndex>, // Part of the tree which is in focus.
 
+    /// Fraction transitions currently in progress.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    animations: Vec<FractionAnimation>,
+
+    /// Leaves collapsed or expanded since the dock area was last shown, and whether to animate the change.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    collapse_changes: Vec<(SurfaceIndex, NodeIndex, bool)>,
+
+    /// Ids of tabs waiting for the application to confirm or cancel closing them.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    closing_tabs: Vec<Id>,
+
+    /// Ids of tabs whose closing has been confirmed, removed the next time the dock area is shown.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    confirmed_closes: Vec<Id>,
+
+    /// Ids of tabs selected by Ctrl+clicking their titles.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    selected_tabs: Vec<Id>,
+
+    /// Layouts recorded for undoing and redoing changes, if enabled.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    history: Option<LayoutHistory>,
+
+    /// Recently used tabs, from the most recent one.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    recent_tabs: Vec<(SurfaceIndex, NodeIndex, TabIndex)>,
+
+    /// Metrics collected the last time the dock area was shown, if enabled.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    last_metrics: Option<LayoutMetrics>,
+
+    /// Surfaces shown in layers of their own the last time the dock area was shown, from the topmost one.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    surface_stack: Vec<SurfaceIndex>,
+
+    /// The tab dragged the last time the dock area was shown, if any.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    current_drag: Option<DragInfo>,
+
+    /// Tabs whose titles are scrolled into view the next time the dock area is shown.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    scroll_to_tabs: Vec<(SurfaceIndex, NodeIndex, TabIndex)>,
+
     /// Contains translations of text shown in [`DockArea`](crate::DockArea).
     pub translations: Translations,
 }
@@ -67,10 +159,97 @@ impl<Tab> DockState<Tab> {
         Self {
             surfaces: vec![Surface::Main(Tree::new(tabs))],
             focused_surface: None,
+            animations: Vec::new(),
+            collapse_changes: Vec::new(),
+            closing_tabs: Vec::new(),
+            confirmed_closes: Vec::new(),
+            selected_tabs: Vec::new(),
+            history: None,
+            recent_tabs: Vec::new(),
+            last_metrics: None,
+            surface_stack: Vec::new(),
+            current_drag: None,
+            scroll_to_tabs: Vec::new(),
             translations: Translations::english(),
         }
     }
 
+    /// Create a new tree with leaves of the main surface laid out as described by the `layout`.
+    ///
+    /// See [`Tree::from_layout`].
+    pub fn from_layout(layout: LayoutDescription<Tab>) -> Self {
+        let mut dock_state = Self::new(Vec::new());
+        *dock_state.main_surface_mut() = Tree::from_layout(layout);
+        dock_state
+    }
+
+    /// Create a new tree with a grid of `rows` rows with `cols` leaves each on the main surface. `tabs` holds the tabs
+    /// of each leaf, row by row.
+    ///
+    /// See [`LayoutDescription::grid`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `tabs` isn't `rows * cols`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex};
+    /// let dock_state = DockState::grid(2, 2, vec![vec!["a"], vec!["b"], vec!["c"], vec!["d", "e"]]);
+    /// assert_eq!(dock_state.main_surface().num_tabs(), 5);
+    /// assert!(dock_state.main_surface()[NodeIndex::root()].is_vertical());
+    /// ```
+    pub fn grid(rows: usize, cols: usize, tabs: Vec<Vec<Tab>>) -> Self {
+        Self::from_layout(LayoutDescription::grid(rows, cols, tabs))
+    }
+
+    /// Computes the rects which nodes of the main surface would take if the [`DockArea`](crate::DockArea) was shown
+    /// in `available_rect` with the default [`Style`], without showing it.
+    ///
+    /// See [`DockState::compute_layout_with_style`] and [`Tree::compute_layout`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex};
+    /// # use egui::{pos2, Rect};
+    /// let mut dock_state = DockState::new(vec!["left"]);
+    /// let [_, right] =
+    ///     dock_state
+    ///         .main_surface_mut()
+    ///         .split_right(NodeIndex::root(), 0.25, vec!["right"]);
+    ///
+    /// let layout = dock_state.compute_layout(Rect::from_min_max(pos2(0.0, 0.0), pos2(400.0, 300.0)));
+    /// let right_rect = layout.rect(right).unwrap();
+    /// assert!((right_rect.left() - 100.0).abs() <= 1.0);
+    /// assert_eq!(right_rect.right(), 400.0);
+    /// ```
+    pub fn compute_layout(&self, available_rect: Rect) -> ComputedLayout {
+        self.compute_layout_with_style(available_rect, &Style::default(), 1.0)
+    }
+
+    /// Computes the rects which nodes of the main surface would take if the [`DockArea`](crate::DockArea) was shown
+    /// in `available_rect` with the given `style`, at the given `pixels_per_point`, without showing it.
+    ///
+    /// Takes [`Style::dock_area_padding`] and [`Style::main_surface_border_stroke`] into account like the dock area
+    /// does. Nodes of the tree aren't modified.
+    pub fn compute_layout_with_style(
+        &self,
+        available_rect: Rect,
+        style: &Style,
+        pixels_per_point: f32,
+    ) -> ComputedLayout {
+        let mut rect = available_rect;
+        if let Some(margin) = style.dock_area_padding {
+            rect.min += margin.left_top();
+            rect.max -= margin.right_bottom();
+        }
+        rect = rect.expand(-style.main_surface_border_stroke.width / 2.0);
+        self.main_surface()
+            .compute_layout(rect, style, pixels_per_point)
+    }
+
     /// Sets translations of text later displayed in [`DockArea`](crate::DockArea).
     pub fn with_translations(mut self, translations: Translations) -> Self {
         self.translations = translations;
@@ -112,6 +291,26 @@ impl<Tab> DockState<Tab> {
         }
     }
 
+    /// Get the [`AutoHideState`] which corresponds to a [`SurfaceIndex`].
+    ///
+    /// Returns `None` if the surface isn't [`AutoHidden`](Surface::AutoHidden) or doesn't exist.
+    pub fn get_auto_hide_state(&self, surface: SurfaceIndex) -> Option<&AutoHideState> {
+        match self.surfaces.get(surface.0)? {
+            Surface::AutoHidden(_, state) => Some(state),
+            _ => None,
+        }
+    }
+
+    /// Get the [`AutoHideState`] which corresponds to a [`SurfaceIndex`], e.g. to move the leaf to another edge.
+    ///
+    /// Returns `None` if the surface isn't [`AutoHidden`](Surface::AutoHidden) or doesn't exist.
+    pub fn get_auto_hide_state_mut(&mut self, surface: SurfaceIndex) -> Option<&mut AutoHideState> {
+        match self.surfaces.get_mut(surface.0)? {
+            Surface::AutoHidden(_, state) => Some(state),
+            _ => None,
+        }
+    }
+
     /// Get the [`WindowState`] which corresponds to a [`SurfaceIndex`].
     ///
     /// Returns `None` if the surface is an [`Empty`](Surface::Empty), [`Main`](Surface::Main), or doesn't exist.
@@ -141,6 +340,46 @@ impl<Tab> DockState<Tab> {
         self.surfaces.get(surface.0)
     }
 
+    /// Finds the current index of the surface with the given [`SurfaceId`], which can then be passed to any method
+    /// taking a [`SurfaceIndex`].
+    ///
+    /// Returns [`None`] if the surface has been removed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui::Rect;
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex, TabInsert};
+    /// let mut dock_state = DockState::new(vec!["a", "b", "c"]);
+    /// let main = SurfaceIndex::main();
+    /// let first = dock_state.detach_tab((main, NodeIndex::root(), TabIndex(1)), Rect::NOTHING);
+    /// let second = dock_state.detach_tab((main, NodeIndex::root(), TabIndex(1)), Rect::NOTHING);
+    /// let second_id = dock_state.surface_id(second).unwrap();
+    ///
+    /// // The index of the first window is given to the next window, but ids aren't reused.
+    /// let first_id = dock_state.surface_id(first).unwrap();
+    /// dock_state.remove_surface(first);
+    /// let third = dock_state.add_window(vec!["d"]);
+    /// assert_eq!(third, first);
+    /// assert_eq!(dock_state.surface_by_id(first_id), None);
+    ///
+    /// let second = dock_state.surface_by_id(second_id).unwrap();
+    /// dock_state.move_tab((second, NodeIndex::root(), TabIndex(0)), (main, NodeIndex::root(), TabInsert::Append));
+    /// assert_eq!(dock_state.surface_by_id(second_id), None);
+    /// ```
+    pub fn surface_by_id(&self, id: SurfaceId) -> Option<SurfaceIndex> {
+        self.surfaces
+            .iter()
+            .position(|surface| surface.id() == Some(id))
+            .map(SurfaceIndex)
+    }
+
+    /// Returns the [`SurfaceId`] of the surface at the given index, or [`None`] if there's no surface there.
+    #[inline]
+    pub fn surface_id(&self, surface: SurfaceIndex) -> Option<SurfaceId> {
+        self.surfaces.get(surface.0)?.id()
+    }
+
     /// Returns true if the specified surface exists and isn't [`Empty`](Surface::Empty).
     #[inline]
     pub fn is_surface_valid(&self, surface_index: SurfaceIndex) -> bool {
@@ -149,15 +388,125 @@ impl<Tab> DockState<Tab> {
             .is_some_and(|surface| !surface.is_empty())
     }
 
-    /// Returns a list of all valid [`SurfaceIndex`]es.
+    /// Returns a list of all valid [`SurfaceIndex`]es, which stays the same when surfaces are added or removed later.
+    ///
+    /// Prefer [`DockState::valid_surface_index_iter`] or [`DockState::surface_index_iter`] when the list isn't needed
+    /// afterwards, as they don't allocate.
     #[inline]
     pub(crate) fn valid_surface_indices(&self) -> Box<[SurfaceIndex]> {
-        (0..self.surfaces.len())
-            .filter_map(|index| {
-                let index = SurfaceIndex(index);
-                self.is_surface_valid(index).then_some(index)
-            })
-            .collect()
+        self.valid_surface_index_iter().collect()
+    }
+
+    /// Returns an [`Iterator`] over the indices of all valid surfaces.
+    #[inline]
+    pub(crate) fn valid_surface_index_iter(&self) -> impl Iterator<Item = SurfaceIndex> + '_ {
+        self.surface_index_iter()
+            .filter(|&index| self.is_surface_valid(index))
+    }
+
+    /// Returns an [`Iterator`] over the indices of all surfaces, including [`Empty`](Surface::Empty) ones.
+    ///
+    /// It doesn't borrow the dock state, so that surfaces can be modified while iterating. Check each surface with
+    /// [`DockState::is_surface_valid`] before using it.
+    #[inline(always)]
+    pub(crate) fn surface_index_iter(&self) -> impl Iterator<Item = SurfaceIndex> {
+        (0..self.surfaces.len()).map(SurfaceIndex)
+    }
+
+    /// Returns the tree of a surface, or [`DockError::InvalidSurface`] if there's no such surface.
+    ///
+    /// This is a non-panicking alternative to indexing the [`DockState`] with a [`SurfaceIndex`].
+    pub fn try_surface(&self, surface: SurfaceIndex) -> Result<&Tree<Tab>, DockError> {
+        self.get_surface(surface)
+            .and_then(|surface| surface.node_tree())
+            .ok_or(DockError::InvalidSurface(surface))
+    }
+
+    /// Returns the tree of a surface mutably, or [`DockError::InvalidSurface`] if there's no such surface.
+    pub fn try_surface_mut(&mut self, surface: SurfaceIndex) -> Result<&mut Tree<Tab>, DockError> {
+        self.get_surface_mut(surface)
+            .and_then(|surface| surface.node_tree_mut())
+            .ok_or(DockError::InvalidSurface(surface))
+    }
+
+    /// Returns the node at `node` of `surface`, or an error if there's no such node.
+    pub(crate) fn check_node(
+        &self,
+        surface: SurfaceIndex,
+        node: NodeIndex,
+    ) -> Result<&Node<Tab>, DockError> {
+        self.try_surface(surface)?
+            .iter()
+            .nth(node.0)
+            .filter(|node| !node.is_empty())
+            .ok_or(DockError::InvalidNode(surface, node))
+    }
+
+    /// Returns the leaf at `node` of `surface`, or an error if there's no such leaf.
+    pub(crate) fn check_leaf(
+        &self,
+        surface: SurfaceIndex,
+        node: NodeIndex,
+    ) -> Result<&Node<Tab>, DockError> {
+        let leaf = self.check_node(surface, node)?;
+        if leaf.is_leaf() {
+            Ok(leaf)
+        } else {
+            Err(DockError::NotALeaf(surface, node))
+        }
+    }
+
+    /// Returns an error if there's no tab at the given index.
+    pub(crate) fn check_tab(
+        &self,
+        surface: SurfaceIndex,
+        node: NodeIndex,
+        tab: TabIndex,
+    ) -> Result<&Tab, DockError> {
+        self.check_leaf(surface, node)?
+            .tabs()
+            .and_then(|tabs| tabs.get(tab.0))
+            .ok_or(DockError::InvalidTab(surface, node, tab))
+    }
+
+    /// Returns an error if a tab can't be put at `destination`.
+    pub(crate) fn check_destination(&self, destination: TabDestination) -> Result<(), DockError> {
+        match destination {
+            TabDestination::Window(_) => {}
+            TabDestination::Node(surface, node, insert) => {
+                self.check_leaf(surface, node)?;
+                if let TabInsert::Insert(index) = insert {
+                    if index.0 > self[surface][node].tabs_count() {
+                        return Err(DockError::InvalidTab(surface, node, index));
+                    }
+                }
+            }
+            TabDestination::EmptySurface(surface) => {
+                if !self.try_surface(surface)?.is_empty() {
+                    return Err(DockError::SurfaceNotEmpty(surface));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns an error if the tab at `source` doesn't exist or can't be moved to `destination`.
+    pub(crate) fn check_move(
+        &self,
+        (surface, node, tab): (SurfaceIndex, NodeIndex, TabIndex),
+        destination: TabDestination,
+    ) -> Result<(), DockError> {
+        self.check_tab(surface, node, tab)?;
+        self.check_destination(destination)?;
+        // The tab is removed before being inserted, so its own node has one tab fewer to insert it between.
+        if let TabDestination::Node(dst_surface, dst_node, TabInsert::Insert(index)) = destination {
+            if (dst_surface, dst_node) == (surface, node)
+                && index.0 == self[surface][node].tabs_count()
+            {
+                return Err(DockError::InvalidTab(surface, node, index));
+            }
+        }
+        Ok(())
     }
 
     /// Remove a surface based on its [`SurfaceIndex`]
@@ -171,6 +520,7 @@ impl<Tab> DockState<Tab> {
         assert!(!surface_index.is_main());
         (surface_index.0 < self.surfaces.len()).then(|| {
             self.focused_surface = Some(SurfaceIndex::main());
+            self.forget_recent_tabs(surface_index);
             if surface_index.0 == self.surfaces.len() - 1 {
                 self.surfaces.pop().unwrap()
             } else {
@@ -180,17 +530,485 @@ impl<Tab> DockState<Tab> {
         })
     }
 
+    /// Removes a window like [`remove_surface`](Self::remove_surface), but returns an error instead of panicking
+    /// when trying to remove the main surface or a surface which doesn't exist.
+    pub fn try_remove_surface(
+        &mut self,
+        surface_index: SurfaceIndex,
+    ) -> Result<Surface<Tab>, DockError> {
+        if surface_index.is_main() {
+            return Err(DockError::MainSurface);
+        }
+        self.try_surface(surface_index)?;
+        Ok(self.remove_surface(surface_index).unwrap())
+    }
+
+    /// Moves all tabs of the window at `surface_index` into the main surface, keeping the layout of the window, and
+    /// removes the window.
+    ///
+    /// The root of the main surface is split on the `split` side like with [`Tree::split_tree`], so `fraction` is the
+    /// part of the main surface taken by the top or left child. If the main surface is empty, the window's layout
+    /// replaces it. Does nothing if `surface_index` doesn't refer to a window.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, Split};
+    /// let mut dock_state = DockState::new(vec!["a"]);
+    /// let window = dock_state.add_window(vec!["b", "c"]);
+    ///
+    /// dock_state.dock_window(window, Split::Left, 0.5);
+    /// assert_eq!(dock_state.main_surface().num_tabs(), 3);
+    /// assert_eq!(dock_state.iter_surfaces().count(), 1);
+    /// ```
+    pub fn dock_window(&mut self, surface_index: SurfaceIndex, split: Split, fraction: f32) {
+        if !matches!(
+            self.surfaces.get(surface_index.0),
+            Some(Surface::Window(..))
+        ) {
+            return;
+        }
+        let Some(Surface::Window(tree, _)) = self.remove_surface(surface_index) else {
+            return;
+        };
+        if tree.num_tabs() == 0 {
+            return;
+        }
+        let main = self.main_surface_mut();
+        if main.num_tabs() == 0 && main.iter().all(|node| !node.is_central()) {
+            *main = tree;
+        } else {
+            main.split_tree(NodeIndex::root(), split, fraction, tree);
+        }
+        self.focused_surface = Some(SurfaceIndex::main());
+    }
+
+    /// Moves a leaf into a strip along the `edge` of the main surface, from which it's shown over the main surface
+    /// until it loses focus, like auto-hidden panels of IDEs.
+    ///
+    /// The leaf keeps its active tab and becomes the root of a new [`Surface::AutoHidden`], whose index is returned.
+    /// It's as wide (or tall) when shown as it was before being hidden.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node isn't a leaf.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, Edge, NodeIndex, SurfaceIndex};
+    /// let mut dock_state = DockState::new(vec!["Editor"]);
+    /// let [_, explorer] = dock_state
+    ///     .main_surface_mut()
+    ///     .split_left(NodeIndex::root(), 0.2, vec!["Explorer"]);
+    ///
+    /// let surface = dock_state.set_auto_hide((SurfaceIndex::main(), explorer), Edge::Left);
+    /// assert_eq!(dock_state.main_surface().num_tabs(), 1);
+    ///
+    /// dock_state.dock_auto_hidden(surface);
+    /// assert_eq!(dock_state.main_surface().num_tabs(), 2);
+    /// ```
+    pub fn set_auto_hide(
+        &mut self,
+        (src_surface, src_node): (SurfaceIndex, NodeIndex),
+        edge: Edge,
+    ) -> SurfaceIndex {
+        let rect = self[src_surface][src_node].rect();
+        let size = rect
+            .filter(|rect| rect.is_positive() && rect.is_finite())
+            .map_or(Style::AUTO_HIDE_DEFAULT_SIZE, |rect| {
+                if edge.is_vertical() {
+                    rect.width()
+                } else {
+                    rect.height()
+                }
+            });
+        let Node::Leaf {
+            tabs,
+            active,
+            history,
+            scroll,
+            ..
+        } = &mut self[src_surface][src_node]
+        else {
+            panic!("only leaves can be auto-hidden");
+        };
+        let tabs = std::mem::take(tabs);
+        let active = std::mem::replace(active, TabIndex(0));
+        let history = std::mem::take(history);
+        let scroll = std::mem::take(scroll);
+
+        self.remove_leaf_following_recent((src_surface, src_node));
+        if self[src_surface].is_empty() && !src_surface.is_main() {
+            self.remove_surface(src_surface);
+        }
+
+        let mut tree = Tree::new(tabs);
+        if let Node::Leaf {
+            active: new_active,
+            history: new_history,
+            scroll: new_scroll,
+            ..
+        } = &mut tree[NodeIndex::root()]
+        {
+            *new_active = active;
+            *new_history = history;
+            *new_scroll = scroll;
+        }
+        let index = self.find_empty_surface_index();
+        let surface = Surface::AutoHidden(tree, AutoHideState::new(edge, size));
+        if index.0 < self.surfaces.len() {
+            self.surfaces[index.0] = surface;
+        } else {
+            self.surfaces.push(surface);
+        }
+        index
+    }
+
+    /// Auto-hides a leaf like [`set_auto_hide`](Self::set_auto_hide), but returns an error instead of panicking
+    /// when the leaf doesn't exist.
+    pub fn try_set_auto_hide(
+        &mut self,
+        (src_surface, src_node): (SurfaceIndex, NodeIndex),
+        edge: Edge,
+    ) -> Result<SurfaceIndex, DockError> {
+        self.check_leaf(src_surface, src_node)?;
+        Ok(self.set_auto_hide((src_surface, src_node), edge))
+    }
+
+    /// Docks an auto-hidden leaf back into the main surface on the side of its edge, taking about as much space as
+    /// it did when shown, and removes its surface. Does nothing if `surface_index` doesn't refer to an auto-hidden
+    /// leaf.
+    pub fn dock_auto_hidden(&mut self, surface_index: SurfaceIndex) {
+        let Some(state) = self.get_auto_hide_state(surface_index).cloned() else {
+            return;
+        };
+        let Some(Surface::AutoHidden(tree, _)) = self.remove_surface(surface_index) else {
+            return;
+        };
+        if tree.num_tabs() == 0 {
+            return;
+        }
+        let main = self.main_surface_mut();
+        if main.num_tabs() == 0 && main.iter().all(|node| !node.is_central()) {
+            *main = tree;
+        } else {
+            let main_size = main
+                .root_node()
+                .and_then(|root| root.rect())
+                .filter(|rect| rect.is_positive() && rect.is_finite())
+                .map(|rect| {
+                    if state.edge().is_vertical() {
+                        rect.width()
+                    } else {
+                        rect.height()
+                    }
+                });
+            let share = main_size.map_or(0.25, |main_size| {
+                (state.size() / main_size).clamp(0.05, 0.95)
+            });
+            let fraction = match state.edge() {
+                Edge::Left | Edge::Top => share,
+                Edge::Right | Edge::Bottom => 1.0 - share,
+            };
+            main.split_tree(NodeIndex::root(), state.edge().split(), fraction, tree);
+        }
+        self.focused_surface = Some(SurfaceIndex::main());
+    }
+
     /// Sets which is the active tab within a specific node on a given surface.
     #[inline]
     pub fn set_active_tab(
         &mut self,
         (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
     ) {
-        if let Some(Node::Leaf { active, .. }) = self[surface_index].nodes.get_mut(node_index.0) {
-            *active = tab_index;
+        self[surface_index].set_active_tab(node_index, tab_index);
+        self.touch_recent_tab((surface_index, node_index));
+    }
+
+    /// Sets the active tab like [`set_active_tab`](Self::set_active_tab), but returns an error instead of panicking
+    /// when the tab doesn't exist.
+    pub fn try_set_active_tab(
+        &mut self,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+    ) -> Result<(), DockError> {
+        self.check_tab(surface_index, node_index, tab_index)?;
+        self.set_active_tab((surface_index, node_index, tab_index));
+        Ok(())
+    }
+
+    /// Brings a tab to the front: makes it the active tab of its leaf, focuses the leaf and its surface, and scrolls
+    /// the tab bar so that the tab's title is visible the next time a [`DockArea`](crate::DockArea) is shown.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex};
+    /// let mut dock_state = DockState::new(vec!["a", "b", "c"]);
+    /// let tab = (SurfaceIndex::main(), NodeIndex::root(), TabIndex(2));
+    ///
+    /// dock_state.focus_tab(tab);
+    /// assert_eq!(dock_state.find_active_focused().map(|(_, tab)| *tab), Some("c"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the surface doesn't exist. Use [`try_focus_tab`](Self::try_focus_tab) to get an error instead, which
+    /// also checks that the node is a leaf and that it has the tab.
+    pub fn focus_tab(
+        &mut self,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+    ) {
+        self[surface_index].set_active_tab(node_index, tab_index);
+        self.set_focused_node_and_surface((surface_index, node_index));
+        self.scroll_to_tabs
+            .push((surface_index, node_index, tab_index));
+    }
+
+    /// Focuses a tab like [`focus_tab`](Self::focus_tab), but returns an error instead of panicking when the tab
+    /// doesn't exist.
+    pub fn try_focus_tab(
+        &mut self,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+    ) -> Result<(), DockError> {
+        self.check_tab(surface_index, node_index, tab_index)?;
+        self.focus_tab((surface_index, node_index, tab_index));
+        Ok(())
+    }
+
+    /// Scrolls the tab bar of a tab's leaf so that the tab's title is visible the next time a
+    /// [`DockArea`](crate::DockArea) is shown, without changing the active tab or the focus, unlike
+    /// [`focus_tab`](Self::focus_tab).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::DockState;
+    /// # let mut dock_state = DockState::new(vec!["a", "b"]);
+    /// dock_state.push_to_focused_leaf("log");
+    /// if let Some(tab) = dock_state.find_tab(&"log") {
+    ///     dock_state.scroll_tab_into_view(tab);
+    /// }
+    /// ```
+    #[inline]
+    pub fn scroll_tab_into_view(
+        &mut self,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+    ) {
+        self.scroll_to_tabs
+            .push((surface_index, node_index, tab_index));
+    }
+
+    /// Scrolls the tab bar of a leaf so that the title of its active tab is visible the next time a
+    /// [`DockArea`](crate::DockArea) is shown. Does nothing if the leaf has no tabs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node isn't a leaf.
+    pub fn scroll_active_tab_into_view(&mut self, (surface, node): (SurfaceIndex, NodeIndex)) {
+        let Node::Leaf { tabs, active, .. } = &self[surface][node] else {
+            panic!("node was not a leaf");
+        };
+        if active.0 < tabs.len() {
+            let active = *active;
+            self.scroll_tab_into_view((surface, node, active));
         }
     }
 
+    /// Returns how far the tab bar of a leaf is scrolled from its start, in points, or [`None`] if the leaf doesn't
+    /// exist.
+    ///
+    /// See [`Node::tab_bar_scroll`].
+    #[inline]
+    pub fn tab_bar_scroll(&self, (surface, node): (SurfaceIndex, NodeIndex)) -> Option<f32> {
+        self.check_leaf(surface, node).ok()?.tab_bar_scroll()
+    }
+
+    /// Scrolls the tab bar of a leaf `offset` points from its start, e.g. to restore a scroll position saved with
+    /// [`tab_bar_scroll`](Self::tab_bar_scroll).
+    ///
+    /// See [`Node::set_tab_bar_scroll`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node isn't a leaf.
+    #[inline]
+    pub fn set_tab_bar_scroll(&mut self, (surface, node): (SurfaceIndex, NodeIndex), offset: f32) {
+        self[surface][node].set_tab_bar_scroll(offset);
+    }
+
+    /// Scrolls the tab bar of a leaf like [`set_tab_bar_scroll`](Self::set_tab_bar_scroll), but returns an error
+    /// instead of panicking when the leaf doesn't exist.
+    pub fn try_set_tab_bar_scroll(
+        &mut self,
+        (surface, node): (SurfaceIndex, NodeIndex),
+        offset: f32,
+    ) -> Result<(), DockError> {
+        self.check_leaf(surface, node)?;
+        self.set_tab_bar_scroll((surface, node), offset);
+        Ok(())
+    }
+
+    /// Returns the tabs which should be scrolled into view since the last call, leaving out those which have been
+    /// moved or closed in the meantime.
+    pub(crate) fn take_scroll_to_tabs(&mut self) -> Vec<(SurfaceIndex, NodeIndex, TabIndex)> {
+        let mut tabs = std::mem::take(&mut self.scroll_to_tabs);
+        tabs.retain(|&(surface, node, tab)| self.check_tab(surface, node, tab).is_ok());
+        tabs
+    }
+
+    /// Smoothly changes the fraction of a [`Vertical`](Node::Vertical) or [`Horizontal`](Node::Horizontal) node
+    /// to `target` over `duration` seconds, replacing any animation already running on that node.
+    ///
+    /// The animation is advanced each time a [`DockArea`](crate::DockArea) showing this [`DockState`] is shown, and
+    /// a repaint is requested until it finishes. Dragging the node's separator cancels the animation.
+    ///
+    /// # Panics
+    ///
+    /// If `target` isn't in range 0..=1.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex};
+    /// let mut dock_state = DockState::new(vec!["a", "b"]);
+    /// dock_state
+    ///     .main_surface_mut()
+    ///     .split_left(NodeIndex::root(), 0.5, vec!["c"]);
+    ///
+    /// dock_state.animate_fraction((SurfaceIndex::main(), NodeIndex::root()), 0.2, 0.3);
+    /// assert!(dock_state.is_animating());
+    /// ```
+    pub fn animate_fraction(
+        &mut self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        target: f32,
+        duration: f32,
+    ) {
+        assert!((0.0..=1.0).contains(&target));
+        self.cancel_fraction_animation((surface_index, node_index));
+        self.animations.push(FractionAnimation {
+            surface: surface_index,
+            node: node_index,
+            target,
+            duration: duration.max(0.0),
+            start: None,
+        });
+    }
+
+    /// Returns `true` if any animation started with [`animate_fraction`](Self::animate_fraction) is still running.
+    #[inline]
+    pub fn is_animating(&self) -> bool {
+        !self.animations.is_empty()
+    }
+
+    /// Stops the fraction animation of the given node, leaving its fraction at the current value.
+    pub(crate) fn cancel_fraction_animation(
+        &mut self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+    ) {
+        self.animations
+            .retain(|animation| (animation.surface, animation.node) != (surface_index, node_index));
+    }
+
+    /// Advances all fraction animations to `time` (in seconds), dropping the ones which have finished or whose node
+    /// is no longer a parent.
+    ///
+    /// Returns `true` if any animation is still running.
+    pub(crate) fn step_animations(&mut self, time: f64) -> bool {
+        let mut animations = std::mem::take(&mut self.animations);
+        animations.retain_mut(|animation| {
+            let node = self
+                .surfaces
+                .get_mut(animation.surface.0)
+                .and_then(|surface| surface.node_tree_mut())
+                .and_then(|tree| tree.nodes.get_mut(animation.node.0));
+            match node {
+                Some(Node::Vertical { fraction, .. } | Node::Horizontal { fraction, .. }) => {
+                    let (value, finished) = animation.step(time, *fraction);
+                    *fraction = value;
+                    !finished
+                }
+                _ => false,
+            }
+        });
+        self.animations = animations;
+        self.is_animating()
+    }
+
+    /// Returns `true` if the tab with the given `id` (see [`TabViewer::id`](crate::TabViewer::id)) is waiting to be
+    /// closed after [`TabViewer::on_close`](crate::TabViewer::on_close) returned
+    /// [`OnCloseResponse::Pending`](crate::OnCloseResponse::Pending).
+    #[inline]
+    pub fn is_closing(&self, id: Id) -> bool {
+        self.closing_tabs.contains(&id)
+    }
+
+    /// Closes a tab which is [waiting to be closed](Self::is_closing), e.g. after the user confirmed discarding
+    /// unsaved changes.
+    ///
+    /// The tab is removed the next time the [`DockArea`](crate::DockArea) is shown, which reports it with
+    /// [`DockEvent::TabClosed`](crate::DockEvent::TabClosed). Does nothing if the tab isn't waiting to be closed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, OnCloseResponse, TabViewer};
+    /// # use egui::{Id, Ui, WidgetText};
+    /// struct Editor {
+    ///     // Tabs the user should be asked about.
+    ///     unsaved: Vec<Id>,
+    /// }
+    ///
+    /// impl TabViewer for Editor {
+    ///     type Tab = String;
+    ///     # fn title(&mut self, tab: &mut String) -> WidgetText { tab.as_str().into() }
+    ///     # fn ui(&mut self, ui: &mut Ui, tab: &mut String) {}
+    ///
+    ///     fn on_close(&mut self, tab: &mut String) -> OnCloseResponse {
+    ///         // Show a dialog asking for confirmation somewhere else.
+    ///         let id = self.id(tab);
+    ///         self.unsaved.push(id);
+    ///         OnCloseResponse::Pending
+    ///     }
+    /// }
+    ///
+    /// // When the user answers the dialog:
+    /// # let mut dock_state = DockState::new(vec!["notes.txt".to_owned()]);
+    /// # let mut editor = Editor { unsaved: vec![Id::new("notes.txt")] };
+    /// # let discard = true;
+    /// for id in editor.unsaved.drain(..) {
+    ///     if discard {
+    ///         dock_state.confirm_close(id);
+    ///     } else {
+    ///         dock_state.cancel_close(id);
+    ///     }
+    /// }
+    /// ```
+    pub fn confirm_close(&mut self, id: Id) {
+        if let Some(index) = self.closing_tabs.iter().position(|&closing| closing == id) {
+            self.closing_tabs.remove(index);
+            self.confirmed_closes.push(id);
+        }
+    }
+
+    /// Keeps a tab which is [waiting to be closed](Self::is_closing) open.
+    pub fn cancel_close(&mut self, id: Id) {
+        self.closing_tabs.retain(|&closing| closing != id);
+    }
+
+    /// Marks the tab with the given `id` as waiting to be closed.
+    pub(crate) fn mark_closing(&mut self, id: Id) {
+        if !self.is_closing(id) {
+            self.closing_tabs.push(id);
+        }
+    }
+
+    /// Returns the ids of tabs whose closing has been confirmed since the last call.
+    pub(crate) fn take_confirmed_closes(&mut self) -> Vec<Id> {
+        std::mem::take(&mut self.confirmed_closes)
+    }
+
     /// Sets the currently focused leaf to `node_index` if the node at `node_index` is a leaf.
     #[inline]
     pub fn set_focused_node_and_surface(
@@ -202,20 +1020,55 @@ impl<Tab> DockState<Tab> {
             if self[surface_index][node_index].is_leaf() {
                 self.focused_surface = Some(surface_index);
                 self[surface_index].set_focused_node(node_index);
+                self.touch_recent_tab((surface_index, node_index));
                 return;
             }
         }
         self.focused_surface = None;
     }
 
+    /// Makes the leaf at `node_index` on the main surface the central node, which always exists,
+    /// even when all of its tabs are closed or moved away.
+    ///
+    /// See [`Tree::set_central_node`].
+    #[inline]
+    pub fn set_central_node(&mut self, node_index: NodeIndex) {
+        self[SurfaceIndex::main()].set_central_node(node_index);
+    }
+
+    /// Sets the central node like [`set_central_node`](Self::set_central_node), but returns an error instead of
+    /// panicking when the node isn't a leaf of the main surface.
+    pub fn try_set_central_node(&mut self, node_index: NodeIndex) -> Result<(), DockError> {
+        self.check_leaf(SurfaceIndex::main(), node_index)?;
+        self.set_central_node(node_index);
+        Ok(())
+    }
+
+    /// Returns the index of the central node of the main surface, if there is one.
+    #[inline]
+    pub fn central_node(&self) -> Option<NodeIndex> {
+        self[SurfaceIndex::main()].central_node()
+    }
+
     /// Moves a tab from a node to another node.
     /// You need to specify with [`TabDestination`] how the tab should be moved.
     pub fn move_tab(
         &mut self,
-        (src_surface, src_node, src_tab): (SurfaceIndex, NodeIndex, TabIndex),
+        source: (SurfaceIndex, NodeIndex, TabIndex),
         dst_tab: impl Into<TabDestination>,
     ) {
-        match dst_tab.into() {
+        self.move_tab_with_split_fraction(source, dst_tab.into(), 0.5);
+    }
+
+    /// Moves a tab like [`move_tab`](Self::move_tab), giving it `split_fraction` of the area of the destination node
+    /// if it's split.
+    pub(crate) fn move_tab_with_split_fraction(
+        &mut self,
+        (src_surface, src_node, src_tab): (SurfaceIndex, NodeIndex, TabIndex),
+        dst_tab: TabDestination,
+        split_fraction: f32,
+    ) {
+        match dst_tab {
             TabDestination::Window(position) => {
                 self.detach_tab((src_surface, src_node, src_tab), position);
                 return;
@@ -231,29 +1084,144 @@ impl<Tab> DockState<Tab> {
 
                 // Call `Node::remove_tab` to avoid auto remove of the node by `Tree::remove_tab` from Tree.
                 let tab = self[src_surface][src_node].remove_tab(src_tab).unwrap();
+                self.forget_recent_tab((src_surface, src_node, src_tab));
                 match dst_tab {
                     TabInsert::Split(split) => {
-                        self[dst_surface].split(dst_node, split, 0.5, Node::leaf(tab));
+                        self[dst_surface].split(
+                            dst_node,
+                            split,
+                            split.fraction_for_new_node(split_fraction),
+                            Node::leaf(tab),
+                        );
                     }
 
                     TabInsert::Insert(index) => self[dst_surface][dst_node].insert_tab(index, tab),
                     TabInsert::Append => self[dst_surface][dst_node].append_tab(tab),
+                    TabInsert::Explode => {
+                        self[dst_surface][dst_node].append_tab(tab);
+                        self[dst_surface].explode_leaf(dst_node);
+                    }
                 }
             }
             TabDestination::EmptySurface(dst_surface) => {
                 assert!(self[dst_surface].is_empty());
                 let tab = self[src_surface][src_node].remove_tab(src_tab).unwrap();
+                self.forget_recent_tab((src_surface, src_node, src_tab));
                 self[dst_surface] = Tree::new(vec![tab])
             }
         }
         if self[src_surface][src_node].is_leaf() && self[src_surface][src_node].tabs_count() == 0 {
-            self[src_surface].remove_leaf(src_node);
+            self.remove_leaf_following_recent((src_surface, src_node));
         }
         if self[src_surface].is_empty() && !src_surface.is_main() {
             self.remove_surface(src_surface);
         }
     }
 
+    /// Moves a tab like [`move_tab`](Self::move_tab), but returns an error instead of panicking when the tab or the
+    /// destination doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockError, DockState, NodeIndex, SurfaceIndex, TabIndex, TabInsert};
+    /// let mut dock_state = DockState::new(vec!["a", "b"]);
+    /// let main = SurfaceIndex::main();
+    ///
+    /// let result = dock_state.try_move_tab(
+    ///     (main, NodeIndex::root(), TabIndex(5)),
+    ///     (main, NodeIndex::root(), TabInsert::Append),
+    /// );
+    /// assert_eq!(result, Err(DockError::InvalidTab(main, NodeIndex::root(), TabIndex(5))));
+    /// ```
+    pub fn try_move_tab(
+        &mut self,
+        source: (SurfaceIndex, NodeIndex, TabIndex),
+        dst_tab: impl Into<TabDestination>,
+    ) -> Result<(), DockError> {
+        let destination = dst_tab.into();
+        self.check_move(source, destination)?;
+        self.move_tab(source, destination);
+        Ok(())
+    }
+
+    /// Puts a new `tab` at the given destination, the same way [`move_tab`](Self::move_tab) would put an existing
+    /// one there.
+    ///
+    /// Returns the surface index the tab has ended up in.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, Split, SurfaceIndex, TabInsert};
+    /// let mut dock_state = DockState::new(vec!["a"]);
+    /// let surface = dock_state.insert_tab(
+    ///     "b",
+    ///     (SurfaceIndex::main(), NodeIndex::root(), TabInsert::Split(Split::Right)),
+    /// );
+    ///
+    /// assert_eq!(surface, SurfaceIndex::main());
+    /// assert_eq!(dock_state.main_surface().num_tabs(), 2);
+    /// ```
+    pub fn insert_tab(&mut self, tab: Tab, destination: impl Into<TabDestination>) -> SurfaceIndex {
+        self.insert_tab_with_split_fraction(tab, destination.into(), 0.5)
+    }
+
+    /// Puts a new tab like [`insert_tab`](Self::insert_tab), giving it `split_fraction` of the area of the destination
+    /// node if it's split.
+    pub(crate) fn insert_tab_with_split_fraction(
+        &mut self,
+        tab: Tab,
+        destination: TabDestination,
+        split_fraction: f32,
+    ) -> SurfaceIndex {
+        match destination {
+            TabDestination::Window(window_rect) => {
+                let surface_index = self.add_window(vec![tab]);
+                let state = self.get_window_state_mut(surface_index).unwrap();
+                state.set_position(window_rect.min);
+                state.set_size(window_rect.size());
+                surface_index
+            }
+            TabDestination::Node(dst_surface, dst_node, dst_tab) => {
+                match dst_tab {
+                    TabInsert::Split(split) => {
+                        self[dst_surface].split(
+                            dst_node,
+                            split,
+                            split.fraction_for_new_node(split_fraction),
+                            Node::leaf(tab),
+                        );
+                    }
+                    TabInsert::Insert(index) => self[dst_surface][dst_node].insert_tab(index, tab),
+                    TabInsert::Append => self[dst_surface][dst_node].append_tab(tab),
+                    TabInsert::Explode => {
+                        self[dst_surface][dst_node].append_tab(tab);
+                        self[dst_surface].explode_leaf(dst_node);
+                    }
+                }
+                dst_surface
+            }
+            TabDestination::EmptySurface(dst_surface) => {
+                assert!(self[dst_surface].is_empty());
+                self[dst_surface] = Tree::new(vec![tab]);
+                dst_surface
+            }
+        }
+    }
+
+    /// Puts a new tab like [`insert_tab`](Self::insert_tab), but returns an error instead of panicking when the
+    /// destination doesn't exist.
+    pub fn try_insert_tab(
+        &mut self,
+        tab: Tab,
+        destination: impl Into<TabDestination>,
+    ) -> Result<SurfaceIndex, DockError> {
+        let destination = destination.into();
+        self.check_destination(destination)?;
+        Ok(self.insert_tab(tab, destination))
+    }
+
     /// Takes a tab out of its current surface and puts it in a new window.
     /// Returns the surface index of the new window.
     pub fn detach_tab(
@@ -263,6 +1231,7 @@ impl<Tab> DockState<Tab> {
     ) -> SurfaceIndex {
         // Remove the tab from the tree and it add to a new window.
         let tab = self[src_surface][src_node].remove_tab(src_tab).unwrap();
+        self.forget_recent_tab((src_surface, src_node, src_tab));
         let surface_index = self.add_window(vec![tab]);
 
         // Set the window size and position to match `window_rect`.
@@ -276,7 +1245,7 @@ impl<Tab> DockState<Tab> {
 
         // Clean up any empty leaves and surfaces which may be left behind from the detachment.
         if self[src_surface][src_node].is_leaf() && self[src_surface][src_node].tabs_count() == 0 {
-            self[src_surface].remove_leaf(src_node);
+            self.remove_leaf_following_recent((src_surface, src_node));
         }
         if self[src_surface].is_empty() && !src_surface.is_main() {
             self.remove_surface(src_surface);
@@ -284,6 +1253,175 @@ impl<Tab> DockState<Tab> {
         surface_index
     }
 
+    /// Takes all tabs out of a leaf and puts them in a new window, keeping the active tab and the scroll offset of
+    /// the tab bar. The leaf is then removed, unless it's the [central node](crate::Tree::set_central_node).
+    /// Returns the surface index of the new window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node at the given index isn't a leaf.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui::Rect;
+    /// # use egui_dock::{DockState, NodeIndex, Split, SurfaceIndex, TabIndex};
+    /// let mut dock_state = DockState::new(vec!["editor"]);
+    /// let [_, tools] = dock_state
+    ///     .main_surface_mut()
+    ///     .split_right(NodeIndex::root(), 0.7, vec!["files", "search"]);
+    /// dock_state.set_active_tab((SurfaceIndex::main(), tools, TabIndex(1)));
+    ///
+    /// let window = dock_state.detach_leaf((SurfaceIndex::main(), tools), Rect::NOTHING);
+    /// assert_eq!(dock_state.main_surface().num_tabs(), 1);
+    /// assert_eq!(
+    ///     dock_state[window][NodeIndex::root()].tabs(),
+    ///     Some(&["files", "search"][..])
+    /// );
+    /// assert_eq!(dock_state[window].find_active().map(|(_, tab)| *tab), Some("search"));
+    /// ```
+    pub fn detach_leaf(
+        &mut self,
+        (src_surface, src_node): (SurfaceIndex, NodeIndex),
+        window_rect: Rect,
+    ) -> SurfaceIndex {
+        let was_focused = self.focused_leaf() == Some((src_surface, src_node));
+        let Node::Leaf {
+            tabs,
+            active,
+            history,
+            scroll,
+            ..
+        } = &mut self[src_surface][src_node]
+        else {
+            panic!("only leaves can be detached");
+        };
+        let tabs = std::mem::take(tabs);
+        let active = std::mem::replace(active, TabIndex(0));
+        let history = std::mem::take(history);
+        let scroll = std::mem::take(scroll);
+
+        // Clean up first, so that the window may take the place of the one it's detached from.
+        self.remove_leaf_following_recent((src_surface, src_node));
+        if self[src_surface].is_empty() && !src_surface.is_main() {
+            self.remove_surface(src_surface);
+        }
+
+        let surface_index = self.add_window(tabs);
+        if let Node::Leaf {
+            active: new_active,
+            history: new_history,
+            scroll: new_scroll,
+            ..
+        } = &mut self[surface_index][NodeIndex::root()]
+        {
+            *new_active = active;
+            *new_history = history;
+            *new_scroll = scroll;
+        }
+        if was_focused {
+            self.set_focused_node_and_surface((surface_index, NodeIndex::root()));
+        }
+
+        // Set the window size and position to match `window_rect`.
+        let state = self.get_window_state_mut(surface_index).unwrap();
+        state.set_position(window_rect.min);
+        if src_surface.is_main() {
+            state.set_size(window_rect.size() * 0.8);
+        } else {
+            state.set_size(window_rect.size());
+        }
+        surface_index
+    }
+
+    /// Detaches a leaf like [`detach_leaf`](Self::detach_leaf), but returns an error instead of panicking when the
+    /// leaf doesn't exist.
+    pub fn try_detach_leaf(
+        &mut self,
+        (src_surface, src_node): (SurfaceIndex, NodeIndex),
+        window_rect: Rect,
+    ) -> Result<SurfaceIndex, DockError> {
+        self.check_leaf(src_surface, src_node)?;
+        Ok(self.detach_leaf((src_surface, src_node), window_rect))
+    }
+
+    /// Detaches a tab like [`detach_tab`](Self::detach_tab), but returns an error instead of panicking when the tab
+    /// doesn't exist.
+    pub fn try_detach_tab(
+        &mut self,
+        (src_surface, src_node, src_tab): (SurfaceIndex, NodeIndex, TabIndex),
+        window_rect: Rect,
+    ) -> Result<SurfaceIndex, DockError> {
+        self.check_tab(src_surface, src_node, src_tab)?;
+        Ok(self.detach_tab((src_surface, src_node, src_tab), window_rect))
+    }
+
+    /// Hides or shows the tab bar of a leaf, e.g. to give the whole leaf to a central viewport.
+    ///
+    /// See [`Node::set_tab_bar_hidden`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node isn't a leaf.
+    #[inline]
+    pub fn set_tab_bar_hidden(&mut self, (surface, node): (SurfaceIndex, NodeIndex), hidden: bool) {
+        self[surface][node].set_tab_bar_hidden(hidden);
+    }
+
+    /// Hides or shows the tab bar of a leaf like [`set_tab_bar_hidden`](Self::set_tab_bar_hidden), but returns an
+    /// error instead of panicking when the leaf doesn't exist.
+    pub fn try_set_tab_bar_hidden(
+        &mut self,
+        (surface, node): (SurfaceIndex, NodeIndex),
+        hidden: bool,
+    ) -> Result<(), DockError> {
+        self.check_leaf(surface, node)?;
+        self.set_tab_bar_hidden((surface, node), hidden);
+        Ok(())
+    }
+
+    /// Makes a leaf draw its tab bar with the given `style` instead of [`Style::tab_bar`](crate::Style::tab_bar),
+    /// e.g. a thinner bar for a tool panel, or go back to the global style when `style` is [`None`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node isn't a leaf.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, Style, SurfaceIndex};
+    /// # let mut dock_state = DockState::new(vec!["viewport"]);
+    /// let [_, tools] = dock_state
+    ///     .main_surface_mut()
+    ///     .split_right(NodeIndex::root(), 0.8, vec!["tools"]);
+    ///
+    /// let mut tab_bar = Style::default().tab_bar;
+    /// tab_bar.height = 18.0;
+    /// dock_state.set_tab_bar_style((SurfaceIndex::main(), tools), Some(tab_bar));
+    /// dock_state.set_tab_bar_hidden((SurfaceIndex::main(), NodeIndex::root().left()), true);
+    /// ```
+    #[inline]
+    pub fn set_tab_bar_style(
+        &mut self,
+        (surface, node): (SurfaceIndex, NodeIndex),
+        style: Option<TabBarStyle>,
+    ) {
+        self[surface][node].set_tab_bar_style(style);
+    }
+
+    /// Sets the style of the tab bar of a leaf like [`set_tab_bar_style`](Self::set_tab_bar_style), but returns an
+    /// error instead of panicking when the leaf doesn't exist.
+    pub fn try_set_tab_bar_style(
+        &mut self,
+        (surface, node): (SurfaceIndex, NodeIndex),
+        style: Option<TabBarStyle>,
+    ) -> Result<(), DockError> {
+        self.check_leaf(surface, node)?;
+        self.set_tab_bar_style((surface, node), style);
+        Ok(())
+    }
+
     /// Currently focused leaf.
     #[inline]
     pub fn focused_leaf(&self) -> Option<(SurfaceIndex, NodeIndex)> {
@@ -291,6 +1429,15 @@ impl<Tab> DockState<Tab> {
         self[surface].focused_leaf().map(|leaf| (surface, leaf))
     }
 
+    /// Returns the window containing the currently focused leaf, or [`None`] if the focused leaf is in the main
+    /// surface or nothing is focused.
+    #[inline]
+    pub fn focused_window(&self) -> Option<SurfaceIndex> {
+        self.focused_leaf()
+            .map(|(surface, _)| surface)
+            .filter(|surface| !surface.is_main())
+    }
+
     /// Remove a tab at the specified surface, node, and tab index.
     /// This method will yield the removed tab, or `None` if it doesn't exist.
     pub fn remove_tab(
@@ -298,12 +1445,41 @@ impl<Tab> DockState<Tab> {
         (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
     ) -> Option<Tab> {
         let removed_tab = self[surface_index].remove_tab((node_index, tab_index));
+        self.forget_recent_tab((surface_index, node_index, tab_index));
         if !surface_index.is_main() && self[surface_index].is_empty() {
             self.remove_surface(surface_index);
         }
         removed_tab
     }
 
+    /// Removes a tab like [`remove_tab`](Self::remove_tab), but returns an error instead of panicking when the tab
+    /// doesn't exist.
+    pub fn try_remove_tab(
+        &mut self,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+    ) -> Result<Tab, DockError> {
+        self.check_tab(surface_index, node_index, tab_index)?;
+        Ok(self
+            .remove_tab((surface_index, node_index, tab_index))
+            .unwrap())
+    }
+
+    /// Removes a leaf with all of its tabs like [`Tree::remove_leaf`], but returns an error instead of panicking when
+    /// the leaf doesn't exist.
+    ///
+    /// Removes the surface as well if it's a window left without nodes.
+    pub fn try_remove_leaf(
+        &mut self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+    ) -> Result<(), DockError> {
+        self.check_leaf(surface_index, node_index)?;
+        self.remove_leaf_following_recent((surface_index, node_index));
+        if !surface_index.is_main() && self[surface_index].is_empty() {
+            self.remove_surface(surface_index);
+        }
+        Ok(())
+    }
+
     /// Creates two new nodes by splitting a given `parent` node and assigns them as its children. The first (old) node
     /// inherits content of the `parent` from before the split, and the second (new) has `tabs`.
     ///
@@ -325,6 +1501,25 @@ impl<Tab> DockState<Tab> {
         index
     }
 
+    /// Splits a node like [`split`](Self::split), but returns an error instead of panicking when the node doesn't
+    /// exist, `fraction` isn't in the range `0.0..=1.0`, or `new` has no tabs.
+    pub fn try_split(
+        &mut self,
+        (surface, parent): (SurfaceIndex, NodeIndex),
+        split: Split,
+        fraction: f32,
+        new: Node<Tab>,
+    ) -> Result<[NodeIndex; 2], DockError> {
+        self.check_node(surface, parent)?;
+        if !(0.0..=1.0).contains(&fraction) {
+            return Err(DockError::InvalidFraction(fraction));
+        }
+        if new.tabs_count() == 0 {
+            return Err(DockError::NoTabs);
+        }
+        Ok(self.split((surface, parent), split, fraction, new))
+    }
+
     /// Adds a window with its own list of tabs.
     ///
     /// Returns the [`SurfaceIndex`] of the new window, which will remain constant through the windows lifetime.
@@ -477,6 +1672,7 @@ impl<Tab> DockState<Tab> {
             surfaces,
             focused_surface,
             translations,
+            ..
         } = self;
         let surfaces = surfaces
             .iter()
@@ -488,6 +1684,17 @@ impl<Tab> DockState<Tab> {
         DockState {
             surfaces,
             focused_surface: *focused_surface,
+            animations: Vec::new(),
+            collapse_changes: Vec::new(),
+            closing_tabs: Vec::new(),
+            confirmed_closes: Vec::new(),
+            selected_tabs: Vec::new(),
+            history: None,
+            recent_tabs: Vec::new(),
+            last_metrics: None,
+            surface_stack: Vec::new(),
+            current_drag: None,
+            scroll_to_tabs: Vec::new(),
             translations: translations.clone(),
         }
     }
@@ -529,24 +1736,98 @@ impl<Tab> DockState<Tab> {
     }
 
     /// Removes all tabs for which `predicate` returns `false`.
-    /// Any remaining empty [`Node`]s and [`Surface`]s are also removed.
+    /// Any remaining empty [`Node`]s and windows are also removed.
+    ///
+    /// Same as [`retain_all_tabs`](Self::retain_all_tabs).
+    #[deprecated = "Use `retain_all_tabs` instead"]
+    #[inline]
+    pub fn retain_tabs<F>(&mut self, predicate: F)
+    where
+        F: FnMut(&mut Tab) -> bool,
+    {
+        self.retain_all_tabs(predicate);
+    }
+
+    /// Removes all tabs for which `predicate` returns `false` from every surface.
+    ///
+    /// Leaves left without tabs are removed, unless they're the [central node](Tree::set_central_node), and so are
+    /// windows and auto-hidden leaves left without any. The main surface is always kept, and the indices of the
+    /// remaining surfaces don't change. If the focused leaf is removed, the most recently used tab which is left gets
+    /// focused. The [recently used tabs](Self::recent_tabs) follow the tabs which are left to their new places.
+    ///
+    /// # Examples
     ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex};
+    /// let mut dock_state = DockState::new(vec!["editor", "preview"]);
+    /// let first = dock_state.add_window(vec!["preview"]);
+    /// let second = dock_state.add_window(vec!["terminal"]);
+    /// dock_state.set_focused_node_and_surface((SurfaceIndex::main(), NodeIndex::root()));
+    /// dock_state.set_focused_node_and_surface((first, NodeIndex::root()));
+    ///
+    /// dock_state.retain_all_tabs(|tab| *tab != "preview");
+    ///
+    /// assert!(!dock_state.is_surface_valid(first));
+    /// assert_eq!(dock_state[second][NodeIndex::root()].tabs(), Some(&["terminal"][..]));
+    /// assert_eq!(dock_state.main_surface().num_tabs(), 1);
+    /// assert_eq!(dock_state.focused_leaf(), Some((SurfaceIndex::main(), NodeIndex::root())));
     /// ```
-    /// # use egui_dock::{DockState, Node};
-    /// let mut dock_state = DockState::new(["tab1", "tab2", "outlier"].map(str::to_string).to_vec());
-    /// dock_state.retain_tabs(|tab| tab.starts_with("tab"));
     ///
-    /// let tabs: Vec<_> = dock_state.iter_all_tabs().map(|(_, tab)| tab.to_owned()).collect();
-    /// assert_eq!(tabs, vec!["tab1".to_string(), "tab2".to_string()]);
+    /// Tabs which are left keep their place in the history of the surface, even when their leaves move:
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex};
+    /// let mut dock_state = DockState::new(vec!["a", "b"]);
+    /// let main = SurfaceIndex::main();
+    /// let [_, right] = dock_state.main_surface_mut().split_right(NodeIndex::root(), 0.5, vec!["c"]);
+    /// let [_, below] = dock_state.main_surface_mut().split_below(right, 0.5, vec!["d", "e"]);
+    ///
+    /// for (node, tab) in [(NodeIndex::root().left(), 1), (below, 1), (right.left(), 0)] {
+    ///     dock_state.set_focused_node_and_surface((main, node));
+    ///     dock_state.set_active_tab((main, node, TabIndex(tab)));
+    /// }
+    ///
+    /// // Removing "c" moves the leaf of "d" and "e" up, and the leaf it was focused in is gone.
+    /// dock_state.retain_all_tabs(|tab| *tab != "c" && *tab != "d");
+    ///
+    /// let focused = dock_state.focused_leaf().unwrap();
+    /// assert_eq!(dock_state[main][focused.1].tabs(), Some(&["e"][..]));
+    /// let recent: Vec<_> = dock_state.recent_tabs().collect();
+    /// let left = NodeIndex::root().left();
+    /// assert_eq!(recent, [(main, right, TabIndex(0)), (main, left, TabIndex(1)), (main, left, TabIndex(0))]);
     /// ```
-    pub fn retain_tabs<F>(&mut self, mut predicate: F)
+    pub fn retain_all_tabs<F>(&mut self, mut predicate: F)
     where
         F: FnMut(&mut Tab) -> bool,
     {
-        self.surfaces.retain_mut(|surface| {
-            surface.retain_tabs(&mut predicate);
-            !surface.is_empty()
-        });
+        let had_focus = self.focused_leaf().is_some();
+        for surface_index in self.valid_surface_indices().iter().copied() {
+            if !self.retain_surface_tabs(surface_index, &mut predicate) {
+                continue;
+            }
+            if !surface_index.is_main() && self[surface_index].num_tabs() == 0 {
+                self.remove_surface(surface_index);
+            }
+        }
+
+        let focus_kept = self
+            .focused_leaf()
+            .is_some_and(|(surface, node)| self.check_leaf(surface, node).is_ok());
+        if had_focus && !focus_kept {
+            self.focused_surface = None;
+            self.focus_recent_tab();
+        }
+    }
+
+    /// Sets the fractions of all split nodes in every surface so that all leaves of a surface get the same area.
+    ///
+    /// See [`Tree::equalize_fractions`].
+    pub fn equalize_all(&mut self) {
+        for surface in self.surfaces.iter_mut() {
+            if let Some(tree) = surface.node_tree_mut() {
+                tree.equalize_fractions();
+            }
+        }
     }
 }
 
@@ -562,9 +1843,10 @@ where
     ///
     /// In case there are several hits, only the first is returned.
     ///
-    /// See also: [`find_main_surface_tab`](DockState::find_main_surface_tab)
+    /// See also: [`find_main_surface_tab`](DockState::find_main_surface_tab) and
+    /// [`find_tab_by_id`](DockState::find_tab_by_id), which doesn't require tabs to implement [`PartialEq`].
     pub fn find_tab(&self, needle_tab: &Tab) -> Option<(SurfaceIndex, NodeIndex, TabIndex)> {
-        for &surface_index in self.valid_surface_indices().iter() {
+        for surface_index in self.valid_surface_index_iter() {
             if !self.surfaces[surface_index.0].is_empty() {
                 if let Some((node_index, tab_index)) = self[surface_index].find_tab(needle_tab) {
                     return Some((surface_index, node_index, tab_index));
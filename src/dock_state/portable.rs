@@ -0,0 +1,105 @@
+use crate::DockState;
+
+/// A small, serializable stand-in for a tab, like the name of a panel or the path of an opened file.
+///
+/// Created from tabs with [`TabCodec::encode`], and turned back into tabs with [`TabCodec::decode`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TabPayload(pub String);
+
+impl TabPayload {
+    /// Returns the payload as a string slice.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for TabPayload {
+    #[inline]
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for TabPayload {
+    #[inline]
+    fn from(value: &str) -> Self {
+        Self(value.to_owned())
+    }
+}
+
+/// A [`DockState`] in which every tab is replaced by its [`TabPayload`], created with
+/// [`DockState::to_portable`].
+///
+/// Unlike the original one, it can be saved with `serde` (with the `serde` feature enabled) even if the tabs can't,
+/// and turned back into a [`DockState`] with [`DockState::from_portable`].
+pub type PortableDockState = DockState<TabPayload>;
+
+/// Converts tabs to and from [`TabPayload`]s, so that a [`DockState`] can be saved even if its tabs can't be
+/// serialized themselves, e.g. because they hold GPU handles.
+///
+/// # Examples
+///
+/// ```rust
+/// # use egui_dock::{DockState, TabCodec, TabPayload};
+/// struct Panel {
+///     name: &'static str,
+///     texture: Option<u64>, // Something which can't be saved.
+/// }
+///
+/// struct PanelCodec;
+///
+/// impl TabCodec for PanelCodec {
+///     type Tab = Panel;
+///
+///     fn encode(&self, tab: &Panel) -> TabPayload {
+///         tab.name.into()
+///     }
+///
+///     fn decode(&self, payload: TabPayload) -> Option<Panel> {
+///         let name = ["Scene", "Inspector"].into_iter().find(|name| *name == payload.as_str())?;
+///         Some(Panel { name, texture: None })
+///     }
+/// }
+///
+/// let dock_state = DockState::new(vec![
+///     Panel { name: "Scene", texture: Some(1) },
+///     Panel { name: "Inspector", texture: None },
+/// ]);
+/// let portable = dock_state.to_portable(&PanelCodec);
+///
+/// // `portable` can now be serialized, and later restored:
+/// let restored = DockState::from_portable(&portable, &PanelCodec);
+/// let names: Vec<_> = restored.iter_all_tabs().map(|(_, tab)| tab.name).collect();
+/// assert_eq!(names, ["Scene", "Inspector"]);
+/// ```
+pub trait TabCodec {
+    /// The type of tab this codec converts.
+    type Tab;
+
+    /// Creates the payload by which `tab` is saved.
+    fn encode(&self, tab: &Self::Tab) -> TabPayload;
+
+    /// Creates a tab from a saved `payload`, or returns [`None`] if it can't be restored, in which case the tab is
+    /// left out.
+    fn decode(&self, payload: TabPayload) -> Option<Self::Tab>;
+}
+
+impl<Tab> DockState<Tab> {
+    /// Replaces every tab with its [`TabPayload`] made by `codec`, keeping the layout as it is.
+    ///
+    /// See [`TabCodec`] for an example.
+    pub fn to_portable(&self, codec: &impl TabCodec<Tab = Tab>) -> PortableDockState {
+        self.map_tabs(|tab| codec.encode(tab))
+    }
+
+    /// Restores a [`DockState`] saved with [`to_portable`](Self::to_portable), creating its tabs from their payloads
+    /// with `codec`.
+    ///
+    /// Tabs which `codec` fails to decode are left out, like with [`filter_map_tabs`](Self::filter_map_tabs), along
+    /// with any nodes and surfaces left empty.
+    pub fn from_portable(portable: &PortableDockState, codec: &impl TabCodec<Tab = Tab>) -> Self {
+        portable.filter_map_tabs(|payload| codec.decode(payload.clone()))
+    }
+}
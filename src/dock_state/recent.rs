@@ -0,0 +1,248 @@
+use crate::{DockState, Node, NodeIndex, SurfaceIndex, TabIndex};
+
+/// How many activations are remembered by [`DockState::recent_tabs`].
+const RECENT_TABS_LIMIT: usize = 64;
+
+impl<Tab> DockState<Tab> {
+    /// Returns the tabs which have been activated, from the most recently used one.
+    ///
+    /// A tab is used when it becomes the active tab of the focused leaf, either by clicking it in a
+    /// [`DockArea`](crate::DockArea) or with [`DockState::set_active_tab`] and
+    /// [`DockState::set_focused_node_and_surface`]. Entries follow tabs as other tabs in their leaf are closed or
+    /// moved away, as other leaves are removed and as a [`LayoutSnapshot`](crate::LayoutSnapshot) is applied, and
+    /// tabs which no longer exist are skipped.
+    ///
+    /// When the focused leaf is closed in a [`DockArea`](crate::DockArea), the most recently used tab which is left
+    /// is focused.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex};
+    /// let mut dock_state = DockState::new(vec!["a", "b", "c"]);
+    /// let main = SurfaceIndex::main();
+    /// let root = NodeIndex::root();
+    ///
+    /// dock_state.set_active_tab((main, root, TabIndex(2)));
+    /// dock_state.set_active_tab((main, root, TabIndex(0)));
+    /// dock_state.remove_tab((main, root, TabIndex(1)));
+    ///
+    /// let recent: Vec<_> = dock_state.recent_tabs().collect();
+    /// assert_eq!(recent, [(main, root, TabIndex(0)), (main, root, TabIndex(1))]);
+    /// ```
+    pub fn recent_tabs(&self) -> impl Iterator<Item = (SurfaceIndex, NodeIndex, TabIndex)> + '_ {
+        self.recent_tabs
+            .iter()
+            .copied()
+            .filter(|&(surface, node, tab)| self.check_tab(surface, node, tab).is_ok())
+    }
+
+    /// Makes the active tab of the given leaf the most recently used one.
+    pub(crate) fn touch_recent_tab(&mut self, (surface, node): (SurfaceIndex, NodeIndex)) {
+        let Some(active) = self
+            .check_leaf(surface, node)
+            .ok()
+            .and_then(Node::active_tab)
+        else {
+            return;
+        };
+        if self.check_tab(surface, node, active).is_err() {
+            return;
+        }
+        let entry = (surface, node, active);
+        if self.recent_tabs.first() == Some(&entry) {
+            return;
+        }
+        self.recent_tabs.retain(|&recent| recent != entry);
+        self.recent_tabs.insert(0, entry);
+        self.recent_tabs.truncate(RECENT_TABS_LIMIT);
+    }
+
+    /// Makes the active tab of the focused leaf the most recently used one.
+    pub(crate) fn touch_focused_tab(&mut self) {
+        if let Some(focused) = self.focused_leaf() {
+            self.touch_recent_tab(focused);
+        }
+    }
+
+    /// Forgets the tab at the given index, and shifts the entries of the tabs following it in its leaf.
+    pub(crate) fn forget_recent_tab(
+        &mut self,
+        (surface, node, tab): (SurfaceIndex, NodeIndex, TabIndex),
+    ) {
+        self.recent_tabs
+            .retain(|&entry| entry != (surface, node, tab));
+        for (_, _, index) in self
+            .recent_tabs
+            .iter_mut()
+            .filter(|(s, n, index)| *s == surface && *n == node && index.0 > tab.0)
+        {
+            index.0 -= 1;
+        }
+    }
+
+    /// Forgets all tabs of the given surface.
+    pub(crate) fn forget_recent_tabs(&mut self, surface: SurfaceIndex) {
+        self.recent_tabs.retain(|&(s, _, _)| s != surface);
+    }
+
+    /// Removes a leaf like [`Tree::remove_leaf`], forgetting its tabs and making the entries of the tabs which are
+    /// moved up along with its sibling follow them.
+    ///
+    /// [`Tree::remove_leaf`]: crate::Tree::remove_leaf
+    pub(crate) fn remove_leaf_following_recent(
+        &mut self,
+        (surface, node): (SurfaceIndex, NodeIndex),
+    ) {
+        let moves_nodes = !self[surface][node].is_central();
+        self[surface].remove_leaf(node);
+        self.recent_tabs
+            .retain(|&(s, n, _)| s != surface || n != node);
+        if moves_nodes {
+            for (_, n, _) in self
+                .recent_tabs
+                .iter_mut()
+                .filter(|(s, _, _)| *s == surface)
+            {
+                *n = n.after_removal_of(node);
+            }
+        }
+    }
+
+    /// Removes the tabs of a surface for which `predicate` returns `false`, like [`Tree::retain_tabs`], and makes the
+    /// entries of the tabs which are left follow them to the leaves they end up in.
+    ///
+    /// Returns `false` if no tab was removed.
+    ///
+    /// [`Tree::retain_tabs`]: crate::Tree::retain_tabs
+    pub(crate) fn retain_surface_tabs<F>(&mut self, surface: SurfaceIndex, mut predicate: F) -> bool
+    where
+        F: FnMut(&mut Tab) -> bool,
+    {
+        let Some(tree) = self.surfaces[surface.0].node_tree_mut() else {
+            return false;
+        };
+
+        // A copy of the tree marks the recently used tabs with their place in the list, and loses the same tabs.
+        let mut marks = tree.map_tabs(|_| None);
+        for (rank, &(s, node, tab)) in self.recent_tabs.iter().enumerate() {
+            let mark = marks
+                .iter_mut()
+                .nth(node.0)
+                .and_then(Node::tabs_mut)
+                .and_then(|tabs| tabs.get_mut(tab.0));
+            if let (true, Some(mark)) = (s == surface, mark) {
+                *mark = Some(rank);
+            }
+        }
+
+        let mut kept = Vec::new();
+        tree.retain_tabs(|tab| {
+            let keep = predicate(tab);
+            kept.push(keep);
+            keep
+        });
+        if kept.iter().all(|&keep| keep) {
+            return false;
+        }
+        let mut kept = kept.into_iter();
+        marks.retain_tabs(|_| kept.next().unwrap_or(true));
+
+        let mut moved = vec![None; self.recent_tabs.len()];
+        for (node, leaf) in marks.iter().enumerate() {
+            for (tab, rank) in leaf.tabs().unwrap_or_default().iter().enumerate() {
+                if let Some(rank) = *rank {
+                    moved[rank] = Some((surface, NodeIndex(node), TabIndex(tab)));
+                }
+            }
+        }
+        self.recent_tabs = std::mem::take(&mut self.recent_tabs)
+            .into_iter()
+            .zip(moved)
+            .filter_map(|(entry, moved)| {
+                if entry.0 == surface {
+                    moved
+                } else {
+                    Some(entry)
+                }
+            })
+            .collect();
+        true
+    }
+
+    /// Focuses the most recently used tab which still exists, making it active in its leaf.
+    pub(crate) fn focus_recent_tab(&mut self) {
+        let recent = self.recent_tabs().next();
+        if let Some((surface, node, tab)) = recent {
+            self[surface].set_active_tab(node, tab);
+            self.set_focused_node_and_surface((surface, node));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use egui::Id;
+
+    use crate::{DockState, NodeIndex, SurfaceIndex, TabIndex};
+
+    const MAIN: SurfaceIndex = SurfaceIndex::main();
+
+    /// Returns a main surface split into `a` on the left and `b` above `c` on the right, where `c` and then `b` have
+    /// been focused.
+    fn dock_state() -> DockState<&'static str> {
+        let mut dock_state = DockState::new(vec!["a"]);
+        let tree = dock_state.main_surface_mut();
+        let [_, right] = tree.split_right(NodeIndex::root(), 0.5, vec!["b"]);
+        let [b, c] = tree.split_below(right, 0.5, vec!["c"]);
+        dock_state.set_focused_node_and_surface((MAIN, c));
+        dock_state.set_focused_node_and_surface((MAIN, b));
+        dock_state
+    }
+
+    fn recent_tabs(dock_state: &DockState<&'static str>) -> Vec<&'static str> {
+        dock_state
+            .recent_tabs()
+            .map(|(surface, node, tab)| dock_state[surface][node].tabs().unwrap()[tab.0])
+            .collect()
+    }
+
+    #[test]
+    fn recent_tabs_follow_leaf_removal() {
+        let mut dock_state = dock_state();
+        assert_eq!(recent_tabs(&dock_state), ["b", "c"]);
+
+        dock_state.try_remove_leaf((MAIN, NodeIndex(1))).unwrap();
+        assert_eq!(
+            dock_state.recent_tabs().collect::<Vec<_>>(),
+            [
+                (MAIN, NodeIndex(1), TabIndex(0)),
+                (MAIN, NodeIndex(2), TabIndex(0))
+            ]
+        );
+        assert_eq!(recent_tabs(&dock_state), ["b", "c"]);
+    }
+
+    #[test]
+    fn recent_tabs_follow_transaction_cleanup() {
+        let mut dock_state = dock_state();
+        dock_state
+            .transaction(|edit| edit.remove_tab((MAIN, NodeIndex(1), TabIndex(0))).map(drop))
+            .unwrap();
+        assert_eq!(recent_tabs(&dock_state), ["b", "c"]);
+    }
+
+    #[test]
+    fn recent_tabs_follow_applied_snapshot() {
+        let mut dock_state = dock_state();
+        let snapshot = DockState::new(vec!["c", "a", "b"]).snapshot(|tab| Id::new(tab));
+        dock_state.apply_snapshot(&snapshot, |tab| Id::new(tab));
+        assert_eq!(
+            dock_state.recent_tabs().collect::<Vec<_>>(),
+            [
+                (MAIN, NodeIndex::root(), TabIndex(2)),
+                (MAIN, NodeIndex::root(), TabIndex(0))
+            ]
+        );
+    }
+}
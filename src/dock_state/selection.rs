@@ -0,0 +1,119 @@
+use egui::{Id, Rect};
+
+use crate::{DockState, NodeIndex, SurfaceIndex, TabIndex};
+
+impl<Tab> DockState<Tab> {
+    /// Returns the ids of the selected tabs, in the order they were selected.
+    ///
+    /// Tabs are selected by Ctrl+clicking (Cmd+clicking on macOS) their titles in a [`DockArea`](crate::DockArea)
+    /// with [`selectable_tabs`](crate::DockArea::selectable_tabs) enabled, or with
+    /// [`set_tab_selected`](Self::set_tab_selected). The ids are the ones returned by
+    /// [`TabViewer::id`](crate::TabViewer::id), and may belong to tabs which have been removed since.
+    #[inline]
+    pub fn selected_tabs(&self) -> &[Id] {
+        &self.selected_tabs
+    }
+
+    /// Returns whether the tab with the given `id` is selected.
+    #[inline]
+    pub fn is_tab_selected(&self, id: Id) -> bool {
+        self.selected_tabs.contains(&id)
+    }
+
+    /// Adds the tab with the given `id` to the selection, or removes it from the selection.
+    pub fn set_tab_selected(&mut self, id: Id, selected: bool) {
+        let index = self
+            .selected_tabs
+            .iter()
+            .position(|&selected| selected == id);
+        match (index, selected) {
+            (None, true) => self.selected_tabs.push(id),
+            (Some(index), false) => {
+                self.selected_tabs.remove(index);
+            }
+            _ => {}
+        }
+    }
+
+    /// Deselects all tabs.
+    #[inline]
+    pub fn clear_tab_selection(&mut self) {
+        self.selected_tabs.clear();
+    }
+
+    /// Takes several tabs out of their surfaces and puts them together in a new window, like
+    /// [`detach_tab`](Self::detach_tab) does with a single one.
+    ///
+    /// The tabs keep the order they're in the dock state, and the first one is made active. Leaves and windows left
+    /// empty are removed. Returns the surface index of the new window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tabs` is empty or any of the tabs doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui::Rect;
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex};
+    /// let mut dock_state = DockState::new(vec!["a", "b", "c"]);
+    /// let main = SurfaceIndex::main();
+    /// let [_, right] = dock_state.main_surface_mut().split_right(NodeIndex::root(), 0.5, vec!["d"]);
+    ///
+    /// let window = dock_state.detach_tabs(
+    ///     vec![(main, right, TabIndex(0)), (main, NodeIndex::root().left(), TabIndex(1))],
+    ///     Rect::NOTHING,
+    /// );
+    /// assert_eq!(dock_state[window][NodeIndex::root()].tabs(), Some(&["b", "d"][..]));
+    /// assert_eq!(dock_state.main_surface()[NodeIndex::root()].tabs(), Some(&["a", "c"][..]));
+    /// ```
+    pub fn detach_tabs(
+        &mut self,
+        mut tabs: Vec<(SurfaceIndex, NodeIndex, TabIndex)>,
+        window_rect: Rect,
+    ) -> SurfaceIndex {
+        assert!(!tabs.is_empty(), "no tabs to detach");
+        tabs.sort_by_key(|&(surface, node, tab)| (surface.0, node.0, tab.0));
+        tabs.dedup();
+        let from_main = tabs[0].0.is_main();
+
+        // Taking the tabs out from the last one keeps the indices of the remaining ones valid.
+        let mut detached = Vec::with_capacity(tabs.len());
+        let mut emptied = Vec::new();
+        for &(surface, node, tab) in tabs.iter().rev() {
+            detached.push(self[surface][node].remove_tab(tab).unwrap());
+            self.forget_recent_tab((surface, node, tab));
+            if self[surface][node].tabs_count() == 0 && emptied.last() != Some(&(surface, node)) {
+                emptied.push((surface, node));
+            }
+        }
+        detached.reverse();
+
+        // Clean up first, so that the window may take the place of one it's detached from. Each removed leaf may
+        // move the ones left to remove, so their indices are updated.
+        while let Some((surface, node)) = emptied.pop() {
+            let moves_nodes = !self[surface][node].is_central();
+            self.remove_leaf_following_recent((surface, node));
+            if moves_nodes {
+                for (other_surface, other_node) in &mut emptied {
+                    if *other_surface == surface {
+                        *other_node = other_node.after_removal_of(node);
+                    }
+                }
+            }
+            if self[surface].is_empty() && !surface.is_main() {
+                self.remove_surface(surface);
+            }
+        }
+
+        let surface_index = self.add_window(detached);
+        let state = self.get_window_state_mut(surface_index).unwrap();
+        state.set_position(window_rect.min);
+        if from_main {
+            state.set_size(window_rect.size() * 0.8);
+        } else {
+            state.set_size(window_rect.size());
+        }
+        surface_index
+    }
+}
@@ -0,0 +1,159 @@
+use egui::Id;
+
+use crate::{DockState, Node, NodeIndex, Surface, SurfaceIndex, TabIndex};
+
+/// The arrangement of a [`DockState`] at some point in time, taken with [`DockState::snapshot`].
+///
+/// It holds the structure and geometry of every surface, but not the tabs themselves: each tab is replaced by its id,
+/// so taking a snapshot is cheap even if tabs are expensive to clone. Snapshots can be compared with each other and
+/// restored with [`DockState::apply_snapshot`], e.g. to undo a change of the layout.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct LayoutSnapshot {
+    surfaces: Vec<Surface<Id>>,
+    focused_surface: Option<SurfaceIndex>,
+}
+
+impl LayoutSnapshot {
+    /// Returns the surfaces of the snapshot, in which tabs are represented by their ids.
+    #[inline]
+    pub fn surfaces(&self) -> &[Surface<Id>] {
+        &self.surfaces
+    }
+
+    /// Returns the surface which was focused when the snapshot was taken.
+    #[inline]
+    pub fn focused_surface(&self) -> Option<SurfaceIndex> {
+        self.focused_surface
+    }
+}
+
+impl<Tab> DockState<Tab> {
+    /// Takes a [`LayoutSnapshot`] of the current arrangement of tabs, identifying each tab by the id returned by
+    /// `tab_id`.
+    ///
+    /// The ids should be stable and unique, like the ones returned by [`TabViewer::id`](crate::TabViewer::id).
+    /// `tab_id` is called for every tab in the order of [`iter_all_tabs`](Self::iter_all_tabs).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui::Id;
+    /// # use egui_dock::{DockState, NodeIndex};
+    /// let mut dock_state = DockState::new(vec!["a", "b"]);
+    /// let snapshot = dock_state.snapshot(|tab| Id::new(tab));
+    ///
+    /// let b = dock_state.main_surface_mut().remove_tab((NodeIndex::root(), 1.into())).unwrap();
+    /// dock_state.main_surface_mut().split_right(NodeIndex::root(), 0.5, vec![b]);
+    /// assert_ne!(dock_state.snapshot(|tab| Id::new(tab)), snapshot);
+    ///
+    /// dock_state.apply_snapshot(&snapshot, |tab| Id::new(tab));
+    /// assert_eq!(dock_state.snapshot(|tab| Id::new(tab)), snapshot);
+    /// ```
+    pub fn snapshot(&self, mut tab_id: impl FnMut(&Tab) -> Id) -> LayoutSnapshot {
+        LayoutSnapshot {
+            surfaces: self
+                .surfaces
+                .iter()
+                .map(|surface| surface.map_tabs(&mut tab_id))
+                .collect(),
+            focused_surface: self.focused_surface,
+        }
+    }
+
+    /// Rearranges the tabs as they were when `snapshot` was taken, identifying each tab by the id returned by
+    /// `tab_id`, which is called for every tab in the order of [`iter_all_tabs`](Self::iter_all_tabs).
+    ///
+    /// Tabs which were in the snapshot but aren't in this [`DockState`] anymore are left out, along with the nodes
+    /// and windows they leave empty. Tabs which weren't in the snapshot are pushed to the first leaf of the main
+    /// surface.
+    pub fn apply_snapshot(
+        &mut self,
+        snapshot: &LayoutSnapshot,
+        mut tab_id: impl FnMut(&Tab) -> Id,
+    ) {
+        let mut tabs = Vec::new();
+        let mut places = Vec::new();
+        for (surface_index, surface) in std::mem::take(&mut self.surfaces).into_iter().enumerate() {
+            if let Surface::Main(tree) | Surface::Window(tree, _) | Surface::AutoHidden(tree, _) =
+                surface
+            {
+                for (node_index, node) in tree.nodes.into_iter().enumerate() {
+                    if let Node::Leaf {
+                        tabs: leaf_tabs, ..
+                    } = node
+                    {
+                        for (tab_index, tab) in leaf_tabs.into_iter().enumerate() {
+                            places.push((
+                                SurfaceIndex(surface_index),
+                                NodeIndex(node_index),
+                                TabIndex(tab_index),
+                            ));
+                            tabs.push((tab_id(&tab), tab));
+                        }
+                    }
+                }
+            }
+        }
+        let recent_ids: Vec<Id> = self
+            .recent_tabs
+            .iter()
+            .filter_map(|recent| {
+                let index = places.iter().position(|place| place == recent)?;
+                Some(tabs[index].0)
+            })
+            .collect();
+
+        // Leaving out the same tabs from the snapshot first gives the layout the tabs end up in.
+        let mut ids: Vec<Id> = tabs.iter().map(|&(id, _)| id).collect();
+        let layout: Vec<Surface<Id>> = snapshot
+            .surfaces
+            .iter()
+            .map(|surface| {
+                surface.filter_map_tabs(|id| {
+                    let index = ids.iter().position(|tab_id| tab_id == id)?;
+                    Some(ids.remove(index))
+                })
+            })
+            .collect();
+        self.surfaces = layout
+            .iter()
+            .map(|surface| {
+                surface.filter_map_tabs(|id| {
+                    let index = tabs.iter().position(|(tab_id, _)| tab_id == id)?;
+                    Some(tabs.remove(index).1)
+                })
+            })
+            .collect();
+        self.recent_tabs = recent_ids
+            .iter()
+            .filter_map(|recent| {
+                layout
+                    .iter()
+                    .enumerate()
+                    .find_map(|(surface_index, surface)| {
+                        let (node_index, tab_index) = surface
+                            .node_tree()?
+                            .iter()
+                            .enumerate()
+                            .find_map(|(node_index, node)| {
+                                let tab_index = node.tabs()?.iter().position(|id| id == recent)?;
+                                Some((node_index, tab_index))
+                            })?;
+                        Some((
+                            SurfaceIndex(surface_index),
+                            NodeIndex(node_index),
+                            TabIndex(tab_index),
+                        ))
+                    })
+            })
+            .collect();
+        for (_, tab) in tabs {
+            self.main_surface_mut().push_to_first_leaf(tab);
+        }
+        self.focused_surface = snapshot
+            .focused_surface
+            .filter(|&surface| self.get_surface(surface).is_some_and(|s| !s.is_empty()));
+        self.animations.clear();
+    }
+}
@@ -1,11 +1,11 @@
-use crate::{Node, NodeIndex, Tree, WindowState};
+use crate::{AutoHideState, Node, NodeIndex, SurfaceId, Tree, WindowState};
 
 /// A [`Surface`] is the highest level component in a [`DockState`](crate::DockState). [`Surface`]s represent an area
 /// in which nodes are placed.
 ///
 /// Typically, you're only using one surface, which is the main surface. However, if you drag
 /// a tab out in a way which creates a window, you also create a new surface in which nodes can appear.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum Surface<Tab> {
     /// An empty surface, with nothing inside (practically, a null surface).
@@ -16,6 +16,10 @@ pub enum Surface<Tab> {
 
     /// A windowed surface with a state.
     Window(Tree<Tab>, WindowState),
+
+    /// A leaf hidden along an edge of the main surface, see
+    /// [`DockState::set_auto_hide`](crate::DockState::set_auto_hide).
+    AutoHidden(Tree<Tab>, AutoHideState),
 }
 
 impl<Tab> Surface<Tab> {
@@ -24,12 +28,24 @@ impl<Tab> Surface<Tab> {
         matches!(self, Self::Empty)
     }
 
+    /// Returns the id of the surface, or [`None`] if it's [`Empty`](Self::Empty).
+    ///
+    /// See [`SurfaceId`].
+    pub fn id(&self) -> Option<SurfaceId> {
+        match self {
+            Surface::Main(_) => Some(SurfaceId::MAIN),
+            Surface::Window(_, state) => Some(state.id()),
+            Surface::AutoHidden(_, state) => Some(state.id()),
+            Surface::Empty => None,
+        }
+    }
+
     /// Get access to the node tree of this surface.
     pub fn node_tree(&self) -> Option<&Tree<Tab>> {
         match self {
             Surface::Empty => None,
             Surface::Main(tree) => Some(tree),
-            Surface::Window(tree, _) => Some(tree),
+            Surface::Window(tree, _) | Surface::AutoHidden(tree, _) => Some(tree),
         }
     }
 
@@ -38,7 +54,7 @@ impl<Tab> Surface<Tab> {
         match self {
             Surface::Empty => None,
             Surface::Main(tree) => Some(tree),
-            Surface::Window(tree, _) => Some(tree),
+            Surface::Window(tree, _) | Surface::AutoHidden(tree, _) => Some(tree),
         }
     }
 
@@ -96,6 +112,14 @@ impl<Tab> Surface<Tab> {
                     Surface::Window(tree, window_state.clone())
                 }
             }
+            Surface::AutoHidden(tree, auto_hide_state) => {
+                let tree = tree.filter_map_tabs(function);
+                if tree.is_empty() {
+                    Surface::Empty
+                } else {
+                    Surface::AutoHidden(tree, auto_hide_state.clone())
+                }
+            }
         }
     }
 
@@ -125,7 +149,8 @@ impl<Tab> Surface<Tab> {
     where
         F: FnMut(&mut Tab) -> bool,
     {
-        if let Surface::Main(tree) | Surface::Window(tree, _) = self {
+        if let Surface::Main(tree) | Surface::Window(tree, _) | Surface::AutoHidden(tree, _) = self
+        {
             tree.retain_tabs(predicate);
             if tree.is_empty() {
                 *self = Surface::Empty;
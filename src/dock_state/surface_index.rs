@@ -1,4 +1,9 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
 /// Wrapper around indices to the collection of Surfaces inside a [`DockState`](crate::DockState).
+///
+/// Indices of removed windows are reused by windows added later, see [`SurfaceId`] for a stable way to refer to a
+/// window.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct SurfaceIndex(pub usize);
@@ -23,3 +28,31 @@ impl SurfaceIndex {
         self.0 == Self::main().0
     }
 }
+
+/// Identifies a surface for as long as it exists, unlike a [`SurfaceIndex`], which may be given to another surface once
+/// the one it belonged to is removed.
+///
+/// Ids of windows are given out when their [`WindowState`](crate::WindowState) is created, and are unique within
+/// a run of the application. They aren't saved with the `serde` feature, so windows of a deserialized
+/// [`DockState`](crate::DockState) get new ones.
+///
+/// Use [`DockState::surface_by_id`](crate::DockState::surface_by_id) to find the current index of a surface.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SurfaceId(u64);
+
+impl SurfaceId {
+    /// The id of the main surface.
+    pub const MAIN: Self = Self(0);
+
+    /// Returns a new id, different from all ids returned before.
+    pub(crate) fn next() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Returns if this is the id of the main surface.
+    #[inline(always)]
+    pub const fn is_main(self) -> bool {
+        self.0 == Self::MAIN.0
+    }
+}
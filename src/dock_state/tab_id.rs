@@ -0,0 +1,85 @@
+use egui::Id;
+
+use crate::{DockError, DockState, NodeIndex, SurfaceIndex, TabDestination, TabIndex, TabViewer};
+
+impl<Tab> DockState<Tab> {
+    /// Finds the tab whose [`TabViewer::id`] is `id`.
+    ///
+    /// Unlike [`find_tab`](Self::find_tab), this doesn't require tabs to implement [`PartialEq`]. In case there are
+    /// several tabs with the same id, only the first one is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui::{Id, Ui, WidgetText};
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex, TabViewer};
+    /// struct Viewer;
+    ///
+    /// impl TabViewer for Viewer {
+    ///     type Tab = (u32, std::sync::mpsc::Receiver<String>);
+    ///
+    ///     fn title(&mut self, (index, _): &mut Self::Tab) -> WidgetText {
+    ///         format!("Channel {index}").into()
+    ///     }
+    ///
+    ///     fn ui(&mut self, _ui: &mut Ui, _tab: &mut Self::Tab) {}
+    ///
+    ///     fn id(&mut self, (index, _): &mut Self::Tab) -> Id {
+    ///         Id::new(*index)
+    ///     }
+    /// }
+    ///
+    /// let tabs = (0..3).map(|index| (index, std::sync::mpsc::channel().1)).collect();
+    /// let mut dock_state = DockState::new(tabs);
+    ///
+    /// let found = dock_state.find_tab_by_id(Id::new(2u32), &mut Viewer);
+    /// assert_eq!(found, Some((SurfaceIndex::main(), NodeIndex::root(), TabIndex(2))));
+    /// ```
+    pub fn find_tab_by_id(
+        &mut self,
+        id: Id,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> Option<(SurfaceIndex, NodeIndex, TabIndex)> {
+        for surface_index in self.surface_index_iter() {
+            if !self.is_surface_valid(surface_index) {
+                continue;
+            }
+            for (node_index, node) in self[surface_index].iter_mut().enumerate() {
+                let Some(tabs) = node.tabs_mut() else {
+                    continue;
+                };
+                if let Some(tab_index) = tabs.iter_mut().position(|tab| tab_viewer.id(tab) == id) {
+                    return Some((surface_index, NodeIndex(node_index), TabIndex(tab_index)));
+                }
+            }
+        }
+        None
+    }
+
+    /// Moves the tab whose [`TabViewer::id`] is `id` like [`move_tab`](Self::move_tab).
+    ///
+    /// Returns [`DockError::TabNotFound`] if there's no such tab, or another error if the destination doesn't exist.
+    pub fn move_tab_by_id(
+        &mut self,
+        id: Id,
+        destination: impl Into<TabDestination>,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> Result<(), DockError> {
+        let source = self
+            .find_tab_by_id(id, tab_viewer)
+            .ok_or(DockError::TabNotFound(id))?;
+        self.try_move_tab(source, destination)
+    }
+
+    /// Removes the tab whose [`TabViewer::id`] is `id` like [`remove_tab`](Self::remove_tab).
+    ///
+    /// Returns the removed tab, or [`None`] if there's no such tab.
+    pub fn remove_tab_by_id(
+        &mut self,
+        id: Id,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> Option<Tab> {
+        let tab = self.find_tab_by_id(id, tab_viewer)?;
+        self.remove_tab(tab)
+    }
+}
@@ -0,0 +1,465 @@
+use std::fmt::{self, Write};
+
+use crate::{
+    DockState, Node, NodeIndex, PortableDockState, Split, Surface, TabCodec, TabIndex, TabPayload,
+    Tree,
+};
+
+/// Describes why a layout couldn't be read with [`DockState::from_text_layout`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LayoutParseError {
+    /// Offset in bytes from the start of the text at which the error was found.
+    pub offset: usize,
+
+    /// What was expected at [`offset`](Self::offset).
+    pub expected: String,
+}
+
+impl fmt::Display for LayoutParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected {} at byte {}", self.expected, self.offset)
+    }
+}
+
+impl std::error::Error for LayoutParseError {}
+
+/// How many splits can be nested in a layout read with [`DockState::from_text_layout`].
+///
+/// Nodes of a [`Tree`] are stored in a binary heap, which doubles in size with each level, so deeper layouts are
+/// rejected before they take up all memory or overflow the stack.
+const MAX_DEPTH: usize = 16;
+
+impl<Tab> DockState<Tab> {
+    /// Writes the arrangement of leaves and tabs into a short text which can be edited by hand, kept in a config
+    /// file and compared between versions, and read back with [`from_text_layout`](Self::from_text_layout).
+    ///
+    /// Each tab is written as its [`TabPayload`] made by `codec`. The main surface is written on the first line,
+    /// and each window on a line of its own:
+    ///
+    /// - `leaf[a, *b]` is a leaf with tabs `a` and `b`, of which `b` is the active one.
+    /// - `h(0.3, left, right)` puts `left` and `right` side by side, giving `0.3` of the width to `left`.
+    /// - `v(0.3, top, bottom)` stacks `top` above `bottom`, giving `0.3` of the height to `top`.
+    /// - `window(tree)` is a window with the given layout.
+    ///
+    /// Payloads which aren't made of letters, digits and `_-./:` only are put in double quotes, with `"` and `\`
+    /// escaped by a backslash. Only the layout is kept, while the positions of windows, collapsed leaves and other
+    /// settings are left out. Auto-hidden leaves are written as windows.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, TabCodec, TabPayload};
+    /// struct NameCodec;
+    ///
+    /// impl TabCodec for NameCodec {
+    ///     type Tab = String;
+    ///
+    ///     fn encode(&self, tab: &String) -> TabPayload {
+    ///         tab.as_str().into()
+    ///     }
+    ///
+    ///     fn decode(&self, payload: TabPayload) -> Option<String> {
+    ///         Some(payload.0)
+    ///     }
+    /// }
+    ///
+    /// let mut dock_state = DockState::new(vec!["Files".to_owned()]);
+    /// let [_, right] = dock_state
+    ///     .main_surface_mut()
+    ///     .split_right(NodeIndex::root(), 0.25, vec!["main.rs".to_owned(), "lib.rs".to_owned()]);
+    /// dock_state
+    ///     .main_surface_mut()
+    ///     .split_below(right, 0.75, vec!["Terminal 1".to_owned()]);
+    ///
+    /// let text = dock_state.to_text_layout(&NameCodec);
+    /// assert_eq!(
+    ///     text,
+    ///     r#"h(0.25, leaf[*Files], v(0.75, leaf[*main.rs, lib.rs], leaf[*"Terminal 1"]))"#
+    /// );
+    ///
+    /// let restored = DockState::from_text_layout(&text, &NameCodec).unwrap();
+    /// assert_eq!(restored.to_text_layout(&NameCodec), text);
+    /// ```
+    pub fn to_text_layout(&self, codec: &impl TabCodec<Tab = Tab>) -> String {
+        let portable = self.to_portable(codec);
+        let mut text = String::new();
+        for surface in portable.iter_surfaces() {
+            match surface {
+                Surface::Main(tree) => write_tree(&mut text, tree, NodeIndex::root()),
+                Surface::Window(tree, _) | Surface::AutoHidden(tree, _) => {
+                    text.push_str("\nwindow(");
+                    write_tree(&mut text, tree, NodeIndex::root());
+                    text.push(')');
+                }
+                Surface::Empty => {}
+            }
+        }
+        text
+    }
+
+    /// Reads a layout written by [`to_text_layout`](Self::to_text_layout), creating its tabs from their payloads
+    /// with `codec`.
+    ///
+    /// Tabs which `codec` fails to decode are left out, like with [`from_portable`](Self::from_portable), along with
+    /// any nodes and windows left empty. Spaces and line breaks between the parts of the layout are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`LayoutParseError`] if the text isn't a valid layout, or if it nests splits more than 16 levels
+    /// deep.
+    pub fn from_text_layout(
+        text: &str,
+        codec: &impl TabCodec<Tab = Tab>,
+    ) -> Result<Self, LayoutParseError> {
+        let mut parser = Parser {
+            text,
+            offset: 0,
+            depth: 0,
+        };
+        let mut portable = PortableDockState::new(Vec::new());
+        *portable.main_surface_mut() = parser.tree()?;
+        while !parser.at_end() {
+            parser.keyword("window")?;
+            parser.expect('(')?;
+            let tree = parser.tree()?;
+            parser.expect(')')?;
+            if tree.num_tabs() > 0 {
+                let window = portable.add_window(Vec::new());
+                portable[window] = tree;
+            }
+        }
+        let mut dock_state = Self::from_portable(&portable, codec);
+        let empty_windows: Vec<_> = dock_state
+            .valid_surface_index_iter()
+            .filter(|&surface| !surface.is_main() && dock_state[surface].num_tabs() == 0)
+            .collect();
+        for surface in empty_windows {
+            dock_state.remove_surface(surface);
+        }
+        Ok(dock_state)
+    }
+}
+
+/// Writes the node at `node_index` of `tree` along with all of its children.
+fn write_tree(text: &mut String, tree: &Tree<TabPayload>, node_index: NodeIndex) {
+    if node_index.0 >= tree.len() {
+        text.push_str("leaf[]");
+        return;
+    }
+    let (name, fraction) = match &tree[node_index] {
+        Node::Empty => {
+            text.push_str("leaf[]");
+            return;
+        }
+        Node::Leaf { tabs, active, .. } => {
+            text.push_str("leaf[");
+            for (index, tab) in tabs.iter().enumerate() {
+                if index > 0 {
+                    text.push_str(", ");
+                }
+                if index == active.0 {
+                    text.push('*');
+                }
+                write_payload(text, tab);
+            }
+            text.push(']');
+            return;
+        }
+        Node::Horizontal { fraction, .. } => ("h", *fraction),
+        Node::Vertical { fraction, .. } => ("v", *fraction),
+    };
+    let _ = write!(text, "{name}({fraction}, ");
+    write_tree(text, tree, node_index.left());
+    text.push_str(", ");
+    write_tree(text, tree, node_index.right());
+    text.push(')');
+}
+
+/// Writes a payload as it is if it's made of characters allowed in bare names, or in quotes otherwise.
+fn write_payload(text: &mut String, payload: &TabPayload) {
+    let payload = payload.as_str();
+    if !payload.is_empty() && payload.chars().all(is_bare_char) {
+        text.push_str(payload);
+        return;
+    }
+    text.push('"');
+    for c in payload.chars() {
+        if matches!(c, '"' | '\\') {
+            text.push('\\');
+        }
+        text.push(c);
+    }
+    text.push('"');
+}
+
+/// Returns `true` if `c` can be a part of a tab payload written without quotes.
+fn is_bare_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '/' | ':')
+}
+
+/// Reads a layout from text, keeping track of how far it's got.
+struct Parser<'a> {
+    text: &'a str,
+    offset: usize,
+
+    /// How many splits the parser is inside of.
+    depth: usize,
+}
+
+impl Parser<'_> {
+    /// Returns the text left to read, skipping whitespace.
+    fn rest(&mut self) -> &str {
+        let rest = &self.text[self.offset..];
+        self.offset += rest.len() - rest.trim_start().len();
+        &self.text[self.offset..]
+    }
+
+    fn at_end(&mut self) -> bool {
+        self.rest().is_empty()
+    }
+
+    fn error(&self, expected: impl Into<String>) -> LayoutParseError {
+        LayoutParseError {
+            offset: self.offset,
+            expected: expected.into(),
+        }
+    }
+
+    /// Skips `c` if it's next, returning whether it was.
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.rest().starts_with(c);
+        if found {
+            self.offset += c.len_utf8();
+        }
+        found
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), LayoutParseError> {
+        if self.eat(c) {
+            return Ok(());
+        }
+        Err(self.error(format!("`{c}`")))
+    }
+
+    /// Reads a bare word, returning an empty string if there's none.
+    fn word(&mut self) -> &str {
+        let rest = self.rest();
+        let len = rest.find(|c| !is_bare_char(c)).unwrap_or(rest.len());
+        let start = self.offset;
+        self.offset += len;
+        &self.text[start..self.offset]
+    }
+
+    fn keyword(&mut self, keyword: &'static str) -> Result<(), LayoutParseError> {
+        let start = self.offset;
+        if self.word() == keyword {
+            return Ok(());
+        }
+        self.offset = start;
+        Err(self.error(format!("`{keyword}`")))
+    }
+
+    fn fraction(&mut self) -> Result<f32, LayoutParseError> {
+        self.rest();
+        let start = self.offset;
+        match self.word().parse::<f32>() {
+            Ok(fraction) if (0.0..=1.0).contains(&fraction) => Ok(fraction),
+            _ => {
+                self.offset = start;
+                Err(self.error("a fraction between 0 and 1"))
+            }
+        }
+    }
+
+    fn payload(&mut self) -> Result<TabPayload, LayoutParseError> {
+        if !self.eat('"') {
+            let word = self.word().to_owned();
+            if word.is_empty() {
+                return Err(self.error("a tab"));
+            }
+            return Ok(TabPayload(word));
+        }
+        let mut payload = String::new();
+        let mut chars = self.text[self.offset..].char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.offset += index + 1;
+                    return Ok(TabPayload(payload));
+                }
+                '\\' => match chars.next() {
+                    Some((_, c)) => payload.push(c),
+                    None => break,
+                },
+                c => payload.push(c),
+            }
+        }
+        self.offset = self.text.len();
+        Err(self.error("`\"`"))
+    }
+
+    fn tree(&mut self) -> Result<Tree<TabPayload>, LayoutParseError> {
+        self.rest();
+        let start = self.offset;
+        let split = match self.word() {
+            "leaf" => return self.leaf(),
+            "h" => Split::Right,
+            "v" => Split::Below,
+            _ => {
+                self.offset = start;
+                return Err(self.error("`leaf`, `h` or `v`"));
+            }
+        };
+        if self.depth == MAX_DEPTH {
+            self.offset = start;
+            return Err(self.error(format!(
+                "`leaf`, as splits can't be nested more than {MAX_DEPTH} levels deep"
+            )));
+        }
+        self.depth += 1;
+        self.expect('(')?;
+        let fraction = self.fraction()?;
+        self.expect(',')?;
+        let mut first = self.tree()?;
+        self.expect(',')?;
+        let second = self.tree()?;
+        self.expect(')')?;
+        self.depth -= 1;
+
+        // A side without tabs leaves its space to the other one.
+        Ok(match (first.num_tabs() > 0, second.num_tabs() > 0) {
+            (true, true) => {
+                first.split_tree(NodeIndex::root(), split, fraction, second);
+                first
+            }
+            (false, true) => second,
+            _ => first,
+        })
+    }
+
+    fn leaf(&mut self) -> Result<Tree<TabPayload>, LayoutParseError> {
+        self.expect('[')?;
+        let mut tabs = Vec::new();
+        let mut active = 0;
+        if !self.eat(']') {
+            loop {
+                if self.eat('*') {
+                    active = tabs.len();
+                }
+                tabs.push(self.payload()?);
+                if self.eat(']') {
+                    break;
+                }
+                if !self.eat(',') {
+                    return Err(self.error("`,` or `]`"));
+                }
+            }
+        }
+        let mut tree = Tree::new(tabs);
+        tree.set_active_tab(NodeIndex::root(), TabIndex(active));
+        Ok(tree)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NameCodec;
+
+    impl TabCodec for NameCodec {
+        type Tab = String;
+
+        fn encode(&self, tab: &String) -> TabPayload {
+            tab.as_str().into()
+        }
+
+        fn decode(&self, payload: TabPayload) -> Option<String> {
+            Some(payload.0)
+        }
+    }
+
+    fn parse(text: &str) -> Result<DockState<String>, LayoutParseError> {
+        DockState::from_text_layout(text, &NameCodec)
+    }
+
+    fn error_offset(text: &str) -> usize {
+        parse(text).unwrap_err().offset
+    }
+
+    fn nested(depth: usize) -> String {
+        "h(0.5, ".repeat(depth) + "leaf[a]" + &", leaf[b])".repeat(depth)
+    }
+
+    #[test]
+    fn quoted_payloads_round_trip() {
+        let text = r#"leaf["a \"b\" \\ c", *d, "", "x,y]"]"#;
+        let dock_state = parse(text).unwrap();
+        let leaf = &dock_state.main_surface()[NodeIndex::root()];
+        assert_eq!(leaf.tabs().unwrap(), [r#"a "b" \ c"#, "d", "", "x,y]"]);
+        assert_eq!(leaf.active_tab(), Some(TabIndex(1)));
+        assert_eq!(dock_state.to_text_layout(&NameCodec), text);
+    }
+
+    #[test]
+    fn windows_are_read_after_the_main_surface() {
+        let dock_state =
+            parse("leaf[a]\nwindow(v(0.5, leaf[b], leaf[c]))\nwindow(leaf[])").unwrap();
+        let windows: Vec<_> = dock_state
+            .valid_surface_index_iter()
+            .filter(|surface| !surface.is_main())
+            .collect();
+        assert_eq!(windows.len(), 1);
+        assert_eq!(dock_state[windows[0]].num_tabs(), 2);
+    }
+
+    #[test]
+    fn fractions_outside_of_range_are_rejected() {
+        for text in [
+            "h(1.5, leaf[a], leaf[b])",
+            "v(-0.1, leaf[a], leaf[b])",
+            "h(x, leaf[a], leaf[b])",
+        ] {
+            let error = parse(text).unwrap_err();
+            assert_eq!(error.offset, 2, "{text}");
+            assert_eq!(error.expected, "a fraction between 0 and 1", "{text}");
+        }
+    }
+
+    #[test]
+    fn unterminated_strings_are_rejected() {
+        for text in [r#"leaf["abc"#, r#"leaf["abc\"#, r#"leaf["abc\""#] {
+            let error = parse(text).unwrap_err();
+            assert_eq!(error.offset, text.len(), "{text}");
+            assert_eq!(error.expected, "`\"`", "{text}");
+        }
+    }
+
+    #[test]
+    fn trailing_garbage_is_rejected() {
+        assert_eq!(error_offset("leaf[a] junk"), 8);
+        assert_eq!(error_offset("leaf[a] window(leaf[b]) )"), 24);
+        assert_eq!(error_offset("leaf[a] window(leaf[b]"), 22);
+    }
+
+    #[test]
+    fn errors_point_at_the_unexpected_part() {
+        assert_eq!(error_offset(""), 0);
+        assert_eq!(error_offset("  x(0.5, leaf[a], leaf[b])"), 2);
+        assert_eq!(error_offset("leaf[a b]"), 7);
+        assert_eq!(error_offset("leaf[a,]"), 7);
+        assert_eq!(error_offset("h(0.5 leaf[a], leaf[b])"), 6);
+        assert_eq!(error_offset("h(0.5, leaf[a], leaf[b]"), 23);
+    }
+
+    #[test]
+    fn nesting_is_limited() {
+        assert!(parse(&nested(MAX_DEPTH)).is_ok());
+
+        let error = parse(&nested(MAX_DEPTH + 1)).unwrap_err();
+        assert_eq!(error.offset, "h(0.5, ".len() * MAX_DEPTH);
+
+        // Deep enough to overflow the stack without the limit.
+        assert!(parse(&nested(100_000)).is_err());
+    }
+}
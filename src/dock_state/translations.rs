@@ -6,6 +6,13 @@ pub struct Translations {
     pub tab_context_menu: TabContextMenuTranslations,
     /// Text overrides for buttons in windows.
     pub leaf: LeafTranslations,
+    /// Text overrides for the taskbar listing minimized windows, see
+    /// [`DockArea::window_taskbar`](crate::DockArea::window_taskbar).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "WindowTaskbarTranslations::english")
+    )]
+    pub window_taskbar: WindowTaskbarTranslations,
 }
 
 /// Specifies text in buttons displayed in the context menu displayed upon right-clicking on a tab.
@@ -16,6 +23,20 @@ pub struct TabContextMenuTranslations {
     pub close_button: String,
     /// Button that undocks the tab into a new window.
     pub eject_button: String,
+    /// Button that undocks all selected tabs into a single new window, see
+    /// [`DockArea::selectable_tabs`](crate::DockArea::selectable_tabs).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "TabContextMenuTranslations::english_eject_selected_button")
+    )]
+    pub eject_selected_button: String,
+    /// Button that gives all leaves of the tab's surface the same area,
+    /// see [`DockArea::equalize_in_context_menu`](crate::DockArea::equalize_in_context_menu).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "TabContextMenuTranslations::english_equalize_button")
+    )]
+    pub equalize_button: String,
 }
 
 /// Specifies text displayed in the primary buttons on a tab bar.
@@ -50,6 +71,31 @@ pub struct LeafTranslations {
     /// Message in the tooltip shown while hovering over a collapse button of a leaf.
     /// Used when the secondary buttons are accessible using modifiers and from the context menu.
     pub minimize_button_modifier_menu_hint: String,
+    /// Message in the tooltip shown while hovering over the tab search button of a leaf, also used as the hint
+    /// in the search field of its popup.
+    pub search_button: String,
+    /// Title of the group of tabs which aren't in any group, shown when other tabs of the leaf are,
+    /// see [`TabViewer::group_of`](crate::TabViewer::group_of).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "LeafTranslations::english_ungrouped_tabs")
+    )]
+    pub ungrouped_tabs: String,
+    /// Message in the tooltip shown while hovering over a split button of a leaf,
+    /// see [`DockArea::show_split_buttons`](crate::DockArea::show_split_buttons).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "LeafTranslations::english_split_button")
+    )]
+    pub split_button: String,
+}
+
+/// Specifies text displayed in the taskbar listing windows minimized into it.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct WindowTaskbarTranslations {
+    /// Message in the tooltip shown while hovering over the entry of a minimized window.
+    pub restore_tooltip: String,
 }
 
 impl Translations {
@@ -58,6 +104,7 @@ impl Translations {
         Self {
             tab_context_menu: TabContextMenuTranslations::english(),
             leaf: LeafTranslations::english(),
+            window_taskbar: WindowTaskbarTranslations::english(),
         }
     }
 }
@@ -68,8 +115,18 @@ impl TabContextMenuTranslations {
         Self {
             close_button: String::from("Close"),
             eject_button: String::from("Eject"),
+            eject_selected_button: Self::english_eject_selected_button(),
+            equalize_button: Self::english_equalize_button(),
         }
     }
+
+    fn english_eject_selected_button() -> String {
+        String::from("Eject selected")
+    }
+
+    fn english_equalize_button() -> String {
+        String::from("Equalize sizes")
+    }
 }
 
 impl LeafTranslations {
@@ -96,6 +153,26 @@ impl LeafTranslations {
             minimize_button_modifier_menu_hint: String::from(
                 "Press modifier keys (Shift by default) or right click to minimize this window.",
             ),
+            search_button: String::from("Search tabs"),
+            ungrouped_tabs: Self::english_ungrouped_tabs(),
+            split_button: Self::english_split_button(),
+        }
+    }
+
+    fn english_ungrouped_tabs() -> String {
+        String::from("Other")
+    }
+
+    fn english_split_button() -> String {
+        String::from("Split")
+    }
+}
+
+impl WindowTaskbarTranslations {
+    /// Default English translations.
+    pub fn english() -> Self {
+        Self {
+            restore_tooltip: String::from("Click to restore this window."),
         }
     }
 }
@@ -0,0 +1,132 @@
+use egui::Rect;
+
+use crate::{
+    utils::{expand_to_pixel, map_to_pixel, pad_rect},
+    widgets::separator::constrain_fraction,
+    Node, NodeIndex, Style, Tree,
+};
+
+/// Rects which the nodes of a [`Tree`] take when laid out in a given area, computed with
+/// [`Tree::compute_layout`] or [`DockState::compute_layout`](crate::DockState::compute_layout) without showing
+/// a [`DockArea`](crate::DockArea).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ComputedLayout {
+    rects: Vec<Option<Rect>>,
+}
+
+impl ComputedLayout {
+    /// Returns the rect of the node at `node_index`, or [`None`] if the node is [`Empty`](Node::Empty).
+    #[inline]
+    pub fn rect(&self, node_index: NodeIndex) -> Option<Rect> {
+        self.rects.get(node_index.0).copied().flatten()
+    }
+
+    /// Iterates over the indices and rects of all non-empty nodes, in the order of their indices.
+    pub fn iter(&self) -> impl Iterator<Item = (NodeIndex, Rect)> + '_ {
+        self.rects
+            .iter()
+            .enumerate()
+            .filter_map(|(index, rect)| Some((NodeIndex(index), (*rect)?)))
+    }
+}
+
+impl<Tab> Tree<Tab> {
+    /// Computes the rects which nodes of the tree would take if its root was shown in `rect`, the same way a
+    /// [`DockArea`](crate::DockArea) would with the given `style`, at the given `pixels_per_point`.
+    ///
+    /// Collapsed leaves are laid out as if they were done collapsing. Leaves collapsing into side strips (see
+    /// [`DockArea::collapse_into_side_strips`](crate::DockArea::collapse_into_side_strips)) keep their width. The
+    /// tree itself isn't modified.
+    pub fn compute_layout(
+        &self,
+        rect: Rect,
+        style: &Style,
+        pixels_per_point: f32,
+    ) -> ComputedLayout {
+        let mut rects = vec![None; self.len()];
+        if self.is_empty() {
+            return ComputedLayout { rects };
+        }
+        rects[0] = Some(rect);
+
+        for node_index in self.breadth_first_index_iter() {
+            let node = &self[node_index];
+            if !node.is_parent() {
+                continue;
+            }
+            let Some(parent_rect) = rects[node_index.0] else {
+                continue;
+            };
+            let rect = expand_to_pixel(parent_rect, pixels_per_point);
+            let (left, right) = (&self[node_index.left()], &self[node_index.right()]);
+
+            let border = match node {
+                // A collapsed child only takes the height of the tab bars of its collapsed leaves.
+                Node::Vertical { .. } if left.is_collapsed() => {
+                    rect.min.y + (left.collapsed_leaf_count().max(1) as f32) * style.tab_bar.height
+                }
+                Node::Vertical { .. } if right.is_collapsed() => {
+                    rect.max.y - (right.collapsed_leaf_count().max(1) as f32) * style.tab_bar.height
+                }
+                Node::Vertical { fraction, .. } | Node::Horizontal { fraction, .. } => {
+                    let (left_min, left_max) = self.size_limits(node_index.left());
+                    let (right_min, right_max) = self.size_limits(node_index.right());
+                    let (size, start, limits) = if node.is_horizontal() {
+                        (
+                            rect.width(),
+                            rect.min.x,
+                            ((left_min.x, left_max.x), (right_min.x, right_max.x)),
+                        )
+                    } else {
+                        (
+                            rect.height(),
+                            rect.min.y,
+                            ((left_min.y, left_max.y), (right_min.y, right_max.y)),
+                        )
+                    };
+                    let fraction = constrain_fraction(
+                        *fraction,
+                        size,
+                        style.separator.width,
+                        limits.0,
+                        limits.1,
+                    );
+                    start + size * fraction
+                }
+                Node::Empty | Node::Leaf { .. } => unreachable!(),
+            };
+
+            let left_border = map_to_pixel(
+                border - style.separator.width * 0.5,
+                pixels_per_point,
+                f32::round,
+            );
+            let right_border = map_to_pixel(
+                border + style.separator.width * 0.5,
+                pixels_per_point,
+                f32::round,
+            );
+            let (left_rect, right_rect) = if node.is_horizontal() {
+                (
+                    rect.intersect(Rect::everything_left_of(left_border)),
+                    rect.intersect(Rect::everything_right_of(right_border)),
+                )
+            } else {
+                (
+                    rect.intersect(Rect::everything_above(left_border)),
+                    rect.intersect(Rect::everything_below(right_border)),
+                )
+            };
+            rects[node_index.left().0] = (!left.is_empty()).then_some(left_rect);
+            rects[node_index.right().0] = (!right.is_empty()).then_some(right_rect);
+        }
+
+        for (node, rect) in self.iter().zip(&mut rects) {
+            if let (true, false, Some(leaf_rect)) = (node.is_leaf(), node.is_collapsed(), rect) {
+                *leaf_rect = pad_rect(*leaf_rect, style.leaf_padding);
+            }
+        }
+
+        ComputedLayout { rects }
+    }
+}
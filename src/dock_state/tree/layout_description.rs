@@ -0,0 +1,81 @@
+/// Describes a layout of leaves to build a [`Tree`](crate::Tree) from with
+/// [`Tree::from_layout`](crate::Tree::from_layout), without splitting nodes one by one.
+///
+/// Parts of a [`Row`](Self::Row) or a [`Column`](Self::Column) take a share of its size proportional to their weights,
+/// so `vec![(1.0, a), (3.0, b)]` gives a quarter of the space to `a`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use egui_dock::{LayoutDescription, Tree};
+/// // A file tree on the left, an editor with a terminal below it, and an outline on the right.
+/// let layout = LayoutDescription::Row(vec![
+///     (1.0, LayoutDescription::Leaf(vec!["Files"])),
+///     (
+///         3.0,
+///         LayoutDescription::Column(vec![
+///             (3.0, LayoutDescription::Leaf(vec!["main.rs", "lib.rs"])),
+///             (1.0, LayoutDescription::Leaf(vec!["Terminal"])),
+///         ]),
+///     ),
+///     (1.0, LayoutDescription::Leaf(vec!["Outline"])),
+/// ]);
+/// let tree = Tree::from_layout(layout);
+/// assert_eq!(tree.num_tabs(), 5);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum LayoutDescription<Tab> {
+    /// A leaf with the given tabs.
+    Leaf(Vec<Tab>),
+
+    /// Parts placed side by side, from left to right, each with its weight.
+    Row(Vec<(f32, LayoutDescription<Tab>)>),
+
+    /// Parts stacked from top to bottom, each with its weight.
+    Column(Vec<(f32, LayoutDescription<Tab>)>),
+}
+
+impl<Tab> LayoutDescription<Tab> {
+    /// A grid of `rows` rows with `cols` cells each, all of the same size. `tabs` holds the tabs of each cell, row by
+    /// row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `tabs` isn't `rows * cols`.
+    pub fn grid(rows: usize, cols: usize, tabs: Vec<Vec<Tab>>) -> Self {
+        assert_eq!(
+            tabs.len(),
+            rows * cols,
+            "a grid of {rows}x{cols} cells needs the tabs of {} cells",
+            rows * cols
+        );
+        let mut cells = tabs.into_iter();
+        Self::Column(
+            (0..rows)
+                .map(|_| {
+                    let row = (0..cols)
+                        .filter_map(|_| cells.next())
+                        .map(|tabs| (1.0, Self::Leaf(tabs)))
+                        .collect();
+                    (1.0, Self::Row(row))
+                })
+                .collect(),
+        )
+    }
+
+    /// A layout commonly used by IDEs: a narrow `left` column, e.g. for a file tree, a wide `center` column for
+    /// editors, and a narrow `right` column, e.g. for an outline.
+    pub fn three_columns(left: Vec<Tab>, center: Vec<Tab>, right: Vec<Tab>) -> Self {
+        Self::Row(vec![
+            (1.0, Self::Leaf(left)),
+            (3.0, Self::Leaf(center)),
+            (1.0, Self::Leaf(right)),
+        ])
+    }
+
+    /// A `master` list on the left, taking a third of the width, and the `detail` view of the selected item on the
+    /// right.
+    pub fn master_detail(master: Vec<Tab>, detail: Vec<Tab>) -> Self {
+        Self::Row(vec![(1.0, Self::Leaf(master)), (2.0, Self::Leaf(detail))])
+    }
+}
@@ -20,13 +20,21 @@ pub mod node;
 /// Wrapper around indices to the collection of nodes inside a [`Tree`].
 pub mod node_index;
 
+/// Description of a layout to build a [`Tree`] from.
+pub mod layout_description;
+
+/// Rects of nodes computed without showing a [`DockArea`](crate::DockArea).
+pub mod computed_layout;
+
+pub use computed_layout::ComputedLayout;
+pub use layout_description::LayoutDescription;
 pub use node::Node;
 pub use node_index::NodeIndex;
 pub use tab_index::TabIndex;
 pub use tab_iter::TabIter;
 
 use egui::ahash::HashSet;
-use egui::Rect;
+use egui::{vec2, Rect, Vec2};
 use std::{
     cmp::max,
     fmt,
@@ -58,9 +66,43 @@ impl Split {
     pub const fn is_left_right(self) -> bool {
         matches!(self, Split::Left | Split::Right)
     }
+
+    /// Returns the fraction to split a node this way with, so that the new node takes `fraction` of its area.
+    ///
+    /// The fraction of a split node always belongs to its left or top child, which is the new node for
+    /// [`Split::Left`] and [`Split::Above`], and the old one otherwise.
+    pub(crate) fn fraction_for_new_node(self, fraction: f32) -> f32 {
+        match self {
+            Split::Left | Split::Above => fraction,
+            Split::Right | Split::Below => 1.0 - fraction,
+        }
+    }
+}
+
+/// Direction from a node towards one of its neighbors, like for moving a tab with the keyboard.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[allow(missing_docs)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl Direction {
+    /// Returns the [`Split`] which creates a new node in this direction.
+    pub const fn split(self) -> Split {
+        match self {
+            Direction::Left => Split::Left,
+            Direction::Right => Split::Right,
+            Direction::Up => Split::Above,
+            Direction::Down => Split::Below,
+        }
+    }
 }
 
 /// Specify how a tab should be added to a Node.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum TabInsert {
     /// Split the node in the given direction.
     Split(Split),
@@ -70,9 +112,14 @@ pub enum TabInsert {
 
     /// Append the tab to the node.
     Append,
+
+    /// Split the node into an even grid of leaves, one for each of its tabs and one for the inserted tab, see
+    /// [`Tree::explode_leaf`].
+    Explode,
 }
 
 /// The destination for a tab which is being moved.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum TabDestination {
     /// Move to a new window with this rect.
     Window(Rect),
@@ -103,6 +150,16 @@ impl TabDestination {
     }
 }
 
+/// Returns the numbers of leaves in each row of the grid [`Tree::explode_leaf`] makes out of a leaf with `count` tabs.
+pub(crate) fn explode_grid(count: usize) -> Vec<usize> {
+    let columns = (1..).find(|columns| columns * columns >= count).unwrap();
+    let mut rows = vec![columns; count / columns];
+    if count % columns > 0 {
+        rows.push(count % columns);
+    }
+    rows
+}
+
 /// Binary tree representing the relationships between [`Node`]s.
 ///
 /// # Implementation details
@@ -120,7 +177,7 @@ impl TabDestination {
 /// For "Vertical" nodes:
 ///  - left child contains Top node.
 ///  - right child contains Bottom node.
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Tree<Tab> {
     // Binary tree vector
@@ -177,6 +234,44 @@ impl<Tab> Tree<Tab> {
         }
     }
 
+    /// Creates a new [`Tree`] with leaves laid out as described by the `layout`.
+    ///
+    /// Leaves without tabs are left out, and the rest of their row or column takes their space.
+    ///
+    /// See [`LayoutDescription`] for an example.
+    pub fn from_layout(layout: LayoutDescription<Tab>) -> Self {
+        match layout {
+            LayoutDescription::Leaf(tabs) => Self::new(tabs),
+            LayoutDescription::Row(parts) => Self::from_layout_parts(parts, Split::Right),
+            LayoutDescription::Column(parts) => Self::from_layout_parts(parts, Split::Below),
+        }
+    }
+
+    /// Builds a tree from parts of a row or a column, placing each part on the `split` side of the ones before it.
+    fn from_layout_parts(parts: Vec<(f32, LayoutDescription<Tab>)>, split: Split) -> Self {
+        let mut parts = parts
+            .into_iter()
+            .map(|(weight, layout)| (weight.max(0.0), Self::from_layout(layout)))
+            .filter(|(_, tree)| tree.num_tabs() > 0)
+            .rev();
+        let Some((mut weight, mut tree)) = parts.next() else {
+            return Self::new(Vec::new());
+        };
+        // Starting from the last part, keep splitting the previous one to put everything after it on its side.
+        for (part_weight, mut part) in parts {
+            let total = part_weight + weight;
+            let fraction = if total > 0.0 {
+                part_weight / total
+            } else {
+                0.5
+            };
+            part.split_tree(NodeIndex::root(), split, fraction, tree);
+            tree = part;
+            weight = total;
+        }
+        tree
+    }
+
     /// Returns the viewport [`Rect`] and the `Tab` inside the first leaf node,
     /// or `None` if no leaf exists in the [`Tree`].
     #[inline]
@@ -222,6 +317,221 @@ impl<Tab> Tree<Tab> {
         self.nodes.iter_mut()
     }
 
+    /// Returns the index of the parent of the node at `node`, or [`None`] if it's the root or isn't in the tree.
+    ///
+    /// The tree doesn't store links to parents. Nodes are kept in a binary heap layout, where the index of a parent
+    /// follows from the index of its child, so this takes constant time. Operations which move nodes, like
+    /// [`split`](Self::split) and [`remove_leaf`](Self::remove_leaf), move whole subtrees between places in the heap,
+    /// which keeps the relation right without any links to update.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{NodeIndex, Tree};
+    /// let mut tree = Tree::new(vec!["tab 1"]);
+    /// let [_, right] = tree.split_right(NodeIndex::root(), 0.5, vec!["tab 2"]);
+    /// assert_eq!(tree.parent(right), Some(NodeIndex::root()));
+    /// assert_eq!(tree.parent(NodeIndex::root()), None);
+    /// ```
+    #[inline]
+    pub fn parent(&self, node: NodeIndex) -> Option<NodeIndex> {
+        self.contains_node(node).then(|| node.parent()).flatten()
+    }
+
+    /// Returns an [`Iterator`] over the parent of the node at `node`, its parent, and so on up to the root.
+    /// It's empty if the node isn't in the tree.
+    ///
+    /// The iterator doesn't borrow the tree, so the ancestors can be modified while iterating.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{NodeIndex, Tree};
+    /// let mut tree = Tree::new(vec!["tab 1"]);
+    /// let [left, _] = tree.split_right(NodeIndex::root(), 0.5, vec!["tab 2"]);
+    /// let [_, below] = tree.split_below(left, 0.5, vec!["tab 3"]);
+    ///
+    /// let ancestors: Vec<_> = tree.ancestors(below).collect();
+    /// assert_eq!(ancestors, [left, NodeIndex::root()]);
+    /// ```
+    #[inline]
+    pub fn ancestors(&self, node: NodeIndex) -> impl Iterator<Item = NodeIndex> {
+        let ancestors = if self.contains_node(node) {
+            usize::MAX
+        } else {
+            0
+        };
+        node.ancestors().take(ancestors)
+    }
+
+    /// Returns an [`Iterator`] over every non-empty node of the tree along with its parent and, if it's a
+    /// [`Node::Vertical`] or [`Node::Horizontal`], its children.
+    ///
+    /// Nodes come in depth-first order, with each parent before its children and the left (or top) subtree before
+    /// the right (or bottom) one. This allows external tools to rebuild the hierarchy without relying on how indices
+    /// of the nodes are laid out.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{NodeIndex, Tree};
+    /// let mut tree = Tree::new(vec!["a"]);
+    /// let [left, right] = tree.split_right(NodeIndex::root(), 0.5, vec!["c"]);
+    /// let [top, bottom] = tree.split_below(left, 0.5, vec!["b"]);
+    ///
+    /// let relations: Vec<_> = tree.node_relations().collect();
+    /// assert_eq!(
+    ///     relations,
+    ///     [
+    ///         (NodeIndex::root(), None, Some([left, right])),
+    ///         (left, Some(NodeIndex::root()), Some([top, bottom])),
+    ///         (top, Some(left), None),
+    ///         (bottom, Some(left), None),
+    ///         (right, Some(NodeIndex::root()), None),
+    ///     ]
+    /// );
+    /// ```
+    pub fn node_relations(
+        &self,
+    ) -> impl Iterator<Item = (NodeIndex, Option<NodeIndex>, Option<[NodeIndex; 2]>)> + '_ {
+        let mut stack = Vec::new();
+        if self.contains_node(NodeIndex::root()) {
+            stack.push(NodeIndex::root());
+        }
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            let children = self[node].is_parent().then(|| [node.left(), node.right()]);
+            if let Some([left, right]) = children {
+                stack.push(right);
+                stack.push(left);
+            }
+            Some((node, node.parent(), children))
+        })
+    }
+
+    /// Returns the leaf next to the leaf at `node` in the given `direction`, or [`None`] if there's no such leaf or
+    /// `node` isn't a leaf.
+    ///
+    /// The leaves are compared by their rects as of the last time the tree was shown, so the leaf which is
+    /// actually adjacent on the screen is found regardless of how deeply the nodes are nested. Out of several leaves on
+    /// that side, the closest one is picked, preferring the one facing the middle of `node`. If the tree hasn't been
+    /// shown yet, the leaf is looked for in the closest split going in that direction instead.
+    ///
+    /// This can be used to move the focus or tabs with the keyboard, see
+    /// [`DockState::move_active_tab`](crate::DockState::move_active_tab).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui::{Pos2, Rect};
+    /// # use egui_dock::{Direction, NodeIndex, Style, Tree};
+    /// let mut tree = Tree::new(vec!["a"]);
+    /// let [left, right] = tree.split_right(NodeIndex::root(), 0.5, vec!["c"]);
+    /// let [_, b] = tree.split_below(left, 0.2, vec!["b"]);
+    /// let [_, d] = tree.split_below(right, 0.3, vec!["d"]);
+    ///
+    /// let rect = Rect::from_min_max(Pos2::ZERO, Pos2::new(800.0, 600.0));
+    /// let layout = tree.compute_layout(rect, &Style::default(), 1.0);
+    /// for (node, rect) in layout.iter() {
+    ///     tree[node].set_rect(rect);
+    /// }
+    ///
+    /// // Most of "b" is next to "d", even though "c" is the first leaf in the right column.
+    /// assert_eq!(tree.neighbor_in_direction(b, Direction::Right), Some(d));
+    /// assert_eq!(tree.neighbor_in_direction(b, Direction::Down), None);
+    /// ```
+    pub fn neighbor_in_direction(
+        &self,
+        node: NodeIndex,
+        direction: Direction,
+    ) -> Option<NodeIndex> {
+        if !self.nodes.get(node.0).is_some_and(|node| node.is_leaf()) {
+            return None;
+        }
+        let rect = self[node].rect().filter(|rect| rect.is_positive());
+        let Some(rect) = rect else {
+            return self.neighbor_in_structure(node, direction);
+        };
+
+        let (source_range, middle) = match direction {
+            Direction::Left | Direction::Right => (rect.y_range(), rect.center().y),
+            Direction::Up | Direction::Down => (rect.x_range(), rect.center().x),
+        };
+
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|&(index, other)| index != node.0 && other.is_leaf())
+            .filter_map(|(index, other)| {
+                let other = other.rect().filter(|rect| rect.is_positive())?;
+                let (gap, range) = match direction {
+                    Direction::Left => (rect.min.x - other.max.x, other.y_range()),
+                    Direction::Right => (other.min.x - rect.max.x, other.y_range()),
+                    Direction::Up => (rect.min.y - other.max.y, other.x_range()),
+                    Direction::Down => (other.min.y - rect.max.y, other.x_range()),
+                };
+                let overlap = range.max.min(source_range.max) - range.min.max(source_range.min);
+                // Leaves only touching the corners or lying on the other side of `node` aren't next to it.
+                (gap > -1.0 && overlap > 0.0).then(|| {
+                    let offset = (range.min - middle).max(middle - range.max).max(0.0);
+                    (NodeIndex(index), gap.max(0.0), offset)
+                })
+            })
+            .min_by(|(_, gap_a, offset_a), (_, gap_b, offset_b)| {
+                gap_a.total_cmp(gap_b).then(offset_a.total_cmp(offset_b))
+            })
+            .map(|(index, _, _)| index)
+    }
+
+    /// Finds the leaf next to `node` in the given `direction` without looking at rects, going up to the closest split
+    /// which has a node on that side, and then down to the leaf of that node closest to the side facing `node`.
+    fn neighbor_in_structure(&self, node: NodeIndex, direction: Direction) -> Option<NodeIndex> {
+        let split = direction.split();
+        let towards_left = matches!(direction, Direction::Left | Direction::Up);
+        let mut child = node;
+        let mut next = loop {
+            let parent = child.parent()?;
+            let along = match self[parent] {
+                Node::Horizontal { .. } => split.is_left_right(),
+                Node::Vertical { .. } => split.is_top_bottom(),
+                _ => false,
+            };
+            if along && child.is_left() != towards_left {
+                break if towards_left {
+                    parent.left()
+                } else {
+                    parent.right()
+                };
+            }
+            child = parent;
+        };
+        loop {
+            match self.nodes.get(next.0)? {
+                Node::Leaf { .. } => return Some(next),
+                Node::Empty => return None,
+                parent => {
+                    let along = if parent.is_horizontal() {
+                        split.is_left_right()
+                    } else {
+                        split.is_top_bottom()
+                    };
+                    let (near, far) = if along && towards_left {
+                        (next.right(), next.left())
+                    } else {
+                        (next.left(), next.right())
+                    };
+                    next = if self.contains_node(near) { near } else { far };
+                }
+            }
+        }
+    }
+
+    /// Returns whether there's a non-empty node at `node`.
+    #[inline]
+    fn contains_node(&self, node: NodeIndex) -> bool {
+        self.nodes.get(node.0).is_some_and(|node| !node.is_empty())
+    }
+
     /// Returns an [`Iterator`] of [`NodeIndex`] ordered in a breadth first manner.
     #[inline(always)]
     pub(crate) fn breadth_first_index_iter(&self) -> impl Iterator<Item = NodeIndex> {
@@ -479,10 +789,95 @@ impl<Tab> Tree<Tab> {
         split: Split,
         fraction: f32,
         new: Node<Tab>,
+    ) -> [NodeIndex; 2] {
+        assert_ne!(new.tabs_count(), 0);
+        self.insert_split(parent, split, fraction, new)
+    }
+
+    /// Splits `parent` like [`split`](Self::split), but puts all nodes of another `tree` on the new side instead of a
+    /// single leaf, keeping its layout.
+    ///
+    /// Returns the indices of the old node and the root of the inserted tree, in this order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tree` has no tabs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{NodeIndex, Split, Tree};
+    /// let mut tree = Tree::new(vec!["a"]);
+    /// let mut other = Tree::new(vec!["b"]);
+    /// other.split_below(NodeIndex::root(), 0.5, vec!["c"]);
+    ///
+    /// let [_, new] = tree.split_tree(NodeIndex::root(), Split::Right, 0.5, other);
+    /// assert!(tree[new].is_vertical());
+    /// assert_eq!(tree.num_tabs(), 3);
+    /// ```
+    pub fn split_tree(
+        &mut self,
+        parent: NodeIndex,
+        split: Split,
+        fraction: f32,
+        tree: Tree<Tab>,
+    ) -> [NodeIndex; 2] {
+        assert_ne!(tree.num_tabs(), 0);
+        let focused = tree.focused_node;
+        let mut nodes = tree.nodes.into_iter().enumerate();
+        let (_, root) = nodes.next().expect("a tree with tabs has a root");
+        let index = self.insert_split(parent, split, fraction, root);
+
+        // Move the rest of the nodes to the same places relative to the new root.
+        let graft = |source: NodeIndex| {
+            let mut path = Vec::new();
+            let mut node = source;
+            while let Some(parent) = node.parent() {
+                path.push(node == parent.left());
+                node = parent;
+            }
+            path.into_iter().rev().fold(
+                index[1],
+                |node, left| if left { node.left() } else { node.right() },
+            )
+        };
+        let mut leaves = Vec::new();
+        for (source, node) in nodes {
+            if node.is_empty() {
+                continue;
+            }
+            let destination = graft(NodeIndex(source));
+            if destination.0 >= self.nodes.len() {
+                let level = destination.level();
+                self.nodes
+                    .resize_with((1 << (level + 1)) - 1, || Node::Empty);
+            }
+            if node.is_leaf() {
+                leaves.push(destination);
+            }
+            self[destination] = node;
+        }
+        if self[index[1]].is_leaf() {
+            leaves.push(index[1]);
+        }
+
+        for &leaf in &leaves {
+            self.node_update_collapsed(leaf);
+        }
+        self.focused_node = focused.map(graft).or(leaves.first().copied());
+        index
+    }
+
+    /// Splits `parent`, putting the `new` node on the `split` side of it.
+    fn insert_split(
+        &mut self,
+        parent: NodeIndex,
+        split: Split,
+        fraction: f32,
+        new: Node<Tab>,
     ) -> [NodeIndex; 2] {
         let old = self[parent].split(split, fraction);
         assert!(old.is_leaf() || old.is_parent());
-        assert_ne!(new.tabs_count(), 0);
         // Resize vector to fit the new size of the binary tree.
         {
             let index = self.nodes.iter().rposition(|n| !n.is_empty()).unwrap_or(0);
@@ -579,15 +974,62 @@ impl<Tab> Tree<Tab> {
     /// This method will not never panic and instead removes focus from all nodes when given an invalid index.
     #[inline]
     pub fn set_focused_node(&mut self, node_index: NodeIndex) {
-        self.focused_node = self
-            .nodes
-            .get(node_index.0)
-            .filter(|node| node.is_leaf())
-            .map(|_| node_index);
+        self.set_focused_leaf(Some(node_index));
+    }
+
+    /// Sets the currently focused leaf to `leaf` if it's a leaf, or removes focus from all nodes otherwise.
+    #[inline]
+    pub(crate) fn set_focused_leaf(&mut self, leaf: Option<NodeIndex>) {
+        self.focused_node =
+            leaf.filter(|leaf| self.nodes.get(leaf.0).is_some_and(|node| node.is_leaf()));
+    }
+
+    /// Makes the leaf at `node_index` the central node of the [`Tree`].
+    ///
+    /// The central node is never removed from the tree: closing or moving away all of its tabs leaves it empty, and it
+    /// takes up the remaining space when its siblings are removed. Only one leaf can be central at a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node at `node_index` is not a [`Leaf`](Node::Leaf).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, Split};
+    /// let mut dock_state = DockState::new(vec!["viewport"]);
+    /// let surface = dock_state.main_surface_mut();
+    /// let [viewport, _] = surface.split_left(NodeIndex::root(), 0.2, vec!["inspector"]);
+    /// surface.set_central_node(viewport);
+    /// assert_eq!(surface.central_node(), Some(viewport));
+    ///
+    /// surface.remove_tab((viewport, egui_dock::TabIndex(0)));
+    /// assert!(surface[viewport].is_central());
+    /// assert_eq!(surface[viewport].tabs_count(), 0);
+    /// ```
+    pub fn set_central_node(&mut self, node_index: NodeIndex) {
+        assert!(self[node_index].is_leaf());
+        for (index, node) in self.nodes.iter_mut().enumerate() {
+            if let Node::Leaf { central, .. } = node {
+                *central = index == node_index.0;
+            }
+        }
+    }
+
+    /// Returns the index of the central node of the [`Tree`], if there is one.
+    ///
+    /// See [`set_central_node`](Self::set_central_node).
+    pub fn central_node(&self) -> Option<NodeIndex> {
+        self.nodes
+            .iter()
+            .position(|node| node.is_central())
+            .map(NodeIndex)
     }
 
     /// Removes the given node from the [`Tree`].
     ///
+    /// If the node is the [central node](Self::set_central_node), only its tabs are removed.
+    ///
     /// # Panics
     ///
     /// - If the tree is empty.
@@ -596,6 +1038,22 @@ impl<Tab> Tree<Tab> {
         assert!(!self.is_empty());
         assert!(self[node].is_leaf());
 
+        if let Node::Leaf {
+            tabs,
+            tab_rects,
+            active,
+            history,
+            central: true,
+            ..
+        } = &mut self[node]
+        {
+            tabs.clear();
+            tab_rects.clear();
+            history.clear();
+            *active = TabIndex(0);
+            return;
+        }
+
         let Some(parent) = node.parent() else {
             self.nodes.clear();
             return;
@@ -686,8 +1144,8 @@ impl<Tab> Tree<Tab> {
     /// Sets which is the active tab within a specific node.
     #[inline]
     pub fn set_active_tab(&mut self, node_index: NodeIndex, tab_index: TabIndex) {
-        if let Some(Node::Leaf { active, .. }) = self.nodes.get_mut(node_index.0) {
-            *active = tab_index;
+        if let Some(node) = self.nodes.get_mut(node_index.0) {
+            node.set_active_tab(tab_index);
         }
     }
 
@@ -732,13 +1190,14 @@ impl<Tab> Tree<Tab> {
 
     /// Removes the tab at the given ([`NodeIndex`], [`TabIndex`]) pair.
     ///
-    /// If the node is emptied after the tab is removed, the node will also be removed.
+    /// If the node is emptied after the tab is removed, the node will also be removed, unless it's the
+    /// [central node](Self::set_central_node).
     ///
     /// Returns the removed tab if it exists, or `None` otherwise.
     pub fn remove_tab(&mut self, (node_index, tab_index): (NodeIndex, TabIndex)) -> Option<Tab> {
         let node = &mut self[node_index];
         let tab = node.remove_tab(tab_index);
-        if node.tabs_count() == 0 {
+        if node.tabs_count() == 0 && !node.is_central() {
             self.remove_leaf(node_index);
         }
         tab
@@ -802,14 +1261,142 @@ impl<Tab> Tree<Tab> {
     where
         F: FnMut(&mut Tab) -> bool,
     {
-        let mut emptied_nodes = HashSet::default();
-        for (index, node) in self.nodes.iter_mut().enumerate() {
+        for node in self.nodes.iter_mut().filter(|node| node.is_leaf()) {
             node.retain_tabs(&mut predicate);
             if node.is_empty() {
-                emptied_nodes.insert(NodeIndex(index));
+                // Put back without tabs, so that it's removed below along with its place in the layout.
+                *node = Node::leaf_with(Vec::new());
+            }
+        }
+        // Removing a leaf moves the nodes below its parent, so the next one is looked up again each time.
+        while let Some(index) = self
+            .nodes
+            .iter()
+            .position(|node| node.is_leaf() && node.tabs_count() == 0 && !node.is_central())
+        {
+            self.remove_leaf(NodeIndex(index));
+        }
+    }
+
+    /// Sets the fractions of all split nodes so that every leaf gets the same area, like the even layouts of terminal
+    /// multiplexers.
+    ///
+    /// ```
+    /// # use egui_dock::{Node, NodeIndex, Tree};
+    /// let mut tree = Tree::new(vec!["a"]);
+    /// let [_, right] = tree.split_right(NodeIndex::root(), 0.8, vec!["b"]);
+    /// tree.split_below(right, 0.3, vec!["c"]);
+    /// tree.equalize_fractions();
+    ///
+    /// // The left leaf takes one third of the width, the two on the right split the rest in half.
+    /// let Node::Horizontal { fraction, .. } = tree[NodeIndex::root()] else { unreachable!() };
+    /// assert_eq!(fraction, 1.0 / 3.0);
+    /// let Node::Vertical { fraction, .. } = tree[right] else { unreachable!() };
+    /// assert_eq!(fraction, 0.5);
+    /// ```
+    pub fn equalize_fractions(&mut self) {
+        // Children always come after their parents, so count the leaves from the bottom up.
+        let mut leaf_counts = vec![0usize; self.nodes.len()];
+        for index in (0..self.nodes.len()).rev() {
+            leaf_counts[index] = match &self.nodes[index] {
+                Node::Empty => 0,
+                Node::Leaf { .. } => 1,
+                Node::Vertical { .. } | Node::Horizontal { .. } => {
+                    let node = NodeIndex(index);
+                    leaf_counts.get(node.left().0).copied().unwrap_or(0)
+                        + leaf_counts.get(node.right().0).copied().unwrap_or(0)
+                }
+            };
+        }
+
+        for (index, node) in self.nodes.iter_mut().enumerate() {
+            if let Node::Vertical { fraction, .. } | Node::Horizontal { fraction, .. } = node {
+                let node = NodeIndex(index);
+                let left = leaf_counts.get(node.left().0).copied().unwrap_or(0);
+                let right = leaf_counts.get(node.right().0).copied().unwrap_or(0);
+                if left > 0 && right > 0 {
+                    *fraction = left as f32 / (left + right) as f32;
+                }
+            }
+        }
+    }
+
+    /// Splits a leaf into an even grid of leaves, one for each of its tabs, taking the tabs out in the order they're
+    /// in. The grid has as many columns as needed for it to be about as tall as it's wide, and its last row may have
+    /// fewer, wider leaves.
+    ///
+    /// The leaf with the active tab is focused. Returns the indices of the leaves, in the order of their tabs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node isn't a leaf.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{NodeIndex, Tree};
+    /// let mut tree = Tree::new(vec!["a", "b", "c", "d"]);
+    /// let leaves = tree.explode_leaf(NodeIndex::root());
+    ///
+    /// // Two rows of two leaves each.
+    /// assert_eq!(leaves.len(), 4);
+    /// assert!(tree[NodeIndex::root()].is_vertical());
+    /// assert_eq!(tree[leaves[1]].tabs(), Some(&["b"][..]));
+    /// assert_eq!(tree[leaves[2]].tabs(), Some(&["c"][..]));
+    /// ```
+    pub fn explode_leaf(&mut self, node_index: NodeIndex) -> Vec<NodeIndex> {
+        assert!(self[node_index].is_leaf(), "the node isn't a leaf");
+        let count = self[node_index].tabs_count();
+        if count < 2 {
+            return vec![node_index];
+        }
+        let active = self[node_index].active_tab().map_or(0, |tab| tab.0);
+
+        // The first tab stays in the leaf, so that it keeps its settings.
+        let mut tabs: Vec<_> = (1..count)
+            .rev()
+            .filter_map(|index| self[node_index].remove_tab(TabIndex(index)))
+            .collect();
+        tabs.reverse();
+        self[node_index].set_active_tab(TabIndex(0));
+        let mut tabs = tabs.into_iter();
+        let rows: Vec<Vec<Tab>> = explode_grid(count)
+            .into_iter()
+            .enumerate()
+            .map(|(row, len)| {
+                let len = if row == 0 { len - 1 } else { len };
+                tabs.by_ref().take(len).collect()
+            })
+            .collect();
+
+        // Each split gives the old node its share of what's left, so that all rows and columns are the same size.
+        let row_count = rows.len();
+        let mut row_leaves: Vec<(NodeIndex, Vec<Tab>)> = Vec::with_capacity(row_count);
+        let mut current = node_index;
+        for (row, mut row_tabs) in rows.into_iter().enumerate() {
+            if row > 0 {
+                let first = row_tabs.remove(0);
+                let fraction = 1.0 / (row_count - row + 1) as f32;
+                let [above, below] = self.split(current, Split::Below, fraction, Node::leaf(first));
+                row_leaves.last_mut().unwrap().0 = above;
+                current = below;
+            }
+            row_leaves.push((current, row_tabs));
+        }
+        let mut leaves = Vec::with_capacity(count);
+        for (row_leaf, row_tabs) in row_leaves {
+            let column_count = row_tabs.len() + 1;
+            let mut current = row_leaf;
+            for (column, tab) in row_tabs.into_iter().enumerate() {
+                let fraction = 1.0 / (column_count - column) as f32;
+                let [left, right] = self.split(current, Split::Right, fraction, Node::leaf(tab));
+                leaves.push(left);
+                current = right;
             }
+            leaves.push(current);
         }
-        self.balance(emptied_nodes);
+        self.focused_node = Some(leaves[active]);
+        leaves
     }
 
     /// Sets the collapsing state of the [`Tree`].
@@ -832,6 +1419,33 @@ impl<Tab> Tree<Tab> {
         self.collapsed_leaf_count
     }
 
+    /// Returns the minimum and maximum size of the node at `node_index`,
+    /// taking the size limits of all of its subnodes into account.
+    pub(crate) fn size_limits(&self, node_index: NodeIndex) -> (Vec2, Vec2) {
+        let Some(node) = self.nodes.get(node_index.0) else {
+            return (Vec2::ZERO, Vec2::INFINITY);
+        };
+        let (mut min, mut max) = (node.layout_min_size(), node.max_size());
+        if node.is_parent() {
+            let (left_min, left_max) = self.size_limits(node_index.left());
+            let (right_min, right_max) = self.size_limits(node_index.right());
+            let (children_min, children_max) = if node.is_horizontal() {
+                (
+                    vec2(left_min.x + right_min.x, left_min.y.max(right_min.y)),
+                    vec2(left_max.x + right_max.x, left_max.y.min(right_max.y)),
+                )
+            } else {
+                (
+                    vec2(left_min.x.max(right_min.x), left_min.y + right_min.y),
+                    vec2(left_max.x.min(right_max.x), left_max.y + right_max.y),
+                )
+            };
+            min = min.max(children_min);
+            max = max.min(children_max);
+        }
+        (min, max)
+    }
+
     fn balance(&mut self, emptied_nodes: HashSet<NodeIndex>) {
         let mut emptied_parents = HashSet::default();
         for parent_index in emptied_nodes.into_iter().filter_map(|ni| ni.parent()) {
@@ -856,10 +1470,7 @@ impl<Tab> Tree<Tab> {
         let collapsed = self[node_index].is_collapsed();
         if !collapsed {
             // Recursively notify parent nodes that the leaf has expanded
-            let mut parent_index_option = node_index.parent();
-            while let Some(parent_index) = parent_index_option {
-                parent_index_option = parent_index.parent();
-
+            for parent_index in node_index.ancestors() {
                 // Update collapsed leaf count and collapse status
                 let left_count = self[parent_index.left()].collapsed_leaf_count();
                 let right_count = self[parent_index.right()].collapsed_leaf_count();
@@ -876,10 +1487,7 @@ impl<Tab> Tree<Tab> {
             self.set_collapsed_leaf_count(self[root_index].collapsed_leaf_count());
         } else {
             // Recursively notify parent nodes that the leaf has collapsed
-            let mut parent_index_option = node_index.parent();
-            while let Some(parent_index) = parent_index_option {
-                parent_index_option = parent_index.parent();
-
+            for parent_index in node_index.ancestors() {
                 // Update collapsed leaf count and collapse status
                 let left_count = self[parent_index.left()].collapsed_leaf_count();
                 let right_count = self[parent_index.right()].collapsed_leaf_count();
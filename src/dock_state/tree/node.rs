@@ -1,8 +1,8 @@
-use crate::{Split, TabIndex};
-use egui::Rect;
+use crate::{Split, TabBarStyle, TabIndex};
+use egui::{emath::NumExt, Rect, Vec2};
 
 /// Represents an abstract node of a [`Tree`](crate::Tree).
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum Node<Tab> {
     /// Empty node.
@@ -16,17 +16,51 @@ pub enum Node<Tab> {
         /// The tab body rectangle.
         viewport: Rect,
 
+        /// The rectangles of the visible parts of tab titles, as of the last frame they were shown.
+        #[cfg_attr(feature = "serde", serde(skip))]
+        tab_rects: Vec<Rect>,
+
         /// All the tabs in this node.
         tabs: Vec<Tab>,
 
         /// The opened tab.
         active: TabIndex,
 
+        /// Tabs which were opened in this leaf, from the least to the most recently opened one.
+        /// Used for choosing which tab to open after the active one is removed.
+        #[cfg_attr(feature = "serde", serde(default))]
+        history: Vec<TabIndex>,
+
         /// Scroll amount of the tab bar.
         scroll: f32,
 
         /// Whether the leaf is collapsed.
         collapsed: bool,
+
+        /// Whether this is the central leaf of the tree, which is never removed, even when it has no tabs.
+        #[cfg_attr(feature = "serde", serde(default))]
+        central: bool,
+
+        /// The minimum size of this node, enforced when laying out the tree.
+        #[cfg_attr(feature = "serde", serde(default))]
+        min_size: Option<Vec2>,
+
+        /// The maximum size of this node, enforced when laying out the tree.
+        #[cfg_attr(feature = "serde", serde(default))]
+        max_size: Option<Vec2>,
+
+        /// The size this leaf needs to fit its tab bar and the body of each of its tabs, as reported by
+        /// [`TabViewer::min_body_size`](crate::TabViewer::min_body_size) the last frame it was shown.
+        #[cfg_attr(feature = "serde", serde(skip))]
+        content_min_size: Vec2,
+
+        /// Whether the tab bar of this leaf is hidden, leaving the whole leaf to the body of its active tab.
+        #[cfg_attr(feature = "serde", serde(default))]
+        tab_bar_hidden: bool,
+
+        /// The style of the tab bar of this leaf, used in place of [`Style::tab_bar`](crate::Style::tab_bar).
+        #[cfg_attr(feature = "serde", serde(default))]
+        tab_bar_style: Option<TabBarStyle>,
     },
 
     /// Parent node in the vertical orientation.
@@ -42,6 +76,14 @@ pub enum Node<Tab> {
 
         /// The number of collapsed leaf subnodes.
         collapsed_leaf_count: i32,
+
+        /// The minimum size of this node, enforced when laying out the tree.
+        #[cfg_attr(feature = "serde", serde(default))]
+        min_size: Option<Vec2>,
+
+        /// The maximum size of this node, enforced when laying out the tree.
+        #[cfg_attr(feature = "serde", serde(default))]
+        max_size: Option<Vec2>,
     },
 
     /// Parent node in the horizontal orientation.
@@ -57,6 +99,14 @@ pub enum Node<Tab> {
 
         /// The number of collapsed leaf subnodes.
         collapsed_leaf_count: i32,
+
+        /// The minimum size of this node, enforced when laying out the tree.
+        #[cfg_attr(feature = "serde", serde(default))]
+        min_size: Option<Vec2>,
+
+        /// The maximum size of this node, enforced when laying out the tree.
+        #[cfg_attr(feature = "serde", serde(default))]
+        max_size: Option<Vec2>,
     },
 }
 
@@ -67,10 +117,18 @@ impl<Tab> Node<Tab> {
         Self::Leaf {
             rect: Rect::NOTHING,
             viewport: Rect::NOTHING,
+            tab_rects: Vec::new(),
             tabs: vec![tab],
             active: TabIndex(0),
+            history: Vec::new(),
             scroll: 0.0,
             collapsed: false,
+            central: false,
+            min_size: None,
+            max_size: None,
+            content_min_size: Vec2::ZERO,
+            tab_bar_hidden: false,
+            tab_bar_style: None,
         }
     }
 
@@ -80,10 +138,18 @@ impl<Tab> Node<Tab> {
         Self::Leaf {
             rect: Rect::NOTHING,
             viewport: Rect::NOTHING,
+            tab_rects: Vec::new(),
             tabs,
             active: TabIndex(0),
+            history: Vec::new(),
             scroll: 0.0,
             collapsed: false,
+            central: false,
+            min_size: None,
+            max_size: None,
+            content_min_size: Vec2::ZERO,
+            tab_bar_hidden: false,
+            tab_bar_style: None,
         }
     }
 
@@ -111,6 +177,20 @@ impl<Tab> Node<Tab> {
         }
     }
 
+    /// Get the [`Rect`] of the visible part of a tab's title in the tab bar, as of the last frame it was shown.
+    ///
+    /// Returns [`None`] if the node isn't a [`Leaf`](Node::Leaf) or the tab's title isn't shown.
+    #[inline]
+    pub fn tab_rect(&self, tab_index: TabIndex) -> Option<Rect> {
+        match self {
+            Node::Leaf { tab_rects, .. } => tab_rects
+                .get(tab_index.0)
+                .copied()
+                .filter(|rect| rect.is_positive()),
+            _ => None,
+        }
+    }
+
     /// Returns `true` if the node is a [`Empty`](Node::Empty), otherwise `false`.
     #[inline(always)]
     pub const fn is_empty(&self) -> bool {
@@ -142,6 +222,14 @@ impl<Tab> Node<Tab> {
         self.is_horizontal() || self.is_vertical()
     }
 
+    /// Returns `true` if the node is the central [`Leaf`](Node::Leaf) of its tree, otherwise `false`.
+    ///
+    /// See [`Tree::set_central_node`](crate::Tree::set_central_node).
+    #[inline(always)]
+    pub const fn is_central(&self) -> bool {
+        matches!(self, Self::Leaf { central: true, .. })
+    }
+
     /// Returns `true` if the node is collapsed, otherwise `false`.
     #[inline(always)]
     pub fn is_collapsed(&self) -> bool {
@@ -157,6 +245,37 @@ impl<Tab> Node<Tab> {
         }
     }
 
+    /// Returns `true` if the node is a [`Leaf`](Node::Leaf) whose tab bar is hidden, otherwise `false`.
+    #[inline(always)]
+    pub const fn is_tab_bar_hidden(&self) -> bool {
+        matches!(
+            self,
+            Self::Leaf {
+                tab_bar_hidden: true,
+                ..
+            }
+        )
+    }
+
+    /// Returns the style of the tab bar of a [`Leaf`](Node::Leaf) if it has its own, otherwise [`None`].
+    #[inline]
+    pub fn tab_bar_style(&self) -> Option<&TabBarStyle> {
+        match self {
+            Node::Leaf { tab_bar_style, .. } => tab_bar_style.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Returns how far the tab bar of a [`Leaf`](Node::Leaf) is scrolled from its start, in points, otherwise
+    /// [`None`].
+    #[inline]
+    pub fn tab_bar_scroll(&self) -> Option<f32> {
+        match self {
+            Node::Leaf { scroll, .. } => Some(0.0 - *scroll),
+            _ => None,
+        }
+    }
+
     /// Returns the number of layers of collapsed leaf subnodes.
     pub fn collapsed_leaf_count(&self) -> i32 {
         match self {
@@ -195,12 +314,16 @@ impl<Tab> Node<Tab> {
                 rect,
                 fully_collapsed: self.is_collapsed(),
                 collapsed_leaf_count: self.collapsed_leaf_count(),
+                min_size: None,
+                max_size: None,
             },
             Split::Above | Split::Below => Node::Vertical {
                 fraction,
                 rect,
                 fully_collapsed: self.is_collapsed(),
                 collapsed_leaf_count: self.collapsed_leaf_count(),
+                min_size: None,
+                max_size: None,
             },
         };
         std::mem::replace(self, src)
@@ -299,8 +422,15 @@ impl<Tab> Node<Tab> {
     #[inline]
     pub fn append_tab(&mut self, tab: Tab) {
         match self {
-            Node::Leaf { tabs, active, .. } => {
+            Node::Leaf {
+                tabs,
+                active,
+                history,
+                ..
+            } => {
                 *active = TabIndex(tabs.len());
+                history.retain(|entry| *entry != *active);
+                history.push(*active);
                 tabs.push(tab);
             }
             _ => panic!("node was not a leaf"),
@@ -326,6 +456,47 @@ impl<Tab> Node<Tab> {
         }
     }
 
+    /// Hides or shows the tab bar of the leaf. A leaf with a hidden tab bar still shows it while it's collapsed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is not a [`Leaf`](Node::Leaf) node.
+    #[inline]
+    pub fn set_tab_bar_hidden(&mut self, hidden: bool) {
+        match self {
+            Node::Leaf { tab_bar_hidden, .. } => *tab_bar_hidden = hidden,
+            _ => panic!("node was not a leaf"),
+        }
+    }
+
+    /// Sets the style of the tab bar of the leaf, or makes it use [`Style::tab_bar`](crate::Style::tab_bar) again
+    /// when `style` is [`None`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is not a [`Leaf`](Node::Leaf) node.
+    #[inline]
+    pub fn set_tab_bar_style(&mut self, style: Option<TabBarStyle>) {
+        match self {
+            Node::Leaf { tab_bar_style, .. } => *tab_bar_style = style,
+            _ => panic!("node was not a leaf"),
+        }
+    }
+
+    /// Scrolls the tab bar of the leaf `offset` points from its start. The offset is limited to the width by which
+    /// the tabs overflow the tab bar the next time it's shown.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is not a [`Leaf`](Node::Leaf) node.
+    #[inline]
+    pub fn set_tab_bar_scroll(&mut self, offset: f32) {
+        match self {
+            Node::Leaf { scroll, .. } => *scroll = -offset.at_least(0.0),
+            _ => panic!("node was not a leaf"),
+        }
+    }
+
     /// Sets the number of layers of collapsed leaf subnodes.
     ///
     /// # Panics
@@ -346,6 +517,94 @@ impl<Tab> Node<Tab> {
         }
     }
 
+    /// Returns the minimum size of this node, or [`Vec2::ZERO`] if it doesn't have one.
+    #[inline]
+    pub fn min_size(&self) -> Vec2 {
+        match self {
+            Node::Leaf { min_size, .. }
+            | Node::Vertical { min_size, .. }
+            | Node::Horizontal { min_size, .. } => min_size.unwrap_or(Vec2::ZERO),
+            Node::Empty => Vec2::ZERO,
+        }
+    }
+
+    /// Returns the minimum size used when laying out this node, which for leaves is at least the size needed by the
+    /// bodies of their tabs.
+    #[inline]
+    pub(crate) fn layout_min_size(&self) -> Vec2 {
+        match self {
+            Node::Leaf {
+                content_min_size, ..
+            } => self.min_size().max(*content_min_size),
+            _ => self.min_size(),
+        }
+    }
+
+    /// Sets the size a leaf needs for its tab bar and the bodies of its tabs. Does nothing for other nodes.
+    #[inline]
+    pub(crate) fn set_content_min_size(&mut self, size: Vec2) {
+        if let Node::Leaf {
+            content_min_size, ..
+        } = self
+        {
+            *content_min_size = size;
+        }
+    }
+
+    /// Returns the maximum size of this node, or [`Vec2::INFINITY`] if it doesn't have one.
+    #[inline]
+    pub fn max_size(&self) -> Vec2 {
+        match self {
+            Node::Leaf { max_size, .. }
+            | Node::Vertical { max_size, .. }
+            | Node::Horizontal { max_size, .. } => max_size.unwrap_or(Vec2::INFINITY),
+            Node::Empty => Vec2::INFINITY,
+        }
+    }
+
+    /// Sets the minimum size of this node. Separators can't be dragged in a way that would make the node smaller,
+    /// and the surrounding nodes shrink first when the available space gets smaller.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is an [`Empty`](Node::Empty) node.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex};
+    /// # use egui::vec2;
+    /// let mut dock_state = DockState::new(vec!["toolbar"]);
+    /// let root = dock_state.main_surface_mut().root_node_mut().unwrap();
+    /// root.set_min_size(vec2(200.0, 0.0));
+    /// assert_eq!(root.min_size(), vec2(200.0, 0.0));
+    /// ```
+    #[inline]
+    pub fn set_min_size(&mut self, size: Vec2) {
+        match self {
+            Node::Leaf { min_size, .. }
+            | Node::Vertical { min_size, .. }
+            | Node::Horizontal { min_size, .. } => *min_size = Some(size),
+            Node::Empty => panic!("node was empty"),
+        }
+    }
+
+    /// Sets the maximum size of this node. Separators can't be dragged in a way that would make the node bigger,
+    /// and the surrounding nodes grow first when the available space gets bigger.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is an [`Empty`](Node::Empty) node.
+    #[inline]
+    pub fn set_max_size(&mut self, size: Vec2) {
+        match self {
+            Node::Leaf { max_size, .. }
+            | Node::Vertical { max_size, .. }
+            | Node::Horizontal { max_size, .. } => *max_size = Some(size),
+            Node::Empty => panic!("node was empty"),
+        }
+    }
+
     /// Adds a `tab` to the node.
     ///
     /// # Panics
@@ -355,9 +614,18 @@ impl<Tab> Node<Tab> {
     #[inline]
     pub fn insert_tab(&mut self, index: TabIndex, tab: Tab) {
         match self {
-            Node::Leaf { tabs, active, .. } => {
+            Node::Leaf {
+                tabs,
+                active,
+                history,
+                ..
+            } => {
                 tabs.insert(index.0, tab);
+                for entry in history.iter_mut().filter(|entry| **entry >= index) {
+                    entry.0 += 1;
+                }
                 *active = index;
+                history.push(index);
             }
             _ => unreachable!(),
         }
@@ -366,16 +634,42 @@ impl<Tab> Node<Tab> {
     /// Removes a tab at given `index` from the node.
     /// Returns the removed tab if the node is a `Leaf`, or `None` otherwise.
     ///
+    /// If the removed tab was the active one, the tab that was active before it becomes active.
+    ///
     /// # Panics
     ///
     /// Panics if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{Node, TabIndex};
+    /// let mut node = Node::leaf_with(vec!["a", "b", "c"]);
+    /// node.set_active_tab(TabIndex(2));
+    /// node.set_active_tab(TabIndex(0));
+    ///
+    /// node.remove_tab(TabIndex(0));
+    /// assert_eq!(node.active_tab(), Some(TabIndex(1)));
+    /// assert_eq!(node.tabs().unwrap()[1], "c");
+    /// ```
     #[inline]
     pub fn remove_tab(&mut self, tab_index: TabIndex) -> Option<Tab> {
         match self {
-            Node::Leaf { tabs, active, .. } => {
-                if tab_index <= *active {
+            Node::Leaf {
+                tabs,
+                active,
+                history,
+                ..
+            } => {
+                let mut kept = vec![true; tabs.len()];
+                kept[tab_index.0] = false;
+                let (new_active, new_history) = retain_history(*active, history, &kept);
+                if tab_index == *active && !new_history.is_empty() {
+                    *active = new_active;
+                } else if tab_index <= *active {
                     active.0 = active.0.saturating_sub(1);
                 }
+                *history = new_history;
 
                 Some(tabs.remove(tab_index.0))
             }
@@ -383,6 +677,81 @@ impl<Tab> Node<Tab> {
         }
     }
 
+    /// Returns the index of the active tab if the node is a [`Leaf`](Node::Leaf), or `None` otherwise.
+    #[inline]
+    pub fn active_tab(&self) -> Option<TabIndex> {
+        match self {
+            Node::Leaf { active, .. } => Some(*active),
+            _ => None,
+        }
+    }
+
+    /// Sets the active tab of the node and remembers it in its history of active tabs.
+    ///
+    /// Does nothing if the node is not a [`Leaf`](Node::Leaf).
+    #[inline]
+    pub fn set_active_tab(&mut self, tab_index: TabIndex) {
+        if let Node::Leaf { active, .. } = self {
+            *active = tab_index;
+            self.record_active_tab();
+        }
+    }
+
+    /// Adds the active tab to the top of the history of active tabs, if it isn't already there.
+    pub(crate) fn record_active_tab(&mut self) {
+        if let Node::Leaf {
+            tabs,
+            active,
+            history,
+            ..
+        } = self
+        {
+            if active.0 < tabs.len() && history.last() != Some(active) {
+                history.retain(|entry| entry != active);
+                history.push(*active);
+            }
+        }
+    }
+
+    /// Reorders the tabs of a leaf so that the ones marked in `first` come before the others, keeping the order
+    /// within both groups.
+    pub(crate) fn move_tabs_to_front(&mut self, first: &[bool]) {
+        let Node::Leaf {
+            tabs,
+            active,
+            history,
+            ..
+        } = self
+        else {
+            return;
+        };
+        let order: Vec<usize> = (0..tabs.len())
+            .filter(|&index| first[index])
+            .chain((0..tabs.len()).filter(|&index| !first[index]))
+            .collect();
+        if order.iter().enumerate().all(|(new, &old)| new == old) {
+            return;
+        }
+
+        let mut new_indices = vec![0; tabs.len()];
+        for (new, &old) in order.iter().enumerate() {
+            new_indices[old] = new;
+        }
+        let mut old_tabs: Vec<_> = std::mem::take(tabs).into_iter().map(Some).collect();
+        *tabs = order
+            .iter()
+            .map(|&old| old_tabs[old].take().unwrap())
+            .collect();
+        if let Some(&new) = new_indices.get(active.0) {
+            active.0 = new;
+        }
+        for entry in history.iter_mut() {
+            if let Some(&new) = new_indices.get(entry.0) {
+                entry.0 = new;
+            }
+        }
+    }
+
     /// Gets the number of tabs in the node.
     #[inline]
     pub fn tabs_count(&self) -> usize {
@@ -393,8 +762,8 @@ impl<Tab> Node<Tab> {
     }
 
     /// Returns a new [`Node`] while mapping and filtering the tab type.
-    /// If this [`Node`] remains empty, it will change to [`Node::Empty`].
-    pub fn filter_map_tabs<F, NewTab>(&self, function: F) -> Node<NewTab>
+    /// If this [`Node`] remains empty, it will change to [`Node::Empty`], unless it's the central leaf.
+    pub fn filter_map_tabs<F, NewTab>(&self, mut function: F) -> Node<NewTab>
     where
         F: FnMut(&Tab) -> Option<NewTab>,
     {
@@ -404,20 +773,44 @@ impl<Tab> Node<Tab> {
                 viewport,
                 tabs,
                 active,
+                history,
                 scroll,
                 collapsed,
+                central,
+                min_size,
+                max_size,
+                tab_bar_hidden,
+                tab_bar_style,
+                ..
             } => {
-                let tabs: Vec<_> = tabs.iter().filter_map(function).collect();
-                if tabs.is_empty() {
+                let mut kept = Vec::with_capacity(tabs.len());
+                let tabs: Vec<_> = tabs
+                    .iter()
+                    .filter_map(|tab| {
+                        let tab = function(tab);
+                        kept.push(tab.is_some());
+                        tab
+                    })
+                    .collect();
+                if tabs.is_empty() && !central {
                     Node::Empty
                 } else {
+                    let (active, history) = retain_history(*active, history, &kept);
                     Node::Leaf {
                         rect: *rect,
                         viewport: *viewport,
+                        tab_rects: Vec::new(),
                         tabs,
-                        active: *active,
+                        active,
+                        history,
                         scroll: *scroll,
                         collapsed: *collapsed,
+                        central: *central,
+                        min_size: *min_size,
+                        max_size: *max_size,
+                        content_min_size: Vec2::ZERO,
+                        tab_bar_hidden: *tab_bar_hidden,
+                        tab_bar_style: tab_bar_style.clone(),
                     }
                 }
             }
@@ -427,22 +820,30 @@ impl<Tab> Node<Tab> {
                 fraction,
                 fully_collapsed,
                 collapsed_leaf_count,
+                min_size,
+                max_size,
             } => Node::Vertical {
                 rect: *rect,
                 fraction: *fraction,
                 fully_collapsed: *fully_collapsed,
                 collapsed_leaf_count: *collapsed_leaf_count,
+                min_size: *min_size,
+                max_size: *max_size,
             },
             Node::Horizontal {
                 rect,
                 fraction,
                 fully_collapsed,
                 collapsed_leaf_count,
+                min_size,
+                max_size,
             } => Node::Horizontal {
                 rect: *rect,
                 fraction: *fraction,
                 fully_collapsed: *fully_collapsed,
                 collapsed_leaf_count: *collapsed_leaf_count,
+                min_size: *min_size,
+                max_size: *max_size,
             },
         }
     }
@@ -466,16 +867,49 @@ impl<Tab> Node<Tab> {
     }
 
     /// Removes all tabs for which `predicate` returns `false`.
-    /// If this [`Node`] remains empty, it will change to [`Node::Empty`].
-    pub fn retain_tabs<F>(&mut self, predicate: F)
+    /// If this [`Node`] remains empty, it will change to [`Node::Empty`], unless it's the central leaf.
+    pub fn retain_tabs<F>(&mut self, mut predicate: F)
     where
         F: FnMut(&mut Tab) -> bool,
     {
-        if let Node::Leaf { tabs, .. } = self {
-            tabs.retain_mut(predicate);
-            if tabs.is_empty() {
+        if let Node::Leaf {
+            tabs,
+            active,
+            history,
+            central,
+            ..
+        } = self
+        {
+            let mut kept = Vec::with_capacity(tabs.len());
+            tabs.retain_mut(|tab| {
+                let keep = predicate(tab);
+                kept.push(keep);
+                keep
+            });
+            (*active, *history) = retain_history(*active, history, &kept);
+            if tabs.is_empty() && !*central {
                 *self = Node::Empty;
             }
         }
     }
 }
+
+/// Updates the active tab and the history of active tabs of a leaf after removing the tabs for which `kept` is
+/// `false`. If the active tab is removed, the most recently active remaining tab takes its place.
+fn retain_history(
+    active: TabIndex,
+    history: &[TabIndex],
+    kept: &[bool],
+) -> (TabIndex, Vec<TabIndex>) {
+    let retained = |index: TabIndex| {
+        kept.get(index.0)
+            .copied()
+            .unwrap_or(false)
+            .then(|| TabIndex(kept[..index.0].iter().filter(|&&keep| keep).count()))
+    };
+    let history: Vec<_> = history.iter().copied().filter_map(retained).collect();
+    let active = retained(active)
+        .or(history.last().copied())
+        .unwrap_or(TabIndex(0));
+    (active, history)
+}
@@ -52,6 +52,22 @@ impl NodeIndex {
         }
     }
 
+    /// Returns an [`Iterator`] over the indices of the parent of the current node, its parent, and so on up to the
+    /// root.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::NodeIndex;
+    /// let node = NodeIndex::root().left().right();
+    /// let ancestors: Vec<_> = node.ancestors().collect();
+    /// assert_eq!(ancestors, [NodeIndex::root().left(), NodeIndex::root()]);
+    /// ```
+    #[inline]
+    pub fn ancestors(self) -> impl Iterator<Item = Self> {
+        std::iter::successors(self.parent(), |node| node.parent())
+    }
+
     /// Returns the number of nodes leading from the root to the current node, including `self`.
     #[inline(always)]
     pub const fn level(self) -> usize {
@@ -70,6 +86,32 @@ impl NodeIndex {
         self.0 % 2 == 0
     }
 
+    /// Returns where the current node ends up after the leaf at `removed` is removed with
+    /// [`Tree::remove_leaf`](crate::Tree::remove_leaf), which moves the subtree of its sibling up a level.
+    pub(crate) fn after_removal_of(self, removed: NodeIndex) -> Self {
+        let Some(parent) = removed.parent() else {
+            return self;
+        };
+        let sibling = if removed.is_left() {
+            parent.right()
+        } else {
+            parent.left()
+        };
+        let mut path = Vec::new();
+        let mut node = self;
+        while node != sibling {
+            path.push(node.is_left());
+            match node.parent() {
+                Some(up) if up.0 >= sibling.0 => node = up,
+                _ => return self,
+            }
+        }
+        path.iter().rev().fold(
+            parent,
+            |node, &left| if left { node.left() } else { node.right() },
+        )
+    }
+
     #[inline]
     pub(super) const fn children_at(self, level: usize) -> Range<usize> {
         let base = 1 << level;
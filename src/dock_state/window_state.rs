@@ -1,11 +1,16 @@
-use egui::{Id, Pos2, Rect, Vec2};
+use crate::SurfaceId;
+use egui::{viewport::WindowLevel, Id, Pos2, Rect, Vec2, ViewportBuilder, ViewportId};
 
 /// The state of a [`Surface::Window`](crate::Surface::Window).
 ///
 /// Doubles as a handle for the surface, allowing the user to set its size and position.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct WindowState {
+    /// Identifies the window for as long as it exists.
+    #[cfg_attr(feature = "serde", serde(skip, default = "SurfaceId::next"))]
+    id: SurfaceId,
+
     /// The [`Rect`] that this window was last taking up.
     screen_rect: Option<Rect>,
 
@@ -27,11 +32,93 @@ pub struct WindowState {
 
     /// True if the window is minimized
     minimized: bool,
+
+    /// The [`Rect`] this window took up before it was maximized, if it's maximized.
+    #[cfg_attr(feature = "serde", serde(default))]
+    restore_rect: Option<Rect>,
+
+    /// How the position of the window is kept when the window bounds change.
+    #[cfg_attr(feature = "serde", serde(default))]
+    anchor: WindowAnchor,
+
+    /// True if a title bar is shown above the contents of the window.
+    #[cfg_attr(feature = "serde", serde(default))]
+    title_bar: bool,
+
+    /// The smallest size the window can be resized to.
+    #[cfg_attr(feature = "serde", serde(default))]
+    min_size: Option<Vec2>,
+
+    /// The largest size the window can be resized to.
+    #[cfg_attr(feature = "serde", serde(default))]
+    max_size: Option<Vec2>,
+
+    /// Opacity of the window and its contents, in range `0.0..=1.0`.
+    #[cfg_attr(feature = "serde", serde(default = "WindowState::default_opacity"))]
+    opacity: f32,
+
+    /// True if the window is kept above the other windows.
+    #[cfg_attr(feature = "serde", serde(default))]
+    always_on_top: bool,
+
+    /// True if the window is shown as a borderless panel instead of an [`egui::Window`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    borderless: bool,
+
+    /// The size of the window when it's shown as a borderless panel and isn't collapsed.
+    #[cfg_attr(feature = "serde", serde(default))]
+    panel_size: Option<Vec2>,
+
+    /// The window bounds this window was last shown in.
+    #[cfg_attr(feature = "serde", serde(default))]
+    bounds: Option<Rect>,
+
+    /// True once the window has been fitted into the window bounds, which happens the first time it's shown.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    fitted: bool,
+
+    /// The native viewport this window was last shown in, if any.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    viewport_id: Option<ViewportId>,
+
+    /// Where the window should be moved in the stack of windows the next time it's shown.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    next_order: Option<WindowOrder>,
+
+    /// True if the window contained the focused leaf the last time it was shown.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    focused: bool,
+}
+
+/// How the position of a window is kept when the window bounds change, e.g. when a layout saved on one monitor is
+/// restored on another one.
+///
+/// Either way, a window which would end up partly outside of the window bounds the first time it's shown is moved
+/// and shrunk to fit inside of them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum WindowAnchor {
+    /// The window keeps its position and size in points.
+    #[default]
+    Absolute,
+
+    /// The window keeps its position and size relative to the window bounds, e.g. a window in the right half of the
+    /// bounds stays in the right half when they get bigger.
+    Relative,
+}
+
+/// Where a window is moved in the stack of windows, see [`WindowState::bring_to_front`] and
+/// [`WindowState::send_to_back`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum WindowOrder {
+    Front,
+    Back,
 }
 
 impl Default for WindowState {
     fn default() -> Self {
         Self {
+            id: SurfaceId::next(),
             screen_rect: None,
             dragged: false,
             next_position: None,
@@ -39,16 +126,40 @@ impl Default for WindowState {
             expanded_height: None,
             new: true,
             minimized: false,
+            restore_rect: None,
+            anchor: WindowAnchor::default(),
+            title_bar: false,
+            min_size: None,
+            max_size: None,
+            opacity: Self::default_opacity(),
+            always_on_top: false,
+            borderless: false,
+            panel_size: None,
+            bounds: None,
+            fitted: false,
+            viewport_id: None,
+            next_order: None,
+            focused: false,
         }
     }
 }
 
 impl WindowState {
+    /// Size of a borderless panel which hasn't been given one.
+    const DEFAULT_PANEL_SIZE: Vec2 = Vec2::new(320.0, 240.0);
+
     /// Create a default window state.
     pub(crate) fn new() -> Self {
         Self::default()
     }
 
+    /// Returns the id of the window, which stays the same while its [`SurfaceIndex`](crate::SurfaceIndex) may be
+    /// reused by other windows after it's removed.
+    #[inline(always)]
+    pub const fn id(&self) -> SurfaceId {
+        self.id
+    }
+
     /// Set the position for this window in screen coordinates.
     pub fn set_position(&mut self, position: Pos2) -> &mut Self {
         self.next_position = Some(position);
@@ -75,6 +186,237 @@ impl WindowState {
         self.dragged
     }
 
+    /// Shows the window above all other windows the next time it's shown.
+    ///
+    /// A window shown natively (see [`DockArea::native_windows`](crate::DockArea::native_windows)) gets focused
+    /// by the operating system instead.
+    pub fn bring_to_front(&mut self) -> &mut Self {
+        self.next_order = Some(WindowOrder::Front);
+        self
+    }
+
+    /// Shows the window below the other windows of its [`DockArea`](crate::DockArea) the next time it's shown.
+    ///
+    /// This has no effect on windows shown natively (see
+    /// [`DockArea::native_windows`](crate::DockArea::native_windows)).
+    pub fn send_to_back(&mut self) -> &mut Self {
+        self.next_order = Some(WindowOrder::Back);
+        self
+    }
+
+    /// Returns `true` if the window contained the focused leaf the last time it was shown.
+    ///
+    /// See also [`DockState::focused_window`](crate::DockState::focused_window).
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Sets how the position of the window is kept when the window bounds change, see [`WindowAnchor`].
+    pub fn set_anchor(&mut self, anchor: WindowAnchor) -> &mut Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Returns how the position of the window is kept when the window bounds change.
+    pub fn anchor(&self) -> WindowAnchor {
+        self.anchor
+    }
+
+    /// Sets whether a title bar is shown above the contents of the window.
+    ///
+    /// The title bar shows the title given by [`TabViewer::window_title`](crate::TabViewer::window_title) and the
+    /// widgets added in [`TabViewer::window_title_bar_ui`](crate::TabViewer::window_title_bar_ui), and the window
+    /// can be dragged by it. Windows shown natively (see
+    /// [`DockArea::native_windows`](crate::DockArea::native_windows)) use the title bar of the operating system
+    /// instead.
+    ///
+    /// Title bars can also be turned on for all windows with [`WindowStyle::title_bars`](crate::WindowStyle::title_bars).
+    pub fn set_title_bar(&mut self, title_bar: bool) -> &mut Self {
+        self.title_bar = title_bar;
+        self
+    }
+
+    /// Returns `true` if a title bar is shown above the contents of the window.
+    pub fn has_title_bar(&self) -> bool {
+        self.title_bar
+    }
+
+    /// Sets the smallest size the window can be resized to in egui points.
+    ///
+    /// See also [`TabViewer::window_size_bounds`](crate::TabViewer::window_size_bounds).
+    pub fn set_min_size(&mut self, min_size: Vec2) -> &mut Self {
+        self.min_size = Some(min_size);
+        self
+    }
+
+    /// Returns the smallest size the window can be resized to, if it has been set with
+    /// [`set_min_size`](Self::set_min_size).
+    pub fn min_size(&self) -> Option<Vec2> {
+        self.min_size
+    }
+
+    /// Sets the largest size the window can be resized to in egui points. Use [`f32::INFINITY`] to only limit
+    /// the width or the height.
+    ///
+    /// See also [`TabViewer::window_size_bounds`](crate::TabViewer::window_size_bounds).
+    pub fn set_max_size(&mut self, max_size: Vec2) -> &mut Self {
+        // Infinity can't be stored in some formats, e.g. JSON.
+        self.max_size = Some(max_size.min(Vec2::splat(f32::MAX)));
+        self
+    }
+
+    /// Returns the largest size the window can be resized to, if it has been set with
+    /// [`set_max_size`](Self::set_max_size).
+    pub fn max_size(&self) -> Option<Vec2> {
+        self.max_size
+    }
+
+    /// Sets the opacity of the window and its contents in range `0.0..=1.0`, e.g. to make a floating tool window
+    /// translucent.
+    ///
+    /// This has no effect on windows shown natively (see
+    /// [`DockArea::native_windows`](crate::DockArea::native_windows)).
+    pub fn set_opacity(&mut self, opacity: f32) -> &mut Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Returns the opacity of the window and its contents.
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    /// Sets whether the window is kept above the other windows of its [`DockArea`](crate::DockArea), even when
+    /// another one is focused.
+    ///
+    /// Windows shown natively (see [`DockArea::native_windows`](crate::DockArea::native_windows)) are kept above
+    /// all other windows of the operating system instead.
+    pub fn set_always_on_top(&mut self, always_on_top: bool) -> &mut Self {
+        self.always_on_top = always_on_top;
+        self
+    }
+
+    /// Returns `true` if the window is kept above the other windows.
+    pub fn is_always_on_top(&self) -> bool {
+        self.always_on_top
+    }
+
+    /// Sets whether the window is shown as a borderless panel inside of the [`DockArea`](crate::DockArea) instead of
+    /// an [`egui::Window`], e.g. in kiosk applications where the decorations of egui windows clash with their own
+    /// theme.
+    ///
+    /// The panel can be moved by dragging its empty space or its [title bar](Self::set_title_bar), but it can only
+    /// be resized with [`set_size`](Self::set_size). It has no frame, unless one is given by
+    /// [`TabViewer::window_frame_override`](crate::TabViewer::window_frame_override). This has no effect on
+    /// windows shown natively (see [`DockArea::native_windows`](crate::DockArea::native_windows)).
+    pub fn set_borderless(&mut self, borderless: bool) -> &mut Self {
+        self.borderless = borderless;
+        self
+    }
+
+    /// Returns `true` if the window is shown as a borderless panel.
+    pub fn is_borderless(&self) -> bool {
+        self.borderless
+    }
+
+    #[inline(always)]
+    fn default_opacity() -> f32 {
+        1.0
+    }
+
+    /// Returns the range of sizes the window can take, narrowed down by the `min` and `max` sizes from elsewhere.
+    fn size_limits(&self, (min, max): (Vec2, Vec2)) -> (Vec2, Vec2) {
+        let min = self.min_size.unwrap_or(Vec2::ZERO).max(min);
+        let max = self.max_size.unwrap_or(Vec2::INFINITY).min(max).max(min);
+        (min, max)
+    }
+
+    /// Moves the window the first time it's shown so that it's inside of `bounds`, carrying it over from the bounds
+    /// it was last shown in according to its [anchor](WindowAnchor).
+    fn fit_into(&mut self, bounds: Rect) {
+        let Some(old_rect) = self.screen_rect else {
+            return;
+        };
+        let mut rect = old_rect;
+        if let (WindowAnchor::Relative, Some(old_bounds)) = (self.anchor, self.bounds) {
+            if old_bounds.is_positive() {
+                let min = (rect.min - old_bounds.min) / old_bounds.size() * bounds.size();
+                let size = rect.size() / old_bounds.size() * bounds.size();
+                rect = Rect::from_min_size(bounds.min + min, size);
+            }
+        }
+        let size = rect.size().min(bounds.size());
+        let min = rect.min.clamp(bounds.min, bounds.max - size);
+
+        if min != old_rect.min {
+            self.next_position.get_or_insert(min);
+        }
+        if size != old_rect.size() {
+            self.next_size.get_or_insert(size);
+        }
+    }
+
+    /// Returns `true` if the window has been [maximized](Self::maximize) and not restored since.
+    pub fn is_maximized(&self) -> bool {
+        self.restore_rect.is_some()
+    }
+
+    /// Makes the window fill `bounds`, remembering where it was so it can be [restored](Self::restore).
+    ///
+    /// Does nothing if the window is already maximized.
+    pub fn maximize(&mut self, bounds: Rect) -> &mut Self {
+        if self.restore_rect.is_none() {
+            self.restore_rect = Some(self.rect());
+            self.set_position(bounds.min).set_size(bounds.size());
+        }
+        self
+    }
+
+    /// Puts a [maximized](Self::maximize) window back where it was before, and gives it its previous size.
+    ///
+    /// Does nothing if the window isn't maximized.
+    pub fn restore(&mut self) -> &mut Self {
+        if let Some(rect) = self.restore_rect.take() {
+            if rect.is_positive() {
+                self.set_position(rect.min).set_size(rect.size());
+            }
+        }
+        self
+    }
+
+    /// Returns the [`Rect`] the window took up before it was maximized, if it's maximized.
+    pub(crate) fn restore_rect(&self) -> Option<Rect> {
+        self.restore_rect
+    }
+
+    #[inline(always)]
+    pub(crate) fn next_order(&mut self) -> Option<WindowOrder> {
+        self.next_order.take()
+    }
+
+    #[inline(always)]
+    pub(crate) fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    #[inline(always)]
+    pub(crate) fn set_dragged(&mut self, dragged: bool) {
+        self.dragged = dragged;
+    }
+
+    /// Returns the [`ViewportId`] of the native window this surface was last shown in, or `None` if it's shown
+    /// inside an [`egui::Window`].
+    ///
+    /// See [`DockArea::native_windows`](crate::DockArea::native_windows).
+    pub fn viewport_id(&self) -> Option<ViewportId> {
+        self.viewport_id
+    }
+
+    #[inline(always)]
+    pub(crate) fn set_screen_rect(&mut self, rect: Rect) {
+        self.screen_rect = Some(rect);
+    }
+
     /// Set the height of this window when it is expanded.
     #[inline(always)]
     pub(crate) fn set_expanded_height(&mut self, height: f32) -> &mut Self {
@@ -114,18 +456,35 @@ impl WindowState {
     }
 
     //the 'static in this case means that the `open` field is always `None`
-    pub(crate) fn create_window(&mut self, id: Id, bounds: Rect) -> egui::Window<'static> {
+    ///
+    /// `size_limits` are the smallest and largest sizes of the window given by
+    /// [`TabViewer::window_size_bounds`](crate::TabViewer::window_size_bounds).
+    pub(crate) fn create_window(
+        &mut self,
+        id: Id,
+        bounds: Rect,
+        size_limits: (Vec2, Vec2),
+    ) -> egui::Window<'static> {
         let new = self.new;
+        if !self.fitted {
+            self.fit_into(bounds);
+            self.fitted = true;
+        }
+        self.bounds = Some(bounds);
+        let (min_size, max_size) = self.size_limits(size_limits);
         let mut window_constructor = egui::Window::new("")
             .id(id)
             .constrain_to(bounds)
-            .title_bar(false);
+            .title_bar(false)
+            .min_size(min_size)
+            .max_size(max_size);
+        self.viewport_id = None;
 
         if let Some(position) = self.next_position() {
             window_constructor = window_constructor.current_pos(position);
         }
         if let Some(size) = self.next_size() {
-            window_constructor = window_constructor.fixed_size(size);
+            window_constructor = window_constructor.fixed_size(size.max(min_size).min(max_size));
         }
         // Reset the height of the window if it is now expanded
         if new {
@@ -136,4 +495,78 @@ impl WindowState {
         self.new = false;
         window_constructor
     }
+
+    /// Creates the [`egui::Area`] showing this window as a borderless panel, along with the size of the panel when
+    /// it isn't collapsed.
+    ///
+    /// `size_limits` are the smallest and largest sizes of the window given by
+    /// [`TabViewer::window_size_bounds`](crate::TabViewer::window_size_bounds).
+    pub(crate) fn create_area(
+        &mut self,
+        id: Id,
+        bounds: Rect,
+        size_limits: (Vec2, Vec2),
+    ) -> (egui::Area, Vec2) {
+        if !self.fitted {
+            self.fit_into(bounds);
+            self.fitted = true;
+        }
+        self.bounds = Some(bounds);
+        let (min_size, max_size) = self.size_limits(size_limits);
+        let mut area = egui::Area::new(id).constrain_to(bounds).movable(true);
+        self.viewport_id = None;
+
+        if let Some(position) = self.next_position() {
+            area = area.current_pos(position);
+        }
+        let size = self
+            .next_size()
+            .or(self.panel_size)
+            .or(self.screen_rect.map(|rect| rect.size()))
+            .unwrap_or(Self::DEFAULT_PANEL_SIZE)
+            .max(min_size)
+            .min(max_size);
+        self.panel_size = Some(size);
+        self.new = false;
+        (area, size)
+    }
+
+    /// Creates the builder of a native viewport showing this window.
+    ///
+    /// `origin` is the screen position of the parent viewport, which window positions are relative to.
+    pub(crate) fn create_viewport(
+        &mut self,
+        viewport_id: ViewportId,
+        title: String,
+        origin: Pos2,
+        size_limits: (Vec2, Vec2),
+    ) -> ViewportBuilder {
+        let (min_size, max_size) = self.size_limits(size_limits);
+        let mut builder = ViewportBuilder::default()
+            .with_title(title)
+            .with_min_inner_size(min_size)
+            .with_max_inner_size(max_size.min(Vec2::splat(f32::MAX)))
+            .with_window_level(if self.always_on_top {
+                WindowLevel::AlwaysOnTop
+            } else {
+                WindowLevel::Normal
+            });
+
+        // When the window is shown natively for the first time, put it where it last was.
+        if self.viewport_id != Some(viewport_id) {
+            if let Some(rect) = self.screen_rect {
+                self.next_position.get_or_insert(rect.min);
+                self.next_size.get_or_insert(rect.size());
+            }
+        }
+        if let Some(position) = self.next_position() {
+            builder = builder.with_position(origin + position.to_vec2());
+        }
+        if let Some(size) = self.next_size() {
+            builder = builder.with_inner_size(size.max(min_size).min(max_size));
+        }
+        self.viewport_id = Some(viewport_id);
+        self.new = false;
+        builder
+    }
 }
@@ -192,12 +192,14 @@
 //! Example usage:
 //!
 //! ```rust
-//! # use egui_dock::{DockState, TabContextMenuTranslations, Translations, LeafTranslations};
+//! # use egui_dock::{DockState, TabContextMenuTranslations, Translations, LeafTranslations, WindowTaskbarTranslations};
 //! # type Tab = ();
 //! let translations_pl = Translations {
 //!     tab_context_menu: TabContextMenuTranslations {
 //!         close_button: "Zamknij zakładkę".to_string(),
 //!         eject_button: "Przenieś zakładkę do nowego okna".to_string(),
+//!         eject_selected_button: "Przenieś zaznaczone zakładki do nowego okna".to_string(),
+//!         equalize_button: "Wyrównaj rozmiary".to_string(),
 //!     },
 //!     leaf: LeafTranslations {
 //!         close_button_disabled_tooltip: "Ten węzeł zawiera niezamykalne zakładki.".to_string(),
@@ -210,7 +212,13 @@
 //!         minimize_button_menu_hint: "Kliknij prawym przyciskiem myszy, aby zminimalizować to okno.".to_string(),
 //!         minimize_button_modifier_hint: "Naciśnij klawisze modyfikujące (domyślnie Shift), aby zminimalizować to okno.".to_string(),
 //!         minimize_button_modifier_menu_hint: "Naciśnij klawisze modyfikujące (domyślnie Shift) lub kliknij prawym przyciskiem myszy, aby zminimalizować to okno.".to_string(),
-//!     }
+//!         search_button: "Szukaj zakładek".to_string(),
+//!         ungrouped_tabs: "Inne".to_string(),
+//!         split_button: "Podziel".to_string(),
+//!     },
+//!     window_taskbar: WindowTaskbarTranslations {
+//!         restore_tooltip: "Kliknij, aby przywrócić to okno.".to_string(),
+//!     },
 //! };
 //! let dock_state = DockState::<Tab>::new(vec![]).with_translations(translations_pl);
 //!
@@ -218,6 +226,7 @@
 //! let mut dock_state = DockState::<Tab>::new(vec![]);
 //! dock_state.translations.tab_context_menu.close_button = "タブを閉じる".to_string();
 //! dock_state.translations.tab_context_menu.eject_button = "タブを新しいウィンドウへ移動".to_string();
+//! dock_state.translations.tab_context_menu.equalize_button = "サイズを揃える".to_string();
 //! dock_state.translations.leaf.close_button_disabled_tooltip = "このノードは閉じられないタブがある".to_string();
 //! dock_state.translations.leaf.close_all_button = "ウィンドウを閉じる".to_string();
 //! dock_state.translations.leaf.close_all_button_menu_hint = "右クリックでこのウィンドウを閉じる".to_string();
@@ -228,6 +237,10 @@
 //! dock_state.translations.leaf.minimize_button_menu_hint = "右クリックでウィンドウを最小化する".to_string();
 //! dock_state.translations.leaf.minimize_button_modifier_hint = "修飾キー（デフォルトではShift）を押すと、このウィンドウが最小化されます".to_string();
 //! dock_state.translations.leaf.minimize_button_modifier_menu_hint = "修飾キー（デフォルトではShift）を押すか、右クリックしてこのウィンドウを最小化する".to_string();
+//! dock_state.translations.leaf.search_button = "タブを検索".to_string();
+//! dock_state.translations.leaf.ungrouped_tabs = "その他".to_string();
+//! dock_state.translations.leaf.split_button = "分割".to_string();
+//! dock_state.translations.window_taskbar.restore_tooltip = "クリックしてこのウィンドウを元に戻す".to_string();
 //! ```
 
 #![warn(missing_docs)]
@@ -250,4 +263,15 @@ pub mod style;
 /// Widgets provided by the library.
 pub mod widgets;
 
+/// Helpers for driving a [`DockArea`] in tests.
+///
+/// [`testing::DockHarness`] runs a headless [`egui::Context`] itself rather than building on `egui_kittest`.
+/// `egui_kittest` finds what to interact with through the AccessKit tree. Many of the targets of a dock area aren't
+/// widgets with nodes of their own: the drop overlay buttons and the areas tabs can be dropped onto are only painted
+/// while a tab is dragged. They can only be found by position. The harness takes those positions from
+/// [`LayoutMetrics`], which the area reports every frame. This also keeps the `testing` feature free of extra
+/// dependencies.
+#[cfg(feature = "testing")]
+pub mod testing;
+
 mod utils;
@@ -1,4 +1,4 @@
-use egui::{ecolor::*, Margin, Rounding, Stroke};
+use egui::{ecolor::*, Align, Margin, Rounding, Shadow, Stroke, TextStyle, Ui, Vec2};
 
 /// Left or right alignment for tab add button.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -9,6 +9,63 @@ pub enum TabAddAlign {
     Right,
 }
 
+/// How tab titles which don't fit in [`TabStyle::maximum_width`] are shortened.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TitleTruncation {
+    /// The end of the title is replaced with an ellipsis.
+    #[default]
+    Truncate,
+
+    /// The middle of the title is replaced with an ellipsis, keeping both its start and end visible, which is useful
+    /// for file paths.
+    MiddleEllipsis,
+
+    /// The title is wrapped onto multiple lines. Lines which don't fit in the tab bar are cut off.
+    Wrap,
+}
+
+/// Edge of a leaf along which its tab bar is shown.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TabBarPosition {
+    /// Above the tab body, with tabs laid out horizontally.
+    #[default]
+    Top,
+
+    /// Below the tab body, with tabs laid out horizontally.
+    Bottom,
+
+    /// On the left of the tab body, with tabs stacked vertically and their titles rotated.
+    Left,
+
+    /// On the right of the tab body, with tabs stacked vertically and their titles rotated.
+    Right,
+}
+
+impl TabBarPosition {
+    /// Returns `true` if tabs are stacked vertically, i.e. the position is [`Left`](Self::Left) or
+    /// [`Right`](Self::Right).
+    #[inline(always)]
+    pub const fn is_vertical(self) -> bool {
+        matches!(self, Self::Left | Self::Right)
+    }
+}
+
+/// How the height of tab bars is determined.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TabBarHeightMode {
+    /// Tab bars are [`TabBarStyle::height`] high.
+    #[default]
+    Fixed,
+
+    /// Tab bars are as high as a line of text in the [`TextStyle::Button`] font used for tab titles, plus some
+    /// padding, so that titles aren't cut off when the font is made larger. [`TabBarStyle::height`] is updated with
+    /// the computed height each time the [`DockArea`](crate::DockArea) is shown.
+    AutoFromText,
+}
+
 /// Lets you change how tabs and the [`DockArea`](crate::DockArea) should look and feel.
 /// [`Style`] is divided into several, more specialized structs that handle individual
 /// elements of the UI.
@@ -52,14 +109,25 @@ pub struct Style {
     /// Sets padding to indent from the edges of the window. By `Default` it's `None`.
     pub dock_area_padding: Option<Margin>,
 
+    /// Space left around each leaf, between it and the separators or the edges of its surface. Unlike
+    /// [`TabBodyStyle::inner_margin`], it's left around the tab bar too. Collapsed leaves aren't padded.
+    /// By `Default` it's [`Margin::ZERO`].
+    pub leaf_padding: Margin,
+
     pub main_surface_border_stroke: Stroke,
     pub main_surface_border_rounding: Rounding,
 
+    /// Style of the highlighting rectangle drawn around the focused leaf, showing which one has keyboard focus.
+    ///
+    /// By default this value shows no highlighting.
+    pub focused_leaf_highlight: LeafHighlighting,
+
     pub buttons: ButtonsStyle,
     pub separator: SeparatorStyle,
     pub tab_bar: TabBarStyle,
     pub tab: TabStyle,
     pub overlay: OverlayStyle,
+    pub window: WindowStyle,
 }
 
 /// Specifies the look and feel of buttons.
@@ -90,6 +158,25 @@ pub struct ButtonsStyle {
     /// Color of the add tab button's left border.
     pub add_tab_border_color: Color32,
 
+    /// Width of the contents of the popup under the add tab button. If it's `None`, the popup is as wide as its
+    /// contents. [`TabViewer::add_popup`](crate::TabViewer::add_popup) can override it for each leaf.
+    ///
+    /// By default it's `None`.
+    pub add_tab_popup_width: Option<f32>,
+
+    /// Height above which the contents of the popup under the add tab button are scrolled. If it's `None`, the popup
+    /// is as tall as its contents, as long as it fits on the screen.
+    /// [`TabViewer::add_popup`](crate::TabViewer::add_popup) can override it for each leaf.
+    ///
+    /// By default it's `None`.
+    pub add_tab_popup_max_height: Option<f32>,
+
+    /// Edge of the add tab button the same edge of its popup is lined up with, e.g. [`Align::Max`] makes the popup
+    /// open towards the left.
+    ///
+    /// By default it's [`Align::Min`].
+    pub add_tab_popup_align: Align,
+
     /// Color of the close all tabs button.
     pub close_all_tabs_color: Color32,
 
@@ -128,6 +215,13 @@ pub struct ButtonsStyle {
 
     /// Color of the minimize window button's left border.
     pub minimize_window_border_color: Color32,
+
+    /// Color of the background of the badges shown on the tab bar of a collapsed leaf, counting its tabs or given by
+    /// [`TabViewer::badge`](crate::TabViewer::badge).
+    pub badge_bg_fill: Color32,
+
+    /// Color of the text of the badges shown on the tab bar of a collapsed leaf.
+    pub badge_text_color: Color32,
 }
 
 /// Specifies the look and feel of node separators.
@@ -141,9 +235,10 @@ pub struct SeparatorStyle {
     /// easier to grab. By `Default` it's `4.0`.
     pub extra_interact_width: f32,
 
-    /// Limit for the allowed area for the separator offset. By `Default` it's `175.0`.
-    /// `bigger value > less allowed offset` for the current window size.
-    pub extra: f32,
+    /// Smallest width and height in points a leaf can be resized to by moving the separators next to it, with the
+    /// pointer or the keyboard, or by shrinking the dock area. Collapsed leaves aren't affected.
+    /// By `Default` it's `50.0`.
+    pub min_panel_size: f32,
 
     /// Idle color of the rectangle separator. By `Default` it's [`Color32::BLACK`].
     pub color_idle: Color32,
@@ -156,15 +251,22 @@ pub struct SeparatorStyle {
 }
 
 /// Specifies the look and feel of tab bars.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct TabBarStyle {
     /// Background color of tab bar. By `Default` it's [`Color32::WHITE`].
     pub bg_fill: Color32,
 
-    /// Height of the tab bar. By `Default` it's `24.0`.
+    /// Height of the tab bar, or its width if it's [vertical](TabBarPosition::is_vertical). By `Default` it's `24.0`.
     pub height: f32,
 
+    /// How [`height`](Self::height) is determined. By `Default` it's [`TabBarHeightMode::Fixed`].
+    pub height_mode: TabBarHeightMode,
+
+    /// Edge of each leaf along which its tab bar is shown. Tab bars of collapsed leaves are always shown at the top.
+    /// By `Default` it's [`TabBarPosition::Top`].
+    pub position: TabBarPosition,
+
     /// Show a scroll bar when tab bar overflows. By `Default` it's `true`.
     pub show_scroll_bar_on_overflow: bool,
 
@@ -178,6 +280,11 @@ pub struct TabBarStyle {
     /// Whether tab titles expand to fill the width of their tab bars.
     /// By `Default` it's `false`.
     pub fill_tab_bar: bool,
+
+    /// Whether the tab bar is hidden in leaves containing only a single tab. A thin grab handle is shown in its
+    /// place, which can still be used to drag the tab out of the leaf.
+    /// By `Default` it's `false`.
+    pub hide_when_single_tab: bool,
 }
 
 /// Specifies the look and feel of an individual tab.
@@ -218,6 +325,16 @@ pub struct TabStyle {
     /// The tab title or [`TabBarStyle::fill_tab_bar`] may make the tab
     /// wider than this but never shorter.
     pub minimum_width: Option<f32>,
+
+    /// The maximum width of the tab, past which its title is shortened according to
+    /// [`title_truncation`](Self::title_truncation).
+    ///
+    /// [`TabBarStyle::fill_tab_bar`] may still make the tab wider than this. By `Default` it's `None`.
+    pub maximum_width: Option<f32>,
+
+    /// How titles which don't fit in [`maximum_width`](Self::maximum_width) are shortened.
+    /// By `Default` it's [`TitleTruncation::Truncate`].
+    pub title_truncation: TitleTruncation,
 }
 
 /// Specifies the look and feel of individual tabs while they are being interacted with.
@@ -254,6 +371,28 @@ pub struct TabBodyStyle {
     pub bg_fill: Color32,
 }
 
+/// Specifies the look and feel of window surfaces shown inside [`egui::Window`]s, apart from their contents.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct WindowStyle {
+    /// Background color of windows. By `Default` it's [`Color32::WHITE`].
+    pub fill: Color32,
+
+    /// Border of windows. By `Default` it's `1.0` point wide and light gray.
+    pub stroke: Stroke,
+
+    /// Rounding of the corners of windows. By `Default` it's `6.0`.
+    pub rounding: Rounding,
+
+    /// Shadow cast by windows. By `Default` it's a soft gray shadow below the window.
+    pub shadow: Shadow,
+
+    /// Shows a title bar above the contents of every window, as if
+    /// [`WindowState::set_title_bar`](crate::WindowState::set_title_bar) was set for all of them.
+    /// By `Default` it's `false`.
+    pub title_bars: bool,
+}
+
 /// Specifies the look and feel of the tab drop overlay.
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -276,6 +415,23 @@ pub struct OverlayStyle {
     /// By default this value shows no highlighting.
     pub hovered_leaf_highlight: LeafHighlighting,
 
+    /// Style of the caret drawn between tabs where a tab dragged over a tab bar would be inserted. Tabs dragged
+    /// within the same [`DockArea`](crate::DockArea) make room for themselves instead.
+    ///
+    /// If its width is `0.0`, the whole destination is highlighted with
+    /// [`selection_color`](OverlayStyle::selection_color) instead.
+    pub tab_insert_indicator: TabInsertIndicatorStyle,
+
+    /// How the area a dropped tab would take is highlighted with
+    /// [`OverlayType::HighlightedAreas`], for each kind of destination.
+    pub drop_preview: DropPreviewStyle,
+
+    /// Whether hovering a split drop target previews the layout the dragged tab would create, by outlining the
+    /// leaves of the surface as they would be resized and filling the one the tab would end up in.
+    ///
+    /// By default it's `true`.
+    pub split_preview: bool,
+
     /// Opacity which surfaces will fade to in a range of `0.0..=1.0`.
     pub surface_fade_opacity: f32,
 
@@ -310,6 +466,33 @@ pub struct OverlayFeel {
 
     /// Units which the buttons interact area will be expanded by.
     pub interact_expansion: f32,
+
+    /// How far a tab has to be dragged horizontally (`x`) or vertically (`y`) before it's torn off its tab bar and
+    /// can be dropped elsewhere.
+    pub tear_off_distance: Vec2,
+
+    /// How much of the area of a leaf a tab dropped onto one of its splits takes, in range `0.0..=1.0`.
+    ///
+    /// Can be overridden for each tab with [`TabViewer::preferred_split_fraction`](crate::TabViewer::preferred_split_fraction).
+    /// By default it's `0.5`.
+    pub drop_split_fraction: f32,
+
+    /// Speed in points per second at which overflowing tab bars scroll while a tab is dragged near one of their ends.
+    /// `0.0` disables the scrolling. By default it's `400.0`.
+    pub tab_bar_scroll_speed: f32,
+
+    /// Time in seconds a dragged tab has to be held over the title of another tab before that tab is activated, so
+    /// that the dragged tab can be dropped into its body. `None` never activates hovered tabs.
+    ///
+    /// By default it's `None`.
+    pub tab_hover_activate_delay: Option<f32>,
+
+    /// When some, a tab dragged within its own tab bar stays glued to it, moving only along the bar, until the pointer
+    /// leaves the bar by this many points. Only then is it torn off, following the pointer freely and showing the drop
+    /// overlay, which makes reordering tabs feel steadier. [`OverlayFeel::tear_off_distance`] isn't used by such drags.
+    ///
+    /// By default it's `None`.
+    pub tab_bar_glue_distance: Option<f32>,
 }
 
 /// Specifies the type of overlay used.
@@ -327,6 +510,38 @@ pub enum OverlayType {
     Widgets,
 }
 
+/// How the area which a dropped tab would take is drawn, see [`DropPreviewStyle`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum DropPreviewFill {
+    /// The area is filled with [`OverlayStyle::selection_color`].
+    Filled,
+
+    /// The area is outlined with [`OverlayStyle::selection_color`], [`OverlayStyle::selection_stroke_width`] wide.
+    Stroked,
+}
+
+/// How the area which a dropped tab would take is drawn with [`OverlayType::HighlightedAreas`], for each kind of
+/// destination, see [`OverlayStyle::drop_preview`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct DropPreviewStyle {
+    /// The body of a leaf which the tab would be appended to.
+    ///
+    /// By default it's [`DropPreviewFill::Filled`].
+    pub append: DropPreviewFill,
+
+    /// The new leaf which splitting a leaf would create, sized as it would be after the drop.
+    ///
+    /// By default it's [`DropPreviewFill::Filled`].
+    pub split: DropPreviewFill,
+
+    /// The new window the tab would be put in.
+    ///
+    /// By default it's [`DropPreviewFill::Stroked`].
+    pub window: DropPreviewFill,
+}
+
 /// Highlighting on the currently hovered leaf.
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -344,17 +559,41 @@ pub struct LeafHighlighting {
     pub expansion: f32,
 }
 
+/// Style of the caret marking where a tab dropped on a tab bar would be inserted, see
+/// [`OverlayStyle::tab_insert_indicator`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TabInsertIndicatorStyle {
+    /// Width of the caret, across the tab bar.
+    ///
+    /// By default it's `2.0`.
+    pub width: f32,
+
+    /// Color of the caret.
+    ///
+    /// By default it's `(0, 191, 255)` (light blue).
+    pub color: Color32,
+
+    /// Rounding of the caret's corners.
+    ///
+    /// By default it's `1.0`.
+    pub rounding: Rounding,
+}
+
 impl Default for Style {
     fn default() -> Self {
         Self {
             dock_area_padding: None,
+            leaf_padding: Margin::ZERO,
             main_surface_border_stroke: Stroke::new(f32::default(), Color32::BLACK),
             main_surface_border_rounding: Rounding::default(),
+            focused_leaf_highlight: LeafHighlighting::default(),
             buttons: ButtonsStyle::default(),
             separator: SeparatorStyle::default(),
             tab_bar: TabBarStyle::default(),
             tab: TabStyle::default(),
             overlay: OverlayStyle::default(),
+            window: WindowStyle::default(),
         }
     }
 }
@@ -371,6 +610,9 @@ impl Default for ButtonsStyle {
             add_tab_active_color: Color32::WHITE,
             add_tab_bg_fill: Color32::GRAY,
             add_tab_border_color: Color32::BLACK,
+            add_tab_popup_width: None,
+            add_tab_popup_max_height: None,
+            add_tab_popup_align: Align::Min,
 
             close_all_tabs_color: Color32::WHITE,
             close_all_tabs_active_color: Color32::WHITE,
@@ -387,6 +629,9 @@ impl Default for ButtonsStyle {
             minimize_window_active_color: Color32::WHITE,
             minimize_window_bg_fill: Color32::GRAY,
             minimize_window_border_color: Color32::BLACK,
+
+            badge_bg_fill: Color32::GRAY,
+            badge_text_color: Color32::WHITE,
         }
     }
 }
@@ -396,7 +641,7 @@ impl Default for SeparatorStyle {
         Self {
             width: 1.0,
             extra_interact_width: 2.0,
-            extra: 175.0,
+            min_panel_size: 50.0,
             color_idle: Color32::BLACK,
             color_hovered: Color32::GRAY,
             color_dragged: Color32::WHITE,
@@ -409,10 +654,13 @@ impl Default for TabBarStyle {
         Self {
             bg_fill: Color32::WHITE,
             height: 24.0,
+            height_mode: TabBarHeightMode::default(),
+            position: TabBarPosition::default(),
             show_scroll_bar_on_overflow: true,
             rounding: Rounding::default(),
             hline_color: Color32::BLACK,
             fill_tab_bar: false,
+            hide_when_single_tab: false,
         }
     }
 }
@@ -445,6 +693,8 @@ impl Default for TabStyle {
             tab_body: TabBodyStyle::default(),
             hline_below_active_tab_name: false,
             minimum_width: None,
+            maximum_width: None,
+            title_truncation: TitleTruncation::default(),
         }
     }
 }
@@ -471,6 +721,23 @@ impl Default for TabBodyStyle {
     }
 }
 
+impl Default for WindowStyle {
+    fn default() -> Self {
+        Self {
+            fill: Color32::WHITE,
+            stroke: Stroke::new(1.0, Color32::from_gray(190)),
+            rounding: Rounding::same(6.0),
+            shadow: Shadow {
+                offset: Vec2::new(6.0, 10.0),
+                blur: 8.0,
+                spread: 0.0,
+                color: Color32::from_black_alpha(25),
+            },
+            title_bars: false,
+        }
+    }
+}
+
 impl Default for OverlayStyle {
     fn default() -> Self {
         Self {
@@ -482,6 +749,9 @@ impl Default for OverlayStyle {
             surface_fade_opacity: 0.1,
 
             hovered_leaf_highlight: Default::default(),
+            tab_insert_indicator: Default::default(),
+            drop_preview: Default::default(),
+            split_preview: true,
             button_color: Color32::from_gray(140),
             button_border_stroke: Stroke::new(1.0, Color32::from_gray(60)),
             overlay_type: OverlayType::Widgets,
@@ -498,6 +768,11 @@ impl Default for OverlayFeel {
             center_drop_coverage: 0.25,
             fade_hold_time: 0.2,
             interact_expansion: 20.0,
+            tear_off_distance: Vec2::new(30.0, 6.0),
+            drop_split_fraction: 0.5,
+            tab_bar_scroll_speed: 400.0,
+            tab_hover_activate_delay: None,
+            tab_bar_glue_distance: None,
         }
     }
 }
@@ -513,17 +788,57 @@ impl Default for LeafHighlighting {
     }
 }
 
+impl Default for DropPreviewStyle {
+    fn default() -> Self {
+        Self {
+            append: DropPreviewFill::Filled,
+            split: DropPreviewFill::Filled,
+            window: DropPreviewFill::Stroked,
+        }
+    }
+}
+
+impl Default for TabInsertIndicatorStyle {
+    fn default() -> Self {
+        Self {
+            width: 2.0,
+            color: Color32::from_rgb(0, 191, 255),
+            rounding: Rounding::same(1.0),
+        }
+    }
+}
+
 impl Style {
     pub(crate) const TAB_ADD_BUTTON_SIZE: f32 = 24.0;
     pub(crate) const TAB_ADD_PLUS_SIZE: f32 = 12.0;
     pub(crate) const TAB_CLOSE_BUTTON_SIZE: f32 = 24.0;
     pub(crate) const TAB_CLOSE_X_SIZE: f32 = 9.0;
+    pub(crate) const TAB_MODIFIED_DOT_SIZE: f32 = 12.0;
+    pub(crate) const TAB_MODIFIED_DOT_RADIUS: f32 = 3.0;
     pub(crate) const TAB_CLOSE_ALL_BUTTON_SIZE: f32 = 24.0;
+    pub(crate) const TAB_SEARCH_BUTTON_SIZE: f32 = 24.0;
+    pub(crate) const TAB_SEARCH_ICON_SIZE: f32 = 10.0;
+    pub(crate) const TAB_SEARCH_POPUP_WIDTH: f32 = 200.0;
+    pub(crate) const TAB_SEARCH_POPUP_MAX_HEIGHT: f32 = 300.0;
     pub(crate) const TAB_CLOSE_ALL_SIZE: f32 = 10.0;
     pub(crate) const TAB_COLLAPSE_BUTTON_SIZE: f32 = 24.0;
     pub(crate) const TAB_COLLAPSE_ARROW_SIZE: f32 = 10.0;
     pub(crate) const TAB_EXPAND_BUTTON_SIZE: f32 = 24.0;
     pub(crate) const TAB_EXPAND_ARROW_SIZE: f32 = 10.0;
+    pub(crate) const TAB_GRAB_HANDLE_HEIGHT: f32 = 6.0;
+    pub(crate) const TAB_BAR_TEXT_PADDING: f32 = 4.0;
+    pub(crate) const TAB_BAR_SCROLL_MARGIN: f32 = 24.0;
+    pub(crate) const TAB_GRAB_HANDLE_GRIP_WIDTH: f32 = 24.0;
+    pub(crate) const WINDOW_SNAP_MARGIN: f32 = 16.0;
+    pub(crate) const LEAF_DETACH_DRAG_DISTANCE: f32 = 12.0;
+    pub(crate) const AUTO_HIDE_DEFAULT_SIZE: f32 = 250.0;
+    pub(crate) const AUTO_HIDE_ENTRY_SPACING: f32 = 4.0;
+    pub(crate) const BADGE_PADDING: f32 = 4.0;
+    pub(crate) const BADGE_SPACING: f32 = 2.0;
+    pub(crate) const TAB_GROUP_SPACING: f32 = 4.0;
+    pub(crate) const SPLIT_BUTTON_SIZE: f32 = 20.0;
+    pub(crate) const SPLIT_BUTTON_ICON_SIZE: f32 = 10.0;
+    pub(crate) const SPLIT_BUTTON_MARGIN: f32 = 4.0;
 }
 
 impl Style {
@@ -533,7 +848,7 @@ impl Style {
     /// - [`Style::main_surface_border_stroke`]
     ///
     /// See also: [`ButtonsStyle::from_egui`], [`SeparatorStyle::from_egui`], [`TabBarStyle::from_egui`],
-    /// [`TabStyle::from_egui`]
+    /// [`TabStyle::from_egui`], [`WindowStyle::from_egui`]
     pub fn from_egui(style: &egui::Style) -> Self {
         Self {
             main_surface_border_stroke: Stroke::NONE,
@@ -543,9 +858,236 @@ impl Style {
             tab_bar: TabBarStyle::from_egui(style),
             tab: TabStyle::from_egui(style),
             overlay: OverlayStyle::from_egui(style),
+            window: WindowStyle::from_egui(style),
             ..Self::default()
         }
     }
+
+    /// A preset with shorter tab bars, tighter margins and thinner separators, fitting more content on screen.
+    ///
+    /// Starts from [`Style::from_egui`], so colors follow the given `egui::Style`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::Style;
+    /// let style = Style::compact(&egui::Style::default());
+    /// assert!(style.tab_bar.height < Style::from_egui(&egui::Style::default()).tab_bar.height);
+    /// ```
+    pub fn compact(style: &egui::Style) -> Self {
+        let mut dock = Self::from_egui(style);
+        dock.tab_bar.height = 20.0;
+        dock.tab_bar.height_mode = TabBarHeightMode::Fixed;
+        dock.tab_bar.show_scroll_bar_on_overflow = false;
+        dock.tab.tab_body.inner_margin = Margin::same(2.0);
+        dock.tab.minimum_width = None;
+        dock.tab.maximum_width = Some(160.0);
+        dock.tab.title_truncation = TitleTruncation::MiddleEllipsis;
+        dock.separator.width = 1.0;
+        dock.separator.extra_interact_width = 3.0;
+        dock.separator.min_panel_size = 32.0;
+        dock.overlay.button_spacing = style.spacing.icon_spacing / 2.0;
+        dock.overlay.max_button_size = 60.0;
+        dock.overlay.feel.interact_expansion = 12.0;
+        dock.overlay.feel.tear_off_distance = Vec2::new(20.0, 4.0);
+        dock
+    }
+
+    /// A preset with rounded tabs, tab bars and tab bodies separated by padding around every leaf.
+    ///
+    /// Starts from [`Style::from_egui`], so colors follow the given `egui::Style`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::Style;
+    /// let style = Style::rounded(&egui::Style::default());
+    /// assert_eq!(style.tab.tab_body.rounding, egui::Rounding::same(6.0));
+    /// ```
+    pub fn rounded(style: &egui::Style) -> Self {
+        const RADIUS: f32 = 6.0;
+        let mut dock = Self::from_egui(style);
+        dock.dock_area_padding = Some(Margin::same(4.0));
+        dock.leaf_padding = Margin::same(2.0);
+        dock.main_surface_border_rounding = Rounding::same(RADIUS);
+        dock.tab_bar.rounding = Rounding::same(RADIUS);
+        dock.tab_bar.fill_tab_bar = true;
+        for tab in dock.tab.interaction_styles_mut() {
+            tab.rounding = Rounding::same(RADIUS - 2.0);
+        }
+        dock.tab.tab_body.rounding = Rounding::same(RADIUS);
+        dock.tab.tab_body.inner_margin = Margin::same(8.0);
+        dock.focused_leaf_highlight = LeafHighlighting {
+            color: Color32::TRANSPARENT,
+            rounding: Rounding::same(RADIUS),
+            stroke: Stroke::new(1.0, style.visuals.selection.stroke.color),
+            expansion: 1.0,
+        };
+        dock.overlay.hovered_leaf_highlight.rounding = Rounding::same(RADIUS);
+        dock.overlay.tab_insert_indicator.rounding = Rounding::same(2.0);
+        dock.overlay.tab_insert_indicator.width = 4.0;
+        dock.overlay.drop_preview.window = DropPreviewFill::Filled;
+        dock.window.rounding = Rounding::same(RADIUS + 2.0);
+        dock
+    }
+
+    /// A preset resembling the editor groups of Visual Studio Code: flat rectangular tabs without outlines, the
+    /// active tab marked with a line and the focused leaf with an accent color.
+    ///
+    /// Starts from [`Style::from_egui`], so colors follow the given `egui::Style`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::Style;
+    /// let style = Style::vscode_like(&egui::Style::default());
+    /// assert!(style.tab.hline_below_active_tab_name);
+    /// ```
+    pub fn vscode_like(style: &egui::Style) -> Self {
+        let accent = style.visuals.selection.stroke.color;
+        let mut dock = Self::from_egui(style);
+        dock.tab_bar.height = 30.0;
+        dock.tab_bar.rounding = Rounding::ZERO;
+        dock.tab_bar.fill_tab_bar = false;
+        dock.tab_bar.hline_color = style.visuals.widgets.noninteractive.bg_stroke.color;
+        for tab in dock.tab.interaction_styles_mut() {
+            tab.rounding = Rounding::ZERO;
+            tab.outline_color = Color32::TRANSPARENT;
+        }
+        dock.tab.inactive.bg_fill = style.visuals.faint_bg_color;
+        dock.tab.inactive_with_kb_focus.bg_fill = style.visuals.faint_bg_color;
+        dock.tab.focused.text_color = style.visuals.strong_text_color();
+        dock.tab.hline_below_active_tab_name = true;
+        dock.tab.minimum_width = Some(120.0);
+        dock.tab.tab_body.rounding = Rounding::ZERO;
+        dock.tab.tab_body.stroke = Stroke::NONE;
+        dock.buttons.add_tab_align = TabAddAlign::Left;
+        dock.separator.width = 1.0;
+        dock.separator.color_hovered = accent;
+        dock.separator.color_dragged = accent;
+        dock.focused_leaf_highlight = LeafHighlighting {
+            color: Color32::TRANSPARENT,
+            rounding: Rounding::ZERO,
+            stroke: Stroke::new(1.0, accent),
+            expansion: 0.0,
+        };
+        dock.overlay.overlay_type = OverlayType::HighlightedAreas;
+        dock.overlay.selection_color = accent.linear_multiply(0.3);
+        dock.overlay.tab_insert_indicator.color = accent;
+        dock.window.rounding = Rounding::ZERO;
+        dock.window.shadow.blur = 4.0;
+        dock.window.title_bars = true;
+        dock
+    }
+
+    /// A preset with strong contrast between text, backgrounds and borders for readability, e.g. for users with
+    /// low vision.
+    ///
+    /// Uses black backgrounds with white text and yellow accents if [`egui::Visuals::dark_mode`] is set, and white
+    /// backgrounds with black text and blue accents otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::Style;
+    /// let style = Style::high_contrast(&egui::Style::default());
+    /// assert_eq!(style.tab.active.text_color, egui::Color32::WHITE);
+    /// ```
+    pub fn high_contrast(style: &egui::Style) -> Self {
+        let (bg, fg, accent) = if style.visuals.dark_mode {
+            (Color32::BLACK, Color32::WHITE, Color32::YELLOW)
+        } else {
+            (Color32::WHITE, Color32::BLACK, Color32::from_rgb(0, 0, 192))
+        };
+        let mut dock = Self::from_egui(style);
+        dock.main_surface_border_stroke = Stroke::new(2.0, fg);
+        dock.focused_leaf_highlight = LeafHighlighting {
+            color: Color32::TRANSPARENT,
+            rounding: Rounding::ZERO,
+            stroke: Stroke::new(2.0, accent),
+            expansion: 0.0,
+        };
+
+        let buttons = &mut dock.buttons;
+        for color in [
+            &mut buttons.close_tab_color,
+            &mut buttons.add_tab_color,
+            &mut buttons.close_all_tabs_color,
+            &mut buttons.collapse_tabs_color,
+            &mut buttons.minimize_window_color,
+            &mut buttons.badge_text_color,
+        ] {
+            *color = fg;
+        }
+        for color in [
+            &mut buttons.close_tab_active_color,
+            &mut buttons.add_tab_active_color,
+            &mut buttons.close_all_tabs_active_color,
+            &mut buttons.collapse_tabs_active_color,
+            &mut buttons.minimize_window_active_color,
+            &mut buttons.badge_bg_fill,
+        ] {
+            *color = accent;
+        }
+        for color in [
+            &mut buttons.close_tab_bg_fill,
+            &mut buttons.add_tab_bg_fill,
+            &mut buttons.close_all_tabs_bg_fill,
+            &mut buttons.collapse_tabs_bg_fill,
+            &mut buttons.minimize_window_bg_fill,
+        ] {
+            *color = bg;
+        }
+        for color in [
+            &mut buttons.add_tab_border_color,
+            &mut buttons.close_all_tabs_border_color,
+            &mut buttons.collapse_tabs_border_color,
+            &mut buttons.minimize_window_border_color,
+        ] {
+            *color = fg;
+        }
+        buttons.close_all_tabs_disabled_color = Color32::GRAY;
+
+        dock.separator.width = 2.0;
+        dock.separator.color_idle = fg;
+        dock.separator.color_hovered = accent;
+        dock.separator.color_dragged = accent;
+
+        dock.tab_bar.bg_fill = bg;
+        dock.tab_bar.hline_color = fg;
+
+        for tab in dock.tab.interaction_styles_mut() {
+            tab.bg_fill = bg;
+            tab.outline_color = fg;
+            tab.text_color = fg;
+        }
+        dock.tab.active.outline_color = accent;
+        dock.tab.focused.outline_color = accent;
+        dock.tab.focused.text_color = accent;
+        dock.tab.focused_with_kb_focus.text_color = accent;
+        dock.tab.hovered.bg_fill = fg;
+        dock.tab.hovered.text_color = bg;
+        dock.tab.hline_below_active_tab_name = true;
+        dock.tab.tab_body.bg_fill = bg;
+        dock.tab.tab_body.stroke = Stroke::new(2.0, fg);
+
+        dock.overlay.selection_color = accent.linear_multiply(0.5);
+        dock.overlay.selection_stroke_width = 2.0;
+        dock.overlay.button_color = fg;
+        dock.overlay.button_border_stroke = Stroke::new(2.0, fg);
+        dock.overlay.hovered_leaf_highlight.stroke = Stroke::new(2.0, accent);
+        dock.overlay.tab_insert_indicator.color = accent;
+        dock.overlay.tab_insert_indicator.width = 3.0;
+        dock.overlay.drop_preview = DropPreviewStyle {
+            append: DropPreviewFill::Stroked,
+            split: DropPreviewFill::Stroked,
+            window: DropPreviewFill::Stroked,
+        };
+        dock.window.fill = bg;
+        dock.window.stroke = Stroke::new(2.0, fg);
+        dock.window.shadow = Shadow::NONE;
+        dock
+    }
 }
 
 impl ButtonsStyle {
@@ -567,6 +1109,8 @@ impl ButtonsStyle {
     /// - [`ButtonsStyle::collapse_tabs_color`]
     /// - [`ButtonsStyle::collapse_tabs_active_color`]
     /// - [`ButtonsStyle::collapse_tabs_border_color`]
+    /// - [`ButtonsStyle::badge_bg_fill`]
+    /// - [`ButtonsStyle::badge_text_color`]
     pub fn from_egui(style: &egui::Style) -> Self {
         Self {
             close_tab_bg_fill: style.visuals.widgets.hovered.bg_fill,
@@ -589,6 +1133,8 @@ impl ButtonsStyle {
             minimize_window_color: style.visuals.text_color(),
             minimize_window_active_color: style.visuals.strong_text_color(),
             minimize_window_border_color: style.visuals.widgets.noninteractive.bg_fill,
+            badge_bg_fill: style.visuals.widgets.inactive.bg_fill,
+            badge_text_color: style.visuals.strong_text_color(),
             ..ButtonsStyle::default()
         }
     }
@@ -632,6 +1178,16 @@ impl TabBarStyle {
             ..TabBarStyle::default()
         }
     }
+
+    /// Computes [`height`](Self::height) from the fonts of `ui` if [`height_mode`](Self::height_mode) is
+    /// [`TabBarHeightMode::AutoFromText`].
+    pub(crate) fn update_height(&mut self, ui: &Ui) {
+        if self.height_mode == TabBarHeightMode::AutoFromText {
+            let font_id = TextStyle::Button.resolve(ui.style());
+            let row_height = ui.fonts(|fonts| fonts.row_height(&font_id));
+            self.height = (row_height + 2.0 * Style::TAB_BAR_TEXT_PADDING).ceil();
+        }
+    }
 }
 
 impl TabStyle {
@@ -652,6 +1208,19 @@ impl TabStyle {
             ..Default::default()
         }
     }
+
+    /// Returns the styles of tabs in every interaction state.
+    fn interaction_styles_mut(&mut self) -> [&mut TabInteractionStyle; 7] {
+        [
+            &mut self.active,
+            &mut self.inactive,
+            &mut self.focused,
+            &mut self.hovered,
+            &mut self.active_with_kb_focus,
+            &mut self.inactive_with_kb_focus,
+            &mut self.focused_with_kb_focus,
+        ]
+    }
 }
 
 impl TabInteractionStyle {
@@ -790,6 +1359,26 @@ impl TabBodyStyle {
     }
 }
 
+impl WindowStyle {
+    /// Derives relevant fields from `egui::Style`, making windows look like regular egui windows, and sets the
+    /// remaining fields to their default values.
+    ///
+    /// Fields overwritten by [`egui::Style`] are:
+    /// - [`WindowStyle::fill`]
+    /// - [`WindowStyle::stroke`]
+    /// - [`WindowStyle::rounding`]
+    /// - [`WindowStyle::shadow`]
+    pub fn from_egui(style: &egui::Style) -> Self {
+        Self {
+            fill: style.visuals.window_fill(),
+            stroke: style.visuals.window_stroke(),
+            rounding: style.visuals.window_rounding,
+            shadow: style.visuals.window_shadow,
+            ..WindowStyle::default()
+        }
+    }
+}
+
 impl OverlayStyle {
     /// Derives relevant fields from `egui::Style` and sets the remaining fields to their default values.
     ///
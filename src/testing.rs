@@ -0,0 +1,419 @@
+use egui::{Context, Event, Key, Modifiers, PointerButton, Pos2, RawInput, Rect, Vec2, ViewportId};
+
+use crate::{
+    dock_area::overlay_button_rect, DockArea, DockState, LayoutMetrics, Node, NodeIndex,
+    OverlayButtonKind, Style, SurfaceIndex, TabIndex, TabViewer,
+};
+
+/// Changes the settings of the [`DockArea`] shown by a [`DockHarness`], see [`DockHarness::dock_area`].
+type ConfigureDockArea<Tab> = Box<dyn for<'a> FnMut(DockArea<'a, Tab>) -> DockArea<'a, Tab>>;
+
+/// Shows other widgets next to the [`DockArea`] shown by a [`DockHarness`], see [`DockHarness::show_before`].
+type ShowOther = Box<dyn FnMut(&Context)>;
+
+/// How many frames [`DockHarness::run`] shows at most while waiting for the dock area to settle.
+const MAX_RUN_STEPS: usize = 120;
+
+/// How many frames the pointer takes to move from where a drag starts to where it ends.
+const DRAG_STEPS: usize = 10;
+
+/// Shows a [`DockArea`] in a headless [`egui::Context`] and simulates the user interacting with it, so that the
+/// resulting [`DockState`] can be checked in tests.
+///
+/// Each frame the area is shown inside an [`egui::CentralPanel`] covering the whole screen, with
+/// [`DockArea::collect_metrics`] enabled so that the harness knows where tabs and nodes are. Actions such as
+/// [`DockHarness::drag_tab_to_overlay_button`] feed pointer and keyboard events into the context over as many frames
+/// as a user would, and then [`run`](DockHarness::run) until the area settles.
+///
+/// # Examples
+///
+/// ```rust
+/// # use egui_dock::{DockState, NodeIndex, OverlayButtonKind, Split, SurfaceIndex, TabIndex, TabViewer};
+/// # use egui_dock::testing::DockHarness;
+/// # use egui::{Ui, WidgetText};
+/// # struct MyTabViewer;
+/// # impl TabViewer for MyTabViewer {
+/// #     type Tab = String;
+/// #     fn title(&mut self, tab: &mut Self::Tab) -> WidgetText { tab.as_str().into() }
+/// #     fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {}
+/// # }
+/// let dock_state = DockState::new(vec!["first".to_owned(), "second".to_owned()]);
+/// let mut harness = DockHarness::new(dock_state, MyTabViewer);
+/// harness.run();
+///
+/// let main = SurfaceIndex::main();
+/// harness.drag_tab_to_overlay_button(
+///     (main, NodeIndex::root(), TabIndex(1)),
+///     (main, NodeIndex::root()),
+///     OverlayButtonKind::Split(Split::Right),
+/// );
+/// assert_eq!(harness.leaf_tabs(main), [vec!["first"], vec!["second"]]);
+///
+/// harness.close_tab((main, NodeIndex::root().right(), TabIndex(0)));
+/// assert_eq!(harness.leaf_tabs(main), [vec!["first"]]);
+/// ```
+pub struct DockHarness<V: TabViewer> {
+    ctx: Context,
+    dock_state: DockState<V::Tab>,
+    tab_viewer: V,
+    configure: ConfigureDockArea<V::Tab>,
+    before: ShowOther,
+    after: ShowOther,
+    screen_rect: Rect,
+    time: f64,
+    events: Vec<Event>,
+    modifiers: Modifiers,
+    style: Option<Style>,
+}
+
+impl<V: TabViewer> DockHarness<V> {
+    /// Creates a harness showing `dock_state` with `tab_viewer` on an 800×600 screen.
+    pub fn new(dock_state: DockState<V::Tab>, tab_viewer: V) -> Self {
+        Self {
+            ctx: Context::default(),
+            dock_state,
+            tab_viewer,
+            configure: Box::new(|dock_area| dock_area),
+            before: Box::new(|_| {}),
+            after: Box::new(|_| {}),
+            screen_rect: Rect::from_min_size(Pos2::ZERO, Vec2::new(800.0, 600.0)),
+            time: 0.0,
+            events: Vec::new(),
+            modifiers: Modifiers::NONE,
+            style: None,
+        }
+    }
+
+    /// Sets the size of the screen the dock area is shown on.
+    /// By default it's `800×600`.
+    #[inline(always)]
+    pub fn screen_size(mut self, size: Vec2) -> Self {
+        self.screen_rect = Rect::from_min_size(Pos2::ZERO, size);
+        self
+    }
+
+    /// Changes the settings of the [`DockArea`] every time it's shown.
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, TabViewer};
+    /// # use egui_dock::testing::DockHarness;
+    /// # use egui::{Ui, WidgetText};
+    /// # struct MyTabViewer;
+    /// # impl TabViewer for MyTabViewer {
+    /// #     type Tab = String;
+    /// #     fn title(&mut self, tab: &mut Self::Tab) -> WidgetText { tab.as_str().into() }
+    /// #     fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {}
+    /// # }
+    /// # let dock_state = DockState::new(vec!["tab".to_owned()]);
+    /// let harness = DockHarness::new(dock_state, MyTabViewer)
+    ///     .dock_area(|dock_area| dock_area.show_add_buttons(true).show_close_buttons(false));
+    /// ```
+    #[inline(always)]
+    pub fn dock_area(
+        mut self,
+        configure: impl for<'a> FnMut(DockArea<'a, V::Tab>) -> DockArea<'a, V::Tab> + 'static,
+    ) -> Self {
+        self.configure = Box::new(configure);
+        self
+    }
+
+    /// Shows other widgets with `show` every frame, before the dock area is shown.
+    ///
+    /// Widgets shown in an [`egui::Area`] are on top of the dock area, wherever they're shown from.
+    #[inline(always)]
+    pub fn show_before(mut self, show: impl FnMut(&Context) + 'static) -> Self {
+        self.before = Box::new(show);
+        self
+    }
+
+    /// Shows other widgets with `show` every frame, after the dock area is shown.
+    ///
+    /// Widgets shown in an [`egui::Area`] are on top of the dock area, wherever they're shown from.
+    #[inline(always)]
+    pub fn show_after(mut self, show: impl FnMut(&Context) + 'static) -> Self {
+        self.after = Box::new(show);
+        self
+    }
+
+    /// Returns the context the dock area is shown in.
+    #[inline]
+    pub fn ctx(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// Returns the dock state driven by the harness.
+    #[inline]
+    pub fn dock_state(&self) -> &DockState<V::Tab> {
+        &self.dock_state
+    }
+
+    /// Returns the dock state driven by the harness, to set it up between frames.
+    #[inline]
+    pub fn dock_state_mut(&mut self) -> &mut DockState<V::Tab> {
+        &mut self.dock_state
+    }
+
+    /// Returns the tab viewer the dock area is shown with.
+    #[inline]
+    pub fn tab_viewer(&self) -> &V {
+        &self.tab_viewer
+    }
+
+    /// Returns the tab viewer the dock area is shown with, to set it up between frames.
+    #[inline]
+    pub fn tab_viewer_mut(&mut self) -> &mut V {
+        &mut self.tab_viewer
+    }
+
+    /// Consumes the harness, returning the dock state it drove.
+    #[inline]
+    pub fn into_dock_state(self) -> DockState<V::Tab> {
+        self.dock_state
+    }
+
+    /// Shows one frame, handling the events queued since the last one, 1/60 of a second after the last frame.
+    pub fn step(&mut self) {
+        let input = RawInput {
+            screen_rect: Some(self.screen_rect),
+            time: Some(self.time),
+            predicted_dt: 1.0 / 60.0,
+            events: std::mem::take(&mut self.events),
+            modifiers: self.modifiers,
+            ..Default::default()
+        };
+        self.time += 1.0 / 60.0;
+
+        let Self {
+            ctx,
+            dock_state,
+            tab_viewer,
+            configure,
+            before,
+            after,
+            style,
+            ..
+        } = self;
+        let _ = ctx.run(input, |ctx| {
+            before(ctx);
+            egui::CentralPanel::default().show(ctx, |ui| {
+                let dock_area = configure(DockArea::new(dock_state).collect_metrics(true));
+                let area_style = dock_area.configured_style();
+                *style = Some(
+                    area_style
+                        .cloned()
+                        .unwrap_or_else(|| Style::from_egui(ui.style())),
+                );
+                dock_area.show_inside(ui, tab_viewer);
+            });
+            after(ctx);
+        });
+    }
+
+    /// Shows frames until the dock area stops asking to be repainted, which happens once animations have finished,
+    /// or until two seconds worth of frames have been shown.
+    pub fn run(&mut self) {
+        for _ in 0..MAX_RUN_STEPS {
+            self.step();
+            if !self.ctx.has_requested_repaint_for(&ViewportId::ROOT) && self.events.is_empty() {
+                break;
+            }
+        }
+    }
+
+    /// Queues an event to be handled in the next frame.
+    #[inline]
+    pub fn push_event(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    /// Moves the pointer to `pos` in the next frame.
+    pub fn pointer_move(&mut self, pos: Pos2) {
+        self.push_event(Event::PointerMoved(pos));
+    }
+
+    /// Moves the pointer to `pos` and presses or releases the given `button` there in the next frame.
+    pub fn pointer_button(&mut self, pos: Pos2, button: PointerButton, pressed: bool) {
+        self.pointer_move(pos);
+        self.push_event(Event::PointerButton {
+            pos,
+            button,
+            pressed,
+            modifiers: self.modifiers,
+        });
+    }
+
+    /// Holds down the given modifier keys in the following frames, until they're changed again.
+    #[inline]
+    pub fn set_modifiers(&mut self, modifiers: Modifiers) {
+        self.modifiers = modifiers;
+    }
+
+    /// Clicks the primary pointer button at `pos` and runs until the dock area settles.
+    pub fn click_at(&mut self, pos: Pos2) {
+        self.pointer_button(pos, PointerButton::Primary, true);
+        self.step();
+        self.pointer_button(pos, PointerButton::Primary, false);
+        self.run();
+    }
+
+    /// Presses and releases `key` and runs until the dock area settles.
+    pub fn press_key(&mut self, key: Key) {
+        for pressed in [true, false] {
+            self.push_event(Event::Key {
+                key,
+                physical_key: None,
+                pressed,
+                repeat: false,
+                modifiers: self.modifiers,
+            });
+        }
+        self.run();
+    }
+
+    /// Drags with the primary pointer button from `from` to `to`, moving the pointer over several frames, and runs
+    /// until the dock area settles after releasing it.
+    pub fn drag(&mut self, from: Pos2, to: Pos2) {
+        self.pointer_button(from, PointerButton::Primary, true);
+        self.step();
+        for step in 1..=DRAG_STEPS {
+            self.pointer_move(from.lerp(to, step as f32 / DRAG_STEPS as f32));
+            self.step();
+        }
+        // The overlay keeps preferring destinations hovered on the way for a while, so hold still until it lets go.
+        let hold = self.style().overlay.feel.max_preference_time;
+        for _ in 0..=(hold * 60.0).ceil() as usize {
+            self.step();
+        }
+        self.pointer_button(to, PointerButton::Primary, false);
+        self.run();
+    }
+
+    /// Returns the metrics of the last frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no frame has been shown yet.
+    pub fn metrics(&self) -> &LayoutMetrics {
+        self.dock_state
+            .last_metrics()
+            .expect("the dock area should be shown with `step` or `run` first")
+    }
+
+    /// Returns the rect of a node in the last frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node wasn't shown in the last frame.
+    pub fn node_rect(&self, (surface, node): (SurfaceIndex, NodeIndex)) -> Rect {
+        self.metrics()
+            .node(surface, node)
+            .unwrap_or_else(|| panic!("node {node:?} of surface {surface:?} wasn't shown"))
+            .rect
+    }
+
+    /// Returns the rect of a tab's title in the last frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the title wasn't visible in the last frame.
+    pub fn tab_rect(&self, (surface, node, tab): (SurfaceIndex, NodeIndex, TabIndex)) -> Rect {
+        self.metrics()
+            .tab(surface, node, tab)
+            .unwrap_or_else(|| {
+                panic!("tab {tab:?} of node {node:?} of surface {surface:?} wasn't shown")
+            })
+            .title_rect
+    }
+
+    /// Returns the rect of an overlay button which would be shown over a leaf while dragging a tab onto it, with
+    /// [`OverlayType::Widgets`](crate::OverlayType::Widgets).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the leaf wasn't shown in the last frame.
+    pub fn overlay_button_rect(
+        &self,
+        leaf: (SurfaceIndex, NodeIndex),
+        kind: OverlayButtonKind,
+    ) -> Rect {
+        overlay_button_rect(self.node_rect(leaf), self.style(), kind)
+    }
+
+    /// Returns the position of the middle of the separator of a parent node in the last frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node isn't a parent or wasn't shown in the last frame.
+    pub fn separator_pos(&self, (surface, node): (SurfaceIndex, NodeIndex)) -> Pos2 {
+        let rect = self.node_rect((surface, node));
+        match self.dock_state[surface][node] {
+            Node::Horizontal { fraction, .. } => {
+                Pos2::new(rect.min.x + rect.width() * fraction, rect.center().y)
+            }
+            Node::Vertical { fraction, .. } => {
+                Pos2::new(rect.center().x, rect.min.y + rect.height() * fraction)
+            }
+            _ => panic!("node {node:?} of surface {surface:?} isn't a parent"),
+        }
+    }
+
+    /// Clicks a tab's title, making it the active and focused tab.
+    pub fn click_tab(&mut self, tab: (SurfaceIndex, NodeIndex, TabIndex)) {
+        self.click_at(self.tab_rect(tab).center());
+    }
+
+    /// Clicks a tab's close button.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tab had no close button in the last frame.
+    pub fn close_tab(&mut self, (surface, node, tab): (SurfaceIndex, NodeIndex, TabIndex)) {
+        let close_button = self
+            .metrics()
+            .tab(surface, node, tab)
+            .and_then(|metrics| metrics.close_button_rect)
+            .unwrap_or_else(|| {
+                panic!("tab {tab:?} of node {node:?} of surface {surface:?} has no close button")
+            });
+        self.click_at(close_button.center());
+    }
+
+    /// Drags a tab by its title and drops it at `pos`.
+    pub fn drag_tab_to(&mut self, tab: (SurfaceIndex, NodeIndex, TabIndex), pos: Pos2) {
+        self.drag(self.tab_rect(tab).center(), pos);
+    }
+
+    /// Drags a tab by its title and drops it onto an overlay button shown over `leaf`, which has to be shown with
+    /// [`OverlayType::Widgets`](crate::OverlayType::Widgets).
+    pub fn drag_tab_to_overlay_button(
+        &mut self,
+        tab: (SurfaceIndex, NodeIndex, TabIndex),
+        leaf: (SurfaceIndex, NodeIndex),
+        kind: OverlayButtonKind,
+    ) {
+        self.drag_tab_to(tab, self.overlay_button_rect(leaf, kind).center());
+    }
+
+    /// Drags the separator of a parent node by `delta`.
+    pub fn drag_separator(&mut self, parent: (SurfaceIndex, NodeIndex), delta: Vec2) {
+        let from = self.separator_pos(parent);
+        self.drag(from, from + delta);
+    }
+
+    /// Returns the tabs of every leaf of a surface, in the order of the leaves' indices.
+    pub fn leaf_tabs(&self, surface: SurfaceIndex) -> Vec<Vec<&V::Tab>> {
+        self.dock_state
+            .get_surface(surface)
+            .and_then(|surface| surface.node_tree())
+            .into_iter()
+            .flat_map(|tree| tree.iter())
+            .filter_map(|node| Some(node.tabs()?.iter().collect()))
+            .collect()
+    }
+
+    fn style(&self) -> &Style {
+        self.style
+            .as_ref()
+            .expect("the dock area should be shown with `step` or `run` first")
+    }
+}
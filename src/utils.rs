@@ -31,18 +31,43 @@ pub fn rect_set_size_centered(rect: &mut Rect, size: Vec2) {
     rect.set_center(center);
 }
 
+/// Mirror a rectangle horizontally around the vertical center line of `within` if `mirror` is `true`.
+pub fn rect_mirror_x(rect: Rect, within: Rect, mirror: bool) -> Rect {
+    if !mirror {
+        return rect;
+    }
+    let offset = within.min.x + within.max.x;
+    Rect::from_x_y_ranges(
+        (offset - rect.max.x)..=(offset - rect.min.x),
+        rect.y_range(),
+    )
+}
+
 /// Shrink a rectangle so that the stroke is fully contained inside
 /// the original rectangle.
 pub fn rect_stroke_box(rect: Rect, width: f32) -> Rect {
     rect.expand(-f32::ceil(width / 2.0))
 }
 
+/// Shrink a rectangle by a margin on each side.
+pub fn pad_rect(mut rect: Rect, margin: egui::Margin) -> Rect {
+    rect.min += margin.left_top();
+    rect.max -= margin.right_bottom();
+    rect
+}
+
 /// Fade a `egui_dock::Style` to a certain opacity
 pub(super) fn fade_dock_style(style: &mut Style, factor: f32) {
     style.main_surface_border_stroke.color = style
         .main_surface_border_stroke
         .color
         .linear_multiply(factor);
+    style.focused_leaf_highlight.color = style.focused_leaf_highlight.color.linear_multiply(factor);
+    style.focused_leaf_highlight.stroke.color = style
+        .focused_leaf_highlight
+        .stroke
+        .color
+        .linear_multiply(factor);
     fade_tab_style(&mut style.tab, factor);
     fade_button_style(&mut style.buttons, factor);
     fade_seperator_style(&mut style.separator, factor);
@@ -0,0 +1,85 @@
+use crate::{AllowedSplits, NodeIndex, SurfaceIndex};
+
+/// Where a tab can be dropped while it's being dragged, returned from
+/// [`TabViewer::allowed_destinations`](crate::TabViewer::allowed_destinations).
+///
+/// Drop targets which aren't allowed are greyed out on the overlay and can't be dropped onto.
+///
+/// # Examples
+///
+/// ```rust
+/// # use egui_dock::{AllowedDestinations, AllowedSplits};
+/// // A tool panel which can only be docked next to other panels in the main surface.
+/// let allowed = AllowedDestinations {
+///     windows: false,
+///     new_window: false,
+///     splits: AllowedSplits::TopBottomOnly,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AllowedDestinations {
+    /// Whether the tab can be docked into the main surface.
+    pub main_surface: bool,
+
+    /// Whether the tab can be docked into existing windows.
+    pub windows: bool,
+
+    /// Whether the tab can be dropped outside of any leaf to become a new window.
+    pub new_window: bool,
+
+    /// Which sides of a leaf the tab can split off a new leaf on.
+    pub splits: AllowedSplits,
+
+    /// The only leaves the tab can be docked into, or [`None`] to allow all of them.
+    ///
+    /// Useful for confining a tab to a region of the layout, e.g. the leaves in the left half of the main surface.
+    /// When this is set, the tab can't be dropped onto an empty surface.
+    pub nodes: Option<Vec<(SurfaceIndex, NodeIndex)>>,
+}
+
+impl Default for AllowedDestinations {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl AllowedDestinations {
+    /// Allows dropping the tab anywhere.
+    pub const fn all() -> Self {
+        Self {
+            main_surface: true,
+            windows: true,
+            new_window: true,
+            splits: AllowedSplits::All,
+            nodes: None,
+        }
+    }
+
+    /// Allows dropping the tab only into the main surface.
+    pub const fn main_surface_only() -> Self {
+        Self {
+            main_surface: true,
+            windows: false,
+            new_window: false,
+            splits: AllowedSplits::All,
+            nodes: None,
+        }
+    }
+
+    /// Returns `true` if the tab can be docked into the given `node` of the given `surface`,
+    /// or into the empty `surface` if `node` is [`None`].
+    pub fn allows_node(&self, surface: SurfaceIndex, node: Option<NodeIndex>) -> bool {
+        let surface_allowed = if surface.is_main() {
+            self.main_surface
+        } else {
+            self.windows
+        };
+        surface_allowed
+            && match (&self.nodes, node) {
+                (None, _) => true,
+                (Some(nodes), Some(node)) => nodes.contains(&(surface, node)),
+                (Some(_), None) => false,
+            }
+    }
+}
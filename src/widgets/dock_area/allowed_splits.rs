@@ -1,3 +1,5 @@
+use crate::Split;
+
 /// What directions can this dock be split in?
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
 pub enum AllowedSplits {
@@ -35,4 +37,15 @@ impl AllowedSplits {
             _ => panic!("Provided an invalid value for allowed splits: {u8:0x}"),
         }
     }
+
+    /// Returns `true` if splitting on the given side is allowed.
+    #[inline(always)]
+    pub const fn allows(self, split: Split) -> bool {
+        match self {
+            AllowedSplits::All => true,
+            AllowedSplits::LeftRightOnly => split.is_left_right(),
+            AllowedSplits::TopBottomOnly => split.is_top_bottom(),
+            AllowedSplits::None => false,
+        }
+    }
 }
@@ -0,0 +1,22 @@
+/// Which tab of a leaf becomes active after its active tab is closed, see
+/// [`DockArea::on_close_activate`](crate::DockArea::on_close_activate).
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum CloseActivationPolicy {
+    /// The tab shown to the right of the closed one, or to its left if there's none, like in most web browsers.
+    ///
+    /// Follows the [`LayoutDirection`](crate::LayoutDirection) of the tab bar.
+    NextRight,
+
+    /// The tab shown to the left of the closed one, or to its right if there's none.
+    ///
+    /// Follows the [`LayoutDirection`](crate::LayoutDirection) of the tab bar.
+    NextLeft,
+
+    /// The tab which was active most recently before the closed one.
+    #[default]
+    MostRecentlyUsed,
+
+    /// The tab which takes the index of the closed one, or the last tab if the closed one was the last,
+    /// regardless of the [`LayoutDirection`](crate::LayoutDirection) of the tab bar.
+    KeepIndex,
+}
@@ -0,0 +1,115 @@
+use egui::Id;
+
+use crate::{NodeIndex, Split, SurfaceIndex, TabDestination, TabIndex};
+
+/// Describes a change made to the [`DockState`](crate::DockState) by the user while a [`DockArea`](crate::DockArea)
+/// was shown.
+///
+/// Events are returned from [`DockArea::show`](crate::DockArea::show) and
+/// [`DockArea::show_inside`](crate::DockArea::show_inside) in the order in which they were applied. Indices stored in
+/// an event refer to the layout from right before that event was applied, so they may be invalidated by the events
+/// that follow it.
+///
+/// Tabs are identified with the [`Id`] returned from [`TabViewer::id`](crate::TabViewer::id).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DockEvent {
+    /// A tab has been moved to a different place, either by dropping it or by ejecting it into a new window.
+    TabMoved {
+        /// Id of the moved tab.
+        id: Id,
+
+        /// Where the tab was before being moved.
+        from: (SurfaceIndex, NodeIndex, TabIndex),
+
+        /// Where the tab was moved to.
+        to: TabDestination,
+    },
+
+    /// A copy of a tab made with [`TabViewer::clone_tab`](crate::TabViewer::clone_tab) has been dropped while
+    /// holding the [`DockArea::tab_copy_modifiers`](crate::DockArea::tab_copy_modifiers).
+    TabCopied {
+        /// Id of the new copy.
+        id: Id,
+
+        /// Where the original tab is.
+        from: (SurfaceIndex, NodeIndex, TabIndex),
+
+        /// Where the copy has been put.
+        to: TabDestination,
+    },
+
+    /// A tab has been closed and removed from the [`DockState`](crate::DockState).
+    TabClosed {
+        /// Id of the closed tab.
+        id: Id,
+
+        /// Where the tab was before being closed.
+        from: (SurfaceIndex, NodeIndex, TabIndex),
+    },
+
+    /// A leaf has been split in two as a result of dropping a tab onto one of its sides.
+    NodeSplit {
+        /// Surface containing the split node.
+        surface: SurfaceIndex,
+
+        /// The node that has been split.
+        node: NodeIndex,
+
+        /// Side of the node on which the new leaf has been created.
+        split: Split,
+    },
+
+    /// A tab has been dropped onto another [`DockArea`](crate::DockArea) of the same
+    /// [drag group](crate::DockArea::drag_group) and removed from this one.
+    TabSent {
+        /// Id of the sent tab.
+        id: Id,
+
+        /// Where the tab was before being sent.
+        from: (SurfaceIndex, NodeIndex, TabIndex),
+
+        /// Id of the area which has received the tab.
+        area: Id,
+    },
+
+    /// A tab dragged from another [`DockArea`](crate::DockArea) of the same
+    /// [drag group](crate::DockArea::drag_group) has been dropped onto this one.
+    TabReceived {
+        /// Id of the received tab.
+        id: Id,
+
+        /// Id of the area the tab has been sent from.
+        area: Id,
+
+        /// Where the tab has been put.
+        to: TabDestination,
+    },
+
+    /// A new surface (window) has been created.
+    ///
+    /// The new window may take the index of a window removed in the same frame, in which case
+    /// [`DockEvent::SurfaceRemoved`] with the same index comes first.
+    SurfaceCreated(SurfaceIndex),
+
+    /// A surface (window) has been removed.
+    SurfaceRemoved(SurfaceIndex),
+
+    /// The empty space of the tab bar of a leaf has been double-clicked, see
+    /// [`TabViewer::on_tab_bar_double_click`](crate::TabViewer::on_tab_bar_double_click).
+    TabBarDoubleClicked {
+        /// Surface containing the leaf.
+        surface: SurfaceIndex,
+
+        /// The leaf whose tab bar has been double-clicked.
+        node: NodeIndex,
+    },
+
+    /// The focused leaf has changed.
+    FocusChanged {
+        /// The leaf which was focused before the change.
+        from: Option<(SurfaceIndex, NodeIndex)>,
+
+        /// The leaf which is focused now.
+        to: Option<(SurfaceIndex, NodeIndex)>,
+    },
+}
@@ -0,0 +1,76 @@
+use egui::{Context, Rect, Ui};
+
+use crate::{DockArea, DockResponse, DockState, NodeIndex, SurfaceIndex, TabViewer};
+
+/// Rects of the nodes on the main surface of a [`DockArea`], computed with [`DockArea::layout`] before it's shown
+/// with [`paint`](Self::paint).
+///
+/// Windows are laid out only when they're painted. Changes made to the [`DockState`] while painting, like tabs
+/// being dropped or closed, may still move the nodes around.
+pub struct DockLayout<'tree, Tab> {
+    dock_area: DockArea<'tree, Tab>,
+    ctx: Context,
+    root_rect: Option<Rect>,
+}
+
+impl<'tree, Tab> DockLayout<'tree, Tab> {
+    pub(super) fn new(dock_area: DockArea<'tree, Tab>, ctx: Context) -> Self {
+        let root_rect = dock_area
+            .dock_state
+            .main_surface()
+            .root_node()
+            .and_then(|node| node.rect());
+        Self {
+            dock_area,
+            ctx,
+            root_rect,
+        }
+    }
+
+    /// Returns the rect of the node at `node_index` on the main surface, or [`None`] if there's no such node or it's
+    /// [`Empty`](crate::Node::Empty).
+    #[inline]
+    pub fn rect(&self, node_index: NodeIndex) -> Option<Rect> {
+        let tree = self.dock_area.dock_state.main_surface();
+        (node_index.0 < tree.len())
+            .then(|| tree[node_index].rect())
+            .flatten()
+    }
+
+    /// Iterates over the indices and rects of all non-empty nodes on the main surface, in the order of their indices.
+    pub fn iter(&self) -> impl Iterator<Item = (NodeIndex, Rect)> + '_ {
+        self.dock_area
+            .dock_state
+            .main_surface()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, node)| Some((NodeIndex(index), node.rect()?)))
+    }
+
+    /// Makes the node at `node_index` on the main surface take up `rect`, and lays out its children inside it.
+    ///
+    /// The rect is kept for as long as this [`DockLayout`] is painted, even if it's later changed by resizing the
+    /// parent node.
+    pub fn set_rect(&mut self, node_index: NodeIndex, rect: Rect) {
+        self.dock_area.layout_overrides.push((node_index, rect));
+        if let Some(root_rect) = self.root_rect {
+            let surf_index = SurfaceIndex::main();
+            self.dock_area.dock_state[surf_index][NodeIndex::root()].set_rect(root_rect);
+            self.dock_area
+                .layout_nodes(&self.ctx, surf_index, root_rect);
+        }
+    }
+
+    /// Returns the [`DockState`] being laid out.
+    #[inline]
+    pub fn dock_state(&self) -> &DockState<Tab> {
+        self.dock_area.dock_state
+    }
+
+    /// Shows the [`DockArea`] inside `ui` with the computed layout, like [`DockArea::show_inside`].
+    ///
+    /// `ui` should be the one passed to [`DockArea::layout`].
+    pub fn paint(self, ui: &mut Ui, tab_viewer: &mut impl TabViewer<Tab = Tab>) -> DockResponse {
+        self.dock_area.show_inside(ui, tab_viewer)
+    }
+}
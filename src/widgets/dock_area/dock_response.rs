@@ -0,0 +1,53 @@
+use egui::{Id, Response};
+
+use crate::{DockEvent, NodeIndex, SurfaceIndex};
+
+/// What happened in a [`DockArea`](crate::DockArea) during a frame, returned from
+/// [`DockArea::show`](crate::DockArea::show) and [`DockArea::show_inside`](crate::DockArea::show_inside).
+///
+/// # Examples
+///
+/// ```
+/// # use egui_dock::{DockArea, DockState};
+/// # struct TabViewer {}
+/// # impl egui_dock::TabViewer for TabViewer {
+/// #     type Tab = String;
+/// #     fn title(&mut self, tab: &mut Self::Tab) -> egui::WidgetText { (&*tab).into() }
+/// #     fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab) {}
+/// # }
+/// # let mut dock_state: DockState<String> = DockState::new(vec![]);
+/// # let mut tab_viewer = TabViewer {};
+/// # egui::__run_test_ctx(|ctx| {
+/// # egui::CentralPanel::default().show(ctx, |ui| {
+/// let dock_response = DockArea::new(&mut dock_state).show_inside(ui, &mut tab_viewer);
+/// for id in &dock_response.closed_tabs {
+///     println!("Closed tab {id:?}");
+/// }
+/// dock_response.response.context_menu(|ui| {
+///     if ui.button("Reset layout").clicked() {
+///         ui.close_menu();
+///     }
+/// });
+/// # });
+/// # });
+/// ```
+#[derive(Clone, Debug)]
+pub struct DockResponse {
+    /// The response of the whole area taken by the main surface, which can be clicked where no tab or other widget
+    /// is in the way, e.g. on the empty part of a tab bar.
+    pub response: Response,
+
+    /// The leaf under the pointer, including leaves of windows, or [`None`] if the pointer isn't over any leaf.
+    pub hovered_node: Option<(SurfaceIndex, NodeIndex)>,
+
+    /// The leaf which has the focus at the end of the frame, see
+    /// [`DockState::focused_leaf`](crate::DockState::focused_leaf).
+    pub focused_leaf: Option<(SurfaceIndex, NodeIndex)>,
+
+    /// Ids of the tabs closed during the frame, as returned from [`TabViewer::id`](crate::TabViewer::id).
+    pub closed_tabs: Vec<Id>,
+
+    /// The [`DockEvent`]s describing changes made to the [`DockState`](crate::DockState) during the frame, in the
+    /// order in which they were applied.
+    pub events: Vec<DockEvent>,
+}
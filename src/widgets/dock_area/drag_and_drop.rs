@@ -1,13 +1,50 @@
 use std::ops::BitOrAssign;
 
+use super::drag_group::ForeignTab;
 use crate::{
-    AllowedSplits, NodeIndex, Split, Style, SurfaceIndex, TabDestination, TabIndex, TabInsert,
+    dock_state::tree::explode_grid, AllowedSplits, ComputedLayout, DropPreviewFill, Node,
+    NodeIndex, Split, Style, SurfaceIndex, TabDestination, TabIndex, TabInsert, TabViewer, Tree,
 };
 use egui::{
     emath::inverse_lerp, vec2, Context, Id, LayerId, NumExt, Order, Painter, Pos2, Rect, Stroke,
     Ui, Vec2,
 };
 
+/// Specifies which of the drop target buttons shown on the overlay while dragging a tab is being drawn.
+///
+/// See [`TabViewer::overlay_button_ui`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OverlayButtonKind {
+    /// The button in the middle, which appends the tab to the hovered leaf.
+    Append,
+
+    /// One of the buttons around the middle, which splits the hovered leaf on the given side.
+    Split(Split),
+
+    /// The button in the middle while the [`DockArea::leaf_explode_modifiers`](crate::DockArea::leaf_explode_modifiers)
+    /// are held, which explodes the hovered leaf into a grid.
+    Explode,
+}
+
+/// The drop targets a dragged tab is offered on the hovered leaf.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct DropTargets {
+    /// Splits which are shown on the overlay.
+    pub splits: AllowedSplits,
+
+    /// Splits the dragged tab can create, the others are greyed out.
+    pub enabled_splits: AllowedSplits,
+
+    /// Whether the dragged tab can be docked into the hovered leaf or surface at all.
+    pub dock: bool,
+
+    /// Whether the dragged tab can become a new window.
+    pub new_window: bool,
+
+    /// Number of leaves the hovered leaf would be exploded into if the tab was dropped in its middle, if it would be.
+    pub explode: Option<usize>,
+}
+
 #[derive(Debug, Clone)]
 pub(super) struct HoverData {
     /// Rect of the hovered element.
@@ -18,6 +55,12 @@ pub(super) struct HoverData {
 
     /// If a tab title or the tab head is hovered, this is the rect of it.
     pub tab: Option<Rect>,
+
+    /// Rect of the body of the hovered leaf, which an appended tab would take.
+    pub body: Rect,
+
+    /// Ends of the line between tabs at which the dragged tab would be inserted, if a tab bar is hovered.
+    pub insert_caret: Option<[Pos2; 2]>,
 }
 
 /// Specifies the location of a tab on the tree, used when moving tabs.
@@ -25,6 +68,10 @@ pub(super) struct HoverData {
 pub(super) struct DragData {
     pub src: TreeComponent,
     pub rect: Rect,
+
+    /// Is some when the tab is dragged from another [`DockArea`](crate::DockArea), in which case `src` refers to
+    /// the layout of that area.
+    pub foreign: Option<ForeignTab>,
 }
 
 #[derive(Debug, Clone)]
@@ -84,35 +131,60 @@ fn draw_highlight_rect(rect: Rect, ui: &Ui, style: &Style) {
     );
 }
 
-// Draws one of the Tab drop destination icons inside `rect`, which one you get is specified by `is_top_bottom`.
-fn button_ui(
+// Draws one of the Tab drop destination icons inside `rect`, which one you get is specified by `kind`.
+// The drawing can be replaced by the user with `TabViewer::overlay_button_ui`.
+// Disabled buttons are greyed out and never highlighted, but they still report being hovered.
+#[allow(clippy::too_many_arguments)]
+fn button_ui<Tab>(
     rect: Rect,
     ui: &Ui,
     lock: &mut bool,
     mouse_pos: Pos2,
     style: &Style,
-    split: Option<Split>,
+    kind: OverlayButtonKind,
+    enabled: bool,
+    tab_viewer: &mut impl TabViewer<Tab = Tab>,
 ) -> bool {
+    let is_mouse_over = rect
+        .shrink(rect.width() * 0.1)
+        .expand(style.overlay.feel.interact_expansion)
+        .contains(mouse_pos);
+    let split = match kind {
+        OverlayButtonKind::Split(split) => Some(split),
+        OverlayButtonKind::Append | OverlayButtonKind::Explode => None,
+    };
+    let mut painter = make_overlay_painter(ui);
+    if !enabled {
+        painter.multiply_opacity(DISABLED_BUTTON_OPACITY);
+    }
+    let highlighted = enabled && is_mouse_over && !*lock;
+    if tab_viewer.overlay_button_ui(&painter, rect, kind, highlighted) {
+        lock.bitor_assign(is_mouse_over);
+        return is_mouse_over;
+    }
+
     let visuals = &style.overlay;
     let button_stroke = Stroke::new(1.0, visuals.button_color);
-    let painter = make_overlay_painter(ui);
     painter.rect_stroke(rect, 0.0, visuals.button_border_stroke);
     let rect = rect.shrink(rect.width() * 0.1);
     painter.rect_stroke(rect, 0.0, button_stroke);
     let rim = { Rect::from_two_pos(rect.min, rect.lerp_inside(vec2(1.0, 0.1))) };
     painter.rect(rim, 0.0, visuals.button_color, Stroke::NONE);
 
-    if let Some(split) = split {
+    let dashed_lines: &[Split] = match kind {
+        OverlayButtonKind::Split(split) => &[split],
+        // Lines through the middle both ways, like the grid the leaf is exploded into.
+        OverlayButtonKind::Explode => &[Split::Right, Split::Below],
+        OverlayButtonKind::Append => &[],
+    };
+    for &split in dashed_lines {
         for line in DASHED_LINE_ALPHAS.chunks(2) {
             let start = rect.lerp_inside(lerp_vec(split, line[0]));
             let end = rect.lerp_inside(lerp_vec(split, line[1]));
             painter.line_segment([start, end], button_stroke);
         }
     }
-    let is_mouse_over = rect
-        .expand(style.overlay.feel.interact_expansion)
-        .contains(mouse_pos);
-    if is_mouse_over && !*lock {
+    if highlighted {
         let vertical_alphas = vec2(1.0, 0.5);
         let horizontal_alphas = vec2(0.5, 1.0);
         let rect = match split {
@@ -134,10 +206,47 @@ fn button_ui(
     is_mouse_over
 }
 
+/// Returns the rects of the leaves a leaf taking `rect` would be exploded into, in the order of their tabs, the last
+/// one being where the dropped tab ends up.
+fn explode_preview_cells(rect: Rect, count: usize) -> Vec<Rect> {
+    let rows = explode_grid(count);
+    let row_height = rect.height() / rows.len() as f32;
+    let mut cells = Vec::with_capacity(count);
+    for (row, &columns) in rows.iter().enumerate() {
+        let column_width = rect.width() / columns as f32;
+        for column in 0..columns {
+            let min = rect.min + vec2(column as f32 * column_width, row as f32 * row_height);
+            cells.push(Rect::from_min_size(min, vec2(column_width, row_height)));
+        }
+    }
+    cells
+}
+
+/// Returns the rect of the overlay button of the given `kind` shown over the hovered `rect` while dragging a tab.
+pub(crate) fn overlay_button_rect(rect: Rect, style: &Style, kind: OverlayButtonKind) -> Rect {
+    let total_button_spacing = style.overlay.button_spacing * 2.0;
+    let rect = rect.shrink(style.overlay.button_spacing);
+    let shortest_side = ((rect.width() - total_button_spacing) / 3.0)
+        .min((rect.height() - total_button_spacing) / 3.0)
+        .min(style.overlay.max_button_size);
+    let offset = shortest_side + style.overlay.button_spacing;
+    let offset = match kind {
+        OverlayButtonKind::Append | OverlayButtonKind::Explode => Vec2::ZERO,
+        OverlayButtonKind::Split(Split::Above) => vec2(0.0, -offset),
+        OverlayButtonKind::Split(Split::Below) => vec2(0.0, offset),
+        OverlayButtonKind::Split(Split::Left) => vec2(-offset, 0.0),
+        OverlayButtonKind::Split(Split::Right) => vec2(offset, 0.0),
+    };
+    Rect::from_center_size(rect.center() + offset, Vec2::splat(shortest_side))
+}
+
 const DASHED_LINE_ALPHAS: [f32; 8] = [
     0.0625, 0.1875, 0.3125, 0.4375, 0.5625, 0.6875, 0.8125, 0.9375,
 ];
 
+/// Opacity of the overlay buttons of drop targets the dragged tab isn't allowed to be dropped onto.
+const DISABLED_BUTTON_OPACITY: f32 = 0.3;
+
 #[derive(PartialEq, Eq)]
 enum LockState {
     /// Lock is unlocked.
@@ -165,35 +274,42 @@ impl DragDropState {
         self.hover.tab.is_some()
     }
 
-    pub(super) fn resolve_icon_based(
+    pub(super) fn resolve_icon_based<Tab>(
         &mut self,
         ui: &Ui,
         style: &Style,
-        allowed_splits: AllowedSplits,
-        windows_allowed: bool,
+        targets: DropTargets,
         window_bounds: Rect,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
     ) -> Option<TabDestination> {
         assert!(!self.is_on_title_bar());
 
         draw_highlight_rect(self.hover.rect, ui, style);
         let mut hovering_buttons = false;
-        let total_button_spacing = style.overlay.button_spacing * 2.0;
-        let (rect, pointer) = (self.hover.rect, self.pointer);
-        let rect = rect.shrink(style.overlay.button_spacing);
-        let shortest_side = ((rect.width() - total_button_spacing) / 3.0)
-            .min((rect.height() - total_button_spacing) / 3.0)
-            .min(style.overlay.max_button_size);
-
-        let mut destination: Option<TabDestination> = windows_allowed
-            .then(|| TabDestination::Window(Rect::from_min_size(pointer, self.drag.rect.size())));
+        let pointer = self.pointer;
 
-        let center = rect.center();
-        let rect = Rect::from_center_size(center, Vec2::splat(shortest_side));
+        let mut destination: Option<TabDestination> = targets
+            .new_window
+            .then(|| TabDestination::Window(Rect::from_min_size(pointer, self.drag.rect.size())));
 
-        if button_ui(rect, ui, &mut hovering_buttons, pointer, style, None) {
+        let (center_kind, center_insert) = match targets.explode {
+            Some(_) => (OverlayButtonKind::Explode, TabInsert::Explode),
+            None => (OverlayButtonKind::Append, TabInsert::Append),
+        };
+        if button_ui(
+            overlay_button_rect(self.hover.rect, style, center_kind),
+            ui,
+            &mut hovering_buttons,
+            pointer,
+            style,
+            center_kind,
+            targets.dock,
+            tab_viewer,
+        ) {
             match self.hover.dst {
+                _ if !targets.dock => destination = None,
                 TreeComponent::Node(surface, node) => {
-                    destination = Some(TabDestination::Node(surface, node, TabInsert::Append))
+                    destination = Some(TabDestination::Node(surface, node, center_insert))
                 }
                 TreeComponent::Surface(surface) => {
                     destination = Some(TabDestination::EmptySurface(surface))
@@ -203,27 +319,29 @@ impl DragDropState {
         }
 
         for split in [Split::Below, Split::Right, Split::Above, Split::Left] {
-            match allowed_splits {
+            let enabled = targets.dock && targets.enabled_splits.allows(split);
+            match targets.splits {
                 AllowedSplits::TopBottomOnly if !split.is_top_bottom() => continue,
                 AllowedSplits::LeftRightOnly if !split.is_left_right() => continue,
                 AllowedSplits::None => continue,
                 _ => {
-                    let offset_value = shortest_side + style.overlay.button_spacing;
-                    let offset_vector = match split {
-                        Split::Above => vec2(0.0, -offset_value),
-                        Split::Below => vec2(0.0, offset_value),
-                        Split::Left => vec2(-offset_value, 0.0),
-                        Split::Right => vec2(offset_value, 0.0),
-                    };
                     if button_ui(
-                        Rect::from_center_size(center + offset_vector, Vec2::splat(shortest_side)),
+                        overlay_button_rect(
+                            self.hover.rect,
+                            style,
+                            OverlayButtonKind::Split(split),
+                        ),
                         ui,
                         &mut hovering_buttons,
                         pointer,
                         style,
-                        Some(split),
+                        OverlayButtonKind::Split(split),
+                        enabled,
+                        tab_viewer,
                     ) {
-                        if let TreeComponent::Node(surface, node) = self.hover.dst {
+                        if !enabled {
+                            destination = None;
+                        } else if let TreeComponent::Node(surface, node) = self.hover.dst {
                             destination =
                                 Some(TabDestination::Node(surface, node, TabInsert::Split(split)))
                         }
@@ -250,19 +368,38 @@ impl DragDropState {
         &mut self,
         ui: &Ui,
         style: &Style,
-        allowed_splits: AllowedSplits,
-        windows_allowed: bool,
+        targets: DropTargets,
         window_bounds: Rect,
     ) -> Option<TabDestination> {
-        // If windows are not allowed, any hover over a window is immediately disallowed.
-        if !windows_allowed && self.hover.dst.surface_address() != SurfaceIndex::main() {
-            return None;
+        let windows_allowed = targets.new_window;
+        let allowed_splits = targets.splits & targets.enabled_splits;
+
+        // If the tab can't be docked into the hovered leaf, it can only become a new window.
+        if !targets.dock {
+            let destination = windows_allowed.then(|| {
+                TabDestination::Window(Rect::from_min_size(self.pointer, self.drag.rect.size()))
+            });
+            if let Some(TabDestination::Window(rect)) = destination {
+                let rect = self.window_preview_rect(rect);
+                let rect_bounded = constrain_rect_to_area(ui, rect, window_bounds);
+                draw_preview_rect(rect_bounded, style.overlay.drop_preview.window, ui, style);
+            }
+            self.update_lock(LockState::Unlocked, style, ui.ctx());
+            return destination;
         }
         draw_highlight_rect(self.hover.rect, ui, style);
 
         // Deals with hovers over tab bar and tab titles.
         if let Some(rect) = self.hover.tab {
-            draw_drop_rect(rect, ui, style);
+            // Tabs of this area move apart to show where the dragged one would be inserted instead.
+            let opens_gap =
+                matches!(self.hover.dst, TreeComponent::Tab(..)) && self.drag.foreign.is_none();
+            let indicator = &style.overlay.tab_insert_indicator;
+            match self.hover.insert_caret {
+                _ if opens_gap => {}
+                Some(caret) if indicator.width > 0.0 => draw_insert_caret(caret, ui, style),
+                _ => draw_drop_rect(rect, ui, style),
+            }
             let target_lock_state = if rect.contains(self.pointer) {
                 LockState::SoftLock
             } else {
@@ -274,9 +411,8 @@ impl DragDropState {
 
         // Main cases, splits, window creations, etc.
         let (hover_rect, pointer) = (self.hover.rect, self.pointer);
-        let center = hover_rect.center();
 
-        let (tab_insertion, overlay_rect) = {
+        let tab_insertion = {
             // A reverse lerp of the pointers position relative to the hovered leaf rect.
             // Range is (-0.5, -0.5) to (0.5, 0.5)
             let a_pos = (Pos2::new(
@@ -296,11 +432,14 @@ impl DragDropState {
 
             // Find out what kind of tab insertion (if any) should be used to move this widget.
             if center_drop_rect.contains(a_pos) {
-                (Some(TabInsert::Append), Rect::EVERYTHING)
+                Some(match targets.explode {
+                    Some(_) => TabInsert::Explode,
+                    None => TabInsert::Append,
+                })
             } else if window_drop_rect.contains(a_pos) {
                 match windows_allowed {
-                    true => (None, Rect::NOTHING),
-                    false => (Some(TabInsert::Append), Rect::EVERYTHING),
+                    true => None,
+                    false => Some(TabInsert::Append),
                 }
             } else {
                 // Assessing if were above/below the two linear functions x-y=0 and -x-y=0 determines
@@ -313,27 +452,15 @@ impl DragDropState {
                 };
                 if a_pos == Pos2::ZERO {
                     match windows_allowed {
-                        true => (None, Rect::NOTHING),
-                        false => (Some(TabInsert::Append), Rect::EVERYTHING),
+                        true => None,
+                        false => Some(TabInsert::Append),
                     }
                 } else {
                     match (a_pos.x - a_pos.y > 0., -a_pos.x - a_pos.y > 0.) {
-                        (true, true) => (
-                            Some(TabInsert::Split(Split::Above)),
-                            Rect::everything_above(center.y),
-                        ),
-                        (false, true) => (
-                            Some(TabInsert::Split(Split::Left)),
-                            Rect::everything_left_of(center.x),
-                        ),
-                        (true, false) => (
-                            Some(TabInsert::Split(Split::Right)),
-                            Rect::everything_right_of(center.x),
-                        ),
-                        (false, false) => (
-                            Some(TabInsert::Split(Split::Below)),
-                            Rect::everything_below(center.y),
-                        ),
+                        (true, true) => Some(TabInsert::Split(Split::Above)),
+                        (false, true) => Some(TabInsert::Split(Split::Left)),
+                        (true, false) => Some(TabInsert::Split(Split::Right)),
+                        (false, false) => Some(TabInsert::Split(Split::Below)),
                     }
                 }
             }
@@ -354,10 +481,27 @@ impl DragDropState {
             Some(TabDestination::Window(rect)) => {
                 let rect = self.window_preview_rect(rect);
                 let rect_bounded = constrain_rect_to_area(ui, rect, window_bounds);
-                draw_window_rect(rect_bounded, ui, style);
+                draw_preview_rect(rect_bounded, style.overlay.drop_preview.window, ui, style);
             }
+            // Splits are drawn by the caller, which knows how much space the dropped tab takes.
+            Some(TabDestination::Node(_, _, TabInsert::Split(_))) => {}
+            Some(TabDestination::Node(_, _, TabInsert::Explode)) => {
+                let cells = explode_preview_cells(hover_rect, targets.explode.unwrap_or(1));
+                if let Some((last, others)) = cells.split_last() {
+                    for &cell in others {
+                        draw_preview_rect(cell, DropPreviewFill::Stroked, ui, style);
+                    }
+                    draw_preview_rect(*last, style.overlay.drop_preview.append, ui, style);
+                }
+            }
+            // Appended tabs are shown in the body of the leaf, below its tab bar.
             Some(_) => {
-                draw_drop_rect(hover_rect.intersect(overlay_rect), ui, style);
+                draw_preview_rect(
+                    self.hover.body,
+                    style.overlay.drop_preview.append,
+                    ui,
+                    style,
+                );
             }
             None => (),
         }
@@ -420,14 +564,38 @@ const fn lerp_vec(split: Split, alpha: f32) -> Vec2 {
 
 // Draws a filled rect describing where a tab will be dropped.
 #[inline(always)]
-fn draw_drop_rect(rect: Rect, ui: &Ui, style: &Style) {
+pub(super) fn draw_drop_rect(rect: Rect, ui: &Ui, style: &Style) {
     let painter = make_overlay_painter(ui);
     painter.rect_filled(rect, 0.0, style.overlay.selection_color);
 }
 
+// Draws a rect describing where a tab will be dropped, filled or stroked.
+pub(super) fn draw_preview_rect(rect: Rect, fill: DropPreviewFill, ui: &Ui, style: &Style) {
+    match fill {
+        DropPreviewFill::Filled => draw_drop_rect(rect, ui, style),
+        DropPreviewFill::Stroked => {
+            let width = style.overlay.selection_stroke_width;
+            draw_window_rect(rect.shrink(width * 0.5), ui, style);
+        }
+    }
+}
+
+// Draws a caret between tabs describing where a tab will be inserted.
+fn draw_insert_caret([start, end]: [Pos2; 2], ui: &Ui, style: &Style) {
+    let indicator = &style.overlay.tab_insert_indicator;
+    let rect = Rect::from_two_pos(start, end);
+    let rect = if start.x == end.x {
+        rect.expand2(vec2(indicator.width / 2.0, 0.0))
+    } else {
+        rect.expand2(vec2(0.0, indicator.width / 2.0))
+    };
+    let painter = make_overlay_painter(ui);
+    painter.rect_filled(rect, indicator.rounding, indicator.color);
+}
+
 // Draws a stroked rect describing where a tab will be dropped.
 #[inline(always)]
-fn draw_window_rect(rect: Rect, ui: &Ui, style: &Style) {
+pub(super) fn draw_window_rect(rect: Rect, ui: &Ui, style: &Style) {
     let painter = make_overlay_painter(ui);
     painter.rect_stroke(
         rect,
@@ -439,6 +607,115 @@ fn draw_window_rect(rect: Rect, ui: &Ui, style: &Style) {
     );
 }
 
+/// Returns how much of the area of the destination node `tab` takes when it's dropped onto one of its splits.
+pub(super) fn drop_split_fraction<Tab>(
+    tab: &mut Tab,
+    destination: TabDestination,
+    style: &Style,
+    tab_viewer: &mut impl TabViewer<Tab = Tab>,
+) -> f32 {
+    let fraction = match destination {
+        TabDestination::Node(_, _, TabInsert::Split(split)) => {
+            tab_viewer.preferred_split_fraction(tab, split)
+        }
+        _ => None,
+    };
+    fraction
+        .unwrap_or(style.overlay.feel.drop_split_fraction)
+        .clamp(0.0, 1.0)
+}
+
+/// Returns the part of `rect` which a tab dropped onto its `split` takes, when given `fraction` of it.
+pub(super) fn split_drop_rect(rect: Rect, split: Split, fraction: f32) -> Rect {
+    match split {
+        Split::Left => rect.with_max_x(rect.min.x + rect.width() * fraction),
+        Split::Right => rect.with_min_x(rect.max.x - rect.width() * fraction),
+        Split::Above => rect.with_max_y(rect.min.y + rect.height() * fraction),
+        Split::Below => rect.with_min_y(rect.max.y - rect.height() * fraction),
+    }
+}
+
+/// Lays out the leaves of `tree` as they would be after the dragged tab is dropped onto the `split` of `node`. In the
+/// returned tree, only the dropped tab is `true`.
+///
+/// `src` is the location of the dragged tab when it's moved out of this same tree, in which case its leaf shrinks or
+/// disappears along with it. The dropped tab takes `fraction` of the area of `node`.
+fn split_preview_layout<Tab>(
+    tree: &Tree<Tab>,
+    src: Option<(NodeIndex, TabIndex)>,
+    (node, split, fraction): (NodeIndex, Split, f32),
+    ui: &Ui,
+    style: &Style,
+) -> Option<(Tree<bool>, ComputedLayout)> {
+    let root_rect = tree.root_node().and_then(Node::rect)?;
+
+    // Mirrors `DockState::move_tab` on a copy of the tree, where only the dropped tab is `true`.
+    let mut preview = tree.map_tabs(|_| false);
+    if let Some((src_node, src_tab)) = src {
+        preview[src_node].remove_tab(src_tab);
+    }
+    preview.split(
+        node,
+        split,
+        split.fraction_for_new_node(fraction),
+        Node::leaf(true),
+    );
+    if let Some((src_node, _)) = src {
+        if preview[src_node].is_leaf() && preview[src_node].tabs_count() == 0 {
+            preview.remove_leaf(src_node);
+        }
+    }
+
+    let layout = preview.compute_layout(root_rect, style, ui.ctx().pixels_per_point());
+    Some((preview, layout))
+}
+
+/// Returns the rect of the leaf which the dragged tab would end up in after it's dropped onto the `split` of `node`,
+/// taking the separators and padding into account. See [`split_preview_layout`].
+pub(super) fn split_preview_rect<Tab>(
+    tree: &Tree<Tab>,
+    src: Option<(NodeIndex, TabIndex)>,
+    split: (NodeIndex, Split, f32),
+    ui: &Ui,
+    style: &Style,
+) -> Option<Rect> {
+    let (preview, layout) = split_preview_layout(tree, src, split, ui, style)?;
+    let rect = layout.iter().find_map(|(node_index, rect)| {
+        let tabs = preview[node_index].tabs()?;
+        tabs.contains(&true).then_some(rect)
+    });
+    rect
+}
+
+/// Draws the leaves of `tree` laid out as they would be after the dragged tab is dropped onto the `split` of `node`,
+/// filling the leaf which the tab would end up in. See [`split_preview_layout`].
+pub(super) fn draw_split_preview<Tab>(
+    tree: &Tree<Tab>,
+    src: Option<(NodeIndex, TabIndex)>,
+    split: (NodeIndex, Split, f32),
+    ui: &Ui,
+    style: &Style,
+) {
+    let Some((preview, layout)) = split_preview_layout(tree, src, split, ui, style) else {
+        return;
+    };
+    let painter = make_overlay_painter(ui);
+    let stroke = Stroke::new(
+        style.overlay.selection_stroke_width,
+        style.overlay.selection_color,
+    );
+    for (node_index, rect) in layout.iter() {
+        let Node::Leaf { tabs, .. } = &preview[node_index] else {
+            continue;
+        };
+        if tabs.contains(&true) {
+            painter.rect_filled(rect, 0.0, style.overlay.selection_color);
+        } else {
+            painter.rect_stroke(rect.shrink(stroke.width * 0.5), 0.0, stroke);
+        }
+    }
+}
+
 /// An adapted version of the [`egui::Area`]s code for restricting an area rect to a bound.
 fn constrain_rect_to_area(ui: &Ui, rect: Rect, mut bounds: Rect) -> Rect {
     if rect.width() > bounds.width() {
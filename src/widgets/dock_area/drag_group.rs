@@ -0,0 +1,245 @@
+use std::sync::{Arc, Mutex};
+
+use egui::{Context, Id};
+
+use crate::{DockArea, DockEvent, NodeIndex, SurfaceIndex, TabDestination, TabIndex, TabViewer};
+
+use super::drag_and_drop::{drop_split_fraction, DragData, TreeComponent};
+
+/// Connects a [`DockArea`] to the other areas it can exchange tabs with.
+///
+/// Tabs are handed over through the [`Context`], which requires them to be [`Send`], so the functions doing it are
+/// created where that bound is known to hold.
+pub(super) struct DragGroup<Tab> {
+    pub(super) id: Id,
+    deliver: fn(&Context, Id, Delivery<Tab>),
+    collect: fn(&Context, Id) -> Option<Delivery<Tab>>,
+}
+
+impl<Tab: Send + 'static> DragGroup<Tab> {
+    pub(super) fn new(id: Id) -> Self {
+        Self {
+            id,
+            deliver: |ctx, key, delivery| {
+                let slot = Arc::new(Mutex::new(Some(delivery)));
+                ctx.data_mut(|d| d.insert_temp::<DeliverySlot<Tab>>(key, slot));
+            },
+            collect: |ctx, key| {
+                let slot = ctx.data_mut(|d| d.remove_temp::<DeliverySlot<Tab>>(key))?;
+                let delivery = slot.lock().ok()?.take();
+                delivery
+            },
+        }
+    }
+}
+
+type DeliverySlot<Tab> = Arc<Mutex<Option<Delivery<Tab>>>>;
+
+/// A tab taken out of its [`DockArea`], waiting to be picked up by the area it has been dropped onto.
+pub(super) struct Delivery<Tab> {
+    tab: Tab,
+    source: Id,
+    destination: TabDestination,
+}
+
+/// A tab being dragged from another [`DockArea`] of the same drag group.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct ForeignTab {
+    /// Id of the area the tab is being dragged from.
+    pub area: Id,
+
+    /// Id of the tab from [`TabViewer::id`].
+    pub tab_id: Id,
+
+    pub allowed_in_windows: bool,
+}
+
+/// A tab being dragged, shared with the other areas of the drag group.
+#[derive(Clone, Debug)]
+struct DraggedTab {
+    drag: DragData,
+    foreign: ForeignTab,
+    pass: u64,
+}
+
+/// A request from the area a tab has been dropped onto to the area it has been dragged from.
+#[derive(Clone, Debug)]
+struct TransferRequest {
+    source: (Id, SurfaceIndex, NodeIndex, TabIndex),
+    tab_id: Id,
+    target: Id,
+    destination: TabDestination,
+}
+
+impl<Tab> DockArea<'_, Tab> {
+    /// Shares the tab being dragged in this area with the other areas of the drag group.
+    pub(super) fn publish_dragged_tab(
+        &mut self,
+        ctx: &Context,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let Some(group) = self.drag_group.as_ref().map(|group| group.id) else {
+            return;
+        };
+        let Some(drag) = ctx.data(|d| d.get_temp::<Option<DragData>>(self.id.with("drag_data")))
+        else {
+            return;
+        };
+        let Some(
+            drag @ DragData {
+                src: TreeComponent::Tab(surface, node, tab),
+                foreign: None,
+                ..
+            },
+        ) = drag
+        else {
+            return;
+        };
+        let Some(tab) = self.dock_state[surface][node]
+            .tabs_mut()
+            .and_then(|tabs| tabs.get_mut(tab.0))
+        else {
+            return;
+        };
+        let dragged = DraggedTab {
+            drag,
+            foreign: ForeignTab {
+                area: self.id,
+                tab_id: tab_viewer.id(tab),
+                allowed_in_windows: tab_viewer.allowed_in_windows(tab),
+            },
+            pass: ctx.cumulative_pass_nr(),
+        };
+        ctx.data_mut(|d| d.insert_temp(group.with("dragged_tab"), dragged));
+    }
+
+    /// Returns the tab currently dragged in another area of the drag group, if any.
+    pub(super) fn foreign_drag_data(&self, ctx: &Context) -> Option<DragData> {
+        let group = self.drag_group.as_ref()?.id;
+        let dragged = ctx.data(|d| d.get_temp::<DraggedTab>(group.with("dragged_tab")))?;
+        let is_foreign = dragged.foreign.area != self.id;
+        let is_current = dragged.pass + 1 >= ctx.cumulative_pass_nr();
+        (is_foreign && is_current).then_some(DragData {
+            foreign: Some(dragged.foreign),
+            ..dragged.drag
+        })
+    }
+
+    /// Asks the area a foreign tab is dragged from to hand it over to this area.
+    pub(super) fn request_transfer(
+        &self,
+        ctx: &Context,
+        foreign: ForeignTab,
+        (surface, node, tab): (SurfaceIndex, NodeIndex, TabIndex),
+        destination: TabDestination,
+    ) {
+        let Some(group) = self.drag_group.as_ref().map(|group| group.id) else {
+            return;
+        };
+        let request = TransferRequest {
+            source: (foreign.area, surface, node, tab),
+            tab_id: foreign.tab_id,
+            target: self.id,
+            destination,
+        };
+        ctx.data_mut(|d| {
+            d.remove::<DraggedTab>(group.with("dragged_tab"));
+            d.insert_temp(group.with("transfer_request"), request);
+        });
+    }
+
+    /// Takes out the tab another area of the drag group has asked for and hands it over.
+    pub(super) fn send_requested_tab(
+        &mut self,
+        ctx: &Context,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let Some(group) = &self.drag_group else {
+            return;
+        };
+        let (group, deliver) = (group.id, group.deliver);
+        let key = group.with("transfer_request");
+        let Some(request) = ctx.data(|d| d.get_temp::<TransferRequest>(key)) else {
+            return;
+        };
+        let (area, surface, node, tab) = request.source;
+        if area != self.id {
+            return;
+        }
+        ctx.data_mut(|d| d.remove::<TransferRequest>(key));
+
+        // Make sure the layout hasn't changed since the tab was dropped.
+        let tab_id = self
+            .dock_state
+            .get_surface_mut(surface)
+            .and_then(|surface| surface.node_tree_mut())
+            .filter(|tree| node.0 < tree.len())
+            .and_then(|tree| tree[node].tabs_mut())
+            .and_then(|tabs| tabs.get_mut(tab.0))
+            .map(|tab| tab_viewer.id(tab));
+        if tab_id != Some(request.tab_id) {
+            return;
+        }
+
+        let surfaces = self.surface_snapshot();
+        let Some(removed) = self.dock_state.remove_tab((surface, node, tab)) else {
+            return;
+        };
+        self.events.push(DockEvent::TabSent {
+            id: request.tab_id,
+            from: (surface, node, tab),
+            area: request.target,
+        });
+        self.record_surface_changes(&surfaces);
+
+        deliver(
+            ctx,
+            group_delivery_key(group, request.target),
+            Delivery {
+                tab: removed,
+                source: self.id,
+                destination: request.destination,
+            },
+        );
+    }
+
+    /// Puts a tab handed over by another area of the drag group in its destination.
+    pub(super) fn receive_transferred_tab(
+        &mut self,
+        ctx: &Context,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let Some(group) = &self.drag_group else {
+            return;
+        };
+        let Some(Delivery {
+            mut tab,
+            source,
+            destination,
+        }) = (group.collect)(ctx, group_delivery_key(group.id, self.id))
+        else {
+            return;
+        };
+
+        let id = tab_viewer.id(&mut tab);
+        let style = self.style.as_ref().unwrap();
+        let fraction = drop_split_fraction(&mut tab, destination, style, tab_viewer);
+        let surfaces = self.surface_snapshot();
+        self.dock_state
+            .insert_tab_with_split_fraction(tab, destination, fraction);
+        self.events.push(DockEvent::TabReceived {
+            id,
+            area: source,
+            to: destination,
+        });
+        self.record_surface_changes(&surfaces);
+        if let TabDestination::Node(surface, node, _) = destination {
+            self.new_focused = Some((surface, node));
+        }
+    }
+}
+
+#[inline(always)]
+fn group_delivery_key(group: Id, target: Id) -> Id {
+    group.with(("delivery", target))
+}
@@ -0,0 +1,27 @@
+/// Names a group of tabs within a leaf, returned from [`TabViewer::group_of`](crate::TabViewer::group_of).
+///
+/// Leaves with grouped tabs show a row with the title of each group next to their tab bar, and only the tabs of the
+/// selected group in the tab bar itself.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct GroupId(pub String);
+
+impl GroupId {
+    /// Returns the name of the group.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for GroupId {
+    fn from(name: &str) -> Self {
+        Self(name.to_owned())
+    }
+}
+
+impl From<String> for GroupId {
+    fn from(name: String) -> Self {
+        Self(name)
+    }
+}
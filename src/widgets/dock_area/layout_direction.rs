@@ -0,0 +1,19 @@
+/// The horizontal direction in which the contents of tab bars are laid out.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum LayoutDirection {
+    #[default]
+    /// Tabs start at the left edge of the tab bar, buttons for the whole leaf are shown on the right.
+    LeftToRight,
+
+    /// Tabs start at the right edge of the tab bar, buttons for the whole leaf are shown on the left.
+    /// Suitable for right-to-left locales.
+    RightToLeft,
+}
+
+impl LayoutDirection {
+    /// Returns `true` if the layout is [`RightToLeft`](LayoutDirection::RightToLeft).
+    #[inline(always)]
+    pub const fn is_right_to_left(self) -> bool {
+        matches!(self, LayoutDirection::RightToLeft)
+    }
+}
@@ -4,43 +4,105 @@
 mod show;
 
 // Various components of the `DockArea` which is used when rendering
+mod allowed_destinations;
 mod allowed_splits;
+mod close_activation_policy;
+mod dock_event;
+mod dock_layout;
+mod dock_response;
 mod drag_and_drop;
+mod drag_group;
+mod group_id;
+mod layout_direction;
+mod nesting;
 mod state;
+mod tab_action;
 mod tab_removal;
 
-use crate::{dock_state::DockState, NodeIndex, Style, SurfaceIndex, TabIndex};
+use crate::{
+    dock_state::DockState, NodeIndex, Split, Style, SurfaceIndex, TabBarPosition, TabIndex,
+};
+pub use allowed_destinations::AllowedDestinations;
 pub use allowed_splits::AllowedSplits;
+pub use close_activation_policy::CloseActivationPolicy;
+pub use dock_event::DockEvent;
+pub use dock_layout::DockLayout;
+pub use dock_response::DockResponse;
+#[cfg(feature = "testing")]
+pub(crate) use drag_and_drop::overlay_button_rect;
+pub use drag_and_drop::OverlayButtonKind;
+use drag_group::DragGroup;
+pub use group_id::GroupId;
+pub use layout_direction::LayoutDirection;
+pub use tab_action::TabAction;
 use tab_removal::TabRemoval;
 
-use egui::{emath::*, Id, Modifiers};
+use egui::{emath::*, Context, DragAndDrop, Id, LayerId, Modifiers};
+use std::any::Any;
+
+/// Attaches a drag and drop payload made from a tab to the context.
+type ExternalDragPayload<'tree, Tab> = Box<dyn Fn(&Tab, &Context) + 'tree>;
 
 /// Displays a [`DockState`] in `egui`.
 pub struct DockArea<'tree, Tab> {
+    /// [`Id::NULL`] until it's derived from the [`Ui`](egui::Ui) the area is shown in, unless set with [`Self::id`].
     id: Id,
+    id_salt: Id,
     dock_state: &'tree mut DockState<Tab>,
     style: Option<Style>,
     show_add_popup: bool,
     show_add_buttons: bool,
+    show_split_buttons: bool,
+    show_tab_search_buttons: bool,
     show_close_buttons: bool,
     tab_context_menus: bool,
+    selectable_tabs: bool,
+    equalize_in_context_menu: bool,
     draggable_tabs: bool,
-    show_tab_name_on_hover: bool,
+    allow_tab_copy: bool,
+    tab_copy_modifiers: Modifiers,
+    allow_leaf_explode: bool,
+    leaf_explode_modifiers: Modifiers,
+    locked: bool,
+    show_tab_tooltips: bool,
     show_window_close_buttons: bool,
     show_window_collapse_buttons: bool,
     show_leaf_close_all_buttons: bool,
     show_leaf_collapse_buttons: bool,
+    collapse_into_side_strips: bool,
+    collect_metrics: bool,
     show_secondary_button_hint: bool,
     secondary_button_modifiers: Modifiers,
     secondary_button_on_modifier: bool,
     secondary_button_context_menu: bool,
     allowed_splits: AllowedSplits,
+    layout_direction: LayoutDirection,
+    close_activation_policy: CloseActivationPolicy,
+    tab_middle_click_action: TabAction<'tree, Tab>,
+    tab_double_click_action: TabAction<'tree, Tab>,
+    native_windows: bool,
+    window_snapping: bool,
+    window_taskbar: bool,
     window_bounds: Option<Rect>,
+    external_drag_payload: Option<ExternalDragPayload<'tree, Tab>>,
+    drag_group: Option<DragGroup<Tab>>,
 
     to_remove: Vec<TabRemoval>,
     to_detach: Vec<(SurfaceIndex, NodeIndex, TabIndex)>,
+    to_dock: Vec<(SurfaceIndex, Split)>,
+    to_split: Vec<(SurfaceIndex, NodeIndex, Split)>,
+    to_detach_leaf: Vec<(SurfaceIndex, NodeIndex)>,
+    detach_selection: bool,
     new_focused: Option<(SurfaceIndex, NodeIndex)>,
     tab_hover_rect: Option<(Rect, TabIndex)>,
+    hidden_tabs: Vec<bool>,
+    tab_insert_target: Option<(SurfaceIndex, NodeIndex, TabIndex)>,
+    close_button_rects: Vec<((SurfaceIndex, NodeIndex, TabIndex), Rect)>,
+    surface_layers: Vec<(SurfaceIndex, LayerId)>,
+    raised_layers: Vec<LayerId>,
+    tab_bar_position: TabBarPosition,
+    events: Vec<DockEvent>,
+    layout_overrides: Vec<(NodeIndex, Rect)>,
 }
 
 // Builder
@@ -49,39 +111,88 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
     #[inline(always)]
     pub fn new(tree: &'tree mut DockState<Tab>) -> DockArea<'tree, Tab> {
         Self {
-            id: Id::new("egui_dock::DockArea"),
+            id: Id::NULL,
+            id_salt: Id::new("egui_dock::DockArea"),
             dock_state: tree,
             style: None,
             show_add_popup: false,
             show_add_buttons: false,
+            show_split_buttons: false,
+            show_tab_search_buttons: false,
             show_close_buttons: true,
             tab_context_menus: true,
+            selectable_tabs: false,
+            equalize_in_context_menu: false,
             draggable_tabs: true,
-            show_tab_name_on_hover: false,
+            allow_tab_copy: false,
+            tab_copy_modifiers: Modifiers::COMMAND,
+            allow_leaf_explode: false,
+            leaf_explode_modifiers: Modifiers::ALT,
+            locked: false,
+            show_tab_tooltips: false,
             allowed_splits: AllowedSplits::default(),
+            layout_direction: LayoutDirection::default(),
+            close_activation_policy: CloseActivationPolicy::default(),
+            tab_middle_click_action: TabAction::Close,
+            tab_double_click_action: TabAction::None,
             to_remove: Vec::new(),
             to_detach: Vec::new(),
+            to_dock: Vec::new(),
+            to_split: Vec::new(),
+            to_detach_leaf: Vec::new(),
+            detach_selection: false,
             new_focused: None,
             tab_hover_rect: None,
+            hidden_tabs: Vec::new(),
+            tab_insert_target: None,
+            close_button_rects: Vec::new(),
+            surface_layers: Vec::new(),
+            raised_layers: Vec::new(),
+            tab_bar_position: TabBarPosition::default(),
+            events: Vec::new(),
             window_bounds: None,
+            native_windows: false,
+            window_snapping: false,
+            window_taskbar: false,
+            external_drag_payload: None,
+            drag_group: None,
             show_window_close_buttons: true,
             show_window_collapse_buttons: true,
             show_leaf_close_all_buttons: true,
             show_leaf_collapse_buttons: true,
+            collapse_into_side_strips: false,
+            collect_metrics: false,
             show_secondary_button_hint: true,
             secondary_button_modifiers: Modifiers::SHIFT,
             secondary_button_on_modifier: true,
             secondary_button_context_menu: true,
+            layout_overrides: Vec::new(),
         }
     }
 
-    /// Sets the [`DockArea`] ID. Useful if you have more than one [`DockArea`].
+    /// Sets the [`DockArea`] ID. Useful if you have more than one [`DockArea`] in the same [`Ui`](egui::Ui), or
+    /// want the state of the area to stay the same when it's shown in a different one.
+    ///
+    /// By default it's derived from the ID of the [`Ui`](egui::Ui) the area is shown in and the
+    /// [`id_salt`](Self::id_salt), so areas shown in different `Ui`s, e.g. in a tab of another area, get different
+    /// IDs. Areas sharing an ID share their state, which is reported with a warning painted over the area when
+    /// [`egui::Options::warn_on_id_clash`] is set, as it is in debug builds.
     #[inline(always)]
     pub fn id(mut self, id: Id) -> Self {
         self.id = id;
         self
     }
 
+    /// Sets the source of the [`DockArea`] ID, which is combined with the ID of the [`Ui`](egui::Ui) the area is shown
+    /// in, e.g. to tell apart several areas shown in the same `Ui`. It has no effect if the ID is set with
+    /// [`id`](Self::id).
+    /// By default it's `"egui_dock::DockArea"`.
+    #[inline(always)]
+    pub fn id_salt(mut self, id_salt: impl std::hash::Hash) -> Self {
+        self.id_salt = Id::new(id_salt);
+        self
+    }
+
     /// Sets the look and feel of the [`DockArea`].
     #[inline(always)]
     pub fn style(mut self, style: Style) -> Self {
@@ -103,6 +214,25 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
         self
     }
 
+    /// Shows or hides the buttons on the edges of the body of the hovered leaf, which split the leaf in place
+    /// without dragging a tab. What goes into the new leaf is decided by
+    /// [`TabViewer::on_split`](crate::TabViewer::on_split). Only the splits allowed by
+    /// [`allowed_splits`](Self::allowed_splits) and
+    /// [`TabViewer::allowed_splits_for_node`](crate::TabViewer::allowed_splits_for_node) get a button.
+    /// By default it's `false`.
+    pub fn show_split_buttons(mut self, show_split_buttons: bool) -> Self {
+        self.show_split_buttons = show_split_buttons;
+        self
+    }
+
+    /// Shows or hides the buttons which open a popup for searching through the tabs of a leaf by their titles.
+    /// Choosing a tab in the popup makes it active and scrolls the tab bar to it.
+    /// By default it's `false`.
+    pub fn show_tab_search_buttons(mut self, show_tab_search_buttons: bool) -> Self {
+        self.show_tab_search_buttons = show_tab_search_buttons;
+        self
+    }
+
     /// Shows or hides the tab close buttons.
     /// By default it's `true`.
     pub fn show_close_buttons(mut self, show_close_buttons: bool) -> Self {
@@ -117,6 +247,23 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
         self
     }
 
+    /// Whether tabs can be selected by Ctrl+clicking (Cmd+clicking on macOS) their titles, see
+    /// [`DockState::selected_tabs`]. When several tabs are selected, the context menus of the selected tabs have a
+    /// button which ejects all of them into a single window.
+    /// By default it's `false`.
+    pub fn selectable_tabs(mut self, selectable_tabs: bool) -> Self {
+        self.selectable_tabs = selectable_tabs;
+        self
+    }
+
+    /// Whether tab context menus have a button giving all leaves of the tab's surface the same area,
+    /// see [`Tree::equalize_fractions`](crate::Tree::equalize_fractions).
+    /// By default it's `false`.
+    pub fn equalize_in_context_menu(mut self, equalize_in_context_menu: bool) -> Self {
+        self.equalize_in_context_menu = equalize_in_context_menu;
+        self
+    }
+
     /// Whether tabs can be dragged between nodes and reordered on the tab bar.
     /// By default it's `true`.
     pub fn draggable_tabs(mut self, draggable_tabs: bool) -> Self {
@@ -124,10 +271,73 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
         self
     }
 
+    /// Whether a dragged tab is copied instead of moved when it's dropped while holding the
+    /// [`tab_copy_modifiers`](Self::tab_copy_modifiers).
+    ///
+    /// Tabs are copied with [`TabViewer::clone_tab`](crate::TabViewer::clone_tab), and tabs which it doesn't copy
+    /// are moved as usual.
+    /// By default it's `false`.
+    pub fn allow_tab_copy(mut self, allow_tab_copy: bool) -> Self {
+        self.allow_tab_copy = allow_tab_copy;
+        self
+    }
+
+    /// The key combination which makes dropping a tab copy it, if [`allow_tab_copy`](Self::allow_tab_copy) is set.
+    /// By default it's [`Modifiers::COMMAND`].
+    pub fn tab_copy_modifiers(mut self, tab_copy_modifiers: Modifiers) -> Self {
+        self.tab_copy_modifiers = tab_copy_modifiers;
+        self
+    }
+
+    /// Whether dropping a tab in the middle of a leaf while holding the
+    /// [`leaf_explode_modifiers`](Self::leaf_explode_modifiers) splits the leaf into an even grid, with each of its
+    /// tabs and the dropped one in a leaf of its own (see [`TabInsert::Explode`](crate::TabInsert::Explode)).
+    ///
+    /// This is only offered on leaves which can be split in all directions.
+    /// By default it's `false`.
+    pub fn allow_leaf_explode(mut self, allow_leaf_explode: bool) -> Self {
+        self.allow_leaf_explode = allow_leaf_explode;
+        self
+    }
+
+    /// The key combination which makes dropping a tab explode the leaf it's dropped on, if
+    /// [`allow_leaf_explode`](Self::allow_leaf_explode) is set.
+    /// By default it's [`Modifiers::ALT`].
+    pub fn leaf_explode_modifiers(mut self, leaf_explode_modifiers: Modifiers) -> Self {
+        self.leaf_explode_modifiers = leaf_explode_modifiers;
+        self
+    }
+
+    /// Locks the layout, so that the user can only switch between tabs, but not change the layout in any way: tabs
+    /// can't be dragged, separators can't be resized, the close, add and collapse buttons are hidden and the tab
+    /// context menu doesn't let the user close or eject tabs.
+    ///
+    /// This overrides [`draggable_tabs`](Self::draggable_tabs), [`show_close_buttons`](Self::show_close_buttons),
+    /// [`show_add_buttons`](Self::show_add_buttons), [`show_split_buttons`](Self::show_split_buttons),
+    /// [`show_leaf_close_all_buttons`](Self::show_leaf_close_all_buttons) and
+    /// [`show_leaf_collapse_buttons`](Self::show_leaf_collapse_buttons). Tabs dragged from other areas of the
+    /// same [`drag_group`](Self::drag_group) can't be dropped onto a locked area either.
+    ///
+    /// By default it's `false`.
+    pub fn locked(mut self, locked: bool) -> Self {
+        self.locked = locked;
+        self
+    }
+
     /// Whether tabs show their name when hovered over them.
     /// By default it's `false`.
+    #[deprecated = "consider using `show_tab_tooltips` instead."]
     pub fn show_tab_name_on_hover(mut self, show_tab_name_on_hover: bool) -> Self {
-        self.show_tab_name_on_hover = show_tab_name_on_hover;
+        self.show_tab_tooltips = show_tab_name_on_hover;
+        self
+    }
+
+    /// Whether tabs show a tooltip drawn with [`TabViewer::tab_tooltip_ui`](crate::TabViewer::tab_tooltip_ui) when
+    /// hovered over them, which is their name unless it's overridden.
+    /// By default it's `false`.
+    #[inline(always)]
+    pub fn show_tab_tooltips(mut self, show_tab_tooltips: bool) -> Self {
+        self.show_tab_tooltips = show_tab_tooltips;
         self
     }
 
@@ -138,6 +348,97 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
         self
     }
 
+    /// In which direction the contents of tab bars are laid out. With [`LayoutDirection::RightToLeft`] the whole
+    /// tab bar is mirrored, which is suitable for right-to-left locales. Tab bars on the sides of leaves (see
+    /// [`TabBarStyle::position`](crate::TabBarStyle::position)) aren't affected.
+    /// By default it's [`LayoutDirection::LeftToRight`].
+    pub fn layout_direction(mut self, layout_direction: LayoutDirection) -> Self {
+        self.layout_direction = layout_direction;
+        self
+    }
+
+    /// Which tab of a leaf becomes active after its active tab is closed.
+    /// By default it's [`CloseActivationPolicy::MostRecentlyUsed`].
+    pub fn on_close_activate(mut self, policy: CloseActivationPolicy) -> Self {
+        self.close_activation_policy = policy;
+        self
+    }
+
+    /// What happens when a tab is clicked with the middle mouse button.
+    /// By default it's [`TabAction::Close`].
+    pub fn tab_middle_click_action(mut self, action: TabAction<'tree, Tab>) -> Self {
+        self.tab_middle_click_action = action;
+        self
+    }
+
+    /// What happens when a tab is double-clicked, after it's been activated by the first click.
+    /// By default it's [`TabAction::None`].
+    pub fn tab_double_click_action(mut self, action: TabAction<'tree, Tab>) -> Self {
+        self.tab_double_click_action = action;
+        self
+    }
+
+    /// Makes dragged tabs carry a payload of egui's drag and drop API, created from the dragged tab with `payload`.
+    ///
+    /// This lets other widgets accept tabs dropped onto them, e.g. with
+    /// [`Response::dnd_release_payload`](egui::Response::dnd_release_payload). If a tab is released in a place where
+    /// it would be turned into a window, but the payload is taken by another widget, the tab stays where it was. It's
+    /// up to the application to remove it from the [`DockState`] if needed.
+    ///
+    /// Widgets accepting the payload must be shown before the [`DockArea`] in the frame. egui drops the payload at the
+    /// end of the frame in which the tab is released, so the area decides whether the tab becomes a window while it's
+    /// shown, and takes the payload away from the widgets shown after it if it does.
+    ///
+    /// By default tabs don't carry a payload.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockArea, DockState};
+    /// # let mut dock_state: DockState<String> = DockState::new(vec![]);
+    /// let dock_area = DockArea::new(&mut dock_state).external_drag_payload(|tab: &String| tab.clone());
+    ///
+    /// // Somewhere else in the UI:
+    /// // if let Some(title) = response.dnd_release_payload::<String>() { ... }
+    /// ```
+    pub fn external_drag_payload<P: Any + Send + Sync>(
+        mut self,
+        payload: impl Fn(&Tab) -> P + 'tree,
+    ) -> Self {
+        self.external_drag_payload = Some(Box::new(move |tab, ctx| {
+            DragAndDrop::set_payload(ctx, payload(tab));
+        }));
+        self
+    }
+
+    /// Lets tabs be dragged between this [`DockArea`] and the other ones shown with the same `group` in the same
+    /// [`Context`], even if they show different [`DockState`]s. Each of them needs a unique [`id`](Self::id).
+    ///
+    /// A tab dropped onto another area is removed from this area's [`DockState`] and inserted into the other one
+    /// within the following frame, so all areas of the group should be shown every frame. Both sides report the
+    /// transfer with [`DockEvent::TabSent`] and [`DockEvent::TabReceived`].
+    ///
+    /// By default, areas don't belong to any drag group.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockArea, DockState};
+    /// # use egui::Id;
+    /// # let mut left: DockState<String> = DockState::new(vec![]);
+    /// # let mut right: DockState<String> = DockState::new(vec![]);
+    /// let group = Id::new("editor_panes");
+    /// let left_area = DockArea::new(&mut left).id(Id::new("left")).drag_group(group);
+    /// let right_area = DockArea::new(&mut right).id(Id::new("right")).drag_group(group);
+    /// ```
+    pub fn drag_group(mut self, group: Id) -> Self
+    where
+        Tab: Send + 'static,
+    {
+        self.drag_group = Some(DragGroup::new(group));
+        self
+    }
+
     /// Whether tooltip hints are shown for secondary buttons on tab bars.
     /// By default it's `true`.
     pub fn show_secondary_button_hint(mut self, show_secondary_button_hint: bool) -> Self {
@@ -174,6 +475,46 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
         self
     }
 
+    /// Whether windows snap to the edges of the [window bounds](Self::window_bounds) when dragged there, like
+    /// windows of most operating systems.
+    ///
+    /// Releasing a window at the left or right edge docks it into that half of the main surface, and releasing it
+    /// at the top edge maximizes it (see [`WindowState::maximize`](crate::WindowState::maximize)). A maximized
+    /// window is restored to its previous size when dragged again. This doesn't apply to
+    /// [native windows](Self::native_windows).
+    ///
+    /// By default it's `false`.
+    pub fn window_snapping(mut self, window_snapping: bool) -> Self {
+        self.window_snapping = window_snapping;
+        self
+    }
+
+    /// Whether windows released outside of the [window bounds](Self::window_bounds) are minimized into a taskbar
+    /// instead of being kept inside of them.
+    ///
+    /// The taskbar is a strip along the bottom of the `DockArea` listing the minimized windows by their titles.
+    /// Clicking an entry restores its window and brings it to the front. This doesn't apply to
+    /// [native windows](Self::native_windows).
+    ///
+    /// By default it's `false`.
+    pub fn window_taskbar(mut self, window_taskbar: bool) -> Self {
+        self.window_taskbar = window_taskbar;
+        self
+    }
+
+    /// Whether windows are shown as native OS windows using immediate viewports instead of [`egui::Window`]s,
+    /// allowing them to be moved outside the main window, e.g. to another monitor.
+    ///
+    /// If the backend doesn't support multiple viewports, windows are still shown as [`egui::Window`]s.
+    /// Tabs can't be dragged between different native windows (yet).
+    ///
+    /// By default it's `false`.
+    #[inline(always)]
+    pub fn native_windows(mut self, native_windows: bool) -> Self {
+        self.native_windows = native_windows;
+        self
+    }
+
     /// Enables or disables the close button on windows.
     /// By default it's `true`.
     #[inline(always)]
@@ -201,12 +542,50 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
     }
 
     /// Enables or disables the collapse tabs button on tab bars.
+    ///
+    /// Unless the layout is [`locked`](Self::locked), dragging the button moves the whole leaf into a new window.
+    ///
     /// By default it's `true`.
     #[inline(always)]
     pub fn show_leaf_collapse_buttons(mut self, show_leaf_collapse_buttons: bool) -> Self {
         self.show_leaf_collapse_buttons = show_leaf_collapse_buttons;
         self
     }
+
+    /// Whether collapsed leaves placed side by side with other nodes shrink into a slim strip on their outer edge,
+    /// with their tab titles rotated, instead of keeping their width.
+    ///
+    /// Hovering over a title in a strip temporarily shows the leaf over its neighbor, and clicking a title opens that
+    /// tab in it.
+    ///
+    /// By default it's `false`.
+    #[inline(always)]
+    pub fn collapse_into_side_strips(mut self, collapse_into_side_strips: bool) -> Self {
+        self.collapse_into_side_strips = collapse_into_side_strips;
+        self
+    }
+
+    /// Whether to measure the layout while showing the dock area, so that the rects of nodes and tab titles, the
+    /// drag state and the hovered element can be inspected afterwards with
+    /// [`DockState::last_metrics`](crate::DockState::last_metrics).
+    ///
+    /// Useful for testing and for debugging layouts without screenshots.
+    ///
+    /// By default it's `false`.
+    #[inline(always)]
+    pub fn collect_metrics(mut self, collect_metrics: bool) -> Self {
+        self.collect_metrics = collect_metrics;
+        self
+    }
+}
+
+#[cfg(feature = "testing")]
+impl<Tab> DockArea<'_, Tab> {
+    /// Returns the style the area is shown with if one has been set, for the [`testing`](crate::testing) helpers to
+    /// find the overlay buttons.
+    pub(crate) fn configured_style(&self) -> Option<&Style> {
+        self.style.as_ref()
+    }
 }
 
 impl<Tab> std::fmt::Debug for DockArea<'_, Tab> {
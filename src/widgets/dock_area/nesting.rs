@@ -0,0 +1,141 @@
+use egui::{vec2, Align2, Context, Id, Ui};
+
+use crate::DockArea;
+
+use super::drag_and_drop::HoverData;
+
+/// Ids of the areas being shown in the current pass, outermost first, so that an area shown inside a tab knows
+/// which areas it's nested in.
+#[derive(Clone, Debug, Default)]
+struct ShownAreas {
+    pass: u64,
+    ids: Vec<Id>,
+}
+
+/// A drag hovering an area nested in other areas, which shouldn't handle it themselves.
+#[derive(Clone, Debug)]
+struct ClaimedDrag {
+    pass: u64,
+    ancestors: Vec<Id>,
+}
+
+fn shown_areas_id() -> Id {
+    Id::new("egui_dock::shown_areas")
+}
+
+fn claimed_drag_id() -> Id {
+    Id::new("egui_dock::claimed_drag")
+}
+
+/// Ids of all the areas shown in the current pass, for finding areas which share their id.
+#[derive(Clone, Debug, Default)]
+struct UsedAreaIds {
+    pass: u64,
+    ids: Vec<Id>,
+}
+
+fn used_area_ids_id() -> Id {
+    Id::new("egui_dock::used_area_ids")
+}
+
+impl<Tab> DockArea<'_, Tab> {
+    /// Marks this area as being shown until [`leave_shown_areas`](Self::leave_shown_areas) is called.
+    ///
+    /// Returns the ids of the areas this one is nested in.
+    pub(super) fn enter_shown_areas(&self, ctx: &Context) -> Vec<Id> {
+        let pass = ctx.cumulative_pass_nr();
+        ctx.data_mut(|d| {
+            let shown = d.get_temp_mut_or_default::<ShownAreas>(shown_areas_id());
+            if shown.pass != pass {
+                *shown = ShownAreas {
+                    pass,
+                    ids: Vec::new(),
+                };
+            }
+            let ancestors = shown.ids.clone();
+            shown.ids.push(self.id);
+            ancestors
+        })
+    }
+
+    /// Paints a warning over this area if another area with the same id has already been shown in the current pass
+    /// and [`egui::Options::warn_on_id_clash`] is set.
+    pub(super) fn warn_on_id_clash(&self, ui: &Ui) {
+        let pass = ui.ctx().cumulative_pass_nr();
+        let clash = ui.data_mut(|d| {
+            let used = d.get_temp_mut_or_default::<UsedAreaIds>(used_area_ids_id());
+            if used.pass != pass {
+                *used = UsedAreaIds {
+                    pass,
+                    ids: Vec::new(),
+                };
+            }
+            let clash = used.ids.contains(&self.id);
+            used.ids.push(self.id);
+            clash
+        });
+        if !clash || !ui.ctx().options(|options| options.warn_on_id_clash) {
+            return;
+        }
+
+        let rect = ui.available_rect_before_wrap();
+        let color = ui.visuals().error_fg_color;
+        let painter = ui.ctx().debug_painter();
+        painter.rect_stroke(rect, 0.0, (1.0, color));
+        painter.debug_text(
+            rect.left_top() + vec2(2.0, 2.0),
+            Align2::LEFT_TOP,
+            color,
+            "🔥 Another DockArea has the same id and shares its state. \
+             Give them different ids with `DockArea::id` or `DockArea::id_salt`.",
+        );
+    }
+
+    pub(super) fn leave_shown_areas(&self, ctx: &Context) {
+        ctx.data_mut(|d| {
+            let shown = d.get_temp_mut_or_default::<ShownAreas>(shown_areas_id());
+            if shown.ids.last() == Some(&self.id) {
+                shown.ids.pop();
+            }
+        });
+    }
+
+    /// Keeps the areas this one is nested in from handling the drag if it's hovering this area, starting with the
+    /// next pass.
+    pub(super) fn claim_hovered_drag(&self, ctx: &Context, ancestors: &[Id]) {
+        if ancestors.is_empty() {
+            return;
+        }
+        let hovered = ctx
+            .data(|d| d.get_temp::<Option<HoverData>>(self.id.with("hover_data")))
+            .flatten()
+            .is_some();
+        if !hovered {
+            return;
+        }
+        let pass = ctx.cumulative_pass_nr();
+        ctx.data_mut(|d| {
+            // An area nested in this one has already claimed it.
+            if let Some(claim) = d.get_temp::<ClaimedDrag>(claimed_drag_id()) {
+                if claim.pass == pass && claim.ancestors.contains(&self.id) {
+                    return;
+                }
+            }
+            d.insert_temp(
+                claimed_drag_id(),
+                ClaimedDrag {
+                    pass,
+                    ancestors: ancestors.to_vec(),
+                },
+            );
+        });
+    }
+
+    /// Whether the drag hovers an area nested in this one, which handles it instead.
+    pub(super) fn is_drag_claimed_by_nested_area(&self, ctx: &Context) -> bool {
+        ctx.data(|d| d.get_temp::<ClaimedDrag>(claimed_drag_id()))
+            .is_some_and(|claim| {
+                claim.pass + 1 >= ctx.cumulative_pass_nr() && claim.ancestors.contains(&self.id)
+            })
+    }
+}
@@ -0,0 +1,330 @@
+use std::f32::consts::FRAC_PI_2;
+
+use egui::{
+    epaint::TextShape, pos2, vec2, Align, Context, CursorIcon, Id, Key, Layout, Order, Rect,
+    Rounding, Sense, Stroke, TextStyle, TextWrapMode, Ui, UiBuilder,
+};
+
+use crate::{
+    dock_area::state::State, DockArea, Edge, Node, NodeIndex, Style, SurfaceId, SurfaceIndex,
+    TabIndex, TabViewer,
+};
+
+impl<Tab> DockArea<'_, Tab> {
+    /// Returns the auto-hidden leaves along with the edges they're kept along, in the order of their surfaces.
+    pub(super) fn auto_hidden_surfaces(&self) -> Vec<(SurfaceIndex, Edge)> {
+        self.dock_state
+            .valid_surface_index_iter()
+            .filter_map(|surface| {
+                let state = self.dock_state.get_auto_hide_state(surface)?;
+                Some((surface, state.edge()))
+            })
+            .collect()
+    }
+
+    /// Id under which the [`SurfaceId`] of the auto-hidden leaf currently shown over the main surface is kept.
+    fn open_auto_hidden_id(&self) -> Id {
+        self.id.with("open_auto_hidden")
+    }
+
+    /// Returns the auto-hidden leaf currently shown over the main surface, if it still exists.
+    pub(super) fn open_auto_hidden(&self, ctx: &Context) -> Option<SurfaceIndex> {
+        let id = ctx.data(|data| data.get_temp::<SurfaceId>(self.open_auto_hidden_id()))?;
+        self.dock_state.surface_by_id(id)
+    }
+
+    /// Shows the given auto-hidden leaf over the main surface, or hides the one shown if `surface` is [`None`].
+    pub(super) fn set_open_auto_hidden(&self, ctx: &Context, surface: Option<SurfaceIndex>) {
+        let open_id = self.open_auto_hidden_id();
+        match surface.and_then(|surface| self.dock_state.surface_id(surface)) {
+            Some(id) => ctx.data_mut(|data| data.insert_temp(open_id, id)),
+            None => ctx.data_mut(|data| data.remove::<SurfaceId>(open_id)),
+        }
+    }
+
+    /// Draws the strips along the edges of `rect` which list the tabs of the auto-hidden leaves.
+    ///
+    /// Returns what's left of `rect` for the main surface, and the rects of the strips.
+    pub(super) fn show_auto_hide_strips(
+        &mut self,
+        ui: &mut Ui,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        rect: Rect,
+        auto_hidden: &[(SurfaceIndex, Edge)],
+    ) -> (Rect, Vec<Rect>) {
+        let (inner, strips) = self.auto_hide_strip_rects(rect, auto_hidden);
+        for &(edge, strip) in &strips {
+            let surfaces = auto_hidden
+                .iter()
+                .filter(|&&(_, e)| e == edge)
+                .map(|&(surface, _)| surface);
+            self.show_auto_hide_strip(ui, tab_viewer, strip, edge, surfaces);
+        }
+        ui.allocate_rect(rect, Sense::hover());
+        (inner, strips.into_iter().map(|(_, strip)| strip).collect())
+    }
+
+    /// Splits `rect` into the strips along its edges which list the auto-hidden leaves, and the rest of it, which is
+    /// left for the main surface.
+    pub(super) fn auto_hide_strip_rects(
+        &self,
+        rect: Rect,
+        auto_hidden: &[(SurfaceIndex, Edge)],
+    ) -> (Rect, Vec<(Edge, Rect)>) {
+        let thickness = self.style.as_ref().unwrap().tab_bar.height;
+        let mut inner = rect;
+        let mut strips = Vec::new();
+        for edge in [Edge::Left, Edge::Right, Edge::Top, Edge::Bottom] {
+            if !auto_hidden.iter().any(|&(_, e)| e == edge) {
+                continue;
+            }
+            let strip = match edge {
+                Edge::Left => {
+                    inner.min.x += thickness;
+                    Rect::from_x_y_ranges(rect.min.x..=inner.min.x, inner.y_range())
+                }
+                Edge::Right => {
+                    inner.max.x -= thickness;
+                    Rect::from_x_y_ranges(inner.max.x..=rect.max.x, inner.y_range())
+                }
+                Edge::Top => {
+                    inner.min.y += thickness;
+                    Rect::from_x_y_ranges(inner.x_range(), rect.min.y..=inner.min.y)
+                }
+                Edge::Bottom => {
+                    inner.max.y -= thickness;
+                    Rect::from_x_y_ranges(inner.x_range(), inner.max.y..=rect.max.y)
+                }
+            };
+            strips.push((edge, strip));
+        }
+        (inner, strips)
+    }
+
+    fn show_auto_hide_strip(
+        &mut self,
+        ui: &Ui,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        strip: Rect,
+        edge: Edge,
+        surfaces: impl Iterator<Item = SurfaceIndex>,
+    ) {
+        let style = self.style.as_ref().unwrap();
+        ui.painter()
+            .rect_filled(strip, Rounding::ZERO, style.tab_bar.bg_fill);
+        let px = ui.ctx().pixels_per_point().recip();
+        let border = match edge {
+            Edge::Left => [strip.right_top(), strip.right_bottom()],
+            Edge::Right => [strip.left_top(), strip.left_bottom()],
+            Edge::Top => [strip.left_bottom(), strip.right_bottom()],
+            Edge::Bottom => [strip.left_top(), strip.right_top()],
+        };
+        ui.painter()
+            .line_segment(border, Stroke::new(px, style.tab_bar.hline_color));
+
+        let open = self.open_auto_hidden(ui.ctx());
+        let mut clicked = None;
+        let padding = ui.spacing().button_padding.x;
+        let mut cursor = if edge.is_vertical() {
+            strip.min.y
+        } else {
+            strip.min.x
+        };
+        for surface in surfaces {
+            let Node::Leaf { tabs, active, .. } = &mut self.dock_state[surface][NodeIndex::root()]
+            else {
+                continue;
+            };
+            let active = *active;
+            for (index, tab) in tabs.iter_mut().enumerate() {
+                let galley = tab_viewer.title(tab).into_galley(
+                    ui,
+                    Some(TextWrapMode::Extend),
+                    f32::INFINITY,
+                    TextStyle::Button,
+                );
+                let length = galley.size().x + 2.0 * padding;
+                let entry = if edge.is_vertical() {
+                    Rect::from_x_y_ranges(strip.x_range(), cursor..=cursor + length)
+                } else {
+                    Rect::from_x_y_ranges(cursor..=cursor + length, strip.y_range())
+                };
+                cursor += length + Style::AUTO_HIDE_ENTRY_SPACING;
+
+                let response = ui
+                    .interact(
+                        entry,
+                        self.id.with((surface, index, "auto_hide_entry")),
+                        Sense::click(),
+                    )
+                    .on_hover_cursor(CursorIcon::PointingHand);
+                let shown = open == Some(surface) && active.0 == index;
+                let style = self.style.as_ref().unwrap();
+                let tab_style = if shown {
+                    &style.tab.active
+                } else if response.hovered() {
+                    &style.tab.hovered
+                } else {
+                    &style.tab.inactive
+                };
+                ui.painter()
+                    .rect_filled(entry.shrink(px), tab_style.rounding, tab_style.bg_fill);
+
+                let size = galley.size();
+                let center = entry.center();
+                let text = match edge {
+                    Edge::Left => TextShape::new(
+                        pos2(center.x - size.y / 2.0, center.y + size.x / 2.0),
+                        galley,
+                        tab_style.text_color,
+                    )
+                    .with_angle(-FRAC_PI_2),
+                    Edge::Right => TextShape::new(
+                        pos2(center.x + size.y / 2.0, center.y - size.x / 2.0),
+                        galley,
+                        tab_style.text_color,
+                    )
+                    .with_angle(FRAC_PI_2),
+                    Edge::Top | Edge::Bottom => {
+                        TextShape::new(center - size / 2.0, galley, tab_style.text_color)
+                    }
+                };
+                ui.painter().with_clip_rect(strip).add(text);
+
+                if response.clicked() {
+                    clicked = Some((surface, TabIndex(index), shown));
+                }
+            }
+            cursor += Style::AUTO_HIDE_ENTRY_SPACING;
+        }
+
+        // Clicking the entry of the tab being shown hides it, clicking any other entry shows that tab.
+        match clicked {
+            Some((_, _, true)) => self.set_open_auto_hidden(ui.ctx(), None),
+            Some((surface, tab_index, false)) => {
+                self.set_open_auto_hidden(ui.ctx(), Some(surface));
+                self.dock_state
+                    .set_active_tab((surface, NodeIndex::root(), tab_index));
+                self.new_focused = Some((surface, NodeIndex::root()));
+            }
+            None => {}
+        }
+    }
+
+    /// Shows the auto-hidden leaf whose entry has been clicked over the edge of the main surface's `rect`, until
+    /// something outside of it or the `strips` is clicked or Escape is pressed.
+    pub(super) fn show_auto_hidden_overlay(
+        &mut self,
+        ui: &Ui,
+        state: &mut State,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        rect: Rect,
+        strips: &[Rect],
+    ) {
+        let Some(surface) = self.open_auto_hidden(ui.ctx()) else {
+            return;
+        };
+        let Some(auto_hide) = self.dock_state.get_auto_hide_state(surface).cloned() else {
+            self.set_open_auto_hidden(ui.ctx(), None);
+            return;
+        };
+        let edge = auto_hide.edge();
+
+        let extent = if edge.is_vertical() {
+            rect.width()
+        } else {
+            rect.height()
+        };
+        let size = auto_hide.size().min(extent).max(0.0);
+        let overlay = match edge {
+            Edge::Left => Rect::from_min_size(rect.min, vec2(size, rect.height())),
+            Edge::Right => Rect::from_min_max(pos2(rect.max.x - size, rect.min.y), rect.max),
+            Edge::Top => Rect::from_min_size(rect.min, vec2(rect.width(), size)),
+            Edge::Bottom => Rect::from_min_max(pos2(rect.min.x, rect.max.y - size), rect.max),
+        };
+
+        let pressed_outside = ui.input(|i| {
+            i.pointer.any_pressed()
+                && i.pointer.press_origin().is_some_and(|pos| {
+                    !overlay.contains(pos) && strips.iter().all(|strip| !strip.contains(pos))
+                })
+        });
+        if pressed_outside || ui.input(|i| i.key_pressed(Key::Escape)) {
+            self.set_open_auto_hidden(ui.ctx(), None);
+            return;
+        }
+
+        self.dock_state[surface][NodeIndex::root()].set_rect(overlay);
+        let response = egui::Area::new(self.id.with((surface, "auto_hide_overlay")))
+            .order(Order::Foreground)
+            .fixed_pos(overlay.min)
+            .constrain(false)
+            .show(ui.ctx(), |ui| {
+                ui.painter()
+                    .add(ui.visuals().popup_shadow.as_shape(overlay, Rounding::ZERO));
+                let leaf_ui = &mut ui.new_child(
+                    UiBuilder::new()
+                        .max_rect(overlay)
+                        .layout(Layout::top_down_justified(Align::Min)),
+                );
+                self.show_leaf(
+                    leaf_ui,
+                    state,
+                    (surface, NodeIndex::root()),
+                    tab_viewer,
+                    None,
+                );
+
+                // The inner border of the leaf can be dragged to resize it.
+                let style = self.style.as_ref().unwrap();
+                let grab = style
+                    .separator
+                    .width
+                    .max(style.separator.extra_interact_width);
+                let handle = match edge {
+                    Edge::Left => Rect::from_x_y_ranges(
+                        overlay.max.x - grab / 2.0..=overlay.max.x + grab / 2.0,
+                        overlay.y_range(),
+                    ),
+                    Edge::Right => Rect::from_x_y_ranges(
+                        overlay.min.x - grab / 2.0..=overlay.min.x + grab / 2.0,
+                        overlay.y_range(),
+                    ),
+                    Edge::Top => Rect::from_x_y_ranges(
+                        overlay.x_range(),
+                        overlay.max.y - grab / 2.0..=overlay.max.y + grab / 2.0,
+                    ),
+                    Edge::Bottom => Rect::from_x_y_ranges(
+                        overlay.x_range(),
+                        overlay.min.y - grab / 2.0..=overlay.min.y + grab / 2.0,
+                    ),
+                };
+                let response = ui
+                    .interact(
+                        handle,
+                        self.id.with((surface, "auto_hide_resize")),
+                        Sense::drag(),
+                    )
+                    .on_hover_and_drag_cursor(if edge.is_vertical() {
+                        CursorIcon::ResizeHorizontal
+                    } else {
+                        CursorIcon::ResizeVertical
+                    });
+                if response.dragged() {
+                    let delta = response.drag_delta();
+                    let delta = match edge {
+                        Edge::Left => delta.x,
+                        Edge::Right => -delta.x,
+                        Edge::Top => delta.y,
+                        Edge::Bottom => -delta.y,
+                    };
+                    if let Some(auto_hide) = self.dock_state.get_auto_hide_state_mut(surface) {
+                        auto_hide.set_size((size + delta).clamp(0.0, extent));
+                    }
+                }
+                ui.allocate_rect(overlay, Sense::hover());
+            });
+        self.surface_layers
+            .push((surface, response.response.layer_id));
+    }
+}
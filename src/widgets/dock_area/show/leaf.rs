@@ -1,9 +1,13 @@
 use egui::{
-    emath::TSTransform, epaint::TextShape, lerp, pos2, vec2, Align, Align2, Button, Color32,
-    CursorIcon, Frame, Id, Key, LayerId, Layout, NumExt, Order, Rect, Response, Rounding,
-    ScrollArea, Sense, Shape, Stroke, TextStyle, Ui, UiBuilder, Vec2, WidgetText,
+    emath::TSTransform, epaint::TextShape, lerp, pos2, text::LayoutJob, vec2, Align, Align2,
+    Button, Color32, CursorIcon, Frame, Galley, Id, Key, LayerId, Layout, Margin, NumExt, Order,
+    Pos2, Rect, Response, Rounding, ScrollArea, Sense, Shape, Stroke, TextEdit, TextStyle,
+    TextWrapMode, Ui, UiBuilder, Vec2, WidgetInfo, WidgetText, WidgetType,
+};
+use std::{
+    f32::consts::{FRAC_1_SQRT_2, FRAC_PI_2},
+    sync::Arc,
 };
-use std::ops::RangeInclusive;
 
 use crate::dock_area::tab_removal::TabRemoval;
 use crate::{
@@ -11,11 +15,14 @@ use crate::{
         drag_and_drop::{DragData, DragDropState, HoverData, TreeComponent},
         state::State,
     },
-    utils::{fade_visuals, rect_set_size_centered, rect_stroke_box},
-    DockArea, Node, NodeIndex, Style, SurfaceIndex, TabAddAlign, TabIndex, TabStyle, TabViewer,
+    utils::{
+        fade_dock_style, fade_visuals, rect_mirror_x, rect_set_size_centered, rect_stroke_box,
+    },
+    DockArea, DockEvent, Node, NodeIndex, OnCloseResponse, Split, Style, Surface, SurfaceIndex,
+    TabAction, TabAddAlign, TabBarPosition, TabIndex, TabStyle, TabViewer, TitleTruncation,
 };
 
-use crate::popup::popup_under_widget;
+use crate::popup::{popup_under_widget, popup_under_widget_with_layout, PopupLayout};
 
 impl<Tab> DockArea<'_, Tab> {
     pub(super) fn show_leaf(
@@ -25,9 +32,56 @@ impl<Tab> DockArea<'_, Tab> {
         (surface_index, node_index): (SurfaceIndex, NodeIndex),
         tab_viewer: &mut impl TabViewer<Tab = Tab>,
         fade_style: Option<(&Style, f32)>,
+    ) {
+        // Use the leaf's own tab bar style in place of the global one while showing it.
+        let Some(tab_bar_style) = self.dock_state[surface_index][node_index]
+            .tab_bar_style()
+            .cloned()
+        else {
+            self.show_leaf_styled(
+                ui,
+                state,
+                (surface_index, node_index),
+                tab_viewer,
+                fade_style,
+            );
+            return;
+        };
+        let leaf_fade_style = fade_style.map(|(_, factor)| {
+            let mut style = self.style.clone().unwrap();
+            style.tab_bar = tab_bar_style.clone();
+            fade_dock_style(&mut style, factor);
+            (style, factor)
+        });
+        let global_tab_bar_style =
+            std::mem::replace(&mut self.style.as_mut().unwrap().tab_bar, tab_bar_style);
+        self.show_leaf_styled(
+            ui,
+            state,
+            (surface_index, node_index),
+            tab_viewer,
+            leaf_fade_style
+                .as_ref()
+                .map(|(style, factor)| (style, *factor)),
+        );
+        self.style.as_mut().unwrap().tab_bar = global_tab_bar_style;
+    }
+
+    fn show_leaf_styled(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut State,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        fade_style: Option<(&Style, f32)>,
     ) {
         assert!(self.dock_state[surface_index][node_index].is_leaf());
         let collapsed = self.dock_state[surface_index][node_index].is_collapsed();
+        self.keep_tabs_first((surface_index, node_index), tab_viewer);
+        self.dock_state[surface_index][node_index].record_active_tab();
+        if let Node::Leaf { tab_rects, .. } = &mut self.dock_state[surface_index][node_index] {
+            tab_rects.clear();
+        }
 
         let rect = self.dock_state[surface_index][node_index]
             .rect()
@@ -42,27 +96,115 @@ impl<Tab> DockArea<'_, Tab> {
         ui.spacing_mut().item_spacing = Vec2::ZERO;
         ui.set_clip_rect(rect);
 
-        if self.dock_state[surface_index][node_index].tabs_count() == 0 {
+        if self.dock_state[surface_index][node_index].tabs_count() == 0
+            && !self.dock_state[surface_index][node_index].is_central()
+        {
             return;
         }
-        let tabbar_rect = self.tab_bar(
-            ui,
-            state,
-            (surface_index, node_index),
-            tab_viewer,
-            fade_style.map(|(style, _)| style),
-            collapsed,
-        );
-        self.tab_body(
-            ui,
-            state,
-            (surface_index, node_index),
-            tab_viewer,
-            spacing,
-            tabbar_rect,
-            fade_style,
-            collapsed,
-        );
+        let tab_bar_hidden =
+            !collapsed && self.dock_state[surface_index][node_index].is_tab_bar_hidden();
+        let hide_tab_bar = !collapsed
+            && self.style.as_ref().unwrap().tab_bar.hide_when_single_tab
+            && self.dock_state[surface_index][node_index].tabs_count() == 1;
+        let side_strip = self.side_strip_position((surface_index, node_index));
+        self.tab_bar_position = if collapsed {
+            side_strip.unwrap_or(TabBarPosition::Top)
+        } else {
+            self.style.as_ref().unwrap().tab_bar.position
+        };
+
+        let layout = match self.tab_bar_position {
+            TabBarPosition::Top => None,
+            TabBarPosition::Bottom => Some(Layout::bottom_up(Align::Min)),
+            TabBarPosition::Left => Some(Layout::left_to_right(Align::Min)),
+            TabBarPosition::Right => Some(Layout::right_to_left(Align::Min)),
+        };
+        if tab_bar_hidden {
+            self.tab_body(
+                ui,
+                state,
+                (surface_index, node_index),
+                tab_viewer,
+                spacing,
+                Rect::NOTHING,
+                fade_style,
+                collapsed,
+            );
+        } else if let Some(layout) = layout {
+            // Lay out the tab bar from the edge it's on and give the rest of the leaf to the body.
+            let bar_ui = &mut ui.new_child(
+                UiBuilder::new()
+                    .max_rect(rect)
+                    .layout(layout.with_cross_justify(true))
+                    .id_salt((node_index, "tab_bar")),
+            );
+            let tabbar_rect = self.show_tab_bar(
+                bar_ui,
+                state,
+                (surface_index, node_index),
+                tab_viewer,
+                fade_style.map(|(style, _)| style),
+                collapsed,
+                hide_tab_bar,
+            );
+            let bar_rect = bar_ui.min_rect();
+            let body_rect = match self.tab_bar_position {
+                TabBarPosition::Bottom => rect.with_max_y(bar_rect.top()),
+                TabBarPosition::Left => rect.with_min_x(bar_rect.right()),
+                _ => rect.with_max_x(bar_rect.left()),
+            };
+            let body_ui = &mut ui.new_child(
+                UiBuilder::new()
+                    .max_rect(body_rect)
+                    .layout(Layout::top_down_justified(Align::Min)),
+            );
+            self.tab_body(
+                body_ui,
+                state,
+                (surface_index, node_index),
+                tab_viewer,
+                spacing,
+                tabbar_rect,
+                fade_style,
+                collapsed,
+            );
+        } else {
+            let tabbar_rect = self.show_tab_bar(
+                ui,
+                state,
+                (surface_index, node_index),
+                tab_viewer,
+                fade_style.map(|(style, _)| style),
+                collapsed,
+                hide_tab_bar,
+            );
+            self.tab_body(
+                ui,
+                state,
+                (surface_index, node_index),
+                tab_viewer,
+                spacing,
+                tabbar_rect,
+                fade_style,
+                collapsed,
+            );
+        }
+
+        if self.show_split_buttons && !collapsed {
+            self.split_buttons(ui, state, (surface_index, node_index), tab_viewer);
+        }
+
+        if let (true, Some(position)) = (collapsed, side_strip) {
+            self.side_strip_fly_out(
+                ui,
+                state,
+                (surface_index, node_index),
+                tab_viewer,
+                spacing,
+                fade_style,
+                position,
+            );
+        }
 
         let tabs = self.dock_state[surface_index][node_index]
             .tabs_mut()
@@ -75,6 +217,110 @@ impl<Tab> DockArea<'_, Tab> {
         }
     }
 
+    /// Draws whatever is shown in place of the tab bar of a leaf and returns its rect.
+    #[allow(clippy::too_many_arguments)]
+    fn show_tab_bar(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut State,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        fade_style: Option<&Style>,
+        collapsed: bool,
+        hide_tab_bar: bool,
+    ) -> Rect {
+        // Tabs of other groups than the shown one are left out of the tab bar.
+        let groups = (!collapsed && !hide_tab_bar && !self.tab_bar_position.is_vertical())
+            .then(|| self.leaf_tab_groups((surface_index, node_index), tab_viewer))
+            .flatten();
+        let Some(groups) = groups else {
+            return self.show_tab_bar_row(
+                ui,
+                state,
+                (surface_index, node_index),
+                tab_viewer,
+                fade_style,
+                collapsed,
+                hide_tab_bar,
+            );
+        };
+        self.hide_other_groups((surface_index, node_index), &groups);
+        let bottom = self.tab_bar_position == TabBarPosition::Bottom;
+        if !bottom {
+            self.tab_group_row(
+                ui,
+                state,
+                (surface_index, node_index),
+                tab_viewer,
+                &groups,
+                fade_style,
+            );
+        }
+        let rect = self.show_tab_bar_row(
+            ui,
+            state,
+            (surface_index, node_index),
+            tab_viewer,
+            fade_style,
+            collapsed,
+            hide_tab_bar,
+        );
+        if bottom {
+            self.tab_group_row(
+                ui,
+                state,
+                (surface_index, node_index),
+                tab_viewer,
+                &groups,
+                fade_style,
+            );
+        }
+        self.hidden_tabs.clear();
+        rect
+    }
+
+    /// Draws the tab bar of a leaf, or whatever is shown in place of it, without the row of tab groups and returns
+    /// its rect.
+    #[allow(clippy::too_many_arguments)]
+    fn show_tab_bar_row(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut State,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        fade_style: Option<&Style>,
+        collapsed: bool,
+        hide_tab_bar: bool,
+    ) -> Rect {
+        if hide_tab_bar {
+            self.tab_grab_handle(
+                ui,
+                state,
+                (surface_index, node_index),
+                tab_viewer,
+                fade_style,
+            )
+        } else if self.tab_bar_position.is_vertical() {
+            self.side_tab_bar(
+                ui,
+                state,
+                (surface_index, node_index),
+                tab_viewer,
+                fade_style,
+                collapsed,
+            )
+        } else {
+            self.tab_bar(
+                ui,
+                state,
+                (surface_index, node_index),
+                tab_viewer,
+                fade_style,
+                collapsed,
+            )
+        }
+    }
+
     fn tab_bar(
         &mut self,
         ui: &mut Ui,
@@ -91,12 +337,20 @@ impl<Tab> DockArea<'_, Tab> {
             vec2(ui.available_width(), style.tab_bar.height),
             Sense::hover(),
         );
+        // Registered before the tabs and buttons so that it only gets clicks on the empty space around them.
+        let empty_space_response = ui.interact(
+            tabbar_outer_rect,
+            self.id
+                .with((surface_index, node_index, "tab_bar_empty_space")),
+            Sense::click(),
+        );
         ui.painter().rect_filled(
             tabbar_outer_rect,
             style.tab_bar.rounding,
             style.tab_bar.bg_fill,
         );
 
+        let rtl = self.layout_direction.is_right_to_left();
         let mut available_width = tabbar_outer_rect.width();
         let scroll_bar_width = available_width;
         if available_width == 0.0 {
@@ -109,15 +363,68 @@ impl<Tab> DockArea<'_, Tab> {
             available_width -= Style::TAB_ADD_BUTTON_SIZE;
         }
 
-        if self.show_leaf_close_all_buttons {
-            available_width -= Style::TAB_CLOSE_ALL_BUTTON_SIZE;
-        }
+        let close_all_button_width = if self.show_leaf_close_all_buttons {
+            Style::TAB_CLOSE_ALL_BUTTON_SIZE
+        } else {
+            0.0
+        };
+        let search_button_width = if self.show_tab_search_buttons {
+            Style::TAB_SEARCH_BUTTON_SIZE
+        } else {
+            0.0
+        };
+        available_width -= close_all_button_width + search_button_width;
 
         if self.show_leaf_collapse_buttons {
             available_width -= Style::TAB_COLLAPSE_BUTTON_SIZE;
         }
 
-        let actual_width = {
+        // Show the custom widgets first to know how much space is left for the tabs.
+        let trailing_width = {
+            let right = tabbar_outer_rect.right() - close_all_button_width - search_button_width;
+            let rect = rect_mirror_x(
+                Rect::from_x_y_ranges(
+                    (right - available_width.at_least(0.0))..=right,
+                    tabbar_outer_rect.y_range(),
+                ),
+                tabbar_outer_rect,
+                rtl,
+            );
+            let trailing_ui = &mut ui.new_child(
+                UiBuilder::new()
+                    .max_rect(rect)
+                    .layout(if rtl {
+                        Layout::left_to_right(Align::Center)
+                    } else {
+                        Layout::right_to_left(Align::Center)
+                    })
+                    .id_salt((node_index, "tab_bar_trailing")),
+            );
+            trailing_ui.set_clip_rect(rect);
+            trailing_ui.spacing_mut().item_spacing = ui.ctx().style().spacing.item_spacing;
+            tab_viewer.tab_bar_trailing_ui(trailing_ui, surface_index, node_index);
+            trailing_ui.min_rect().width().min(rect.width())
+        };
+        available_width -= trailing_width;
+
+        // Tell what's in a collapsed leaf without expanding it.
+        if collapsed {
+            let right = tabbar_outer_rect.right()
+                - close_all_button_width
+                - search_button_width
+                - trailing_width;
+            available_width -= self.collapsed_badges(
+                ui,
+                (surface_index, node_index),
+                tab_viewer,
+                tabbar_outer_rect,
+                right,
+                fade_style,
+            );
+        }
+        let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+
+        let (actual_width, tabs_clip_rect) = {
             let Node::Leaf { tabs, scroll, .. } = &mut self.dock_state[surface_index][node_index]
             else {
                 unreachable!()
@@ -136,11 +443,16 @@ impl<Tab> DockArea<'_, Tab> {
                 .to_pos2(),
                 vec2(tabbar_outer_rect.width(), tabbar_outer_rect.height()),
             );
+            let tabbar_inner_rect = rect_mirror_x(tabbar_inner_rect, tabbar_outer_rect, rtl);
 
             let tabs_ui = &mut ui.new_child(
                 UiBuilder::new()
                     .max_rect(tabbar_inner_rect)
-                    .layout(Layout::left_to_right(Align::Center))
+                    .layout(if rtl {
+                        Layout::right_to_left(Align::Center)
+                    } else {
+                        Layout::left_to_right(Align::Center)
+                    })
                     .id_salt("tabs"),
             );
 
@@ -149,6 +461,7 @@ impl<Tab> DockArea<'_, Tab> {
             if self.show_leaf_collapse_buttons {
                 clip_rect = clip_rect.translate(vec2(Style::TAB_COLLAPSE_BUTTON_SIZE, 0.0));
             }
+            let clip_rect = rect_mirror_x(clip_rect, tabbar_outer_rect, rtl);
             tabs_ui.set_clip_rect(clip_rect);
 
             // Desired size for tabs in "expanded" mode.
@@ -171,9 +484,17 @@ impl<Tab> DockArea<'_, Tab> {
             let px = ui.ctx().pixels_per_point().recip();
             let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
 
-            ui.painter().hline(
-                tabs_ui.min_rect().right().min(clip_rect.right())..=tabbar_outer_rect.right(),
-                tabbar_outer_rect.bottom() - px,
+            let hline_range = if rtl {
+                tabbar_outer_rect.left()..=tabs_ui.min_rect().left().max(clip_rect.left())
+            } else {
+                tabs_ui.min_rect().right().min(clip_rect.right())..=tabbar_outer_rect.right()
+            };
+            ui.painter().line_segment(
+                body_edge(
+                    self.tab_bar_position,
+                    Rect::from_x_y_ranges(hline_range, tabbar_outer_rect.y_range()),
+                    px,
+                ),
                 (px, style.tab_bar.hline_color),
             );
 
@@ -184,65 +505,92 @@ impl<Tab> DockArea<'_, Tab> {
                         (clip_rect.width() - tabs_ui.min_rect().width()).at_least(0.0)
                     }
                     TabAddAlign::Right => 0.0,
-                } + if self.show_leaf_close_all_buttons {
-                    Style::TAB_CLOSE_ALL_BUTTON_SIZE
-                } else {
-                    0.0
-                };
+                } + close_all_button_width
+                    + search_button_width
+                    + trailing_width;
+                let rect = rect_mirror_x(
+                    Rect::from_min_max(
+                        tabbar_outer_rect.right_top()
+                            - vec2(Style::TAB_ADD_BUTTON_SIZE + offset, 0.0),
+                        tabbar_outer_rect.right_bottom() - vec2(offset, 0.0),
+                    ),
+                    tabbar_outer_rect,
+                    rtl,
+                );
                 self.tab_plus(
                     ui,
                     surface_index,
                     node_index,
                     tab_viewer,
-                    tabbar_outer_rect,
-                    offset,
+                    shrink_towards_body(rect, self.tab_bar_position, 2.0),
                     fade_style,
                 );
             }
 
             if self.show_leaf_close_all_buttons {
-                // Current leaf contains non-closable tabs.
-                let disabled = if let Node::Leaf { tabs, .. } =
-                    &mut self.dock_state[surface_index][node_index]
-                {
-                    !tabs.iter_mut().all(|tab| tab_viewer.closeable(tab))
-                } else {
-                    unreachable!()
-                };
-
-                // Current window contains non-closable tabs.
-                let close_window_disabled = disabled
-                    || !self.dock_state[surface_index].iter_mut().all(|node| {
-                        if let Node::Leaf { tabs, .. } = node {
-                            tabs.iter_mut().all(|tab| tab_viewer.closeable(tab))
-                        } else {
-                            true
-                        }
-                    });
-
+                let (disabled, close_window_disabled) =
+                    self.close_all_disabled(surface_index, node_index, tab_viewer);
+                let rect = rect_mirror_x(
+                    Rect::from_min_max(
+                        tabbar_outer_rect.right_top() - vec2(Style::TAB_CLOSE_ALL_BUTTON_SIZE, 0.0),
+                        tabbar_outer_rect.right_bottom(),
+                    ),
+                    tabbar_outer_rect,
+                    rtl,
+                );
                 self.tab_close_all(
                     ui,
                     surface_index,
                     node_index,
-                    tabbar_outer_rect,
+                    shrink_towards_body(rect, self.tab_bar_position, 2.0),
                     fade_style,
                     disabled,
                     close_window_disabled,
                 )
             }
 
-            if self.show_leaf_collapse_buttons {
-                self.tab_collapse(
+            if self.show_tab_search_buttons {
+                let rect = rect_mirror_x(
+                    Rect::from_min_max(
+                        tabbar_outer_rect.right_top()
+                            - vec2(search_button_width + close_all_button_width, 0.0),
+                        tabbar_outer_rect.right_bottom() - vec2(close_all_button_width, 0.0),
+                    ),
+                    tabbar_outer_rect,
+                    rtl,
+                );
+                self.tab_search(
                     ui,
                     surface_index,
                     node_index,
+                    tab_viewer,
+                    shrink_towards_body(rect, self.tab_bar_position, 2.0),
+                    fade_style,
+                );
+            }
+
+            if self.show_leaf_collapse_buttons {
+                let rect = rect_mirror_x(
+                    Rect::from_min_max(
+                        tabbar_outer_rect.left_top(),
+                        tabbar_outer_rect.left_bottom()
+                            + vec2(Style::TAB_COLLAPSE_BUTTON_SIZE, 0.0),
+                    ),
                     tabbar_outer_rect,
+                    rtl,
+                );
+                let detachable = self.leaf_detachable((surface_index, node_index), tab_viewer);
+                self.tab_collapse(
+                    ui,
+                    (surface_index, node_index),
+                    rect,
                     fade_style,
                     collapsed,
+                    detachable,
                 )
             }
 
-            tabs_ui.min_rect().width()
+            (tabs_ui.min_rect().width(), clip_rect)
         };
 
         self.tab_bar_scroll(
@@ -252,272 +600,1395 @@ impl<Tab> DockArea<'_, Tab> {
             actual_width,
             available_width,
             scroll_bar_width,
+            tabs_clip_rect,
             &tabbar_response,
             fade_style,
         );
+        self.tab_bar_empty_space_interaction(
+            &empty_space_response,
+            (surface_index, node_index),
+            tab_viewer,
+        );
 
         tabbar_outer_rect
     }
 
-    #[allow(clippy::too_many_arguments)]
-    fn tabs(
+    /// Draws the badges given by [`TabViewer::badge`] for the tabs of a collapsed leaf, followed by the number of its
+    /// tabs, ending at `right` in the tab bar. Returns the width they take up.
+    fn collapsed_badges(
         &mut self,
-        tabs_ui: &mut Ui,
-        state: &mut State,
+        ui: &Ui,
         (surface_index, node_index): (SurfaceIndex, NodeIndex),
         tab_viewer: &mut impl TabViewer<Tab = Tab>,
         tabbar_outer_rect: Rect,
-        preferred_width: Option<f32>,
-        fade: Option<&Style>,
-    ) {
-        assert!(self.dock_state[surface_index][node_index].is_leaf());
-
-        let focused = self.dock_state.focused_leaf();
-        let tabs_len = {
-            let tabs = self.dock_state[surface_index][node_index]
-                .tabs()
-                .expect("This node must be a leaf here");
-            tabs.len()
+        right: f32,
+        fade_style: Option<&Style>,
+    ) -> f32 {
+        let Node::Leaf { tabs, .. } = &mut self.dock_state[surface_index][node_index] else {
+            unreachable!()
         };
+        let mut texts: Vec<String> = tabs
+            .iter_mut()
+            .filter_map(|tab| tab_viewer.badge(tab))
+            .collect();
+        texts.push(tabs.len().to_string());
 
-        for tab_index in 0..tabs_len {
-            let id = self
-                .id
-                .with((surface_index, "surface"))
-                .with((node_index, "node"))
-                .with((tab_index, "tab"));
-            let tab_index = TabIndex(tab_index);
-            let is_being_dragged = tabs_ui.ctx().is_being_dragged(id)
-                && tabs_ui.input(|i| i.pointer.is_decidedly_dragging())
-                && self.draggable_tabs;
-
-            if is_being_dragged {
-                tabs_ui.output_mut(|o| o.cursor_icon = CursorIcon::Grabbing);
-            }
-
-            let (is_active, label, tab_style, closeable) = {
-                let Node::Leaf { tabs, active, .. } =
-                    &mut self.dock_state[surface_index][node_index]
-                else {
-                    unreachable!()
-                };
-                let style = fade.unwrap_or_else(|| self.style.as_ref().unwrap());
-                let tab_style = tab_viewer.tab_style_override(&tabs[tab_index.0], &style.tab);
-                (
-                    *active == tab_index || is_being_dragged,
-                    tab_viewer.title(&mut tabs[tab_index.0]),
-                    tab_style.unwrap_or(style.tab.clone()),
-                    tab_viewer.closeable(&mut tabs[tab_index.0]),
-                )
-            };
+        let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+        let rtl = self.layout_direction.is_right_to_left();
+        let mut cursor = right - Style::BADGE_SPACING;
+        for text in texts.into_iter().rev() {
+            let galley = ui.painter().layout_no_wrap(
+                text,
+                TextStyle::Small.resolve(ui.style()),
+                style.buttons.badge_text_color,
+            );
+            let size = galley.size() + vec2(2.0 * Style::BADGE_PADDING, 2.0);
+            let rect = Rect::from_min_size(
+                pos2(cursor - size.x, tabbar_outer_rect.center().y - size.y / 2.0),
+                size,
+            );
+            let rect = rect_mirror_x(rect, tabbar_outer_rect, rtl);
+            ui.painter()
+                .rect_filled(rect, size.y / 2.0, style.buttons.badge_bg_fill);
+            ui.painter().galley(
+                rect.center() - galley.size() / 2.0,
+                galley,
+                style.buttons.badge_text_color,
+            );
+            cursor -= size.x + Style::BADGE_SPACING;
+        }
+        right - cursor
+    }
 
-            let show_close_button = self.show_close_buttons && closeable;
+    /// Draws a tab bar along the left or right edge of a leaf, with its tabs stacked vertically.
+    fn side_tab_bar(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut State,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        fade_style: Option<&Style>,
+        collapsed: bool,
+    ) -> Rect {
+        assert!(self.dock_state[surface_index][node_index].is_leaf());
 
-            let (response, title_id) = if is_being_dragged {
-                let layer_id = LayerId::new(Order::Tooltip, id);
-                let response = tabs_ui
-                    .scope_builder(UiBuilder::new().layer_id(layer_id), |ui| {
-                        self.tab_title(
+        let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+        let (tabbar_outer_rect, tabbar_response) = ui.allocate_exact_size(
+            vec2(style.tab_bar.height, ui.available_height()),
+            Sense::hover(),
+        );
+        // Registered before the tabs and buttons so that it only gets clicks on the empty space around them.
+        let empty_space_response = ui.interact(
+            tabbar_outer_rect,
+            self.id
+                .with((surface_index, node_index, "tab_bar_empty_space")),
+            Sense::click(),
+        );
+        ui.painter().rect_filled(
+            tabbar_outer_rect,
+            style.tab_bar.rounding,
+            style.tab_bar.bg_fill,
+        );
+        if tabbar_outer_rect.height() == 0.0 {
+            return tabbar_outer_rect;
+        }
+
+        let position = self.tab_bar_position;
+        let width = tabbar_outer_rect.width();
+
+        // The collapse button goes at the top, other buttons at the bottom of the tab bar.
+        let mut tabs_rect = tabbar_outer_rect;
+        if self.show_leaf_collapse_buttons {
+            let rect =
+                Rect::from_min_size(tabs_rect.min, vec2(width, Style::TAB_COLLAPSE_BUTTON_SIZE));
+            tabs_rect.set_top(rect.bottom());
+            let detachable = self.leaf_detachable((surface_index, node_index), tab_viewer);
+            self.tab_collapse(
+                ui,
+                (surface_index, node_index),
+                rect,
+                fade_style,
+                collapsed,
+                detachable,
+            );
+        }
+
+        if self.show_leaf_close_all_buttons {
+            let (disabled, close_window_disabled) =
+                self.close_all_disabled(surface_index, node_index, tab_viewer);
+            let rect = Rect::from_min_max(
+                tabs_rect.left_bottom() - vec2(0.0, Style::TAB_CLOSE_ALL_BUTTON_SIZE),
+                tabs_rect.right_bottom(),
+            );
+            tabs_rect.set_bottom(rect.top());
+            self.tab_close_all(
+                ui,
+                surface_index,
+                node_index,
+                shrink_towards_body(rect, position, 2.0),
+                fade_style,
+                disabled,
+                close_window_disabled,
+            );
+        }
+
+        if self.show_tab_search_buttons {
+            let rect = Rect::from_min_max(
+                tabs_rect.left_bottom() - vec2(0.0, Style::TAB_SEARCH_BUTTON_SIZE),
+                tabs_rect.right_bottom(),
+            );
+            tabs_rect.set_bottom(rect.top());
+            self.tab_search(
+                ui,
+                surface_index,
+                node_index,
+                tab_viewer,
+                shrink_towards_body(rect, position, 2.0),
+                fade_style,
+            );
+        }
+
+        let add_button_rect = self.show_add_buttons.then(|| {
+            let rect = Rect::from_min_max(
+                tabs_rect.left_bottom() - vec2(0.0, Style::TAB_ADD_BUTTON_SIZE),
+                tabs_rect.right_bottom(),
+            );
+            tabs_rect.set_bottom(rect.top());
+            rect
+        });
+
+        // Show the custom widgets first to know how much space is left for the tabs.
+        {
+            let trailing_ui = &mut ui.new_child(
+                UiBuilder::new()
+                    .max_rect(tabs_rect)
+                    .layout(Layout::bottom_up(Align::Center))
+                    .id_salt((node_index, "tab_bar_trailing")),
+            );
+            trailing_ui.set_clip_rect(tabs_rect);
+            trailing_ui.spacing_mut().item_spacing = ui.ctx().style().spacing.item_spacing;
+            tab_viewer.tab_bar_trailing_ui(trailing_ui, surface_index, node_index);
+            let trailing_height = trailing_ui.min_rect().height().min(tabs_rect.height());
+            tabs_rect.set_bottom(tabs_rect.bottom() - trailing_height);
+        }
+        let available_height = tabs_rect.height().at_least(0.0);
+
+        let (actual_height, tabs_end) = {
+            let Node::Leaf { tabs, scroll, .. } = &mut self.dock_state[surface_index][node_index]
+            else {
+                unreachable!()
+            };
+
+            let tabs_ui = &mut ui.new_child(
+                UiBuilder::new()
+                    .max_rect(tabs_rect.translate(vec2(0.0, *scroll)))
+                    .layout(Layout::top_down(Align::Min))
+                    .id_salt("tabs"),
+            );
+            tabs_ui.set_clip_rect(tabs_rect.intersect(tabbar_outer_rect));
+
+            // Desired size for tabs in "expanded" mode.
+            let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+            let preferred_height = style
+                .tab_bar
+                .fill_tab_bar
+                .then_some(available_height / (tabs.len() as f32));
+
+            self.tabs(
+                tabs_ui,
+                state,
+                (surface_index, node_index),
+                tab_viewer,
+                tabbar_outer_rect,
+                preferred_height,
+                fade_style,
+            );
+
+            let tabs_end = tabs_ui.min_rect().bottom().min(tabs_rect.bottom());
+            (tabs_ui.min_rect().height(), tabs_end)
+        };
+
+        // Draw a line from the end of the tabs to the bottom of the tab bar.
+        let px = ui.ctx().pixels_per_point().recip();
+        let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+        ui.painter().line_segment(
+            body_edge(
+                position,
+                Rect::from_x_y_ranges(
+                    tabbar_outer_rect.x_range(),
+                    tabs_end..=tabbar_outer_rect.bottom(),
+                ),
+                px,
+            ),
+            (px, style.tab_bar.hline_color),
+        );
+
+        if let Some(rect) = add_button_rect {
+            let rect = match style.buttons.add_tab_align {
+                TabAddAlign::Left => Rect::from_min_size(
+                    pos2(tabbar_outer_rect.left(), tabs_end),
+                    vec2(width, Style::TAB_ADD_BUTTON_SIZE),
+                ),
+                TabAddAlign::Right => rect,
+            };
+            self.tab_plus(
+                ui,
+                surface_index,
+                node_index,
+                tab_viewer,
+                shrink_towards_body(rect, position, 2.0),
+                fade_style,
+            );
+        }
+
+        // Vertical tab bars are scrolled with the mouse wheel, or by dragging tabs near their ends.
+        let overflow = (actual_height - available_height).at_least(0.0);
+        let auto_scroll = if overflow > 1.0 {
+            self.drag_auto_scroll(ui, state, tabs_rect, true)
+        } else {
+            0.0
+        };
+        let Node::Leaf { scroll, .. } = &mut self.dock_state[surface_index][node_index] else {
+            unreachable!()
+        };
+        if overflow > 1.0 && tabbar_response.hovered() {
+            *scroll += ui.input(|i| i.smooth_scroll_delta.y + i.smooth_scroll_delta.x);
+        }
+        *scroll = (*scroll + auto_scroll).clamp(-overflow, 0.0);
+
+        self.tab_bar_empty_space_interaction(
+            &empty_space_response,
+            (surface_index, node_index),
+            tab_viewer,
+        );
+
+        tabbar_outer_rect
+    }
+
+    /// Shows a leaf collapsed into a side strip over its neighbor while one of its titles is hovered, and keeps it
+    /// shown until the pointer leaves both the strip and the leaf.
+    #[allow(clippy::too_many_arguments)]
+    fn side_strip_fly_out(
+        &mut self,
+        ui: &Ui,
+        state: &mut State,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        spacing: Vec2,
+        fade_style: Option<(&Style, f32)>,
+        position: TabBarPosition,
+    ) {
+        let id = self.id.with((surface_index, node_index, "fly_out"));
+        let Node::Leaf {
+            rect: strip,
+            tab_rects,
+            ..
+        } = &self.dock_state[surface_index][node_index]
+        else {
+            unreachable!()
+        };
+        let strip = *strip;
+        let previous = ui.ctx().data(|data| data.get_temp::<Rect>(id));
+        let pointer = ui.ctx().pointer_hover_pos();
+        let hovered = |rect: Rect| pointer.is_some_and(|pos| rect.contains(pos));
+        let open = ui.ctx().dragged_id().is_none()
+            && (tab_rects.iter().copied().any(hovered)
+                || previous.is_some_and(|fly_out| hovered(fly_out) || hovered(strip)));
+        if !open {
+            if previous.is_some() {
+                ui.ctx().data_mut(|data| data.remove::<Rect>(id));
+            }
+            return;
+        }
+
+        // The leaf is shown as wide as it would be if it wasn't collapsed.
+        let parent = node_index.parent().expect("side strips have a parent");
+        let Node::Horizontal {
+            rect: parent_rect,
+            fraction,
+            ..
+        } = &self.dock_state[surface_index][parent]
+        else {
+            unreachable!()
+        };
+        let expanded_width = match position {
+            TabBarPosition::Left => parent_rect.width() * fraction,
+            _ => parent_rect.width() * (1.0 - fraction),
+        };
+        let size = vec2(
+            (expanded_width - strip.width()).at_least(0.0),
+            strip.height(),
+        );
+        let fly_out = match position {
+            TabBarPosition::Left => Rect::from_min_size(strip.right_top(), size),
+            _ => Rect::from_min_size(strip.left_top() - vec2(size.x, 0.0), size),
+        };
+        ui.ctx().data_mut(|data| data.insert_temp(id, fly_out));
+
+        egui::Area::new(id)
+            .order(Order::Foreground)
+            .fixed_pos(fly_out.min)
+            .constrain(false)
+            .show(ui.ctx(), |ui| {
+                ui.painter()
+                    .add(ui.visuals().popup_shadow.as_shape(fly_out, Rounding::ZERO));
+                let body_ui = &mut ui.new_child(
+                    UiBuilder::new()
+                        .max_rect(fly_out)
+                        .layout(Layout::top_down_justified(Align::Min)),
+                );
+                body_ui.set_clip_rect(fly_out);
+                body_ui.spacing_mut().item_spacing = Vec2::ZERO;
+                self.tab_body(
+                    body_ui,
+                    state,
+                    (surface_index, node_index),
+                    tab_viewer,
+                    spacing,
+                    Rect::NOTHING,
+                    fade_style,
+                    false,
+                );
+                ui.allocate_rect(fly_out, Sense::hover());
+            });
+    }
+
+    /// Shows the preview of a dragged tab from [`TabViewer::drag_preview_ui`] under the pointer.
+    ///
+    /// Returns `true` if the tab viewer has shown a preview.
+    fn drag_preview(
+        &mut self,
+        ui: &Ui,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> bool {
+        let Some(pointer_pos) = ui.ctx().pointer_interact_pos() else {
+            return false;
+        };
+        let Node::Leaf { tabs, viewport, .. } = &mut self.dock_state[surface_index][node_index]
+        else {
+            unreachable!()
+        };
+        let rect = Rect::from_min_size(pointer_pos, viewport.size());
+        let id = self.id.with("drag_preview");
+        let ui = &mut Ui::new(
+            ui.ctx().clone(),
+            id,
+            UiBuilder::new()
+                .layer_id(LayerId::new(Order::Tooltip, id))
+                .max_rect(rect),
+        );
+        ui.set_clip_rect(rect);
+        tab_viewer.drag_preview_ui(ui, &mut tabs[tab_index.0])
+    }
+
+    /// Moves the tabs for which [`TabViewer::stays_first`] returns `true` to the start of the leaf, e.g. after other
+    /// tabs have been dropped in front of them.
+    fn keep_tabs_first(
+        &mut self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let Node::Leaf { tabs, .. } = &mut self.dock_state[surface_index][node_index] else {
+            unreachable!()
+        };
+        if tabs.len() < 2 {
+            return;
+        }
+        let first: Vec<bool> = tabs
+            .iter_mut()
+            .map(|tab| tab_viewer.stays_first(tab))
+            .collect();
+        self.dock_state[surface_index][node_index].move_tabs_to_front(&first);
+    }
+
+    /// Reports double-clicks on the empty space of a tab bar to the tab viewer and as a [`DockEvent`].
+    fn tab_bar_empty_space_interaction(
+        &mut self,
+        response: &Response,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        if response.double_clicked() {
+            tab_viewer.on_tab_bar_double_click(surface_index, node_index);
+            self.events.push(DockEvent::TabBarDoubleClicked {
+                surface: surface_index,
+                node: node_index,
+            });
+        }
+    }
+
+    /// Returns whether the close all button is disabled because the leaf contains non-closable tabs, and whether
+    /// closing the whole window is disabled for the same reason.
+    fn close_all_disabled(
+        &mut self,
+        surface_index: SurfaceIndex,
+        node_index: NodeIndex,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> (bool, bool) {
+        // Current leaf contains non-closable tabs.
+        let disabled =
+            if let Node::Leaf { tabs, .. } = &mut self.dock_state[surface_index][node_index] {
+                !tabs.iter_mut().all(|tab| tab_viewer.closeable(tab))
+            } else {
+                unreachable!()
+            };
+
+        // Current window contains non-closable tabs.
+        let close_window_disabled = disabled
+            || !self.dock_state[surface_index].iter_mut().all(|node| {
+                if let Node::Leaf { tabs, .. } = node {
+                    tabs.iter_mut().all(|tab| tab_viewer.closeable(tab))
+                } else {
+                    true
+                }
+            });
+
+        (disabled, close_window_disabled)
+    }
+
+    /// Draws a thin handle in place of the tab bar of a leaf with a single tab,
+    /// which can be used to drag the tab just like its title.
+    fn tab_grab_handle(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut State,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        fade_style: Option<&Style>,
+    ) -> Rect {
+        let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+        let vertical = self.tab_bar_position.is_vertical();
+        let (handle_rect, _) = ui.allocate_exact_size(
+            if vertical {
+                vec2(Style::TAB_GRAB_HANDLE_HEIGHT, ui.available_height())
+            } else {
+                vec2(ui.available_width(), Style::TAB_GRAB_HANDLE_HEIGHT)
+            },
+            Sense::hover(),
+        );
+        ui.painter()
+            .rect_filled(handle_rect, style.tab_bar.rounding, style.tab_bar.bg_fill);
+
+        let tab_index = TabIndex(0);
+        let id = self
+            .id
+            .with((surface_index, "surface"))
+            .with((node_index, "node"))
+            .with((tab_index.0, "tab"));
+        let (draggable, title) = match &mut self.dock_state[surface_index][node_index] {
+            Node::Leaf { tabs, .. } => (
+                tab_viewer.draggable(&mut tabs[tab_index.0]),
+                tab_viewer.title(&mut tabs[tab_index.0]).text().to_owned(),
+            ),
+            _ => unreachable!(),
+        };
+        let sense = if draggable {
+            Sense::click_and_drag()
+        } else {
+            Sense::click()
+        };
+        let mut response = ui.interact(handle_rect, id, sense);
+        tab_widget_info(&response, &title, true);
+        let is_being_dragged = response.dragged()
+            && ui.input(|i| i.pointer.is_decidedly_dragging())
+            && self.draggable_tabs;
+
+        let grip_color = if response.hovered() || is_being_dragged {
+            style.tab.focused.text_color
+        } else {
+            style.tab_bar.hline_color
+        };
+        let grip_rect = Rect::from_center_size(
+            handle_rect.center(),
+            if vertical {
+                vec2(0.0, Style::TAB_GRAB_HANDLE_GRIP_WIDTH)
+            } else {
+                vec2(Style::TAB_GRAB_HANDLE_GRIP_WIDTH, 0.0)
+            },
+        );
+        ui.painter()
+            .line_segment([grip_rect.min, grip_rect.max], (2.0, grip_color));
+
+        if is_being_dragged {
+            ui.output_mut(|o| o.cursor_icon = CursorIcon::Grabbing);
+            let Node::Leaf { tabs, .. } = &mut self.dock_state[surface_index][node_index] else {
+                unreachable!()
+            };
+            let tab = &mut tabs[tab_index.0];
+
+            // Show the title of the dragged tab under the pointer, since it has no tab bar to be dragged from.
+            let label = tab_viewer.title(tab);
+            let modified = tab_viewer.is_modified(tab);
+            let tab_style = tab_viewer
+                .tab_style_override(tab, &style.tab)
+                .unwrap_or(style.tab.clone());
+            let layer_id = LayerId::new(Order::Tooltip, id);
+            let (title_size, layout) = if vertical {
+                (
+                    vec2(style.tab_bar.height, f32::INFINITY),
+                    Layout::top_down(Align::Center),
+                )
+            } else {
+                (
+                    vec2(f32::INFINITY, style.tab_bar.height),
+                    Layout::left_to_right(Align::Center),
+                )
+            };
+            let title_rect = Rect::from_min_size(handle_rect.min, title_size);
+            if !self.drag_preview(ui, (surface_index, node_index, tab_index), tab_viewer) {
+                ui.scope_builder(
+                    UiBuilder::new()
+                        .layer_id(layer_id)
+                        .max_rect(title_rect)
+                        .layout(layout),
+                    |ui| {
+                        self.tab_title(
                             ui,
                             &tab_style,
-                            id,
+                            id.with("dragged"),
                             label,
-                            is_active && Some((surface_index, node_index)) == focused,
-                            is_active,
-                            is_being_dragged,
-                            preferred_width,
-                            show_close_button,
-                            fade,
+                            true,
+                            true,
+                            true,
+                            true,
+                            modified,
+                            None,
+                            false,
+                            fade_style,
                         )
-                    })
-                    .response;
-                let title_id = response.id;
-
-                let response =
-                    tabs_ui.interact(response.rect, id.with("dragged"), Sense::click_and_drag());
-
-                if let Some(pointer_pos) = tabs_ui.ctx().pointer_interact_pos() {
-                    let start = *state.drag_start.get_or_insert(pointer_pos);
-                    let delta = pointer_pos - start;
-                    if delta.x.abs() > 30.0 || delta.y.abs() > 6.0 {
-                        tabs_ui
-                            .ctx()
-                            .transform_layer_shapes(layer_id, TSTransform::new(delta, 1.0));
-
-                        tabs_ui.memory_mut(|mem| {
-                            mem.data.insert_temp(
-                                self.id.with("drag_data"),
-                                Some(DragData {
-                                    src: TreeComponent::Tab(surface_index, node_index, tab_index),
-                                    rect: self.dock_state[surface_index][node_index]
-                                        .rect()
-                                        .unwrap(),
-                                }),
-                            );
-                        });
-                    }
-                }
-
-                (response, title_id)
-            } else {
-                let (mut response, close_response) = self.tab_title(
-                    tabs_ui,
-                    &tab_style,
-                    id,
-                    label,
-                    is_active && Some((surface_index, node_index)) == focused,
-                    is_active,
-                    is_being_dragged,
-                    preferred_width,
-                    show_close_button,
-                    fade,
+                    },
                 );
-                let title_id = response.id;
-                let close_clicked = close_response.is_some_and(|res| res.clicked());
-                let is_lonely_tab = self.dock_state[surface_index].num_tabs() == 1;
+            }
 
-                if self.show_tab_name_on_hover {
-                    let tabs = self.dock_state[surface_index][node_index]
-                        .tabs_mut()
-                        .expect("This node must be a leaf");
-                    let tab = &mut tabs[tab_index.0];
-                    response = response.on_hover_ui(|ui| {
-                        ui.label(tab_viewer.title(tab));
+            if let Some(pointer_pos) = ui.ctx().pointer_interact_pos() {
+                let start = *state.drag_start.get_or_insert(pointer_pos);
+                let delta = pointer_pos - start;
+                ui.ctx().transform_layer_shapes(
+                    layer_id,
+                    TSTransform::new(pointer_pos - handle_rect.min, 1.0),
+                );
+                if self.is_torn_off(delta) {
+                    ui.memory_mut(|mem| {
+                        mem.data.insert_temp(
+                            self.id.with("drag_data"),
+                            Some(DragData {
+                                src: TreeComponent::Tab(surface_index, node_index, tab_index),
+                                rect: self.dock_state[surface_index][node_index].rect().unwrap(),
+                                foreign: None,
+                            }),
+                        );
                     });
+                    self.set_external_drag_payload(
+                        ui.ctx(),
+                        (surface_index, node_index, tab_index),
+                    );
                 }
+            }
+        } else {
+            if self.draggable_tabs && draggable && ui.ctx().dragged_id().is_none() {
+                response = response.on_hover_cursor(CursorIcon::Grab);
+            }
+            let Node::Leaf { tabs, .. } = &mut self.dock_state[surface_index][node_index] else {
+                unreachable!()
+            };
+            let tab = &mut tabs[tab_index.0];
+            response = response.on_hover_ui(|ui| {
+                ui.label(tab_viewer.title(tab));
+            });
+        }
 
-                if self.tab_context_menus {
-                    let eject_button =
-                        Button::new(&self.dock_state.translations.tab_context_menu.eject_button);
-                    let close_button =
-                        Button::new(&self.dock_state.translations.tab_context_menu.close_button);
+        if response.clicked() {
+            self.new_focused = Some((surface_index, node_index));
+        }
+
+        handle_rect
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn tabs(
+        &mut self,
+        tabs_ui: &mut Ui,
+        state: &mut State,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        tabbar_outer_rect: Rect,
+        preferred_width: Option<f32>,
+        fade: Option<&Style>,
+    ) {
+        assert!(self.dock_state[surface_index][node_index].is_leaf());
+
+        let focused = self.dock_state.focused_leaf();
+        let scroll_to_id = self.id.with((surface_index, node_index, "scroll_to_tab"));
+        let scroll_to = tabs_ui.data(|d| d.get_temp::<TabIndex>(scroll_to_id));
+        if scroll_to.is_some() {
+            tabs_ui.data_mut(|d| d.remove::<TabIndex>(scroll_to_id));
+        }
+        let tabs_len = {
+            let tabs = self.dock_state[surface_index][node_index]
+                .tabs()
+                .expect("This node must be a leaf here");
+            tabs.len()
+        };
+
+        let ctx = tabs_ui.ctx().clone();
+        let tab_list_id = self.id.with((surface_index, node_index, "tab_list"));
+        let vertical = !tabs_ui.layout().is_horizontal();
+        with_tab_list(&ctx, tab_list_id, tabbar_outer_rect, vertical, || {
+            for tab_index in 0..tabs_len {
+                let id = self
+                    .id
+                    .with((surface_index, "surface"))
+                    .with((node_index, "node"))
+                    .with((tab_index, "tab"));
+                let tab_index = TabIndex(tab_index);
+                let is_being_dragged = tabs_ui.ctx().is_being_dragged(id)
+                    && tabs_ui.input(|i| i.pointer.is_decidedly_dragging())
+                    && self.draggable_tabs;
+
+                if is_being_dragged {
+                    tabs_ui.output_mut(|o| o.cursor_icon = CursorIcon::Grabbing);
+                } else if self.hidden_tabs.get(tab_index.0) == Some(&true) {
+                    // Keep the rects of the shown tabs at their indices.
+                    if let Node::Leaf { tab_rects, .. } =
+                        &mut self.dock_state[surface_index][node_index]
+                    {
+                        tab_rects.push(Rect::NOTHING);
+                    }
+                    continue;
+                }
+
+                let gap = self.tab_gap(tabs_ui, state, (surface_index, node_index, tab_index));
+                if gap > 0.0 {
+                    tabs_ui.add_space(gap);
+                }
 
+                let (
+                    is_active,
+                    label,
+                    tab_style,
+                    closeable,
+                    close_button_shown,
+                    draggable,
+                    modified,
+                    tab_id,
+                ) = {
                     let Node::Leaf { tabs, active, .. } =
                         &mut self.dock_state[surface_index][node_index]
                     else {
                         unreachable!()
                     };
-                    let tab = &mut tabs[tab_index.0];
+                    let style = fade.unwrap_or_else(|| self.style.as_ref().unwrap());
+                    let tab_style = tab_viewer.tab_style_override(&tabs[tab_index.0], &style.tab);
+                    (
+                        *active == tab_index || is_being_dragged,
+                        tab_viewer.title(&mut tabs[tab_index.0]),
+                        tab_style.unwrap_or(style.tab.clone()),
+                        tab_viewer.closeable(&mut tabs[tab_index.0]),
+                        tab_viewer.show_close_button(&mut tabs[tab_index.0]),
+                        tab_viewer.draggable(&mut tabs[tab_index.0]),
+                        tab_viewer.is_modified(&mut tabs[tab_index.0]),
+                        tab_viewer.id(&mut tabs[tab_index.0]),
+                    )
+                };
 
-                    response.context_menu(|ui| {
-                        tab_viewer.context_menu(ui, tab, surface_index, node_index);
-                        if (surface_index.is_main() || !is_lonely_tab)
-                            && tab_viewer.allowed_in_windows(tab)
-                            && ui.add(eject_button).clicked()
-                        {
-                            self.to_detach.push((surface_index, node_index, tab_index));
-                            ui.close_menu();
-                        }
-                        if show_close_button && ui.add(close_button).clicked() {
-                            if tab_viewer.on_close(tab) {
-                                self.to_remove
-                                    .push((surface_index, node_index, tab_index).into());
+                // Tabs waiting for the application to confirm closing them can't be closed again.
+                let closing = self.dock_state.is_closing(tab_id);
+                let label = if closing { label.italics() } else { label };
+                let can_close = self.show_close_buttons && closeable && !closing;
+                // Selected tabs look like active ones.
+                let selected = self.selectable_tabs && self.dock_state.is_tab_selected(tab_id);
+                let show_close_button = can_close && close_button_shown;
+
+                let (response, title_id) = if is_being_dragged {
+                    let layer_id = LayerId::new(Order::Tooltip, id);
+                    let trailing_layout = (
+                        self.tab_bar_position,
+                        self.layout_direction.is_right_to_left(),
+                    );
+                    let pointer_pos = tabs_ui.ctx().pointer_interact_pos();
+                    let drag_delta = pointer_pos.map(|pointer_pos| {
+                        pointer_pos - *state.drag_start.get_or_insert(pointer_pos)
+                    });
+                    let glued = pointer_pos.is_some_and(|pointer_pos| {
+                        self.is_glued_to_tab_bar(state, tabbar_outer_rect, pointer_pos)
+                    });
+                    let moved_out = !glued
+                        && drag_delta
+                            .is_some_and(|delta| state.tab_torn_off || self.is_torn_off(delta));
+                    let custom_preview = moved_out
+                        && self.drag_preview(
+                            tabs_ui,
+                            (surface_index, node_index, tab_index),
+                            tab_viewer,
+                        );
+                    let response = tabs_ui
+                        .scope_builder(UiBuilder::new().layer_id(layer_id), |ui| {
+                            // The title is still laid out, so that it keeps its place and the drag going.
+                            if custom_preview {
+                                ui.set_opacity(0.0);
+                            }
+                            let (_, _, trailing_rect) = self.tab_title(
+                                ui,
+                                &tab_style,
+                                id,
+                                label,
+                                is_active && Some((surface_index, node_index)) == focused,
+                                is_active || selected,
+                                is_being_dragged,
+                                draggable,
+                                modified,
+                                preferred_width,
+                                show_close_button,
+                                fade,
+                            );
+                            let tab = &mut self.dock_state[surface_index][node_index]
+                                .tabs_mut()
+                                .expect("This node must be a leaf")[tab_index.0];
+                            tab_title_trailing_ui(
+                                ui,
+                                id,
+                                trailing_rect,
+                                trailing_layout,
+                                tab,
+                                tab_viewer,
+                            );
+                        })
+                        .response;
+                    let title_id = response.id;
+                    state.dragged_tab_size = Some(response.rect.size());
+
+                    let response = tabs_ui.interact(
+                        response.rect,
+                        id.with("dragged"),
+                        Sense::click_and_drag(),
+                    );
+
+                    // A glued tab dropped back into its own place stays where it was.
+                    let on_own_place = glued
+                        && state
+                            .last_hover_pos
+                            .is_some_and(|pos| response.rect.contains(pos));
+
+                    if let Some(delta) = drag_delta {
+                        if glued {
+                            let delta = if self.tab_bar_position.is_vertical() {
+                                vec2(0.0, delta.y)
                             } else {
-                                *active = tab_index;
-                                self.new_focused = Some((surface_index, node_index));
+                                vec2(delta.x, 0.0)
+                            };
+                            tabs_ui
+                                .ctx()
+                                .transform_layer_shapes(layer_id, TSTransform::new(delta, 1.0));
+                        } else if moved_out {
+                            tabs_ui
+                                .ctx()
+                                .transform_layer_shapes(layer_id, TSTransform::new(delta, 1.0));
+                        }
+                        if moved_out || (glued && !on_own_place) {
+                            tabs_ui.memory_mut(|mem| {
+                                mem.data.insert_temp(
+                                    self.id.with("drag_data"),
+                                    Some(DragData {
+                                        src: TreeComponent::Tab(
+                                            surface_index,
+                                            node_index,
+                                            tab_index,
+                                        ),
+                                        rect: self.dock_state[surface_index][node_index]
+                                            .rect()
+                                            .unwrap(),
+                                        foreign: None,
+                                    }),
+                                );
+                            });
+                        }
+                        if moved_out {
+                            self.set_external_drag_payload(
+                                tabs_ui.ctx(),
+                                (surface_index, node_index, tab_index),
+                            );
+                        }
+                    }
+
+                    (response, title_id)
+                } else {
+                    let (mut response, close_response, trailing_rect) = self.tab_title(
+                        tabs_ui,
+                        &tab_style,
+                        id,
+                        label,
+                        is_active && Some((surface_index, node_index)) == focused,
+                        is_active || selected,
+                        is_being_dragged,
+                        draggable,
+                        modified,
+                        preferred_width,
+                        show_close_button,
+                        fade,
+                    );
+                    if let (true, Some(close_response)) = (self.collect_metrics, &close_response) {
+                        self.close_button_rects
+                            .push(((surface_index, node_index, tab_index), close_response.rect));
+                    }
+                    let trailing_layout = (
+                        self.tab_bar_position,
+                        self.layout_direction.is_right_to_left(),
+                    );
+                    let tab = &mut self.dock_state[surface_index][node_index]
+                        .tabs_mut()
+                        .expect("This node must be a leaf")[tab_index.0];
+                    tab_title_trailing_ui(
+                        tabs_ui,
+                        id,
+                        trailing_rect,
+                        trailing_layout,
+                        tab,
+                        tab_viewer,
+                    );
+                    let title_id = response.id;
+                    let mut close_clicked = close_response.is_some_and(|res| res.clicked());
+                    let is_lonely_tab = self.dock_state[surface_index].num_tabs() == 1;
+
+                    if self.show_tab_tooltips {
+                        let tabs = self.dock_state[surface_index][node_index]
+                            .tabs_mut()
+                            .expect("This node must be a leaf");
+                        let tab = &mut tabs[tab_index.0];
+                        response = response.on_hover_ui(|ui| tab_viewer.tab_tooltip_ui(ui, tab));
+                    }
+
+                    if self.tab_context_menus {
+                        let eject_button = Button::new(
+                            &self.dock_state.translations.tab_context_menu.eject_button,
+                        );
+                        let close_button = Button::new(
+                            &self.dock_state.translations.tab_context_menu.close_button,
+                        );
+                        let eject_selected_button = Button::new(
+                            &self
+                                .dock_state
+                                .translations
+                                .tab_context_menu
+                                .eject_selected_button,
+                        );
+                        let many_selected = selected && self.dock_state.selected_tabs().len() > 1;
+                        let equalize_button = Button::new(
+                            &self
+                                .dock_state
+                                .translations
+                                .tab_context_menu
+                                .equalize_button,
+                        );
+                        let mut equalize_clicked = false;
+                        let in_window = self.is_window_surface(surface_index);
+
+                        let Node::Leaf { tabs, .. } =
+                            &mut self.dock_state[surface_index][node_index]
+                        else {
+                            unreachable!()
+                        };
+                        let tab = &mut tabs[tab_index.0];
+
+                        response.context_menu(|ui| {
+                            tab_viewer.context_menu(ui, tab, surface_index, node_index);
+                            if !self.locked
+                                && draggable
+                                && (!in_window || !is_lonely_tab)
+                                && tab_viewer.allowed_in_windows(tab)
+                                && ui.add(eject_button).clicked()
+                            {
+                                self.to_detach.push((surface_index, node_index, tab_index));
+                                ui.close_menu();
                             }
-                            ui.close_menu();
+                            if !self.locked
+                                && many_selected
+                                && ui.add(eject_selected_button).clicked()
+                            {
+                                self.detach_selection = true;
+                                ui.close_menu();
+                            }
+                            if can_close && ui.add(close_button).clicked() {
+                                close_clicked = true;
+                                ui.close_menu();
+                            }
+                            if self.equalize_in_context_menu
+                                && !self.locked
+                                && ui.add(equalize_button).clicked()
+                            {
+                                equalize_clicked = true;
+                                ui.close_menu();
+                            }
+                        });
+
+                        if equalize_clicked {
+                            self.dock_state[surface_index].equalize_fractions();
                         }
-                    });
+                    }
+
+                    if close_clicked {
+                        self.request_close((surface_index, node_index, tab_index), tab_viewer);
+                    }
+
+                    if let Some(pos) = state.last_hover_pos {
+                        // Use response.rect.contains instead of
+                        // response.hovered as the dragged tab covers
+                        // the underlying tab
+                        // The gap opened before the tab belongs to it, so that it stays open while hovered.
+                        let hover_rect = self.extend_by_gap(response.rect, gap);
+                        if state.drag_start.is_some() && hover_rect.contains(pos) {
+                            self.tab_hover_rect = Some((hover_rect, tab_index));
+                            if !is_being_dragged && !is_active {
+                                self.activate_hovered_tab(
+                                    tabs_ui.ctx(),
+                                    state,
+                                    (surface_index, node_index, tab_index),
+                                );
+                            }
+                        }
+                    }
+
+                    (response, title_id)
+                };
+
+                if scroll_to == Some(tab_index) {
+                    self.scroll_tab_into_view(
+                        (surface_index, node_index),
+                        response.rect,
+                        tabs_ui.clip_rect(),
+                    );
+                    tabs_ui.ctx().request_repaint();
                 }
 
-                if close_clicked {
-                    let Node::Leaf { tabs, active, .. } =
-                        &mut self.dock_state[surface_index][node_index]
-                    else {
-                        unreachable!()
+                // Ctrl+clicking a tab toggles its selection instead of activating it, and other clicks deselect all.
+                let selection_clicked = self.selectable_tabs
+                    && response.clicked()
+                    && tabs_ui.input(|i| i.modifiers.command);
+                if selection_clicked {
+                    self.dock_state.set_tab_selected(tab_id, !selected);
+                } else if response.clicked() {
+                    self.dock_state.clear_tab_selection();
+                }
+
+                // Paint hline below each tab unless its active (or option says otherwise).
+                let Node::Leaf {
+                    tabs,
+                    active,
+                    tab_rects,
+                    ..
+                } = &mut self.dock_state[surface_index][node_index]
+                else {
+                    unreachable!()
+                };
+                tab_rects.push(response.rect.intersect(tabs_ui.clip_rect()));
+                let tab = &mut tabs[tab_index.0];
+                let style = fade.unwrap_or_else(|| self.style.as_ref().unwrap());
+                let tab_style = tab_viewer.tab_style_override(tab, &style.tab);
+                let tab_style = tab_style.as_ref().unwrap_or(&style.tab);
+
+                if !is_active || tab_style.hline_below_active_tab_name {
+                    let px = tabs_ui.ctx().pixels_per_point().recip();
+                    let span = if self.tab_bar_position.is_vertical() {
+                        Rect::from_x_y_ranges(tabbar_outer_rect.x_range(), response.rect.y_range())
+                    } else {
+                        Rect::from_x_y_ranges(response.rect.x_range(), tabbar_outer_rect.y_range())
                     };
-                    let tab = &mut tabs[tab_index.0];
+                    tabs_ui.painter().line_segment(
+                        body_edge(self.tab_bar_position, span, px),
+                        (px, style.tab_bar.hline_color),
+                    );
+                }
+
+                if !selection_clicked
+                    && (response.clicked()
+                        || (tabs_ui.memory(|m| m.has_focus(title_id))
+                            && tabs_ui
+                                .input(|i| i.key_pressed(Key::Enter) || i.key_pressed(Key::Space))))
+                {
+                    *active = tab_index;
+                    self.new_focused = Some((surface_index, node_index));
+                }
 
-                    if tab_viewer.on_close(tab) {
-                        self.to_remove
-                            .push((surface_index, node_index, tab_index).into());
+                tab_viewer.on_tab_button(tab, &response);
+
+                // The action is taken out for the time being, so that a custom one can be given the tab.
+                let middle_clicked = response.middle_clicked();
+                if middle_clicked || response.double_clicked() {
+                    let action = match middle_clicked {
+                        true => &mut self.tab_middle_click_action,
+                        false => &mut self.tab_double_click_action,
+                    };
+                    let mut action = std::mem::replace(action, TabAction::None);
+                    self.run_tab_action(
+                        &mut action,
+                        (surface_index, node_index, tab_index),
+                        can_close,
+                        draggable,
+                        tab_viewer,
+                    );
+                    match middle_clicked {
+                        true => self.tab_middle_click_action = action,
+                        false => self.tab_double_click_action = action,
+                    }
+                }
+            }
+        });
+    }
+
+    /// Does what `action` says to a tab which has been clicked in a particular way.
+    fn run_tab_action(
+        &mut self,
+        action: &mut TabAction<'_, Tab>,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+        can_close: bool,
+        draggable: bool,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        match action {
+            TabAction::None => {}
+            TabAction::Close => {
+                if can_close {
+                    self.request_close((surface_index, node_index, tab_index), tab_viewer);
+                }
+            }
+            TabAction::Detach => {
+                let is_lonely_tab = self.dock_state[surface_index].num_tabs() == 1;
+                let in_window = self.is_window_surface(surface_index);
+                let Node::Leaf { tabs, .. } = &mut self.dock_state[surface_index][node_index]
+                else {
+                    unreachable!()
+                };
+                if !self.locked
+                    && draggable
+                    && (!in_window || !is_lonely_tab)
+                    && tab_viewer.allowed_in_windows(&mut tabs[tab_index.0])
+                {
+                    self.to_detach.push((surface_index, node_index, tab_index));
+                }
+            }
+            TabAction::Maximize => {
+                let bounds = self.window_bounds.unwrap();
+                if let Some(window_state) = self.dock_state.get_window_state_mut(surface_index) {
+                    if window_state.is_maximized() {
+                        window_state.restore();
                     } else {
-                        *active = tab_index;
-                        self.new_focused = Some((surface_index, node_index));
+                        window_state.maximize(bounds);
                     }
                 }
+            }
+            TabAction::Custom(callback) => {
+                let Node::Leaf { tabs, .. } = &mut self.dock_state[surface_index][node_index]
+                else {
+                    unreachable!()
+                };
+                callback(&mut tabs[tab_index.0]);
+            }
+        }
+    }
+
+    /// Activates a tab whose title a tab has been dragged over for
+    /// [`OverlayFeel::tab_hover_activate_delay`](crate::OverlayFeel::tab_hover_activate_delay), so that the dragged
+    /// tab can be dropped into its body.
+    fn activate_hovered_tab(
+        &mut self,
+        ctx: &egui::Context,
+        state: &mut State,
+        tab: (SurfaceIndex, NodeIndex, TabIndex),
+    ) {
+        let Some(delay) = self
+            .style
+            .as_ref()
+            .unwrap()
+            .overlay
+            .feel
+            .tab_hover_activate_delay
+        else {
+            return;
+        };
+        let now = ctx.input(|i| i.time);
+        let since = match state.tab_hover_since {
+            Some((hovered, since)) if hovered == tab => since,
+            _ => {
+                state.tab_hover_since = Some((tab, now));
+                now
+            }
+        };
+        let left = delay as f64 - (now - since);
+        if left <= 0.0 {
+            self.dock_state.set_active_tab(tab);
+            state.tab_hover_since = None;
+        } else {
+            ctx.request_repaint_after_secs(left as f32);
+        }
+    }
+
+    /// Returns the ends of the edge of a tab's `rect` at which tabs are inserted before it.
+    fn leading_edge(&self, rect: Rect) -> [Pos2; 2] {
+        if self.tab_bar_position.is_vertical() {
+            [rect.left_top(), rect.right_top()]
+        } else if self.layout_direction.is_right_to_left() {
+            [rect.right_top(), rect.right_bottom()]
+        } else {
+            [rect.left_top(), rect.left_bottom()]
+        }
+    }
+
+    /// Animates the gap opened before a tab when the dragged tab would be inserted in front of it, returning its
+    /// size in this frame.
+    fn tab_gap(&self, ui: &Ui, state: &mut State, tab: (SurfaceIndex, NodeIndex, TabIndex)) -> f32 {
+        let vertical = self.tab_bar_position.is_vertical();
+        let target = match (self.tab_insert_target, state.dragged_tab_size) {
+            (Some(target), Some(size)) if target == tab => {
+                if vertical {
+                    size.y
+                } else {
+                    size.x
+                }
+            }
+            _ => 0.0,
+        };
+        let current = state
+            .tab_gaps
+            .iter()
+            .find(|&&(gap_tab, _)| gap_tab == tab)
+            .map_or(0.0, |&(_, size)| size);
+        if current == target {
+            return current;
+        }
+        let animation_time = ui.style().animation_time;
+        let step = if animation_time > 0.0 {
+            current.max(target) * ui.input(|i| i.stable_dt) / animation_time
+        } else {
+            f32::INFINITY
+        };
+        ui.ctx().request_repaint();
+        state.step_tab_gap(tab, target, step)
+    }
+
+    /// Extends the rect of a tab over the gap of the given size opened before it.
+    fn extend_by_gap(&self, rect: Rect, gap: f32) -> Rect {
+        if self.tab_bar_position.is_vertical() {
+            rect.with_min_y(rect.min.y - gap)
+        } else if self.layout_direction.is_right_to_left() {
+            rect.with_max_x(rect.max.x + gap)
+        } else {
+            rect.with_min_x(rect.min.x - gap)
+        }
+    }
+
+    /// Asks the tab viewer what to do with a tab the user wants to close.
+    fn request_close(
+        &mut self,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let Node::Leaf { tabs, active, .. } = &mut self.dock_state[surface_index][node_index]
+        else {
+            unreachable!()
+        };
+        let tab = &mut tabs[tab_index.0];
+
+        match tab_viewer.on_close(tab) {
+            OnCloseResponse::Close => {
+                self.to_remove
+                    .push((surface_index, node_index, tab_index).into());
+            }
+            OnCloseResponse::Focus => {
+                *active = tab_index;
+                self.new_focused = Some((surface_index, node_index));
+            }
+            OnCloseResponse::Ignore => {}
+            OnCloseResponse::Pending => {
+                let id = tab_viewer.id(tab);
+                self.dock_state.mark_closing(id);
+            }
+        }
+    }
+
+    /// Draws the tab add button.
+    #[allow(clippy::too_many_arguments)]
+    fn tab_plus(
+        &mut self,
+        ui: &mut Ui,
+        surface_index: SurfaceIndex,
+        node_index: NodeIndex,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        rect: Rect,
+        fade_style: Option<&Style>,
+    ) {
+        let ui = &mut ui.new_child(
+            UiBuilder::new()
+                .max_rect(rect)
+                .layout(Layout::left_to_right(Align::Center))
+                .id_salt((node_index, "tab_add")),
+        );
+
+        let (rect, mut response) = ui.allocate_exact_size(ui.available_size(), Sense::click());
+
+        response = response.on_hover_cursor(CursorIcon::PointingHand);
+
+        let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+        let color = if response.hovered() || response.has_focus() {
+            ui.painter()
+                .rect_filled(rect, Rounding::ZERO, style.buttons.add_tab_bg_fill);
+            style.buttons.add_tab_active_color
+        } else {
+            style.buttons.add_tab_color
+        };
+
+        let mut plus_rect = rect;
+
+        rect_set_size_centered(&mut plus_rect, Vec2::splat(Style::TAB_ADD_PLUS_SIZE));
+
+        ui.painter().line_segment(
+            [plus_rect.center_top(), plus_rect.center_bottom()],
+            Stroke::new(1.0, color),
+        );
+        ui.painter().line_segment(
+            [plus_rect.right_center(), plus_rect.left_center()],
+            Stroke::new(1.0, color),
+        );
+
+        // Draw button border on the side facing the tabs.
+        ui.painter().line_segment(
+            self.button_border(rect, false),
+            Stroke::new(
+                ui.ctx().pixels_per_point().recip(),
+                style.buttons.add_tab_border_color,
+            ),
+        );
+
+        let popup_id = ui.id().with("tab_add_popup");
+        let size_id = popup_id.with("size");
+        let size = ui.data(|d| d.get_temp::<Vec2>(size_id));
+        let layout = PopupLayout {
+            width: size
+                .map(|size| size.x)
+                .or(style.buttons.add_tab_popup_width),
+            align: style.buttons.add_tab_popup_align,
+            max_height: size
+                .map(|size| size.y)
+                .or(style.buttons.add_tab_popup_max_height),
+        };
+        let new_size =
+            popup_under_widget_with_layout(ui, popup_id, &response, true, layout, |ui| {
+                tab_viewer.add_popup(ui, surface_index, node_index)
+            });
+        if let Some(new_size) = new_size.filter(|&new_size| new_size != size) {
+            // The popup has already been laid out, so it's resized in the next frame.
+            ui.data_mut(|d| match new_size {
+                Some(new_size) => d.insert_temp(size_id, new_size),
+                None => d.remove::<Vec2>(size_id),
+            });
+            ui.ctx().request_repaint();
+        }
 
-                if let Some(pos) = state.last_hover_pos {
-                    // Use response.rect.contains instead of
-                    // response.hovered as the dragged tab covers
-                    // the underlying tab
-                    if state.drag_start.is_some() && response.rect.contains(pos) {
-                        self.tab_hover_rect = Some((response.rect, tab_index));
-                    }
-                }
+        if response.clicked() {
+            if self.show_add_popup {
+                ui.memory_mut(|mem| mem.toggle_popup(popup_id));
+            }
+            tab_viewer.on_add(surface_index, node_index);
+        }
+    }
+
+    /// Shows the buttons on the edges of the body of a hovered leaf which split it in place, see
+    /// [`DockArea::show_split_buttons`].
+    fn split_buttons(
+        &mut self,
+        ui: &mut Ui,
+        state: &State,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let Node::Leaf { viewport, .. } = self.dock_state[surface_index][node_index] else {
+            unreachable!();
+        };
+        let size = Style::SPLIT_BUTTON_SIZE;
+        if state.drag_start.is_some()
+            || viewport.width().min(viewport.height()) < 3.0 * size
+            || !ui.rect_contains_pointer(viewport)
+        {
+            return;
+        }
 
-                (response, title_id)
+        let allowed_splits =
+            self.allowed_splits & tab_viewer.allowed_splits_for_node(surface_index, node_index);
+        let style = self.style.as_ref().unwrap();
+        let inset = Style::SPLIT_BUTTON_MARGIN + size / 2.0;
+        for split in [Split::Left, Split::Right, Split::Above, Split::Below] {
+            if !allowed_splits.allows(split) {
+                continue;
+            }
+            let center = match split {
+                Split::Left => pos2(viewport.left() + inset, viewport.center().y),
+                Split::Right => pos2(viewport.right() - inset, viewport.center().y),
+                Split::Above => pos2(viewport.center().x, viewport.top() + inset),
+                Split::Below => pos2(viewport.center().x, viewport.bottom() - inset),
             };
+            let rect = Rect::from_center_size(center, Vec2::splat(size));
+            let id = self
+                .id
+                .with((surface_index, node_index, split, "split_button"));
+            let response = ui
+                .interact(rect, id, Sense::click())
+                .on_hover_cursor(CursorIcon::PointingHand);
 
-            // Paint hline below each tab unless its active (or option says otherwise).
-            let Node::Leaf { tabs, active, .. } = &mut self.dock_state[surface_index][node_index]
-            else {
-                unreachable!()
+            let (bg_fill, color) = if response.hovered() || response.has_focus() {
+                (
+                    style.buttons.add_tab_bg_fill,
+                    style.buttons.add_tab_active_color,
+                )
+            } else {
+                (style.tab_bar.bg_fill, style.buttons.add_tab_color)
             };
-            let tab = &mut tabs[tab_index.0];
-            let style = fade.unwrap_or_else(|| self.style.as_ref().unwrap());
-            let tab_style = tab_viewer.tab_style_override(tab, &style.tab);
-            let tab_style = tab_style.as_ref().unwrap_or(&style.tab);
-
-            if !is_active || tab_style.hline_below_active_tab_name {
-                let px = tabs_ui.ctx().pixels_per_point().recip();
-                tabs_ui.painter().hline(
-                    response.rect.x_range(),
-                    tabbar_outer_rect.bottom() - px,
-                    (px, style.tab_bar.hline_color),
-                );
-            }
+            ui.painter().rect(
+                rect,
+                Rounding::same(2.0),
+                bg_fill,
+                Stroke::new(1.0, style.buttons.add_tab_border_color),
+            );
 
-            if response.clicked()
-                || (tabs_ui.memory(|m| m.has_focus(title_id))
-                    && tabs_ui.input(|i| i.key_pressed(Key::Enter) || i.key_pressed(Key::Space)))
-            {
-                *active = tab_index;
-                self.new_focused = Some((surface_index, node_index));
-            }
+            // A pane with the half which becomes the new leaf filled in.
+            let icon = Rect::from_center_size(center, Vec2::splat(Style::SPLIT_BUTTON_ICON_SIZE));
+            let new_half = match split {
+                Split::Left => icon.with_max_x(icon.center().x),
+                Split::Right => icon.with_min_x(icon.center().x),
+                Split::Above => icon.with_max_y(icon.center().y),
+                Split::Below => icon.with_min_y(icon.center().y),
+            };
+            ui.painter().rect_filled(new_half, Rounding::ZERO, color);
+            ui.painter()
+                .rect_stroke(icon, Rounding::ZERO, Stroke::new(1.0, color));
 
-            if self.show_close_buttons && tab_viewer.closeable(tab) && response.middle_clicked() {
-                if tab_viewer.on_close(tab) {
-                    self.to_remove
-                        .push((surface_index, node_index, tab_index).into());
-                } else {
-                    *active = tab_index;
-                    self.new_focused = Some((surface_index, node_index));
-                }
+            let response = response.on_hover_text(&self.dock_state.translations.leaf.split_button);
+            if response.clicked() {
+                self.to_split.push((surface_index, node_index, split));
             }
-
-            tab_viewer.on_tab_button(tab, &response);
         }
     }
 
-    /// Draws the tab add button.
-    #[allow(clippy::too_many_arguments)]
-    fn tab_plus(
+    /// Draws the tab search button and the popup it opens, which lists the tabs of the leaf whose titles contain
+    /// the text typed into it.
+    fn tab_search(
         &mut self,
         ui: &mut Ui,
         surface_index: SurfaceIndex,
         node_index: NodeIndex,
         tab_viewer: &mut impl TabViewer<Tab = Tab>,
-        tabbar_outer_rect: Rect,
-        offset: f32,
+        rect: Rect,
         fade_style: Option<&Style>,
     ) {
-        let rect = Rect::from_min_max(
-            tabbar_outer_rect.right_top() - vec2(Style::TAB_ADD_BUTTON_SIZE + offset, 0.0),
-            tabbar_outer_rect.right_bottom() - vec2(offset, 2.0),
-        );
-
         let ui = &mut ui.new_child(
             UiBuilder::new()
                 .max_rect(rect)
                 .layout(Layout::left_to_right(Align::Center))
-                .id_salt((node_index, "tab_add")),
+                .id_salt((node_index, "tab_search")),
         );
 
-        let (rect, mut response) = ui.allocate_exact_size(ui.available_size(), Sense::click());
-
-        response = response.on_hover_cursor(CursorIcon::PointingHand);
+        let (rect, response) = ui.allocate_exact_size(ui.available_size(), Sense::click());
+        let response = response
+            .on_hover_cursor(CursorIcon::PointingHand)
+            .on_hover_text(self.dock_state.translations.leaf.search_button.as_str());
 
         let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
         let color = if response.hovered() || response.has_focus() {
@@ -528,39 +1999,119 @@ impl<Tab> DockArea<'_, Tab> {
             style.buttons.add_tab_color
         };
 
-        let mut plus_rect = rect;
-
-        rect_set_size_centered(&mut plus_rect, Vec2::splat(Style::TAB_ADD_PLUS_SIZE));
-
-        ui.painter().line_segment(
-            [plus_rect.center_top(), plus_rect.center_bottom()],
-            Stroke::new(1.0, color),
-        );
+        // Draw a magnifying glass.
+        let mut icon_rect = rect;
+        rect_set_size_centered(&mut icon_rect, Vec2::splat(Style::TAB_SEARCH_ICON_SIZE));
+        let radius = icon_rect.width() * 0.35;
+        let center = icon_rect.min + Vec2::splat(radius);
+        ui.painter()
+            .circle_stroke(center, radius, Stroke::new(1.0, color));
         ui.painter().line_segment(
-            [plus_rect.right_center(), plus_rect.left_center()],
+            [
+                center + Vec2::splat(radius * FRAC_1_SQRT_2),
+                icon_rect.right_bottom(),
+            ],
             Stroke::new(1.0, color),
         );
 
-        // Draw button left border.
-        ui.painter().vline(
-            rect.left(),
-            rect.y_range(),
+        // Draw button border on the side facing the tabs.
+        ui.painter().line_segment(
+            self.button_border(rect, false),
             Stroke::new(
                 ui.ctx().pixels_per_point().recip(),
                 style.buttons.add_tab_border_color,
             ),
         );
 
-        let popup_id = ui.id().with("tab_add_popup");
-        popup_under_widget(ui, popup_id, &response, |ui| {
-            tab_viewer.add_popup(ui, surface_index, node_index);
-        });
-
+        let popup_id = ui.id().with("tab_search_popup");
+        let query_id = popup_id.with("query");
         if response.clicked() {
-            if self.show_add_popup {
-                ui.memory_mut(|mem| mem.toggle_popup(popup_id));
+            ui.memory_mut(|mem| mem.toggle_popup(popup_id));
+            ui.data_mut(|d| d.remove::<String>(query_id));
+        }
+
+        let hint = self.dock_state.translations.leaf.search_button.clone();
+        let chosen = popup_under_widget(ui, popup_id, &response, false, |ui| {
+            ui.set_min_width(Style::TAB_SEARCH_POPUP_WIDTH);
+            let mut query = ui
+                .data(|d| d.get_temp::<String>(query_id))
+                .unwrap_or_default();
+            let query_response = ui.add(TextEdit::singleline(&mut query).hint_text(hint));
+            if response.clicked() {
+                query_response.request_focus();
             }
-            tab_viewer.on_add(surface_index, node_index);
+            let query_lowercase = query.to_lowercase();
+            ui.data_mut(|d| d.insert_temp(query_id, query));
+
+            let Node::Leaf { tabs, active, .. } = &mut self.dock_state[surface_index][node_index]
+            else {
+                unreachable!()
+            };
+            let mut first_match = None;
+            let mut chosen = None;
+            ScrollArea::vertical()
+                .max_height(Style::TAB_SEARCH_POPUP_MAX_HEIGHT)
+                .show(ui, |ui| {
+                    for (index, tab) in tabs.iter_mut().enumerate() {
+                        let title = tab_viewer.title(tab);
+                        if !title.text().to_lowercase().contains(&query_lowercase) {
+                            continue;
+                        }
+                        first_match.get_or_insert(TabIndex(index));
+                        if ui.selectable_label(index == active.0, title).clicked() {
+                            chosen = Some(TabIndex(index));
+                        }
+                    }
+                });
+
+            // Pressing enter in the search field picks the first tab on the list.
+            if query_response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
+                chosen = chosen.or(first_match);
+            }
+            chosen
+        })
+        .flatten();
+
+        if let Some(tab_index) = chosen {
+            ui.memory_mut(|mem| mem.close_popup());
+            self.dock_state[surface_index].set_active_tab(node_index, tab_index);
+            self.new_focused = Some((surface_index, node_index));
+            self.scroll_to_tab(ui.ctx(), (surface_index, node_index, tab_index));
+        }
+    }
+
+    /// Scrolls the tab bar of a leaf so that the tab at `tab_rect` is fully visible inside of `clip_rect`.
+    fn scroll_tab_into_view(
+        &mut self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_rect: Rect,
+        clip_rect: Rect,
+    ) {
+        let Node::Leaf { scroll, .. } = &mut self.dock_state[surface_index][node_index] else {
+            unreachable!()
+        };
+        // How far the start and the end of the tab are past the start and the end of the visible part of the tab
+        // bar, in the direction in which the tabs are laid out.
+        let (past_start, past_end) = if self.tab_bar_position.is_vertical() {
+            (
+                tab_rect.top() - clip_rect.top(),
+                tab_rect.bottom() - clip_rect.bottom(),
+            )
+        } else if self.layout_direction.is_right_to_left() {
+            (
+                clip_rect.right() - tab_rect.right(),
+                clip_rect.left() - tab_rect.left(),
+            )
+        } else {
+            (
+                tab_rect.left() - clip_rect.left(),
+                tab_rect.right() - clip_rect.right(),
+            )
+        };
+        if past_start < 0.0 {
+            *scroll -= past_start;
+        } else if past_end > 0.0 {
+            *scroll -= past_end.min(past_start);
         }
     }
 
@@ -572,16 +2123,11 @@ impl<Tab> DockArea<'_, Tab> {
         ui: &mut Ui,
         surface_index: SurfaceIndex,
         node_index: NodeIndex,
-        tabbar_outer_rect: Rect,
+        rect: Rect,
         fade_style: Option<&Style>,
         disabled: bool,
         close_window_disabled: bool,
     ) {
-        let rect = Rect::from_min_max(
-            tabbar_outer_rect.right_top() - vec2(Style::TAB_CLOSE_ALL_BUTTON_SIZE, 0.0),
-            tabbar_outer_rect.right_bottom() - vec2(0.0, 2.0),
-        );
-
         let ui = &mut ui.new_child(
             UiBuilder::new()
                 .max_rect(rect)
@@ -637,7 +2183,7 @@ impl<Tab> DockArea<'_, Tab> {
         } else {
             // Close all tabs in this leaf
             if !disabled {
-                if !surface_index.is_main() && self.secondary_button_context_menu {
+                if self.is_window_surface(surface_index) && self.secondary_button_context_menu {
                     response.context_menu(|ui| {
                         ui.add_enabled_ui(!close_window_disabled, |ui| {
                             if ui
@@ -688,10 +2234,9 @@ impl<Tab> DockArea<'_, Tab> {
             );
         }
 
-        // Draw button left border.
-        ui.painter().vline(
-            rect.left(),
-            rect.y_range(),
+        // Draw button border on the side facing the tabs.
+        ui.painter().line_segment(
+            self.button_border(rect, false),
             Stroke::new(
                 ui.ctx().pixels_per_point().recip(),
                 style.buttons.close_all_tabs_border_color,
@@ -703,21 +2248,42 @@ impl<Tab> DockArea<'_, Tab> {
         }
     }
 
-    /// Draws the collapse button.
+    /// Returns `true` if a leaf can be moved into a new window by dragging its collapse button, which is when all of
+    /// its tabs may be dragged into windows and it isn't already the only leaf in a window.
+    fn leaf_detachable(
+        &mut self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> bool {
+        if self.locked
+            || self.is_window_surface(surface_index)
+                && self.dock_state[surface_index]
+                    .iter()
+                    .filter(|node| node.is_leaf())
+                    .count()
+                    < 2
+        {
+            return false;
+        }
+        let Node::Leaf { tabs, .. } = &mut self.dock_state[surface_index][node_index] else {
+            unreachable!()
+        };
+        !tabs.is_empty()
+            && tabs
+                .iter_mut()
+                .all(|tab| tab_viewer.draggable(tab) && tab_viewer.allowed_in_windows(tab))
+    }
+
+    /// Draws the collapse button, which can also be dragged to move the leaf into a new window if it's `detachable`.
     fn tab_collapse(
         &mut self,
         ui: &mut Ui,
-        surface_index: SurfaceIndex,
-        node_index: NodeIndex,
-        tabbar_outer_rect: Rect,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        rect: Rect,
         fade_style: Option<&Style>,
         collapsed: bool,
+        detachable: bool,
     ) {
-        let rect = Rect::from_min_max(
-            tabbar_outer_rect.left_top(),
-            tabbar_outer_rect.left_bottom() + vec2(Style::TAB_COLLAPSE_BUTTON_SIZE, 0.0),
-        );
-
         let ui = &mut ui.new_child(
             UiBuilder::new()
                 .max_rect(rect)
@@ -725,10 +2291,29 @@ impl<Tab> DockArea<'_, Tab> {
                 .id_salt((node_index, "tab_collapse")),
         );
 
-        let (rect, mut response) = ui.allocate_exact_size(ui.available_size(), Sense::click());
+        let sense = if detachable {
+            Sense::click_and_drag()
+        } else {
+            Sense::click()
+        };
+        let (rect, mut response) = ui.allocate_exact_size(ui.available_size(), sense);
 
         response = response.on_hover_cursor(CursorIcon::PointingHand);
 
+        if response.dragged() {
+            let distance = ui.input(|i| {
+                i.pointer
+                    .press_origin()
+                    .zip(i.pointer.interact_pos())
+                    .map_or(0.0, |(origin, pos)| origin.distance(pos))
+            });
+            if distance > Style::LEAF_DETACH_DRAG_DISTANCE {
+                self.to_detach_leaf.push((surface_index, node_index));
+                // Another leaf may take this one's place and index, and with it the button's id.
+                ui.ctx().stop_dragging();
+            }
+        }
+
         let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
 
         // Whether we're on "secondary button mode" due to modifier keys
@@ -750,13 +2335,13 @@ impl<Tab> DockArea<'_, Tab> {
             Self::draw_chevron_down(ui, style, color, arrow_rect);
         } else {
             // Draw arrow.
-            Self::draw_arrow(collapsed, ui, color, arrow_rect);
+            let rtl = self.layout_direction.is_right_to_left();
+            Self::draw_arrow(collapsed, rtl, ui, color, arrow_rect);
         }
 
-        // Draw button right border.
-        ui.painter().vline(
-            rect.right(),
-            rect.y_range(),
+        // Draw button border on the side facing the tabs.
+        ui.painter().line_segment(
+            self.button_border(rect, true),
             Stroke::new(
                 ui.ctx().pixels_per_point().recip(),
                 style.buttons.collapse_tabs_border_color,
@@ -767,13 +2352,12 @@ impl<Tab> DockArea<'_, Tab> {
             if on_secondary_button {
                 self.window_toggle_minimized(surface_index);
             } else {
-                self.dock_state[surface_index][node_index].set_collapsed(!collapsed);
-                self.dock_state[surface_index].node_update_collapsed(node_index);
-                self.window_update_collapsed(surface_index, node_index);
+                self.dock_state
+                    .animate_leaf_collapsed((surface_index, node_index), !collapsed);
             }
         }
 
-        if !surface_index.is_main() && self.secondary_button_context_menu {
+        if self.is_window_surface(surface_index) && self.secondary_button_context_menu {
             response.context_menu(|ui| {
                 if ui
                     .button(&self.dock_state.translations.leaf.minimize_button)
@@ -790,8 +2374,23 @@ impl<Tab> DockArea<'_, Tab> {
         }
     }
 
+    /// Returns the border of a button at either end of the tab bar, on the side facing the tabs.
+    fn button_border(&self, rect: Rect, before_tabs: bool) -> [Pos2; 2] {
+        if self.tab_bar_position.is_vertical() {
+            if before_tabs {
+                [rect.left_bottom(), rect.right_bottom()]
+            } else {
+                [rect.left_top(), rect.right_top()]
+            }
+        } else if before_tabs != self.layout_direction.is_right_to_left() {
+            [rect.right_top(), rect.right_bottom()]
+        } else {
+            [rect.left_top(), rect.left_bottom()]
+        }
+    }
+
     fn show_tooltip_hints(&mut self, surface_index: SurfaceIndex, response: Response) -> Response {
-        if !surface_index.is_main()
+        if self.is_window_surface(surface_index)
             && self.show_secondary_button_hint
             && (self.secondary_button_context_menu || self.secondary_button_on_modifier)
         {
@@ -821,7 +2420,7 @@ impl<Tab> DockArea<'_, Tab> {
         ui: &mut Ui,
         response: &Response,
     ) -> bool {
-        !surface_index.is_main()
+        self.is_window_surface(surface_index)
             && self.secondary_button_on_modifier
             && ui.input(|i| {
                 i.modifiers
@@ -830,6 +2429,35 @@ impl<Tab> DockArea<'_, Tab> {
             && (response.hovered() || response.has_focus() || response.is_pointer_button_down_on())
     }
 
+    /// Returns `true` if a tab dragged by `delta` has moved far enough to be torn off its tab bar, see
+    /// [`OverlayFeel::tear_off_distance`](crate::OverlayFeel::tear_off_distance).
+    fn is_torn_off(&self, delta: Vec2) -> bool {
+        let distance = self.style.as_ref().unwrap().overlay.feel.tear_off_distance;
+        delta.x.abs() > distance.x || delta.y.abs() > distance.y
+    }
+
+    /// Returns `true` if the dragged tab stays glued to its tab bar, see
+    /// [`OverlayFeel::tab_bar_glue_distance`](crate::OverlayFeel::tab_bar_glue_distance). Once torn off, it can't be
+    /// glued back until it's dropped.
+    fn is_glued_to_tab_bar(&self, state: &mut State, tab_bar: Rect, pointer: Pos2) -> bool {
+        let feel = &self.style.as_ref().unwrap().overlay.feel;
+        let Some(distance) = feel.tab_bar_glue_distance else {
+            return false;
+        };
+        let glued = !state.tab_torn_off && tab_bar.expand(distance).contains(pointer);
+        state.tab_torn_off = !glued;
+        state.glued_tab_bar = glued.then_some(tab_bar);
+        glued
+    }
+
+    /// Returns `true` if the surface is a window, which has a few more buttons than other surfaces.
+    fn is_window_surface(&self, surface_index: SurfaceIndex) -> bool {
+        matches!(
+            self.dock_state.get_surface(surface_index),
+            Some(Surface::Window(..))
+        )
+    }
+
     fn draw_close_window_symbol(ui: &mut Ui, stroke_color: Color32, close_all_rect: Rect) {
         ui.painter().add(Shape::line(
             vec![
@@ -855,9 +2483,16 @@ impl<Tab> DockArea<'_, Tab> {
         );
     }
 
-    fn draw_arrow(collapsed: bool, ui: &mut Ui, color: Color32, arrow_rect: Rect) {
+    fn draw_arrow(collapsed: bool, rtl: bool, ui: &mut Ui, color: Color32, arrow_rect: Rect) {
         ui.painter().add(Shape::convex_polygon(
-            if collapsed {
+            if collapsed && rtl {
+                // Arrow pointing leftwards.
+                vec![
+                    arrow_rect.right_top(),
+                    arrow_rect.right_bottom(),
+                    arrow_rect.left_center(),
+                ]
+            } else if collapsed {
                 // Arrow pointing rightwards.
                 vec![
                     arrow_rect.left_top(),
@@ -915,31 +2550,11 @@ impl<Tab> DockArea<'_, Tab> {
         ));
     }
 
-    /// Updates the collapsed state of the node and its parents.
-    fn window_update_collapsed(&mut self, surface_index: SurfaceIndex, node_index: NodeIndex) {
-        let surface = &mut self.dock_state[surface_index];
-        let collapsed = surface[node_index].is_collapsed();
-        if !collapsed {
-            if let Some(window_state) = self.dock_state.get_window_state_mut(surface_index) {
-                window_state.set_new(true);
-            }
-        } else if surface.root_node().is_some_and(|root| root.is_collapsed()) {
-            let root_index = NodeIndex::root();
-            let surface_height = if surface.root_node().is_some() {
-                surface[root_index].rect().unwrap().height()
-            } else {
-                0.0
-            };
-            if let Some(window_state) = self.dock_state.get_window_state_mut(surface_index) {
-                window_state.set_expanded_height(surface_height);
-            }
-        }
-    }
-
     /// * `active` means "the tab that is opened in the parent panel".
     /// * `focused` means "the tab that was last interacted with".
     ///
-    /// Returns the main button response plus the response of the close button, if any.
+    /// Returns the main button response, the response of the close button, if any, and the area reserved for
+    /// [`TabViewer::tab_title_trailing_ui`] between the text and the close button.
     #[allow(clippy::too_many_arguments)]
     fn tab_title(
         &mut self,
@@ -950,30 +2565,58 @@ impl<Tab> DockArea<'_, Tab> {
         focused: bool,
         active: bool,
         is_being_dragged: bool,
+        draggable: bool,
+        modified: bool,
         preferred_width: Option<f32>,
         show_close_button: bool,
         fade: Option<&Style>,
-    ) -> (Response, Option<Response>) {
+    ) -> (Response, Option<Response>, Rect) {
         let style = fade.unwrap_or_else(|| self.style.as_ref().unwrap());
-        let galley = label.into_galley(ui, None, f32::INFINITY, TextStyle::Button);
         let x_spacing = 8.0;
-        let text_width = galley.size().x + 2.0 * x_spacing;
         let close_button_size = if show_close_button {
             Style::TAB_CLOSE_BUTTON_SIZE.min(style.tab_bar.height)
         } else {
             0.0
         };
+        let modified_size = if modified {
+            Style::TAB_MODIFIED_DOT_SIZE
+        } else {
+            0.0
+        };
+        // The trailing UI is shown after the title has been laid out, so use its size from the last frame.
+        let trailing_size = ui
+            .data(|d| d.get_temp::<f32>(id.with("trailing_ui_size")))
+            .unwrap_or(0.0);
+        let decorations_size = trailing_size + close_button_size + modified_size;
+
+        let max_text_width = tab_style.maximum_width.map_or(f32::INFINITY, |width| {
+            (width - decorations_size - 2.0 * x_spacing).at_least(0.0)
+        });
+        let title = label.text().to_owned();
+        let galley = tab_title_galley(ui, label, max_text_width, tab_style.title_truncation);
+        let text_width = galley.size().x + 2.0 * x_spacing;
 
         // Compute total width of the tab bar.
         let minimum_width = tab_style
             .minimum_width
             .unwrap_or(0.0)
-            .at_least(text_width + close_button_size);
+            .at_least(text_width + decorations_size);
         let tab_width = preferred_width.unwrap_or(0.0).at_least(minimum_width);
 
-        let (_, tab_rect) = ui.allocate_space(vec2(tab_width, ui.available_height()));
-        let mut response = ui.interact(tab_rect, id, Sense::click_and_drag());
-        if ui.ctx().dragged_id().is_none() && self.draggable_tabs {
+        let position = self.tab_bar_position;
+        let (_, tab_rect) = ui.allocate_space(if position.is_vertical() {
+            vec2(ui.available_width(), tab_width)
+        } else {
+            vec2(tab_width, ui.available_height())
+        });
+        let sense = if draggable {
+            Sense::click_and_drag()
+        } else {
+            Sense::click()
+        };
+        let mut response = ui.interact(tab_rect, id, sense);
+        tab_widget_info(&response, &title, active);
+        if ui.ctx().dragged_id().is_none() && self.draggable_tabs && draggable {
             response = response.on_hover_cursor(CursorIcon::Grab);
         }
 
@@ -997,47 +2640,136 @@ impl<Tab> DockArea<'_, Tab> {
             &tab_style.inactive
         };
 
+        // Rounding is specified for tabs above the body, so turn it towards the tab bar's edge.
+        let rounding = rotate_rounding(tab_style.rounding, position);
+
         // Draw the full tab first and then the stroke on top to avoid the stroke
         // mixing with the background color.
         ui.painter()
-            .rect_filled(tab_rect, tab_style.rounding, tab_style.bg_fill);
+            .rect_filled(tab_rect, rounding, tab_style.bg_fill);
         let stroke_rect = rect_stroke_box(tab_rect, 1.0);
         ui.painter().rect_stroke(
             stroke_rect,
-            tab_style.rounding,
+            rounding,
             Stroke::new(1.0, tab_style.outline_color),
         );
         if !is_being_dragged {
             // Make the tab name area connect with the tab ui area.
-            ui.painter().hline(
-                RangeInclusive::new(
-                    stroke_rect.min.x + f32::max(tab_style.rounding.sw, 1.5),
-                    stroke_rect.max.x - f32::max(tab_style.rounding.se, 1.5),
-                ),
-                stroke_rect.bottom(),
-                Stroke::new(2.0, tab_style.bg_fill),
-            );
+            let r = stroke_rect;
+            let edge = match position {
+                TabBarPosition::Top => [
+                    pos2(r.min.x + f32::max(rounding.sw, 1.5), r.bottom()),
+                    pos2(r.max.x - f32::max(rounding.se, 1.5), r.bottom()),
+                ],
+                TabBarPosition::Bottom => [
+                    pos2(r.min.x + f32::max(rounding.nw, 1.5), r.top()),
+                    pos2(r.max.x - f32::max(rounding.ne, 1.5), r.top()),
+                ],
+                TabBarPosition::Left => [
+                    pos2(r.right(), r.min.y + f32::max(rounding.ne, 1.5)),
+                    pos2(r.right(), r.max.y - f32::max(rounding.se, 1.5)),
+                ],
+                TabBarPosition::Right => [
+                    pos2(r.left(), r.min.y + f32::max(rounding.nw, 1.5)),
+                    pos2(r.left(), r.max.y - f32::max(rounding.sw, 1.5)),
+                ],
+            };
+            ui.painter()
+                .line_segment(edge, Stroke::new(2.0, tab_style.bg_fill));
         }
 
+        // On vertical tab bars, the title is read from bottom to top on the left and from top to bottom on the
+        // right, with the close button at its end.
+        let rtl = self.layout_direction.is_right_to_left();
         let mut text_rect = tab_rect;
-        text_rect.set_width(text_rect.width() - close_button_size);
-        let text_pos = {
-            let pos = Align2::CENTER_CENTER.pos_in_rect(&text_rect.shrink2(vec2(x_spacing, 0.0)));
-            pos - galley.size() / 2.0
+        let mut trailing_rect = tab_rect;
+        let mut modified_rect = tab_rect;
+        match position {
+            TabBarPosition::Left => {
+                trailing_rect.set_top(tab_rect.top() + close_button_size);
+                trailing_rect.set_height(trailing_size);
+                modified_rect.set_top(trailing_rect.bottom());
+                modified_rect.set_height(modified_size);
+                text_rect.set_top(modified_rect.bottom());
+            }
+            TabBarPosition::Right => {
+                trailing_rect.set_bottom(tab_rect.bottom() - close_button_size);
+                trailing_rect.set_top(trailing_rect.bottom() - trailing_size);
+                modified_rect.set_bottom(trailing_rect.top());
+                modified_rect.set_top(modified_rect.bottom() - modified_size);
+                text_rect.set_bottom(modified_rect.top());
+            }
+            _ if rtl => {
+                trailing_rect.set_left(tab_rect.left() + close_button_size);
+                trailing_rect.set_width(trailing_size);
+                modified_rect.set_left(trailing_rect.right());
+                modified_rect.set_width(modified_size);
+                text_rect.set_left(modified_rect.right());
+            }
+            _ => {
+                trailing_rect.set_right(tab_rect.right() - close_button_size);
+                trailing_rect.set_left(trailing_rect.right() - trailing_size);
+                modified_rect.set_right(trailing_rect.left());
+                modified_rect.set_left(modified_rect.right() - modified_size);
+                text_rect.set_right(modified_rect.left());
+            }
+        }
+        let center = text_rect.center();
+        let size = galley.size();
+        let text_shape = match position {
+            TabBarPosition::Left => TextShape::new(
+                pos2(center.x - size.y / 2.0, center.y + size.x / 2.0),
+                galley,
+                tab_style.text_color,
+            )
+            .with_angle(-FRAC_PI_2),
+            TabBarPosition::Right => TextShape::new(
+                pos2(center.x + size.y / 2.0, center.y - size.x / 2.0),
+                galley,
+                tab_style.text_color,
+            )
+            .with_angle(FRAC_PI_2),
+            _ => {
+                let pos =
+                    Align2::CENTER_CENTER.pos_in_rect(&text_rect.shrink2(vec2(x_spacing, 0.0)));
+                TextShape::new(pos - size / 2.0, galley, tab_style.text_color)
+            }
         };
 
+        // Wrapped titles may have more lines than fit in the tab bar.
         ui.painter()
-            .add(TextShape::new(text_pos, galley, tab_style.text_color));
+            .with_clip_rect(tab_rect.intersect(ui.clip_rect()))
+            .add(text_shape);
+
+        if modified {
+            ui.painter().circle_filled(
+                modified_rect.center(),
+                Style::TAB_MODIFIED_DOT_RADIUS,
+                tab_style.text_color,
+            );
+        }
 
         let close_response = show_close_button.then(|| {
             let mut close_button_rect = tab_rect;
-            close_button_rect.set_left(text_rect.right());
+            match position {
+                TabBarPosition::Left => close_button_rect.set_bottom(trailing_rect.top()),
+                TabBarPosition::Right => close_button_rect.set_top(trailing_rect.bottom()),
+                _ if rtl => close_button_rect.set_right(trailing_rect.left()),
+                _ => close_button_rect.set_left(trailing_rect.right()),
+            }
             close_button_rect =
                 Rect::from_center_size(close_button_rect.center(), Vec2::splat(close_button_size));
 
             let close_response = ui
                 .interact(close_button_rect, id.with("close-button"), Sense::click())
                 .on_hover_cursor(CursorIcon::PointingHand);
+            close_response.widget_info(|| {
+                WidgetInfo::labeled(
+                    WidgetType::Button,
+                    ui.is_enabled(),
+                    format!("Close {title}"),
+                )
+            });
 
             let color = if close_response.hovered() || close_response.has_focus() {
                 style.buttons.close_tab_active_color
@@ -1046,9 +2778,25 @@ impl<Tab> DockArea<'_, Tab> {
             };
 
             if close_response.hovered() || close_response.has_focus() {
-                let mut rounding = tab_style.rounding;
-                rounding.nw = 0.0;
-                rounding.sw = 0.0;
+                let mut rounding = rounding;
+                match position {
+                    TabBarPosition::Left => {
+                        rounding.sw = 0.0;
+                        rounding.se = 0.0;
+                    }
+                    TabBarPosition::Right => {
+                        rounding.nw = 0.0;
+                        rounding.ne = 0.0;
+                    }
+                    _ if rtl => {
+                        rounding.ne = 0.0;
+                        rounding.se = 0.0;
+                    }
+                    _ => {
+                        rounding.nw = 0.0;
+                        rounding.sw = 0.0;
+                    }
+                }
 
                 ui.painter().rect_filled(
                     close_button_rect,
@@ -1071,7 +2819,7 @@ impl<Tab> DockArea<'_, Tab> {
             close_response
         });
 
-        (response, close_response)
+        (response, close_response, trailing_rect)
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -1083,15 +2831,21 @@ impl<Tab> DockArea<'_, Tab> {
         actual_width: f32,
         available_width: f32,
         scroll_bar_width: f32,
+        tabs_rect: Rect,
         tabbar_response: &Response,
         fade_style: Option<&Style>,
     ) {
         assert_ne!(available_width, 0.0);
 
+        let overflow = (actual_width - available_width).at_least(0.0);
+        let auto_scroll = if overflow > 1.0 {
+            self.drag_auto_scroll(ui, state, tabs_rect, false)
+        } else {
+            0.0
+        };
         let Node::Leaf { scroll, .. } = &mut self.dock_state[surface_index][node_index] else {
             unreachable!()
         };
-        let overflow = (actual_width - available_width).at_least(0.0);
         let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
 
         // Compare to 1.0 and not 0.0 to avoid drawing a scroll bar due
@@ -1114,9 +2868,14 @@ impl<Tab> DockArea<'_, Tab> {
                     scroll_bar_rect.left()..=scroll_bar_rect.right() - scroll_bar_handle_size,
                     scroll_ratio,
                 );
-                let scroll_bar_handle_rect = Rect::from_min_size(
-                    pos2(scroll_bar_handle_start, scroll_bar_rect.min.y),
-                    vec2(scroll_bar_handle_size, bar_height),
+                let rtl = self.layout_direction.is_right_to_left();
+                let scroll_bar_handle_rect = rect_mirror_x(
+                    Rect::from_min_size(
+                        pos2(scroll_bar_handle_start, scroll_bar_rect.min.y),
+                        vec2(scroll_bar_handle_size, bar_height),
+                    ),
+                    scroll_bar_rect,
+                    rtl,
                 );
 
                 let scroll_bar_handle_response = ui.interact(
@@ -1129,7 +2888,9 @@ impl<Tab> DockArea<'_, Tab> {
                 let points_to_scroll_coefficient =
                     overflow / (scroll_bar_rect.width() - scroll_bar_handle_size);
 
-                *scroll -= scroll_bar_handle_response.drag_delta().x * points_to_scroll_coefficient;
+                let drag_delta = scroll_bar_handle_response.drag_delta().x;
+                *scroll -=
+                    if rtl { -drag_delta } else { drag_delta } * points_to_scroll_coefficient;
 
                 if let Some(pos) = state.last_hover_pos {
                     if scroll_bar_rect.contains(pos) {
@@ -1158,7 +2919,47 @@ impl<Tab> DockArea<'_, Tab> {
             }
         }
 
-        *scroll = scroll.clamp(-overflow, 0.0);
+        *scroll = (*scroll + auto_scroll).clamp(-overflow, 0.0);
+    }
+
+    /// Returns how far an overflowing tab bar scrolls in this frame while a tab is dragged near one of the ends of
+    /// `tabs_rect`, the part of the tab bar in which its tabs are visible. Positive values reveal the first tabs.
+    fn drag_auto_scroll(&self, ui: &Ui, state: &State, tabs_rect: Rect, vertical: bool) -> f32 {
+        let speed = self
+            .style
+            .as_ref()
+            .unwrap()
+            .overlay
+            .feel
+            .tab_bar_scroll_speed;
+        let (Some(_), Some(pointer)) = (state.drag_start, state.last_hover_pos) else {
+            return 0.0;
+        };
+        if speed <= 0.0 || !tabs_rect.contains(pointer) {
+            return 0.0;
+        }
+        let (pos, range) = if vertical {
+            (pointer.y, tabs_rect.y_range())
+        } else {
+            (pointer.x, tabs_rect.x_range())
+        };
+        let margin = Style::TAB_BAR_SCROLL_MARGIN.min(range.span() / 2.0);
+        let towards_start = if pos < range.min + margin {
+            true
+        } else if pos > range.max - margin {
+            false
+        } else {
+            return 0.0;
+        };
+        // Tabs start on the right of horizontal tab bars laid out from right to left.
+        let rtl = !vertical && self.layout_direction.is_right_to_left();
+        ui.ctx().request_repaint();
+        let distance = speed * ui.input(|i| i.stable_dt).min(0.1);
+        if towards_start != rtl {
+            distance
+        } else {
+            -distance
+        }
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -1187,6 +2988,30 @@ impl<Tab> DockArea<'_, Tab> {
             unreachable!();
         };
 
+        // Tabs which aren't shown still have their contents laid out if they're kept alive, invisibly. They go first
+        // so that they end up below the widgets of the shown tab.
+        for (index, tab) in tabs.iter_mut().enumerate() {
+            if (!collapsed && index == active.0) || !tab_viewer.keep_alive(tab) {
+                continue;
+            }
+            let style = fade.map_or_else(|| self.style.as_ref().unwrap(), |(style, _)| style);
+            let inner_margin = tab_viewer
+                .tab_style_override(tab, &style.tab)
+                .map_or(style.tab.tab_body.inner_margin, |style| {
+                    style.tab_body.inner_margin
+                });
+            let ui = &mut Ui::new(
+                ui.ctx().clone(),
+                self.id.with(tab_viewer.id(tab)),
+                UiBuilder::new()
+                    .max_rect(body_rect)
+                    .layer_id(ui.layer_id())
+                    .invisible(),
+            );
+            ui.spacing_mut().item_spacing = spacing;
+            tab_contents_ui(ui, tab_viewer, tab, inner_margin, 1.0);
+        }
+
         if !collapsed {
             if let Some(tab) = tabs.get_mut(active.0) {
                 *viewport = body_rect;
@@ -1232,32 +3057,41 @@ impl<Tab> DockArea<'_, Tab> {
                 // Use initial spacing for ui.
                 ui.spacing_mut().item_spacing = spacing;
 
-                // Offset the background rectangle up to hide the top border behind the clip rect.
-                // To avoid anti-aliasing lines when the stroke width is not divisible by two, we
-                // need to calculate the effective anti-aliased stroke width.
+                // Offset the background rectangle towards the tab bar to hide the border facing it
+                // behind the clip rect. To avoid anti-aliasing lines when the stroke width is not
+                // divisible by two, we need to calculate the effective anti-aliased stroke width.
                 let effective_stroke_width = (tabs_style.tab_body.stroke.width / 2.0).ceil() * 2.0;
-                let tab_body_rect = Rect::from_min_max(
-                    ui.clip_rect().min - vec2(0.0, effective_stroke_width),
-                    ui.clip_rect().max,
-                );
+                let clip_rect = ui.clip_rect();
+                let tab_body_rect = match self.tab_bar_position {
+                    TabBarPosition::Top => {
+                        clip_rect.with_min_y(clip_rect.min.y - effective_stroke_width)
+                    }
+                    TabBarPosition::Bottom => {
+                        clip_rect.with_max_y(clip_rect.max.y + effective_stroke_width)
+                    }
+                    TabBarPosition::Left => {
+                        clip_rect.with_min_x(clip_rect.min.x - effective_stroke_width)
+                    }
+                    TabBarPosition::Right => {
+                        clip_rect.with_max_x(clip_rect.max.x + effective_stroke_width)
+                    }
+                };
                 ui.painter().rect_stroke(
                     rect_stroke_box(tab_body_rect, tabs_style.tab_body.stroke.width),
                     tabs_style.tab_body.rounding,
                     tabs_style.tab_body.stroke,
                 );
 
-                ScrollArea::new(tab_viewer.scroll_bars(tab)).show(ui, |ui| {
-                    Frame::none()
-                        .inner_margin(tabs_style.tab_body.inner_margin)
-                        .show(ui, |ui| {
-                            if fade_factor != 1.0 {
-                                fade_visuals(ui.visuals_mut(), fade_factor);
-                            }
-                            let available_rect = ui.available_rect_before_wrap();
-                            ui.expand_to_include_rect(available_rect);
-                            tab_viewer.ui(ui, tab);
-                        });
-                });
+                let inner_margin = tabs_style.tab_body.inner_margin;
+                tab_contents_ui(ui, tab_viewer, tab, inner_margin, fade_factor);
+            } else if tabs.is_empty() {
+                *viewport = body_rect;
+                let ui = &mut ui.new_child(
+                    UiBuilder::new()
+                        .max_rect(body_rect)
+                        .id_salt((node_index, "empty_surface")),
+                );
+                tab_viewer.empty_surface_ui(ui, surface_index);
             }
         }
 
@@ -1269,6 +3103,14 @@ impl<Tab> DockArea<'_, Tab> {
             // if the dragged tab isn't allowed in a window,
             // it's unnecessary to change the hover state
             let is_dragged_valid = match &state.dnd {
+                Some(DragDropState {
+                    drag:
+                        DragData {
+                            foreign: Some(foreign),
+                            ..
+                        },
+                    ..
+                }) => foreign.allowed_in_windows || surface_index == SurfaceIndex::main(),
                 Some(DragDropState {
                     drag: DragData { src, .. },
                     ..
@@ -1303,13 +3145,271 @@ impl<Tab> DockArea<'_, Tab> {
                     }
                 };
 
+                let insert_caret = match dst {
+                    TreeComponent::Tab(..) => tab.map(|rect| self.leading_edge(rect)),
+                    _ => tab.and_then(|tabbar_rect| {
+                        // Appended tabs go after the last one, or at the start of an empty tab bar. If the last
+                        // tab is scrolled out of view, the whole tab bar is highlighted instead.
+                        let node = &self.dock_state[surface_index][node_index];
+                        let Some(last) = node.tabs_count().checked_sub(1) else {
+                            return Some(self.leading_edge(tabbar_rect));
+                        };
+                        let last_tab = node.tab_rect(TabIndex(last))?;
+                        let [start, end] = self.leading_edge(last_tab);
+                        let offset = if self.tab_bar_position.is_vertical() {
+                            vec2(0.0, last_tab.height())
+                        } else if self.layout_direction.is_right_to_left() {
+                            vec2(-last_tab.width(), 0.0)
+                        } else {
+                            vec2(last_tab.width(), 0.0)
+                        };
+                        Some([start + offset, end + offset])
+                    }),
+                };
+
                 ui.memory_mut(|mem| {
                     mem.data.insert_temp(
                         self.id.with("hover_data"),
-                        Some(HoverData { rect, dst, tab }),
+                        Some(HoverData {
+                            rect,
+                            dst,
+                            tab,
+                            body: body_rect,
+                            insert_caret,
+                        }),
                     );
                 });
             }
         }
     }
 }
+
+/// Returns the edge of `rect` facing the tab body when the tab bar is at `position`, moved inwards by `inset`.
+/// Runs `add_tabs` with the AccessKit node of a tab bar as the parent of the widgets created in it, so that screen
+/// readers see its tabs as a list.
+#[cfg_attr(not(feature = "accesskit"), allow(unused_variables))]
+fn with_tab_list<R>(
+    ctx: &egui::Context,
+    id: Id,
+    rect: Rect,
+    vertical: bool,
+    add_tabs: impl FnOnce() -> R,
+) -> R {
+    // The parent node has to exist before any child is added to it.
+    #[cfg(feature = "accesskit")]
+    if ctx
+        .accesskit_node_builder(id, |node| {
+            use egui::accesskit;
+            node.set_role(accesskit::Role::TabList);
+            node.set_orientation(if vertical {
+                accesskit::Orientation::Vertical
+            } else {
+                accesskit::Orientation::Horizontal
+            });
+            node.set_bounds(accesskit::Rect {
+                x0: rect.min.x.into(),
+                y0: rect.min.y.into(),
+                x1: rect.max.x.into(),
+                y1: rect.max.y.into(),
+            });
+        })
+        .is_some()
+    {
+        return ctx.with_accessibility_parent(id, add_tabs);
+    }
+    add_tabs()
+}
+
+/// Describes a tab title to screen readers, as a tab which is `selected` when it's the active tab of its leaf.
+fn tab_widget_info(response: &Response, title: &str, selected: bool) {
+    response.widget_info(|| {
+        WidgetInfo::selected(
+            WidgetType::SelectableLabel,
+            response.enabled(),
+            selected,
+            title,
+        )
+    });
+    #[cfg(feature = "accesskit")]
+    response.ctx.accesskit_node_builder(response.id, |node| {
+        node.set_role(egui::accesskit::Role::Tab);
+        node.clear_toggled();
+        node.set_selected(selected);
+    });
+}
+
+/// Shows the contents of a tab with [`TabViewer::ui`] in the body of its leaf.
+fn tab_contents_ui<Tab>(
+    ui: &mut Ui,
+    tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    tab: &mut Tab,
+    inner_margin: Margin,
+    fade_factor: f32,
+) {
+    tab_viewer.visuals_override(tab, ui.visuals_mut());
+    ScrollArea::new(tab_viewer.scroll_bars(tab)).show(ui, |ui| {
+        Frame::none().inner_margin(inner_margin).show(ui, |ui| {
+            if fade_factor != 1.0 {
+                fade_visuals(ui.visuals_mut(), fade_factor);
+            }
+            let available_rect = ui.available_rect_before_wrap();
+            ui.expand_to_include_rect(available_rect);
+            tab_viewer.ui(ui, tab);
+        });
+    });
+}
+
+fn body_edge(position: TabBarPosition, rect: Rect, inset: f32) -> [Pos2; 2] {
+    match position {
+        TabBarPosition::Top => [
+            pos2(rect.left(), rect.bottom() - inset),
+            pos2(rect.right(), rect.bottom() - inset),
+        ],
+        TabBarPosition::Bottom => [
+            pos2(rect.left(), rect.top() + inset),
+            pos2(rect.right(), rect.top() + inset),
+        ],
+        TabBarPosition::Left => [
+            pos2(rect.right() - inset, rect.top()),
+            pos2(rect.right() - inset, rect.bottom()),
+        ],
+        TabBarPosition::Right => [
+            pos2(rect.left() + inset, rect.top()),
+            pos2(rect.left() + inset, rect.bottom()),
+        ],
+    }
+}
+
+/// Shrinks `rect` by `amount` on the side facing the tab body, so that the line along it stays visible.
+fn shrink_towards_body(rect: Rect, position: TabBarPosition, amount: f32) -> Rect {
+    match position {
+        TabBarPosition::Top => rect.with_max_y(rect.max.y - amount),
+        TabBarPosition::Bottom => rect.with_min_y(rect.min.y + amount),
+        TabBarPosition::Left => rect.with_max_x(rect.max.x - amount),
+        TabBarPosition::Right => rect.with_min_x(rect.min.x + amount),
+    }
+}
+
+/// Turns the rounding of a tab above the body so that its top corners face away from the body.
+fn rotate_rounding(rounding: Rounding, position: TabBarPosition) -> Rounding {
+    let Rounding { nw, ne, sw, se } = rounding;
+    match position {
+        TabBarPosition::Top => rounding,
+        TabBarPosition::Bottom => Rounding {
+            nw: sw,
+            ne: se,
+            sw: nw,
+            se: ne,
+        },
+        TabBarPosition::Left => Rounding {
+            nw: ne,
+            ne: se,
+            sw: nw,
+            se: sw,
+        },
+        TabBarPosition::Right => Rounding {
+            nw: sw,
+            ne: nw,
+            sw: se,
+            se: ne,
+        },
+    }
+}
+
+/// Shows [`TabViewer::tab_title_trailing_ui`] in the area reserved for it by `tab_title`, and
+/// remembers how much space it needs for the next frame.
+/// Lays out the title of a tab, shortening it to fit in `max_width` according to `truncation`.
+fn tab_title_galley(
+    ui: &Ui,
+    label: WidgetText,
+    max_width: f32,
+    truncation: TitleTruncation,
+) -> Arc<Galley> {
+    let wrap_mode = match truncation {
+        TitleTruncation::Truncate => TextWrapMode::Truncate,
+        TitleTruncation::Wrap => TextWrapMode::Wrap,
+        TitleTruncation::MiddleEllipsis => {
+            let galley = label.into_galley(
+                ui,
+                Some(TextWrapMode::Extend),
+                f32::INFINITY,
+                TextStyle::Button,
+            );
+            if galley.size().x <= max_width {
+                return galley;
+            }
+            return middle_ellipsis_galley(ui, &galley, max_width);
+        }
+    };
+    label.into_galley(ui, Some(wrap_mode), max_width, TextStyle::Button)
+}
+
+/// Lays out the text of `galley` again with as many characters replaced with an ellipsis in its middle as needed to
+/// fit in `max_width`. Only the format of the first section of the text is kept.
+fn middle_ellipsis_galley(ui: &Ui, galley: &Galley, max_width: f32) -> Arc<Galley> {
+    let chars: Vec<char> = galley.job.text.chars().collect();
+    let format = galley
+        .job
+        .sections
+        .first()
+        .map(|section| section.format.clone())
+        .unwrap_or_default();
+    let layout = |kept: usize| {
+        let head = kept.div_ceil(2);
+        let tail = kept / 2;
+        let text: String = chars[..head]
+            .iter()
+            .chain(std::iter::once(&'…'))
+            .chain(&chars[chars.len() - tail..])
+            .collect();
+        ui.fonts(|fonts| fonts.layout_job(LayoutJob::single_section(text, format.clone())))
+    };
+
+    // Find the largest number of characters which still fits.
+    let (mut low, mut high) = (0, chars.len());
+    while low < high {
+        let mid = (low + high).div_ceil(2);
+        if layout(mid).size().x <= max_width {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    layout(low)
+}
+
+fn tab_title_trailing_ui<Tab>(
+    ui: &mut Ui,
+    id: Id,
+    rect: Rect,
+    (position, rtl): (TabBarPosition, bool),
+    tab: &mut Tab,
+    tab_viewer: &mut impl TabViewer<Tab = Tab>,
+) {
+    let layout = match position {
+        TabBarPosition::Left | TabBarPosition::Right => Layout::top_down(Align::Center),
+        _ if rtl => Layout::right_to_left(Align::Center),
+        _ => Layout::left_to_right(Align::Center),
+    };
+    let mut trailing_ui = ui.new_child(
+        UiBuilder::new()
+            .id_salt(id.with("trailing_ui"))
+            .max_rect(rect)
+            .layout(layout),
+    );
+    tab_viewer.tab_title_trailing_ui(&mut trailing_ui, tab);
+
+    let used = trailing_ui.min_rect().size();
+    let size = if position.is_vertical() {
+        used.y
+    } else {
+        used.x
+    };
+    let key = id.with("trailing_ui_size");
+    let previous = ui.data(|d| d.get_temp::<f32>(key)).unwrap_or(0.0);
+    if size != previous {
+        ui.data_mut(|d| d.insert_temp(key, size));
+        ui.ctx()
+            .request_discard("Tab title trailing UI changed size");
+    }
+}
@@ -1,4 +1,4 @@
-use egui::{Sense, Ui};
+use egui::{Sense, Ui, UiBuilder};
 
 use crate::{
     dock_area::{
@@ -17,6 +17,55 @@ impl<Tab> DockArea<'_, Tab> {
     ) {
         let surf_index = SurfaceIndex::main();
 
+        // Leave room for the taskbar listing minimized windows along the bottom.
+        let taskbar = self.taskbar_windows(state);
+        if !taskbar.is_empty() {
+            let rect = ui.available_rect_before_wrap();
+            let rect = self.show_window_taskbar(ui, tab_viewer, state, rect, &taskbar);
+            let main_ui = &mut ui.new_child(
+                UiBuilder::new()
+                    .max_rect(rect)
+                    .id_salt((surf_index, "taskbar_main")),
+            );
+            self.show_root_surface_strips(main_ui, tab_viewer, state);
+            return;
+        }
+        self.show_root_surface_strips(ui, tab_viewer, state);
+    }
+
+    fn show_root_surface_strips(
+        &mut self,
+        ui: &mut Ui,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        state: &mut State,
+    ) {
+        let surf_index = SurfaceIndex::main();
+
+        // Leave room for the strips listing auto-hidden leaves along the edges.
+        let auto_hidden = self.auto_hidden_surfaces();
+        if !auto_hidden.is_empty() {
+            let rect = ui.available_rect_before_wrap();
+            let (rect, strips) = self.show_auto_hide_strips(ui, tab_viewer, rect, &auto_hidden);
+            let main_ui = &mut ui.new_child(
+                UiBuilder::new()
+                    .max_rect(rect)
+                    .id_salt((surf_index, "auto_hide_main")),
+            );
+            self.show_main_surface_contents(main_ui, tab_viewer, state);
+            self.show_auto_hidden_overlay(ui, state, tab_viewer, rect, &strips);
+            return;
+        }
+        self.show_main_surface_contents(ui, tab_viewer, state);
+    }
+
+    fn show_main_surface_contents(
+        &mut self,
+        ui: &mut Ui,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        state: &mut State,
+    ) {
+        let surf_index = SurfaceIndex::main();
+
         if self.dock_state.main_surface().is_empty() {
             let rect = ui.available_rect_before_wrap();
             let response = ui.allocate_rect(rect, Sense::hover());
@@ -28,10 +77,18 @@ impl<Tab> DockArea<'_, Tab> {
                             rect,
                             dst: TreeComponent::Surface(surf_index),
                             tab: None,
+                            body: rect,
+                            insert_caret: None,
                         }),
                     );
                 });
             }
+            let ui = &mut ui.new_child(
+                UiBuilder::new()
+                    .max_rect(rect)
+                    .id_salt((surf_index, "empty_surface")),
+            );
+            tab_viewer.empty_surface_ui(ui, surf_index);
             return;
         }
 
@@ -1,24 +1,39 @@
 use egui::{
-    CentralPanel, Color32, Context, CursorIcon, EventFilter, Frame, Key, Pos2, Rect, Rounding,
-    Sense, Ui, Vec2,
+    emath::{easing, lerp},
+    pos2, vec2, CentralPanel, Color32, Context, CursorIcon, DragAndDrop, Frame, Id, Key, Pos2,
+    Rect, Sense, Ui, Vec2,
 };
 
 use duplicate::duplicate;
 use paste::paste;
 
 use crate::{
-    utils::{expand_to_pixel, fade_dock_style, map_to_pixel},
-    AllowedSplits, DockArea, Node, NodeIndex, OverlayType, Style, SurfaceIndex, TabDestination,
-    TabViewer,
+    utils::{expand_to_pixel, fade_dock_style, map_to_pixel, pad_rect},
+    widgets::separator::constrain_fraction,
+    AllowedDestinations, AllowedSplits, CloseActivationPolicy, DockArea, DockEvent, DockResponse,
+    DragInfo, DragMetrics, LayoutMetrics, LayoutSnapshot, MoveResponse, Node, NodeIndex,
+    OverlayType, Separator, Split, SplitOrientation, Style, SurfaceId, SurfaceIndex,
+    TabBarPosition, TabDestination, TabIndex, TabInsert, TabViewer, WindowState,
 };
 
-use super::{drag_and_drop::TreeComponent, state::State, tab_removal::TabRemoval};
+use super::{
+    drag_and_drop::{
+        draw_preview_rect, draw_split_preview, drop_split_fraction, split_drop_rect,
+        split_preview_rect, DropTargets, TreeComponent,
+    },
+    state::State,
+    tab_removal::TabRemoval,
+    DockLayout,
+};
 
+mod auto_hide;
 mod leaf;
 mod main_surface;
+mod tab_group;
+mod taskbar;
 mod window_surface;
 
-impl<Tab> DockArea<'_, Tab> {
+impl<'tree, Tab> DockArea<'tree, Tab> {
     /// Show the `DockArea` at the top level.
     ///
     /// This is the same as doing:
@@ -45,34 +60,107 @@ impl<Tab> DockArea<'_, Tab> {
     ///
     /// So you can't use the [`CentralPanel::show`] when using `DockArea`'s one.
     ///
+    /// Returns a [`DockResponse`] with the [`DockEvent`]s describing changes made to the
+    /// [`DockState`](crate::DockState) during this frame.
+    ///
     /// See also [`show_inside`](Self::show_inside).
     #[inline]
-    pub fn show(self, ctx: &Context, tab_viewer: &mut impl TabViewer<Tab = Tab>) {
+    pub fn show(self, ctx: &Context, tab_viewer: &mut impl TabViewer<Tab = Tab>) -> DockResponse {
         CentralPanel::default()
             .frame(
                 Frame::central_panel(&ctx.style())
                     .inner_margin(0.)
                     .fill(Color32::TRANSPARENT),
             )
-            .show(ctx, |ui| {
-                self.show_inside(ui, tab_viewer);
-            });
+            .show(ctx, |ui| self.show_inside(ui, tab_viewer))
+            .inner
     }
 
     /// Shows the docking hierarchy inside a [`Ui`].
     ///
+    /// Returns a [`DockResponse`] with the response of the area, which can be used for adding a context menu to it,
+    /// and the [`DockEvent`]s describing changes made to the [`DockState`](crate::DockState) during this frame, in
+    /// the order in which they were applied:
+    ///
+    /// ```
+    /// # use egui_dock::{DockArea, DockEvent, DockState};
+    /// # struct TabViewer {}
+    /// # impl egui_dock::TabViewer for TabViewer {
+    /// #     type Tab = String;
+    /// #     fn title(&mut self, tab: &mut Self::Tab) -> egui::WidgetText { (&*tab).into() }
+    /// #     fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab) {}
+    /// # }
+    /// # let mut dock_state: DockState<String> = DockState::new(vec![]);
+    /// # let mut tab_viewer = TabViewer {};
+    /// # egui::__run_test_ctx(|ctx| {
+    /// # egui::CentralPanel::default().show(ctx, |ui| {
+    /// for event in DockArea::new(&mut dock_state).show_inside(ui, &mut tab_viewer).events {
+    ///     if let DockEvent::TabClosed { id, .. } = event {
+    ///         println!("Closed tab {id:?}");
+    ///     }
+    /// }
+    /// # });
+    /// # });
+    /// ```
+    ///
     /// See also [`show`](Self::show).
-    pub fn show_inside(mut self, ui: &mut Ui, tab_viewer: &mut impl TabViewer<Tab = Tab>) {
-        self.style
-            .get_or_insert(Style::from_egui(ui.style().as_ref()));
+    pub fn show_inside(
+        mut self,
+        ui: &mut Ui,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> DockResponse {
+        self.id = self.resolved_id(ui);
+        self.warn_on_id_clash(ui);
+        self.prepare_style(ui);
         self.window_bounds.get_or_insert(ui.ctx().screen_rect());
 
+        // Registered before anything else, so that the widgets shown on top of the area take precedence over it.
+        let response = ui.interact(
+            ui.available_rect_before_wrap(),
+            self.id.with("response"),
+            Sense::click(),
+        );
+
+        if self.locked {
+            self.draggable_tabs = false;
+            self.show_close_buttons = false;
+            self.show_add_buttons = false;
+            self.show_split_buttons = false;
+            self.show_leaf_close_all_buttons = false;
+            self.show_leaf_collapse_buttons = false;
+        }
+
         let mut state = State::load(ui.ctx(), self.id);
+        let ancestors = self.enter_shown_areas(ui.ctx());
+        let focused_before = self.dock_state.focused_leaf();
+
+        if self.dock_state.step_animations(ui.input(|i| i.time)) {
+            ui.ctx().request_repaint();
+        }
+        self.apply_collapse_changes(ui.ctx());
+
+        self.send_requested_tab(ui.ctx(), tab_viewer);
+        self.receive_transferred_tab(ui.ctx(), tab_viewer);
+        self.close_confirmed_tabs(tab_viewer);
+        self.step_history(tab_viewer);
+        for tab in self.dock_state.take_scroll_to_tabs() {
+            self.scroll_to_tab(ui.ctx(), tab);
+        }
 
         // Delay hover position one frame. On touch screens hover_pos() is None when any_released()
         if !ui.input(|i| i.pointer.any_released()) {
             state.last_hover_pos = ui.input(|i| i.pointer.hover_pos());
         }
+        // A tab glued to its tab bar can only be dropped onto it.
+        if let (Some(tab_bar), Some(pointer)) = (state.glued_tab_bar, state.last_hover_pos.as_mut())
+        {
+            let clamped = tab_bar.clamp(*pointer);
+            *pointer = if self.tab_bar_position.is_vertical() {
+                pos2(tab_bar.center().x, clamped.y)
+            } else {
+                pos2(clamped.x, tab_bar.center().y)
+            };
+        }
 
         let (drag_data, hover_data) = ui.memory_mut(|mem| {
             (
@@ -81,10 +169,50 @@ impl<Tab> DockArea<'_, Tab> {
             )
         });
 
+        // Pressing Escape cancels dragging a tab, leaving it where it was.
+        let drag_cancelled = (drag_data.is_some() || state.drag_start.is_some())
+            && ui.input(|i| i.key_pressed(Key::Escape));
+        if drag_cancelled {
+            ui.ctx().stop_dragging();
+            state.reset_drag();
+            if self.external_drag_payload.is_some() {
+                DragAndDrop::clear_payload(ui.ctx());
+            }
+        }
+        let (drag_data, hover_data) = if drag_cancelled {
+            (None, None)
+        } else {
+            (drag_data, hover_data)
+        };
+        // A tab hovering an area shown inside one of the tabs is dropped there instead.
+        let hover_data = hover_data.filter(|_| !self.is_drag_claimed_by_nested_area(ui.ctx()));
+
+        // Let tabs dragged in other areas of the drag group be dropped here.
+        let drag_data = drag_data.or_else(|| {
+            if self.locked {
+                return None;
+            }
+            let foreign = self.foreign_drag_data(ui.ctx())?;
+            if let Some(pointer) = state.last_hover_pos {
+                state.drag_start.get_or_insert(pointer);
+            }
+            Some(foreign)
+        });
+
+        let hovering = hover_data.is_some();
+
+        // A tab released where it would become a window, unless a widget shown before the area has taken its payload.
+        let mut pending_detach = None;
+        let mut drop_destination = None;
+
         if let (Some(source), Some(hover)) = (drag_data, hover_data) {
             let style = self.style.as_ref().unwrap();
             state.set_drag_and_drop(source, hover, ui.ctx(), style);
             let tab_dst = self.show_drag_drop_overlay(ui, &mut state, tab_viewer);
+            drop_destination = tab_dst;
+            if let Some(TabDestination::Node(surface, node, TabInsert::Insert(tab))) = tab_dst {
+                self.tab_insert_target = Some((surface, node, tab));
+            }
             if ui.input(|i| i.pointer.primary_released()) {
                 if let Some(destination) = tab_dst {
                     let source = {
@@ -97,7 +225,22 @@ impl<Tab> DockArea<'_, Tab> {
                             ),
                         }
                     };
-                    self.dock_state.move_tab(source, destination);
+                    if let Some(foreign) = state.dnd.as_ref().unwrap().drag.foreign {
+                        self.request_transfer(ui.ctx(), foreign, source, destination);
+                    } else if self.is_copying_tab(ui)
+                        && self.copy_tab(source, destination, tab_viewer)
+                    {
+                        if self.external_drag_payload.is_some() {
+                            DragAndDrop::clear_payload(ui.ctx());
+                        }
+                    } else if destination.is_window() && self.external_drag_payload.is_some() {
+                        pending_detach = Some((source, destination));
+                    } else {
+                        if self.external_drag_payload.is_some() {
+                            DragAndDrop::clear_payload(ui.ctx());
+                        }
+                        self.move_tab(source, destination, tab_viewer);
+                    }
                 }
             }
         }
@@ -105,6 +248,8 @@ impl<Tab> DockArea<'_, Tab> {
         if ui.input(|i| i.pointer.primary_released()) {
             state.reset_drag();
         }
+        let current_drag = self.current_drag(&state, hovering, drop_destination);
+        self.dock_state.set_current_drag(current_drag);
 
         let style = self.style.as_ref().unwrap();
         let fade_surface =
@@ -117,7 +262,12 @@ impl<Tab> DockArea<'_, Tab> {
             })
         };
 
-        for &surface_index in self.dock_state.valid_surface_indices().iter() {
+        self.update_content_min_sizes(tab_viewer);
+
+        for surface_index in self.dock_state.surface_index_iter() {
+            if !self.dock_state.is_surface_valid(surface_index) {
+                continue;
+            }
             self.show_surface_inside(
                 surface_index,
                 ui,
@@ -128,45 +278,784 @@ impl<Tab> DockArea<'_, Tab> {
                 }),
             );
         }
+        self.claim_hovered_drag(ui.ctx(), &ancestors);
+        if self.tab_hover_rect.is_none() {
+            state.tab_hover_since = None;
+        }
+
+        self.record_surface_stack(ui.ctx());
+        let metrics = self
+            .collect_metrics
+            .then(|| self.layout_metrics(ui, &state, drop_destination));
+        self.dock_state.set_last_metrics(metrics);
+
+        // Indices of the pending detach would be invalidated by tabs removed in this frame.
+        let layout_changed = !self.to_remove.is_empty()
+            || !self.to_detach.is_empty()
+            || self.detach_selection
+            || !self.to_dock.is_empty()
+            || !self.to_detach_leaf.is_empty();
+        let to_split = std::mem::take(&mut self.to_split);
+
+        // Whether the focused leaf is closed, so that the tab used before it gets focused instead of its neighbor.
+        let mut focused_leaf_closed = false;
 
-        for index in self.to_remove.drain(..).rev() {
+        for index in std::mem::take(&mut self.to_remove).into_iter().rev() {
+            let surfaces = self.surface_snapshot();
+            let focused = self.dock_state.focused_leaf();
             match index {
                 TabRemoval::Node(surface, node, tab) => {
-                    self.dock_state[surface].remove_tab((node, tab));
-                    if self.dock_state[surface].is_empty() && !surface.is_main() {
-                        self.dock_state.remove_surface(surface);
-                    }
+                    focused_leaf_closed |= focused == Some((surface, node))
+                        && self.dock_state[surface][node].tabs_count() == 1;
+                    self.record_tabs_closed(surface, node, Some(tab), tab_viewer);
+                    self.remove_closed_tab((surface, node, tab));
                 }
                 TabRemoval::Leaf(surface, node) => {
-                    self.dock_state[surface].remove_leaf(node);
+                    focused_leaf_closed |= focused == Some((surface, node));
+                    self.record_history(tab_viewer);
+                    self.record_tabs_closed(surface, node, None, tab_viewer);
+                    self.dock_state
+                        .remove_leaf_following_recent((surface, node));
                     if self.dock_state[surface].is_empty() && !surface.is_main() {
                         self.dock_state.remove_surface(surface);
                     }
                 }
                 TabRemoval::Window(index) => {
+                    focused_leaf_closed |= focused.is_some_and(|(surface, _)| surface == index);
+                    self.record_history(tab_viewer);
+                    for node in self.dock_state[index].breadth_first_index_iter() {
+                        if self.dock_state[index][node].is_leaf() {
+                            self.record_tabs_closed(index, node, None, tab_viewer);
+                        }
+                    }
                     self.dock_state.remove_surface(index);
                 }
             }
+            self.record_surface_changes(&surfaces);
+        }
+
+        for (surface, split) in std::mem::take(&mut self.to_dock) {
+            self.record_history(tab_viewer);
+            let surfaces = self.surface_snapshot();
+            let from_window = self.window_id(surface);
+            let ids: Vec<_> = self.dock_state[surface]
+                .iter_mut()
+                .filter_map(|node| node.tabs_mut())
+                .flatten()
+                .map(|tab| tab_viewer.id(tab))
+                .collect();
+            self.dock_state.dock_window(surface, split, 0.5);
+            self.record_surface_changes(&surfaces);
+            for id in ids {
+                self.notify_window_change(id, from_window, tab_viewer);
+            }
+        }
+
+        for (surface_index, node_index) in std::mem::take(&mut self.to_detach_leaf) {
+            let window_rect = Rect::from_min_size(
+                state.last_hover_pos.unwrap_or(Pos2::ZERO),
+                self.dock_state[surface_index][node_index]
+                    .rect()
+                    .map_or(Vec2::new(100., 150.), |rect| rect.size()),
+            );
+            self.detach_leaf((surface_index, node_index), window_rect, tab_viewer);
+        }
+
+        if std::mem::take(&mut self.detach_selection) {
+            let window_rect = Rect::from_min_size(
+                state.last_hover_pos.unwrap_or(Pos2::ZERO),
+                Vec2::new(300., 200.),
+            );
+            self.detach_selected_tabs(window_rect, tab_viewer);
         }
 
-        for (surface_index, node_index, tab_index) in self.to_detach.drain(..).rev() {
+        for (surface_index, node_index, tab_index) in
+            std::mem::take(&mut self.to_detach).into_iter().rev()
+        {
             let mouse_pos = state.last_hover_pos;
-            self.dock_state.detach_tab(
+            let window_rect = Rect::from_min_size(
+                mouse_pos.unwrap_or(Pos2::ZERO),
+                self.dock_state[surface_index][node_index]
+                    .rect()
+                    .map_or(Vec2::new(100., 150.), |rect| rect.size()),
+            );
+            self.move_tab(
                 (surface_index, node_index, tab_index),
-                Rect::from_min_size(
-                    mouse_pos.unwrap_or(Pos2::ZERO),
-                    self.dock_state[surface_index][node_index]
-                        .rect()
-                        .map_or(Vec2::new(100., 150.), |rect| rect.size()),
-                ),
+                TabDestination::Window(window_rect),
+                tab_viewer,
             );
         }
 
+        if let Some((source, destination)) = pending_detach {
+            // The payload is gone if a widget has taken it. egui drops it at the end of this frame, so widgets shown
+            // after the area can't be waited for, and mustn't get it once the tab has become a window.
+            if !layout_changed && DragAndDrop::has_any_payload(ui.ctx()) {
+                DragAndDrop::clear_payload(ui.ctx());
+                self.move_tab(source, destination, tab_viewer);
+            }
+        }
+
+        // Indices of the leaves to split would be invalidated by the other changes.
+        if let (false, None, Some(&split)) = (layout_changed, pending_detach, to_split.first()) {
+            self.split_leaf(split, tab_viewer);
+        }
+
         if let Some(focused) = self.new_focused {
             self.dock_state.set_focused_node_and_surface(focused);
+        } else if focused_leaf_closed
+            || (focused_before.is_some() && self.dock_state.focused_leaf().is_none())
+        {
+            // The focused leaf is gone, so focus the tab used before it.
+            self.dock_state.focus_recent_tab();
         }
+        self.dock_state.touch_focused_tab();
 
+        let focused_after = self.dock_state.focused_leaf();
+        if focused_before != focused_after {
+            self.events.push(DockEvent::FocusChanged {
+                from: focused_before,
+                to: focused_after,
+            });
+        }
+
+        self.notify_focused_tab(&mut state, tab_viewer);
+        self.publish_dragged_tab(ui.ctx(), tab_viewer);
         state.store(ui.ctx(), self.id);
+        self.leave_shown_areas(ui.ctx());
+
+        let hovered_node = ui
+            .input(|i| i.pointer.hover_pos())
+            .and_then(|pos| self.dock_state.hit_test(pos))
+            .map(|hit| (hit.surface, hit.node));
+        let closed_tabs = self
+            .events
+            .iter()
+            .filter_map(|event| match *event {
+                DockEvent::TabClosed { id, .. } => Some(id),
+                _ => None,
+            })
+            .collect();
+        DockResponse {
+            response,
+            hovered_node,
+            focused_leaf: focused_after,
+            closed_tabs,
+            events: self.events,
+        }
+    }
+
+    /// Computes the rects of the nodes on the main surface without showing anything, so that they can be inspected
+    /// or adjusted before the [`DockArea`] is shown with [`DockLayout::paint`](super::DockLayout::paint).
+    ///
+    /// The nodes are laid out in the same area of `ui` that [`show_inside`](Self::show_inside) would use, so nothing
+    /// should be added to `ui` in between.
+    ///
+    /// ```
+    /// # use egui_dock::{DockArea, DockState, NodeIndex};
+    /// # struct TabViewer {}
+    /// # impl egui_dock::TabViewer for TabViewer {
+    /// #     type Tab = String;
+    /// #     fn title(&mut self, tab: &mut Self::Tab) -> egui::WidgetText { (&*tab).into() }
+    /// #     fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab) {}
+    /// # }
+    /// # let mut dock_state = DockState::new(vec!["tab".to_owned()]);
+    /// # let mut tab_viewer = TabViewer {};
+    /// # egui::__run_test_ctx(|ctx| {
+    /// # egui::CentralPanel::default().show(ctx, |ui| {
+    /// let mut layout = DockArea::new(&mut dock_state).layout(ui);
+    /// if let Some(rect) = layout.rect(NodeIndex::root()) {
+    ///     // Leave some room for a toolbar drawn on top of the dock area.
+    ///     layout.set_rect(NodeIndex::root(), rect.shrink2(egui::vec2(0.0, 16.0)));
+    /// }
+    /// layout.paint(ui, &mut tab_viewer);
+    /// # });
+    /// # });
+    /// ```
+    pub fn layout(mut self, ui: &Ui) -> DockLayout<'tree, Tab> {
+        self.id = self.resolved_id(ui);
+        self.prepare_style(ui);
+
+        let surf_index = SurfaceIndex::main();
+        if !self.dock_state[surf_index].is_empty() {
+            let mut rect = ui.available_rect_before_wrap();
+            let auto_hidden = self.auto_hidden_surfaces();
+            if !auto_hidden.is_empty() {
+                rect = self.auto_hide_strip_rects(rect, &auto_hidden).0;
+            }
+            let rect = self.pad_dock_area_rect(rect).expand(
+                -self
+                    .style
+                    .as_ref()
+                    .unwrap()
+                    .main_surface_border_stroke
+                    .width
+                    / 2.0,
+            );
+            self.dock_state[surf_index][NodeIndex::root()].set_rect(rect);
+            self.layout_nodes(ui.ctx(), surf_index, rect);
+        }
+
+        DockLayout::new(self, ui.ctx().clone())
+    }
+
+    /// Returns the ID set with [`DockArea::id`], or otherwise the one derived from the ID of `ui` and
+    /// [`DockArea::id_salt`].
+    pub(crate) fn resolved_id(&self, ui: &Ui) -> Id {
+        if self.id == Id::NULL {
+            ui.make_persistent_id(self.id_salt)
+        } else {
+            self.id
+        }
+    }
+
+    /// Derives the style from `ui` if none has been set, and computes the heights of tab bars which are sized by
+    /// their fonts.
+    fn prepare_style(&mut self, ui: &Ui) {
+        self.style
+            .get_or_insert(Style::from_egui(ui.style().as_ref()))
+            .tab_bar
+            .update_height(ui);
+        for (_, node) in self.dock_state.iter_all_nodes_mut() {
+            if let Node::Leaf {
+                tab_bar_style: Some(tab_bar),
+                ..
+            } = node
+            {
+                tab_bar.update_height(ui);
+            }
+        }
+    }
+
+    /// Makes the tab bar of a leaf scroll the given tab into view when it's shown.
+    fn scroll_to_tab(
+        &self,
+        ctx: &Context,
+        (surface, node, tab): (SurfaceIndex, NodeIndex, TabIndex),
+    ) {
+        ctx.data_mut(|d| d.insert_temp(self.id.with((surface, node, "scroll_to_tab")), tab));
+    }
+
+    /// Removes a tab which has been closed, activating another tab of its leaf according to
+    /// [`DockArea::on_close_activate`] if it was the active one.
+    fn remove_closed_tab(&mut self, (surface, node, tab): (SurfaceIndex, NodeIndex, TabIndex)) {
+        let next_active = match &self.dock_state[surface][node] {
+            Node::Leaf { tabs, active, .. } if *active == tab && tabs.len() > 1 => {
+                // Indices of the neighbors once the closed tab is gone.
+                let before = tab.0.saturating_sub(1);
+                let after = if tab.0 + 1 < tabs.len() {
+                    tab.0
+                } else {
+                    tab.0 - 1
+                };
+                let rtl = self.layout_direction.is_right_to_left();
+                match self.close_activation_policy {
+                    CloseActivationPolicy::NextRight => Some(if rtl { before } else { after }),
+                    CloseActivationPolicy::NextLeft => Some(if rtl { after } else { before }),
+                    CloseActivationPolicy::KeepIndex => Some(after),
+                    CloseActivationPolicy::MostRecentlyUsed => None,
+                }
+            }
+            _ => None,
+        };
+        self.dock_state.remove_tab((surface, node, tab));
+        if let Some(next_active) = next_active {
+            self.dock_state[surface][node].set_active_tab(TabIndex(next_active));
+        }
+    }
+
+    /// Moves a tab with [`DockState::move_tab`](crate::DockState::move_tab), unless [`TabViewer::on_tab_move`]
+    /// cancels or redirects it, and records what happened as events.
+    fn move_tab(
+        &mut self,
+        source: (SurfaceIndex, NodeIndex, TabIndex),
+        destination: TabDestination,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let (surface, node, tab) = source;
+        let Some(tabs) = self.dock_state[surface][node].tabs_mut() else {
+            return;
+        };
+        let destination = match tab_viewer.on_tab_move(&mut tabs[tab.0], source, &destination) {
+            MoveResponse::Allow => destination,
+            MoveResponse::Cancel => return,
+            MoveResponse::Redirect(destination) => {
+                if self.dock_state.check_move(source, destination).is_err() {
+                    return;
+                }
+                destination
+            }
+        };
+        let tabs = self.dock_state[surface][node].tabs_mut().unwrap();
+        let id = tab_viewer.id(&mut tabs[tab.0]);
+        let style = self.style.as_ref().unwrap();
+        let fraction = drop_split_fraction(&mut tabs[tab.0], destination, style, tab_viewer);
+        let group = self.drop_group(destination, tab_viewer);
+
+        self.record_history(tab_viewer);
+        let surfaces = self.surface_snapshot();
+        let from_window = self.window_id(surface);
+        self.dock_state
+            .move_tab_with_split_fraction(source, destination, fraction);
+        if let Some(group) = group {
+            if let Some(moved) = self.dock_state.find_tab_by_id(id, tab_viewer) {
+                self.assign_dropped_tab_group(moved, group, tab_viewer);
+            }
+        }
+        self.events.push(DockEvent::TabMoved {
+            id,
+            from: source,
+            to: destination,
+        });
+        if let TabDestination::Node(surface, node, TabInsert::Split(split)) = destination {
+            self.events.push(DockEvent::NodeSplit {
+                surface,
+                node,
+                split,
+            });
+        }
+        self.record_surface_changes(&surfaces);
+        self.notify_window_change(id, from_window, tab_viewer);
+    }
+
+    /// Splits a leaf after its split button was clicked, putting the tab returned from [`TabViewer::on_split`] into
+    /// the new leaf, or otherwise a copy of the active tab of the leaf, or the active tab itself.
+    fn split_leaf(
+        &mut self,
+        (surface, node, split): (SurfaceIndex, NodeIndex, Split),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let destination = TabDestination::Node(surface, node, TabInsert::Split(split));
+        if let Some(mut tab) = tab_viewer.on_split(surface, node, split) {
+            let style = self.style.as_ref().unwrap();
+            let fraction = drop_split_fraction(&mut tab, destination, style, tab_viewer);
+            self.record_history(tab_viewer);
+            self.dock_state
+                .insert_tab_with_split_fraction(tab, destination, fraction);
+            self.events.push(DockEvent::NodeSplit {
+                surface,
+                node,
+                split,
+            });
+            return;
+        }
+        let Node::Leaf { tabs, active, .. } = &self.dock_state[surface][node] else {
+            return;
+        };
+        if active.0 >= tabs.len() {
+            return;
+        }
+        let source = (surface, node, *active);
+        let movable = tabs.len() > 1;
+        if !self.copy_tab(source, destination, tab_viewer) && movable {
+            self.move_tab(source, destination, tab_viewer);
+        }
+    }
+
+    /// Updates the size each leaf needs for its tab bar and the bodies of its tabs, as reported by
+    /// [`TabViewer::min_body_size`], and at least [`SeparatorStyle::min_panel_size`](crate::SeparatorStyle::min_panel_size) unless
+    /// it's collapsed, so that the layout doesn't squash them.
+    fn update_content_min_sizes(&mut self, tab_viewer: &mut impl TabViewer<Tab = Tab>) {
+        let style = self.style.as_ref().unwrap();
+        for (_, node) in self.dock_state.iter_all_nodes_mut() {
+            let Node::Leaf {
+                tabs,
+                collapsed,
+                tab_bar_hidden,
+                tab_bar_style,
+                ..
+            } = node
+            else {
+                continue;
+            };
+            let tab_bar = tab_bar_style.as_ref().unwrap_or(&style.tab_bar);
+            let body_min_size = tabs
+                .iter_mut()
+                .filter_map(|tab| tab_viewer.min_body_size(tab))
+                .reduce(Vec2::max);
+            let size = body_min_size.map_or(Vec2::ZERO, |body| {
+                let tab_bar_height = if *tab_bar_hidden && !*collapsed {
+                    0.0
+                } else if tab_bar.hide_when_single_tab && tabs.len() == 1 {
+                    Style::TAB_GRAB_HANDLE_HEIGHT
+                } else {
+                    tab_bar.height
+                };
+                let leaf = if tab_bar.position.is_vertical() {
+                    body + vec2(tab_bar_height, 0.0)
+                } else {
+                    body + vec2(0.0, tab_bar_height)
+                };
+                leaf + style.leaf_padding.sum()
+            });
+            let size = if *collapsed {
+                size
+            } else {
+                size.max(Vec2::splat(style.separator.min_panel_size))
+            };
+            node.set_content_min_size(size);
+        }
+    }
+
+    /// Returns the edge of the leaf at the given index along which it shrinks into a strip when it's collapsed, or
+    /// [`None`] if it collapses into its tab bar as usual.
+    pub(super) fn side_strip_position(
+        &self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+    ) -> Option<TabBarPosition> {
+        if !self.collapse_into_side_strips || !self.dock_state[surface_index][node_index].is_leaf()
+        {
+            return None;
+        }
+        let parent = node_index.parent()?;
+        self.dock_state[surface_index][parent]
+            .is_horizontal()
+            .then(|| {
+                if node_index == parent.left() {
+                    TabBarPosition::Left
+                } else {
+                    TabBarPosition::Right
+                }
+            })
+    }
+
+    /// Moves a leaf into a new window with [`DockState::detach_leaf`](crate::DockState::detach_leaf) and records
+    /// what happened as events.
+    fn detach_leaf(
+        &mut self,
+        (surface, node): (SurfaceIndex, NodeIndex),
+        window_rect: Rect,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let Some(tabs) = self.dock_state[surface][node].tabs_mut() else {
+            return;
+        };
+        let ids: Vec<_> = tabs.iter_mut().map(|tab| tab_viewer.id(tab)).collect();
+
+        self.record_history(tab_viewer);
+        let surfaces = self.surface_snapshot();
+        let from_window = self.window_id(surface);
+        let window = self.dock_state.detach_leaf((surface, node), window_rect);
+        for (index, &id) in ids.iter().enumerate() {
+            self.events.push(DockEvent::TabMoved {
+                id,
+                from: (surface, node, TabIndex(index)),
+                to: TabDestination::Node(
+                    window,
+                    NodeIndex::root(),
+                    TabInsert::Insert(TabIndex(index)),
+                ),
+            });
+        }
+        self.record_surface_changes(&surfaces);
+        for id in ids {
+            self.notify_window_change(id, from_window, tab_viewer);
+        }
+    }
+
+    /// Detaches the [selected tabs](DockState::selected_tabs) into a new window and records what happened as events,
+    /// clearing the selection.
+    fn detach_selected_tabs(
+        &mut self,
+        window_rect: Rect,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let ids = self.dock_state.selected_tabs().to_vec();
+        self.dock_state.clear_tab_selection();
+        let mut tabs: Vec<_> = ids
+            .into_iter()
+            .filter_map(|id| Some((self.dock_state.find_tab_by_id(id, tab_viewer)?, id)))
+            .collect();
+        if tabs.is_empty() {
+            return;
+        }
+        tabs.sort_by_key(|&((surface, node, tab), _)| (surface.0, node.0, tab.0));
+
+        self.record_history(tab_viewer);
+        let surfaces = self.surface_snapshot();
+        let from_windows: Vec<_> = tabs
+            .iter()
+            .map(|&((surface, _, _), id)| (id, self.window_id(surface)))
+            .collect();
+        let window = self
+            .dock_state
+            .detach_tabs(tabs.iter().map(|&(tab, _)| tab).collect(), window_rect);
+        for (index, (from, id)) in tabs.into_iter().enumerate() {
+            self.events.push(DockEvent::TabMoved {
+                id,
+                from,
+                to: TabDestination::Node(
+                    window,
+                    NodeIndex::root(),
+                    TabInsert::Insert(TabIndex(index)),
+                ),
+            });
+        }
+        self.record_surface_changes(&surfaces);
+        for (id, from_window) in from_windows {
+            self.notify_window_change(id, from_window, tab_viewer);
+        }
+    }
+
+    /// Returns the id of the surface at the given index if it's a window.
+    fn window_id(&mut self, surface: SurfaceIndex) -> Option<SurfaceId> {
+        self.dock_state
+            .get_window_state(surface)
+            .map(WindowState::id)
+    }
+
+    /// Calls [`TabViewer::on_tab_detached`] or [`TabViewer::on_tab_docked`] if the tab with the given `id` has been
+    /// moved into another window or out of the window identified by `from_window`.
+    fn notify_window_change(
+        &mut self,
+        id: Id,
+        from_window: Option<SurfaceId>,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let Some((surface, node, tab)) = self.dock_state.find_tab_by_id(id, tab_viewer) else {
+            return;
+        };
+        let to_window = self.window_id(surface);
+        let Some(tabs) = self.dock_state[surface][node].tabs_mut() else {
+            return;
+        };
+        match (from_window, to_window) {
+            (from, Some(to)) if from != Some(to) => {
+                tab_viewer.on_tab_detached(&mut tabs[tab.0], surface)
+            }
+            (Some(_), None) => tab_viewer.on_tab_docked(&mut tabs[tab.0], surface, node),
+            _ => {}
+        }
+    }
+
+    /// Returns `true` if a tab dropped now would be copied instead of moved.
+    fn is_copying_tab(&self, ui: &Ui) -> bool {
+        self.allow_tab_copy && ui.input(|i| i.modifiers.matches_logically(self.tab_copy_modifiers))
+    }
+
+    /// Returns `true` if a tab dropped in the middle of a leaf now would explode it.
+    fn is_exploding_leaf(&self, ui: &Ui) -> bool {
+        self.allow_leaf_explode
+            && ui.input(|i| i.modifiers.matches_logically(self.leaf_explode_modifiers))
+    }
+
+    /// Puts a copy of a tab made with [`TabViewer::clone_tab`] at `destination` and records what happened as events.
+    ///
+    /// Returns `false` if the tab can't be copied.
+    fn copy_tab(
+        &mut self,
+        source: (SurfaceIndex, NodeIndex, TabIndex),
+        destination: TabDestination,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> bool {
+        let (surface, node, tab) = source;
+        let Some(tabs) = self.dock_state[surface][node].tabs_mut() else {
+            return false;
+        };
+        let Some(mut copy) = tab_viewer.clone_tab(&mut tabs[tab.0]) else {
+            return false;
+        };
+        let id = tab_viewer.id(&mut copy);
+        let style = self.style.as_ref().unwrap();
+        let fraction = drop_split_fraction(&mut copy, destination, style, tab_viewer);
+
+        self.record_history(tab_viewer);
+        let surfaces = self.surface_snapshot();
+        self.dock_state
+            .insert_tab_with_split_fraction(copy, destination, fraction);
+        self.events.push(DockEvent::TabCopied {
+            id,
+            from: source,
+            to: destination,
+        });
+        if let TabDestination::Node(surface, node, TabInsert::Split(split)) = destination {
+            self.events.push(DockEvent::NodeSplit {
+                surface,
+                node,
+                split,
+            });
+        }
+        self.record_surface_changes(&surfaces);
+        true
+    }
+
+    /// Records a [`DockEvent::TabClosed`] for the tab at `tab`, or for every tab in the leaf if `tab` is `None`.
+    fn record_tabs_closed(
+        &mut self,
+        surface: SurfaceIndex,
+        node: NodeIndex,
+        tab: Option<TabIndex>,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let Some(tabs) = self.dock_state[surface][node].tabs_mut() else {
+            return;
+        };
+        for (index, tab_ref) in tabs.iter_mut().enumerate() {
+            let index = TabIndex(index);
+            if tab.map_or(true, |tab| tab == index) {
+                self.events.push(DockEvent::TabClosed {
+                    id: tab_viewer.id(tab_ref),
+                    from: (surface, node, index),
+                });
+            }
+        }
+    }
+
+    /// Takes a snapshot of the layout, identifying tabs with [`TabViewer::id`].
+    fn layout_snapshot(&mut self, tab_viewer: &mut impl TabViewer<Tab = Tab>) -> LayoutSnapshot {
+        let mut ids = self.tab_ids(tab_viewer).into_iter();
+        self.dock_state
+            .snapshot(|_| ids.next().expect("There must be an id for each tab"))
+    }
+
+    /// Returns the ids of all tabs, in the order they're visited by [`DockState::iter_all_tabs`](crate::DockState::iter_all_tabs).
+    fn tab_ids(&mut self, tab_viewer: &mut impl TabViewer<Tab = Tab>) -> Vec<Id> {
+        self.dock_state
+            .iter_all_tabs_mut()
+            .map(|(_, tab)| tab_viewer.id(tab))
+            .collect()
+    }
+
+    /// Calls [`TabViewer::on_tab_unfocused`] and [`TabViewer::on_tab_focused`] if the active tab of the focused leaf
+    /// isn't the same as at the end of the last frame.
+    fn notify_focused_tab(
+        &mut self,
+        state: &mut State,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let focused = self
+            .dock_state
+            .find_active_focused()
+            .map(|(_, tab)| tab_viewer.id(tab));
+        let unfocused = std::mem::replace(&mut state.focused_tab, focused);
+        if focused == unfocused {
+            return;
+        }
+        if let Some(unfocused) = unfocused {
+            // The tab may have been closed in the meantime, in which case there's nothing to notify.
+            for (_, tab) in self.dock_state.iter_all_tabs_mut() {
+                if tab_viewer.id(tab) == unfocused {
+                    tab_viewer.on_tab_unfocused(tab);
+                    break;
+                }
+            }
+        }
+        if let Some((_, tab)) = self.dock_state.find_active_focused() {
+            tab_viewer.on_tab_focused(tab);
+        }
+    }
+
+    /// Forgets the sizes of fly-outs of leaves which have been collapsed or expanded, and finishes the collapsing
+    /// animations of the ones which shouldn't be animated.
+    fn apply_collapse_changes(&mut self, ctx: &Context) {
+        for (surface, node, animated) in self.dock_state.take_collapse_changes() {
+            ctx.data_mut(|data| data.remove::<Rect>(self.id.with((surface, node, "fly_out"))));
+            if animated {
+                continue;
+            }
+            let Some(tree) = self
+                .dock_state
+                .get_surface(surface)
+                .and_then(|s| s.node_tree())
+            else {
+                continue;
+            };
+            // Collapsing a leaf may collapse its parents as well.
+            for node in std::iter::once(node).chain(node.ancestors()) {
+                if let Some(node_ref) = tree.iter().nth(node.0) {
+                    ctx.animate_bool_with_time(
+                        self.id.with((surface, node, "collapse")),
+                        node_ref.is_collapsed(),
+                        0.0,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Records the layout before it's changed, if history is enabled.
+    fn record_history(&mut self, tab_viewer: &mut impl TabViewer<Tab = Tab>) {
+        if self.dock_state.is_history_enabled() {
+            let snapshot = self.layout_snapshot(tab_viewer);
+            self.dock_state.record_history(snapshot);
+        }
+    }
+
+    /// Applies the steps requested with [`DockState::undo`](crate::DockState::undo) and
+    /// [`DockState::redo`](crate::DockState::redo).
+    fn step_history(&mut self, tab_viewer: &mut impl TabViewer<Tab = Tab>) {
+        for step in self.dock_state.take_history_steps() {
+            let current = self.layout_snapshot(tab_viewer);
+            let Some(snapshot) = self.dock_state.step_history(step, current) else {
+                continue;
+            };
+            let surfaces = self.surface_snapshot();
+            let mut ids = self.tab_ids(tab_viewer).into_iter();
+            self.dock_state.apply_snapshot(&snapshot, |_| {
+                ids.next().expect("There must be an id for each tab")
+            });
+            self.record_surface_changes(&surfaces);
+        }
+    }
+
+    /// Removes the tabs whose closing has been confirmed with
+    /// [`DockState::confirm_close`](crate::DockState::confirm_close).
+    fn close_confirmed_tabs(&mut self, tab_viewer: &mut impl TabViewer<Tab = Tab>) {
+        for id in self.dock_state.take_confirmed_closes() {
+            // Look the tab up again each time, as removing a tab may move nodes around.
+            let Some((surface, node, tab)) = self.dock_state.find_tab_by_id(id, tab_viewer) else {
+                continue;
+            };
+
+            let surfaces = self.surface_snapshot();
+            self.record_tabs_closed(surface, node, Some(tab), tab_viewer);
+            self.remove_closed_tab((surface, node, tab));
+            self.record_surface_changes(&surfaces);
+        }
+    }
+
+    /// Attaches the payload made with [`DockArea::external_drag_payload`] to the tab being dragged, unless it has been
+    /// attached already.
+    fn set_external_drag_payload(
+        &self,
+        ctx: &Context,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+    ) {
+        let Some(set_payload) = &self.external_drag_payload else {
+            return;
+        };
+        if DragAndDrop::has_any_payload(ctx) {
+            return;
+        }
+        if let Some(tab) = self.dock_state[surface_index][node_index]
+            .tabs()
+            .and_then(|tabs| tabs.get(tab_index.0))
+        {
+            set_payload(tab, ctx);
+        }
+    }
+
+    /// Returns the valid surfaces along with their ids, to be compared by
+    /// [`record_surface_changes`](Self::record_surface_changes) after the layout is changed.
+    pub(super) fn surface_snapshot(&self) -> Vec<(SurfaceIndex, Option<SurfaceId>)> {
+        self.dock_state
+            .valid_surface_indices()
+            .iter()
+            .map(|&surface| (surface, self.dock_state.surface_id(surface)))
+            .collect()
+    }
+
+    /// Records surfaces which have been created or removed since `before` was taken.
+    ///
+    /// Surfaces are told apart by their ids too, since the index of a removed window can be reused by another one in
+    /// the same frame.
+    pub(super) fn record_surface_changes(&mut self, before: &[(SurfaceIndex, Option<SurfaceId>)]) {
+        let after = self.surface_snapshot();
+        for &(surface, _) in before.iter().filter(|surface| !after.contains(surface)) {
+            self.events.push(DockEvent::SurfaceRemoved(surface));
+        }
+        for &(surface, _) in after.iter().filter(|surface| !before.contains(surface)) {
+            self.events.push(DockEvent::SurfaceCreated(surface));
+        }
     }
 
     /// Returns some when windows are fading, and what surface index is being hovered over
@@ -190,17 +1079,105 @@ impl<Tab> DockArea<'_, Tab> {
         })
     }
 
+    /// Describes the tab dragged this frame, see [`DockState::current_drag`](crate::DockState::current_drag).
+    fn current_drag(
+        &self,
+        state: &State,
+        hovering: bool,
+        destination: Option<TabDestination>,
+    ) -> Option<DragInfo> {
+        let dnd = state.dnd.as_ref()?;
+        let source = match dnd.drag.src {
+            TreeComponent::Tab(surface, node, tab) if dnd.drag.foreign.is_none() => {
+                Some((surface, node, tab))
+            }
+            _ => None,
+        };
+        let valid = destination.is_some_and(|destination| {
+            source.map_or(true, |source| {
+                self.dock_state.check_move(source, destination).is_ok()
+            })
+        });
+        Some(DragInfo {
+            source,
+            pointer: dnd.pointer,
+            hovered_rect: hovering.then_some(dnd.hover.rect),
+            destination,
+            valid,
+        })
+    }
+
+    /// Stores the order the surfaces shown in layers of their own are stacked in, for [`DockState::hit_test`].
+    fn record_surface_stack(&mut self, ctx: &Context) {
+        let order: Vec<_> = ctx.memory(|memory| memory.layer_ids().collect());
+        // Layers are only moved to the top at the end of the frame, which also happens to the one pressed on.
+        let pressed = ctx.input(|i| i.pointer.any_pressed().then(|| i.pointer.press_origin()));
+        if let Some(layer) = pressed.flatten().and_then(|pos| ctx.layer_id_at(pos)) {
+            self.raised_layers.push(layer);
+        }
+        // egui paints layers by their `Order` first, and then in the order of its list, with the topmost one last.
+        let raised = &self.raised_layers;
+        self.surface_layers.sort_by_key(|&(_, layer)| {
+            let position = match raised.iter().rposition(|&other| other == layer) {
+                Some(index) => Some(order.len() + index),
+                None => order.iter().position(|&other| other == layer),
+            };
+            std::cmp::Reverse((layer.order, position.unwrap_or(usize::MAX)))
+        });
+        let stack = self.surface_layers.iter().map(|&(surface, _)| surface);
+        self.dock_state.set_surface_stack(stack.collect());
+    }
+
+    /// Measures the layout shown this frame, see [`DockArea::collect_metrics`].
+    fn layout_metrics(
+        &self,
+        ui: &Ui,
+        state: &State,
+        drop_destination: Option<TabDestination>,
+    ) -> LayoutMetrics {
+        let mut metrics = LayoutMetrics::measure(self.dock_state);
+        for tab in &mut metrics.tabs {
+            tab.close_button_rect = self
+                .close_button_rects
+                .iter()
+                .find(|&&(close_tab, _)| close_tab == (tab.surface, tab.node, tab.tab))
+                .map(|&(_, rect)| rect);
+        }
+        metrics.pointer = ui.input(|i| i.pointer.hover_pos());
+        metrics.hovered = metrics
+            .pointer
+            .and_then(|pointer| self.dock_state.hit_test(pointer));
+        metrics.drag = state.dnd.as_ref().map(|dnd| DragMetrics {
+            tab: match dnd.drag.src {
+                TreeComponent::Tab(surface, node, tab) if dnd.drag.foreign.is_none() => {
+                    Some((surface, node, tab))
+                }
+                _ => None,
+            },
+            hovered_rect: dnd.hover.rect,
+            destination: drop_destination,
+        });
+        metrics.open_auto_hidden = self.open_auto_hidden(ui.ctx());
+        metrics
+    }
+
     /// Resolve where a dragged tab would land given it's dropped this frame, returns `None` when the resulting drop is an invalid move.
     fn show_drag_drop_overlay(
         &mut self,
         ui: &Ui,
         state: &mut State,
-        tab_viewer: &impl TabViewer<Tab = Tab>,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
     ) -> Option<TabDestination> {
         let drag_state = state.dnd.as_mut().unwrap();
         let style = self.style.as_ref().unwrap();
 
-        let deserted_node = {
+        let copying = drag_state.drag.foreign.is_none() && self.is_copying_tab(ui);
+        if copying {
+            ui.output_mut(|o| o.cursor_icon = CursorIcon::Copy);
+        }
+
+        // Dropping the only tab of a leaf onto its own leaf doesn't make room for a split, unless it's copied.
+        let deserted_node = !copying && drag_state.drag.foreign.is_none() && {
             match (
                 drag_state.drag.src.node_address(),
                 drag_state.hover.dst.node_address(),
@@ -220,39 +1197,99 @@ impl<Tab> DockArea<'_, Tab> {
         } else {
             AllowedSplits::All
         };
-        let allowed_splits = self.allowed_splits & restricted_splits;
+        let node_splits = match drag_state.hover.dst.node_address() {
+            (surface, Some(node)) => tab_viewer.allowed_splits_for_node(surface, node),
+            (_, None) => AllowedSplits::All,
+        };
+        let allowed_splits = self.allowed_splits & restricted_splits & node_splits;
 
-        let allowed_in_window = match drag_state.drag.src {
-            TreeComponent::Tab(surface, node, tab) => {
+        let (allowed_in_window, allowed) = match (drag_state.drag.foreign, &drag_state.drag.src) {
+            (Some(foreign), _) => (foreign.allowed_in_windows, AllowedDestinations::all()),
+            (None, &TreeComponent::Tab(surface, node, tab)) => {
                 let Node::Leaf { tabs, .. } = &mut self.dock_state[surface][node] else {
                     unreachable!("tab drags can only come from leaf nodes")
                 };
-                tab_viewer.allowed_in_windows(&mut tabs[tab.0])
+                let tab = &mut tabs[tab.0];
+                (
+                    tab_viewer.allowed_in_windows(tab),
+                    tab_viewer.allowed_destinations(tab),
+                )
             }
             _ => todo!("collections of tabs, like nodes or surfaces, can't be dragged! (yet)"),
         };
+        let (dst_surface, dst_node) = drag_state.hover.dst.node_address();
+
+        // Exploding a leaf splits it both ways, so it has to be allowed to.
+        let explode = match drag_state.hover.dst {
+            TreeComponent::Node(surface, node)
+                if self.is_exploding_leaf(ui)
+                    && allowed_splits == AllowedSplits::All
+                    && allowed.splits == AllowedSplits::All =>
+            {
+                let moved_within = !copying
+                    && drag_state.drag.foreign.is_none()
+                    && drag_state.drag.src.node_address() == (surface, Some(node));
+                Some(self.dock_state[surface][node].tabs_count() + usize::from(!moved_within))
+            }
+            _ => None,
+        };
+        let targets = DropTargets {
+            splits: allowed_splits,
+            enabled_splits: allowed.splits,
+            dock: allowed.allows_node(dst_surface, dst_node)
+                && (dst_surface.is_main() || allowed_in_window),
+            new_window: allowed_in_window && allowed.new_window,
+            explode,
+        };
 
         if let Some(pointer) = state.last_hover_pos {
             drag_state.pointer = pointer;
         }
 
         let window_bounds = self.window_bounds.unwrap();
-        match (style.overlay.overlay_type, drag_state.is_on_title_bar()) {
-            (OverlayType::HighlightedAreas, _) | (_, true) => drag_state.resolve_traditional(
-                ui,
-                style,
-                allowed_splits,
-                allowed_in_window,
-                window_bounds,
-            ),
-            (OverlayType::Widgets, false) => drag_state.resolve_icon_based(
-                ui,
-                style,
-                allowed_splits,
-                allowed_in_window,
-                window_bounds,
-            ),
+        let traditional = style.overlay.overlay_type == OverlayType::HighlightedAreas
+            || drag_state.is_on_title_bar();
+        let destination = if traditional {
+            drag_state.resolve_traditional(ui, style, targets, window_bounds)
+        } else {
+            drag_state.resolve_icon_based(ui, style, targets, window_bounds, tab_viewer)
+        };
+
+        if let Some(TabDestination::Node(surface, node, TabInsert::Split(split))) = destination {
+            // Tabs dragged from other areas aren't at hand, so they take the default amount of space.
+            let fraction = match drag_state.drag.src {
+                TreeComponent::Tab(src_surface, src_node, src_tab)
+                    if drag_state.drag.foreign.is_none() =>
+                {
+                    let tabs = self.dock_state[src_surface][src_node].tabs_mut().unwrap();
+                    drop_split_fraction(
+                        &mut tabs[src_tab.0],
+                        destination.unwrap(),
+                        style,
+                        tab_viewer,
+                    )
+                }
+                _ => style.overlay.feel.drop_split_fraction.clamp(0.0, 1.0),
+            };
+            // The dragged tab only leaves the tree if it's moved within the same surface.
+            let src = match drag_state.drag.src {
+                TreeComponent::Tab(src_surface, src_node, src_tab)
+                    if !copying && drag_state.drag.foreign.is_none() && src_surface == surface =>
+                {
+                    Some((src_node, src_tab))
+                }
+                _ => None,
+            };
+            let tree = &self.dock_state[surface];
+            if style.overlay.split_preview {
+                draw_split_preview(tree, src, (node, split, fraction), ui, style);
+            } else if traditional {
+                let rect = split_preview_rect(tree, src, (node, split, fraction), ui, style)
+                    .unwrap_or_else(|| split_drop_rect(drag_state.hover.rect, split, fraction));
+                draw_preview_rect(rect, style.overlay.drop_preview.split, ui, style);
+            }
         }
+        destination
     }
 
     /// Show a single surface of a [`DockState`].
@@ -266,7 +1303,10 @@ impl<Tab> DockArea<'_, Tab> {
     ) {
         if surf_index.is_main() {
             self.show_root_surface_inside(ui, tab_viewer, state);
-        } else {
+        } else if self.dock_state.get_auto_hide_state(surf_index).is_none()
+            && !self.is_in_taskbar(state, surf_index)
+        {
+            // Auto-hidden leaves are shown along with the main surface, and windows in the taskbar aren't shown.
             self.show_window_surface(ui, surf_index, tab_viewer, state, fade_style);
         }
     }
@@ -281,11 +1321,7 @@ impl<Tab> DockArea<'_, Tab> {
     ) {
         // First compute all rect sizes in the node graph.
         let max_rect = self.allocate_area_for_root_node(ui, surf_index);
-        for node_index in self.dock_state[surf_index].breadth_first_index_iter() {
-            if self.dock_state[surf_index][node_index].is_parent() {
-                self.compute_rect_sizes(ui, (surf_index, node_index), max_rect);
-            }
-        }
+        self.layout_nodes(ui.ctx(), surf_index, max_rect);
 
         // Then, draw the bodies of each leaves.
         for node_index in self.dock_state[surf_index].breadth_first_index_iter() {
@@ -294,9 +1330,11 @@ impl<Tab> DockArea<'_, Tab> {
             }
         }
 
+        let fade_style = fade_style.map(|(style, _)| style);
+        self.draw_focused_leaf_highlight(ui, surf_index, fade_style);
+
         // Finally, draw separators so that their "interaction zone" is above
         // bodies (see `SeparatorStyle::extra_interact_width`).
-        let fade_style = fade_style.map(|(style, _)| style);
         for node_index in self.dock_state[surf_index].breadth_first_index_iter() {
             if self.dock_state[surf_index][node_index].is_parent() {
                 self.show_separator(ui, (surf_index, node_index), fade_style);
@@ -304,14 +1342,81 @@ impl<Tab> DockArea<'_, Tab> {
         }
     }
 
-    fn allocate_area_for_root_node(&mut self, ui: &mut Ui, surface: SurfaceIndex) -> Rect {
-        let style = self.style.as_ref().unwrap();
-        let mut rect = ui.available_rect_before_wrap();
+    /// Draws [`Style::focused_leaf_highlight`] around the focused leaf if it's on this surface.
+    fn draw_focused_leaf_highlight(
+        &self,
+        ui: &Ui,
+        surf_index: SurfaceIndex,
+        fade_style: Option<&Style>,
+    ) {
+        let Some((surface, node)) = self.dock_state.focused_leaf() else {
+            return;
+        };
+        if surface != surf_index {
+            return;
+        }
+        let Some(rect) = self.dock_state[surface][node].rect() else {
+            return;
+        };
+        let highlight = &fade_style
+            .unwrap_or_else(|| self.style.as_ref().unwrap())
+            .focused_leaf_highlight;
+        ui.painter().rect(
+            rect.expand(highlight.expansion),
+            highlight.rounding,
+            highlight.color,
+            highlight.stroke,
+        );
+    }
+
+    /// Computes the rects of all nodes of a surface whose root node has already been given its rect.
+    ///
+    /// Rects set with [`DockLayout::set_rect`](super::DockLayout::set_rect) replace the computed ones on the main
+    /// surface, and the children of such nodes are laid out inside them.
+    pub(super) fn layout_nodes(
+        &mut self,
+        ctx: &Context,
+        surf_index: SurfaceIndex,
+        mut max_rect: Rect,
+    ) {
+        for node_index in self.dock_state[surf_index].breadth_first_index_iter() {
+            if surf_index.is_main() {
+                if let Some(&(_, rect)) = self
+                    .layout_overrides
+                    .iter()
+                    .rev()
+                    .find(|(node, _)| *node == node_index)
+                {
+                    self.dock_state[surf_index][node_index].set_rect(rect);
+                    if node_index == NodeIndex::root() {
+                        max_rect = rect;
+                    }
+                }
+            }
+            if self.dock_state[surf_index][node_index].is_parent() {
+                self.compute_rect_sizes(ctx, (surf_index, node_index), max_rect);
+            }
+        }
 
-        if let Some(margin) = style.dock_area_padding {
-            rect.min += margin.left_top();
-            rect.max -= margin.right_bottom();
+        let padding = self.style.as_ref().unwrap().leaf_padding;
+        for node in self.dock_state[surf_index].iter_mut() {
+            if let (true, false, Some(rect)) = (node.is_leaf(), node.is_collapsed(), node.rect()) {
+                node.set_rect(pad_rect(rect, padding));
+            }
         }
+    }
+
+    /// Shrinks `rect` by [`Style::dock_area_padding`].
+    fn pad_dock_area_rect(&self, rect: Rect) -> Rect {
+        match self.style.as_ref().unwrap().dock_area_padding {
+            Some(margin) => pad_rect(rect, margin),
+            None => rect,
+        }
+    }
+
+    fn allocate_area_for_root_node(&mut self, ui: &mut Ui, surface: SurfaceIndex) -> Rect {
+        let mut rect = self.pad_dock_area_rect(ui.available_rect_before_wrap());
+        let style = self.style.as_ref().unwrap();
 
         ui.painter().rect_stroke(
             rect,
@@ -332,14 +1437,14 @@ impl<Tab> DockArea<'_, Tab> {
 
     fn compute_rect_sizes(
         &mut self,
-        ui: &Ui,
+        ctx: &Context,
         (surface_index, node_index): (SurfaceIndex, NodeIndex),
         max_rect: Rect,
     ) {
         assert!(self.dock_state[surface_index][node_index].is_parent());
 
         let style = self.style.as_ref().unwrap();
-        let pixels_per_point = ui.ctx().pixels_per_point();
+        let pixels_per_point = ctx.pixels_per_point();
 
         let left_collapsed_count =
             self.dock_state[surface_index][node_index.left()].collapsed_leaf_count();
@@ -348,60 +1453,113 @@ impl<Tab> DockArea<'_, Tab> {
         let left_collapsed = self.dock_state[surface_index][node_index.left()].is_collapsed();
         let right_collapsed = self.dock_state[surface_index][node_index.right()].is_collapsed();
 
-        if left_collapsed || right_collapsed {
-            if let Node::Vertical { rect, .. } = &mut self.dock_state[surface_index][node_index] {
+        // How far along the children are in their collapsing animations, 1.0 meaning fully collapsed.
+        let (left_collapse, right_collapse) = {
+            let animation_time = ctx.style().animation_time;
+            let animate = |node: NodeIndex, collapsed: bool| {
+                ctx.animate_bool_with_time_and_easing(
+                    self.id.with((surface_index, node, "collapse")),
+                    collapsed,
+                    animation_time,
+                    easing::cubic_in_out,
+                )
+            };
+            (
+                animate(node_index.left(), left_collapsed),
+                animate(node_index.right(), right_collapsed),
+            )
+        };
+
+        if left_collapse > 0.0 || right_collapse > 0.0 {
+            if let Node::Vertical { rect, fraction, .. } =
+                &mut self.dock_state[surface_index][node_index]
+            {
                 debug_assert!(!rect.any_nan() && rect.is_finite());
                 let rect = expand_to_pixel(*rect, pixels_per_point);
 
-                if left_collapsed {
-                    // EITHER only left collapsed OR left and right both collapsed
-                    let border_y =
-                        rect.min.y + (left_collapsed_count as f32) * style.tab_bar.height;
-                    let left_separator_border = map_to_pixel(
-                        border_y - style.separator.width * 0.5,
-                        pixels_per_point,
-                        f32::round,
-                    );
-                    let right_separator_border = map_to_pixel(
-                        border_y + style.separator.width * 0.5,
-                        pixels_per_point,
-                        f32::round,
-                    );
-                    let left = rect
-                        .intersect(Rect::everything_above(left_separator_border))
-                        .intersect(max_rect);
-                    let right = rect
-                        .intersect(Rect::everything_below(right_separator_border))
-                        .intersect(max_rect);
-                    self.dock_state[surface_index][node_index.left()].set_rect(left);
-                    self.dock_state[surface_index][node_index.right()].set_rect(right);
-                } else {
-                    // Only right collapsed
-                    let border_y =
-                        rect.max.y - (right_collapsed_count as f32) * style.tab_bar.height;
-                    let left_separator_border = map_to_pixel(
-                        border_y - style.separator.width * 0.5,
-                        pixels_per_point,
-                        f32::round,
-                    );
-                    let right_separator_border = map_to_pixel(
-                        border_y + style.separator.width * 0.5,
-                        pixels_per_point,
-                        f32::round,
-                    );
-                    let left = rect
-                        .intersect(Rect::everything_above(left_separator_border))
-                        .intersect(max_rect);
-                    let right = rect
-                        .intersect(Rect::everything_below(right_separator_border))
-                        .intersect(max_rect);
-                    self.dock_state[surface_index][node_index.left()].set_rect(left);
-                    self.dock_state[surface_index][node_index.right()].set_rect(right);
-                }
+                // A collapsed child only takes the height of the tab bars of its collapsed leaves. While animating,
+                // the border moves between that position and the one given by the fraction.
+                let expanded_y = rect.min.y + rect.height() * *fraction;
+                let left_collapsed_y =
+                    rect.min.y + (left_collapsed_count.max(1) as f32) * style.tab_bar.height;
+                let right_collapsed_y =
+                    rect.max.y - (right_collapsed_count.max(1) as f32) * style.tab_bar.height;
+                let border_y = lerp(
+                    lerp(expanded_y..=right_collapsed_y, right_collapse)..=left_collapsed_y,
+                    left_collapse,
+                );
+
+                let left_separator_border = map_to_pixel(
+                    border_y - style.separator.width * 0.5,
+                    pixels_per_point,
+                    f32::round,
+                );
+                let right_separator_border = map_to_pixel(
+                    border_y + style.separator.width * 0.5,
+                    pixels_per_point,
+                    f32::round,
+                );
+                let left = rect
+                    .intersect(Rect::everything_above(left_separator_border))
+                    .intersect(max_rect);
+                let right = rect
+                    .intersect(Rect::everything_below(right_separator_border))
+                    .intersect(max_rect);
+                self.dock_state[surface_index][node_index.left()].set_rect(left);
+                self.dock_state[surface_index][node_index.right()].set_rect(right);
+                return;
+            }
+        }
+
+        // Leaves collapsing into side strips only take the width of their tab bars.
+        let strip_width = |node: NodeIndex| {
+            self.side_strip_position((surface_index, node)).map(|_| {
+                self.dock_state[surface_index][node]
+                    .tab_bar_style()
+                    .map_or(style.tab_bar.height, |tab_bar| tab_bar.height)
+            })
+        };
+        let left_strip = strip_width(node_index.left()).filter(|_| left_collapse > 0.0);
+        let right_strip = strip_width(node_index.right()).filter(|_| right_collapse > 0.0);
+        if left_strip.is_some() || right_strip.is_some() {
+            if let Node::Horizontal { rect, fraction, .. } =
+                &mut self.dock_state[surface_index][node_index]
+            {
+                debug_assert!(!rect.any_nan() && rect.is_finite());
+                let rect = expand_to_pixel(*rect, pixels_per_point);
+
+                let expanded_x = rect.min.x + rect.width() * *fraction;
+                let border_x = match (left_strip, right_strip) {
+                    (Some(width), _) => lerp(expanded_x..=rect.min.x + width, left_collapse),
+                    (None, Some(width)) => lerp(expanded_x..=rect.max.x - width, right_collapse),
+                    (None, None) => unreachable!(),
+                };
+
+                let left_separator_border = map_to_pixel(
+                    border_x - style.separator.width * 0.5,
+                    pixels_per_point,
+                    f32::round,
+                );
+                let right_separator_border = map_to_pixel(
+                    border_x + style.separator.width * 0.5,
+                    pixels_per_point,
+                    f32::round,
+                );
+                let left = rect
+                    .intersect(Rect::everything_left_of(left_separator_border))
+                    .intersect(max_rect);
+                let right = rect
+                    .intersect(Rect::everything_right_of(right_separator_border))
+                    .intersect(max_rect);
+                self.dock_state[surface_index][node_index.left()].set_rect(left);
+                self.dock_state[surface_index][node_index.right()].set_rect(right);
                 return;
             }
         }
 
+        let (left_min, left_max) = self.dock_state[surface_index].size_limits(node_index.left());
+        let (right_min, right_max) = self.dock_state[surface_index].size_limits(node_index.right());
+
         duplicate! {
             [
                 orientation   dim_point  dim_size  left_of    right_of;
@@ -412,6 +1570,14 @@ impl<Tab> DockArea<'_, Tab> {
                 debug_assert!(!rect.any_nan() && rect.is_finite());
                 let rect = expand_to_pixel(*rect, pixels_per_point);
 
+                *fraction = constrain_fraction(
+                    *fraction,
+                    rect.dim_size(),
+                    style.separator.width,
+                    (left_min.dim_point, left_max.dim_point),
+                    (right_min.dim_point, right_max.dim_point),
+                );
+
                 let midpoint = rect.min.dim_point + rect.dim_size() * *fraction;
                 let left_separator_border = map_to_pixel(
                     midpoint - style.separator.width * 0.5,
@@ -444,109 +1610,44 @@ impl<Tab> DockArea<'_, Tab> {
         assert!(self.dock_state[surface_index][node_index].is_parent());
 
         // If either of the children is collapsed, we don't want the user to interact with the separator
-        if (self.dock_state[surface_index][node_index.left()].is_collapsed()
-            || self.dock_state[surface_index][node_index.right()].is_collapsed())
-            && self.dock_state[surface_index][node_index].is_vertical()
-        {
+        let collapsed_side_by_side = |node: NodeIndex| {
+            self.dock_state[surface_index][node].is_collapsed()
+                && (self.dock_state[surface_index][node_index].is_vertical()
+                    || self.side_strip_position((surface_index, node)).is_some())
+        };
+        if collapsed_side_by_side(node_index.left()) || collapsed_side_by_side(node_index.right()) {
             return;
         }
 
         let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
-        let pixels_per_point = ui.ctx().pixels_per_point();
-
-        duplicate! {
-            [
-                orientation   dim_point  dim_size;
-                [Horizontal]  [x]        [width];
-                [Vertical]    [y]        [height];
-            ]
-            if let Node::orientation { fraction, ref rect, .. } = &mut self.dock_state[surface_index][node_index] {
-                let mut separator = *rect;
-
-                let midpoint = rect.min.dim_point + rect.dim_size() * *fraction;
-                separator.min.dim_point = midpoint - style.separator.width * 0.5;
-                separator.max.dim_point = midpoint + style.separator.width * 0.5;
-
-                let mut expand = Vec2::ZERO;
-                expand.dim_point += style.separator.extra_interact_width / 2.0;
-                let interact_rect = separator.expand2(expand);
-
-                let response = ui.allocate_rect(interact_rect, Sense::click_and_drag())
-                    .on_hover_and_drag_cursor(paste!{ CursorIcon::[<Resize orientation>]});
-
-                let should_respond_to_arrow_keys = ui.input(|i| i.modifiers.command || i.modifiers.shift);
-
-                if response.has_focus() {
-                    // Prevent the default behaviour of removing focus from the separators when the
-                    // arrow keys are pressed
-                    ui.memory_mut(|m| m.set_focus_lock_filter(response.id, EventFilter {
-                        horizontal_arrows: should_respond_to_arrow_keys,
-                        vertical_arrows: should_respond_to_arrow_keys,
-                        tab: false,
-                        escape: false
-                    }));
+        let (left_min, left_max) = self.dock_state[surface_index].size_limits(node_index.left());
+        let (right_min, right_max) = self.dock_state[surface_index].size_limits(node_index.right());
+        let (fraction, rect, orientation, axis) =
+            match &mut self.dock_state[surface_index][node_index] {
+                Node::Horizontal { fraction, rect, .. } => {
+                    (fraction, *rect, SplitOrientation::Horizontal, 0)
                 }
-
-                let arrow_key_offset = if response.has_focus() && should_respond_to_arrow_keys {
-                    if ui.input(|i| i.key_pressed(Key::ArrowUp)) {
-                        Some(egui::vec2(0., -16.))
-                    } else if ui.input(|i| i.key_pressed(Key::ArrowDown)) {
-                        Some(egui::vec2(0., 16.))
-                    } else if ui.input(|i| i.key_pressed(Key::ArrowLeft)) {
-                        Some(egui::vec2(-16., 0.))
-                    } else if ui.input(|i| i.key_pressed(Key::ArrowRight)) {
-                        Some(egui::vec2(16., 0.))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                };
-
-                let midpoint = rect.min.dim_point + rect.dim_size() * *fraction;
-                separator.min.dim_point = map_to_pixel(
-                    midpoint - style.separator.width * 0.5,
-                    pixels_per_point,
-                    f32::round,
-                );
-                separator.max.dim_point = map_to_pixel(
-                    midpoint + style.separator.width * 0.5,
-                    pixels_per_point,
-                    f32::round,
-                );
-
-                let color = if response.dragged() {
-                    style.separator.color_dragged
-                } else if response.hovered() || response.has_focus() {
-                    style.separator.color_hovered
-                } else {
-                    style.separator.color_idle
-                };
-
-                ui.painter().rect_filled(separator, Rounding::ZERO, color);
-
-                // Update 'fraction' interaction after drawing separator,
-                // otherwise it may overlap on other separator / bodies when
-                // shrunk fast.
-                if let Some(pos) = response.interact_pointer_pos().or(arrow_key_offset.map(|v| separator.center() + v)) {
-                    let dim_point = pos.dim_point;
-                    let delta = arrow_key_offset.unwrap_or(response.drag_delta()).dim_point;
-
-                    if (delta > 0. && dim_point > midpoint && dim_point < rect.max.dim_point)
-                        || (delta < 0. && dim_point < midpoint && dim_point > rect.min.dim_point)
-                    {
-                        let range = rect.max.dim_point - rect.min.dim_point;
-                        let min = (style.separator.extra / range).min(1.0);
-                        let max = 1.0 - min;
-                        let (min, max) = (min.min(max), max.max(min));
-                        *fraction = (*fraction + delta / range).clamp(min, max);
-                    }
+                Node::Vertical { fraction, rect, .. } => {
+                    (fraction, *rect, SplitOrientation::Vertical, 1)
                 }
+                _ => unreachable!(),
+            };
+        let interacted = Separator::new(
+            self.id.with((surface_index, node_index, "separator")),
+            fraction,
+            orientation,
+        )
+        .style(style.separator.clone())
+        .min_sizes(left_min[axis], right_min[axis])
+        .max_sizes(left_max[axis], right_max[axis])
+        .interactive(!self.locked)
+        .show(ui, rect)
+        .response
+        .changed();
 
-                if response.double_clicked() {
-                    *fraction = 0.5;
-                }
-            }
+        if interacted {
+            self.dock_state
+                .cancel_fraction_animation((surface_index, node_index));
         }
     }
 }
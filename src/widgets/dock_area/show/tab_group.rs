@@ -0,0 +1,187 @@
+use egui::{vec2, Align, Layout, Sense, Stroke, Ui, UiBuilder, WidgetText};
+
+use crate::{
+    dock_area::state::State, DockArea, GroupId, Node, NodeIndex, Style, SurfaceIndex,
+    TabBarPosition, TabDestination, TabIndex, TabInsert, TabViewer,
+};
+
+impl<Tab> DockArea<'_, Tab> {
+    /// Returns the group of each tab of a leaf, or [`None`] if none of its tabs is in a group.
+    pub(super) fn leaf_tab_groups(
+        &mut self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> Option<Vec<Option<GroupId>>> {
+        let tabs = self.dock_state[surface_index][node_index].tabs_mut()?;
+        let groups: Vec<_> = tabs
+            .iter_mut()
+            .map(|tab| tab_viewer.group_of(tab))
+            .collect();
+        groups.iter().any(Option::is_some).then_some(groups)
+    }
+
+    /// Shows the row with the titles of the groups of tabs in a leaf, switching to the group which is clicked or
+    /// hovered by a dragged tab.
+    ///
+    /// `groups` are the groups of the tabs of the leaf, as returned from [`Self::leaf_tab_groups`].
+    pub(super) fn tab_group_row(
+        &mut self,
+        ui: &mut Ui,
+        state: &State,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        groups: &[Option<GroupId>],
+        fade_style: Option<&Style>,
+    ) {
+        let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+        let (rect, _) = ui.allocate_exact_size(
+            vec2(ui.available_width(), style.tab_bar.height),
+            Sense::hover(),
+        );
+        ui.painter().rect_filled(rect, 0.0, style.tab_bar.bg_fill);
+        let separator_y = if self.tab_bar_position == TabBarPosition::Bottom {
+            rect.top()
+        } else {
+            rect.bottom()
+        };
+        ui.painter().hline(
+            rect.x_range(),
+            separator_y,
+            Stroke::new(1.0, style.tab_bar.hline_color),
+        );
+
+        let active_group = self.dock_state[surface_index][node_index]
+            .active_tab()
+            .and_then(|active| groups.get(active.0).cloned().flatten());
+        let mut distinct: Vec<&Option<GroupId>> = Vec::new();
+        for group in groups {
+            if !distinct.contains(&group) {
+                distinct.push(group);
+            }
+        }
+
+        let row_ui = &mut ui.new_child(
+            UiBuilder::new()
+                .max_rect(rect.shrink2(vec2(Style::TAB_GROUP_SPACING, 0.0)))
+                .layout(Layout::left_to_right(Align::Center))
+                .id_salt((surface_index, node_index, "tab_groups")),
+        );
+        row_ui.spacing_mut().item_spacing = vec2(Style::TAB_GROUP_SPACING, 0.0);
+        let mut chosen = None;
+        for group in distinct {
+            let title: WidgetText = match group {
+                Some(group) => tab_viewer.group_title(group),
+                None => self
+                    .dock_state
+                    .translations
+                    .leaf
+                    .ungrouped_tabs
+                    .as_str()
+                    .into(),
+            };
+            let response = row_ui.selectable_label(*group == active_group, title);
+            let drag_hovered = state.drag_start.is_some()
+                && state
+                    .last_hover_pos
+                    .is_some_and(|pos| response.rect.contains(pos));
+            if response.clicked() {
+                self.new_focused = Some((surface_index, node_index));
+                chosen = Some(group);
+            } else if drag_hovered {
+                chosen = Some(group);
+            }
+        }
+
+        if let Some(group) = chosen.filter(|&group| *group != active_group) {
+            if let Some(tab) = self.recent_tab_in_group((surface_index, node_index), groups, group)
+            {
+                self.dock_state[surface_index][node_index].set_active_tab(tab);
+                ui.ctx().request_repaint();
+            }
+        }
+    }
+
+    /// Returns the tab of the given group which was active most recently in a leaf, or its first tab if none of
+    /// them has been active yet.
+    fn recent_tab_in_group(
+        &self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        groups: &[Option<GroupId>],
+        group: &Option<GroupId>,
+    ) -> Option<TabIndex> {
+        let in_group = |tab: &TabIndex| groups.get(tab.0) == Some(group);
+        let Node::Leaf { history, .. } = &self.dock_state[surface_index][node_index] else {
+            return None;
+        };
+        history
+            .iter()
+            .rev()
+            .copied()
+            .find(in_group)
+            .or_else(|| (0..groups.len()).map(TabIndex).find(in_group))
+    }
+
+    /// Marks the tabs which aren't in the group of the active tab of a leaf as hidden from its tab bar.
+    pub(super) fn hide_other_groups(
+        &mut self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        groups: &[Option<GroupId>],
+    ) {
+        let active_group = self.dock_state[surface_index][node_index]
+            .active_tab()
+            .and_then(|active| groups.get(active.0));
+        self.hidden_tabs = groups
+            .iter()
+            .map(|group| Some(group) != active_group)
+            .collect();
+    }
+
+    /// Returns the group shown in the leaf a tab is dropped into, or [`None`] if the leaf doesn't show groups or the
+    /// tab isn't dropped into its tab bar or body.
+    pub(super) fn drop_group(
+        &mut self,
+        destination: TabDestination,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> Option<Option<GroupId>> {
+        let TabDestination::Node(
+            surface_index,
+            node_index,
+            TabInsert::Insert(_) | TabInsert::Append,
+        ) = destination
+        else {
+            return None;
+        };
+        let node = &self.dock_state[surface_index][node_index];
+        let position = node
+            .tab_bar_style()
+            .map_or(self.style.as_ref().unwrap().tab_bar.position, |style| {
+                style.position
+            });
+        if node.is_collapsed() || position.is_vertical() {
+            return None;
+        }
+        let active = node.active_tab()?;
+        let groups = self.leaf_tab_groups((surface_index, node_index), tab_viewer)?;
+        Some(groups.get(active.0).cloned().flatten())
+    }
+
+    /// Puts a tab dropped into a leaf showing groups into the group which was shown, see
+    /// [`TabViewer::set_group`].
+    pub(super) fn assign_dropped_tab_group(
+        &mut self,
+        tab: (SurfaceIndex, NodeIndex, TabIndex),
+        group: Option<GroupId>,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let (surface_index, node_index, tab_index) = tab;
+        let Some(tab) = self.dock_state[surface_index][node_index]
+            .tabs_mut()
+            .and_then(|tabs| tabs.get_mut(tab_index.0))
+        else {
+            return;
+        };
+        if tab_viewer.group_of(tab) != group {
+            tab_viewer.set_group(tab, group);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use egui::{CursorIcon, Rect, Response, Rounding, Sense, Stroke, TextStyle, TextWrapMode, Ui};
+
+use crate::{dock_area::state::State, DockArea, Style, SurfaceIndex, TabViewer};
+
+impl<Tab> DockArea<'_, Tab> {
+    /// Returns the windows minimized into the taskbar in the order they were minimized, forgetting the ones which
+    /// have been closed since.
+    pub(super) fn taskbar_windows(&self, state: &mut State) -> Vec<SurfaceIndex> {
+        if !self.window_taskbar {
+            state.taskbar.clear();
+            return Vec::new();
+        }
+        state
+            .taskbar
+            .retain(|&id| self.dock_state.surface_by_id(id).is_some());
+        state
+            .taskbar
+            .iter()
+            .filter_map(|&id| self.dock_state.surface_by_id(id))
+            .collect()
+    }
+
+    /// Returns `true` if the window is minimized into the taskbar, in which case it isn't shown.
+    pub(super) fn is_in_taskbar(&self, state: &State, surf_index: SurfaceIndex) -> bool {
+        self.dock_state
+            .surface_id(surf_index)
+            .is_some_and(|id| state.taskbar.contains(&id))
+    }
+
+    /// Returns `true` if a window has just been released with the pointer outside of the window bounds, which
+    /// minimizes it into the taskbar.
+    pub(super) fn is_dropped_outside_bounds(&self, ui: &Ui, response: &Response) -> bool {
+        self.window_taskbar
+            && response.drag_stopped()
+            && ui
+                .ctx()
+                .pointer_interact_pos()
+                .is_some_and(|pointer| !self.window_bounds.unwrap().contains(pointer))
+    }
+
+    /// Draws the taskbar listing the windows minimized into it along the bottom of `rect`.
+    ///
+    /// Returns what's left of `rect` for the main surface.
+    pub(super) fn show_window_taskbar(
+        &mut self,
+        ui: &mut Ui,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        state: &mut State,
+        rect: Rect,
+        windows: &[SurfaceIndex],
+    ) -> Rect {
+        let style = self.style.as_ref().unwrap();
+        let mut inner = rect;
+        inner.max.y -= style.tab_bar.height;
+        let strip = Rect::from_x_y_ranges(rect.x_range(), inner.max.y..=rect.max.y);
+        ui.allocate_rect(rect, Sense::hover());
+
+        ui.painter()
+            .rect_filled(strip, Rounding::ZERO, style.tab_bar.bg_fill);
+        let px = ui.ctx().pixels_per_point().recip();
+        ui.painter().hline(
+            strip.x_range(),
+            strip.top(),
+            Stroke::new(px, style.tab_bar.hline_color),
+        );
+
+        let padding = ui.spacing().button_padding.x;
+        let mut cursor = strip.min.x;
+        let mut restored = None;
+        for &surface in windows {
+            let galley = self.window_title(surface, tab_viewer).into_galley(
+                ui,
+                Some(TextWrapMode::Extend),
+                f32::INFINITY,
+                TextStyle::Button,
+            );
+            let entry = Rect::from_x_y_ranges(
+                cursor..=cursor + galley.size().x + 2.0 * padding,
+                strip.y_range(),
+            );
+            cursor = entry.max.x + Style::AUTO_HIDE_ENTRY_SPACING;
+
+            let response = ui
+                .interact(
+                    entry,
+                    self.id.with((surface, "taskbar_entry")),
+                    Sense::click(),
+                )
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .on_hover_text(&self.dock_state.translations.window_taskbar.restore_tooltip);
+            let style = self.style.as_ref().unwrap();
+            let tab_style = if response.hovered() {
+                &style.tab.hovered
+            } else {
+                &style.tab.inactive
+            };
+            ui.painter()
+                .rect_filled(entry.shrink(px), tab_style.rounding, tab_style.bg_fill);
+            ui.painter().with_clip_rect(strip).galley(
+                entry.center() - galley.size() / 2.0,
+                galley,
+                tab_style.text_color,
+            );
+
+            if response.clicked() {
+                restored = Some(surface);
+            }
+        }
+
+        if let Some(surface) = restored {
+            let window_state = self.dock_state.get_window_state_mut(surface).unwrap();
+            let id = window_state.id();
+            window_state.bring_to_front();
+            state.taskbar.retain(|&other| other != id);
+        }
+        inner
+    }
+}
@@ -1,14 +1,40 @@
 use egui::{
-    vec2, Align, Color32, CursorIcon, Frame, Layout, Rect, Response, RichText, Rounding, Sense,
-    Shape, Stroke, Ui, UiBuilder, Vec2, WidgetText,
+    pos2, vec2, Align, Area, CentralPanel, Color32, CursorIcon, Frame, Id, Label, LayerId, Layout,
+    Order, Pos2, Rect, Response, RichText, Rounding, Sense, Shape, Stroke, Ui, UiBuilder, Vec2,
+    ViewportCommand, ViewportId, WidgetText,
 };
 
 use crate::{
-    dock_area::{state::State, tab_removal::TabRemoval},
-    utils::{fade_visuals, rect_set_size_centered},
-    DockArea, Node, NodeIndex, Style, SurfaceIndex, TabViewer,
+    dock_area::{
+        drag_and_drop::{draw_drop_rect, draw_window_rect},
+        state::State,
+        tab_removal::TabRemoval,
+    },
+    dock_state::window_state::WindowOrder,
+    utils::{fade_dock_style, fade_visuals, rect_set_size_centered},
+    DockArea, Node, NodeIndex, Split, Style, SurfaceId, SurfaceIndex, TabViewer,
 };
 
+/// Where a window dragged to an edge of the window bounds goes when it's released.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum WindowSnap {
+    /// Docked into the main surface on the given side.
+    Dock(Split),
+
+    /// Maximized to fill the window bounds.
+    Maximize,
+}
+
+/// What a window surface is shown in, unless it's shown natively.
+enum WindowChrome {
+    /// An [`egui::Window`] with a frame and resize handles.
+    Window(Box<egui::Window<'static>>),
+
+    /// A borderless [`Area`] of the given size, see
+    /// [`WindowState::set_borderless`](crate::WindowState::set_borderless).
+    Panel(Area, Vec2),
+}
+
 impl<Tab> DockArea<'_, Tab> {
     pub(super) fn show_window_surface(
         &mut self,
@@ -18,15 +44,185 @@ impl<Tab> DockArea<'_, Tab> {
         state: &mut State,
         fade_style: Option<(&Style, f32, SurfaceIndex)>,
     ) {
-        // Construct egui window
-        let id = format!("window {surf_index:?}").into();
-        let bounds = self.window_bounds.unwrap();
-        let open = true;
-        let window = self
+        // Use the window's own style in place of the global one while showing it.
+        let Some(window_style) =
+            tab_viewer.window_style_override(surf_index, self.style.as_ref().unwrap())
+        else {
+            self.show_window_surface_styled(ui, surf_index, tab_viewer, state, fade_style);
+            return;
+        };
+        let window_fade_style = fade_style.map(|(_, factor, surface_index)| {
+            let mut style = window_style.clone();
+            fade_dock_style(&mut style, factor);
+            (style, factor, surface_index)
+        });
+        let global_style = self.style.replace(window_style);
+        self.show_window_surface_styled(
+            ui,
+            surf_index,
+            tab_viewer,
+            state,
+            window_fade_style
+                .as_ref()
+                .map(|(style, factor, surface_index)| (style, *factor, *surface_index)),
+        );
+        self.style = global_style;
+    }
+
+    fn show_window_surface_styled(
+        &mut self,
+        ui: &Ui,
+        surf_index: SurfaceIndex,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        state: &mut State,
+        fade_style: Option<(&Style, f32, SurfaceIndex)>,
+    ) {
+        if self.native_windows && !ui.ctx().embed_viewports() {
+            self.show_native_window_surface(ui, surf_index, tab_viewer, state);
+        } else {
+            self.show_egui_window_surface(ui, surf_index, tab_viewer, state, fade_style);
+        }
+
+        // The window may have been closed while it was shown.
+        let focused = self.dock_state.focused_window() == Some(surf_index);
+        if let Some(window_state) = self.dock_state.get_window_state_mut(surf_index) {
+            window_state.set_focused(focused);
+        }
+    }
+
+    /// Id of the [`egui::Window`] showing the window surface with the given id, in the area with the given id.
+    ///
+    /// It's derived from the [`SurfaceId`] rather than the index of the window, so that a window added in place of a
+    /// removed one doesn't take over its position and size.
+    fn egui_window_id(area_id: Id, window_id: SurfaceId) -> Id {
+        area_id.with(("window", window_id))
+    }
+
+    /// Moves the [`egui::Window`] of a window surface in the stack of windows if it has been requested with
+    /// [`WindowState::bring_to_front`](crate::WindowState::bring_to_front) or
+    /// [`WindowState::send_to_back`](crate::WindowState::send_to_back), or if it's
+    /// [always on top](crate::WindowState::set_always_on_top).
+    fn reorder_egui_window(&mut self, ui: &Ui, surf_index: SurfaceIndex) {
+        let window_state = self.dock_state.get_window_state_mut(surf_index).unwrap();
+        let mut raised = Vec::new();
+        match window_state.next_order() {
+            Some(WindowOrder::Front) => raised.push(surf_index),
+            Some(WindowOrder::Back) => {
+                // egui can only move windows to the top, so move all the other ones above this one instead.
+                // They keep their order relative to each other.
+                for surface in self.dock_state.valid_surface_indices().iter().copied() {
+                    if surface != surf_index && self.dock_state.get_window_state(surface).is_some()
+                    {
+                        raised.push(surface);
+                    }
+                }
+            }
+            None => {}
+        }
+        // Focusing another window moves it above this one for at most a frame.
+        if self
+            .dock_state
+            .get_window_state(surf_index)
+            .unwrap()
+            .is_always_on_top()
+        {
+            raised.push(surf_index);
+        }
+        for surface in raised {
+            let window_id = self.dock_state.surface_id(surface).unwrap();
+            let layer = LayerId::new(Order::Middle, Self::egui_window_id(self.id, window_id));
+            ui.ctx().move_to_top(layer);
+            self.raised_layers.push(layer);
+        }
+    }
+
+    /// Shows a window surface in its own native viewport.
+    fn show_native_window_surface(
+        &mut self,
+        ui: &Ui,
+        surf_index: SurfaceIndex,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        state: &mut State,
+    ) {
+        let window_id = self.dock_state.surface_id(surf_index).unwrap();
+        let viewport_id = ViewportId::from_hash_of((self.id, window_id));
+        let title = self.window_title(surf_index, tab_viewer).text().to_owned();
+        if let Some(WindowOrder::Front) = self
             .dock_state
             .get_window_state_mut(surf_index)
             .unwrap()
-            .create_window(id, bounds);
+            .next_order()
+        {
+            ui.ctx()
+                .send_viewport_cmd_to(viewport_id, ViewportCommand::Focus);
+        }
+        let origin = ui
+            .ctx()
+            .input(|i| i.viewport().inner_rect)
+            .map_or(Pos2::ZERO, |rect| rect.min);
+        let builder = self
+            .dock_state
+            .get_window_state_mut(surf_index)
+            .unwrap()
+            .create_viewport(
+                viewport_id,
+                title,
+                origin,
+                tab_viewer
+                    .window_size_bounds(surf_index)
+                    .unwrap_or((Vec2::ZERO, Vec2::INFINITY)),
+            );
+
+        ui.ctx()
+            .show_viewport_immediate(viewport_id, builder, |ctx, _class| {
+                CentralPanel::default()
+                    .frame(Frame::central_panel(&ctx.style()).inner_margin(0.))
+                    .show(ctx, |ui| {
+                        self.render_nodes(ui, tab_viewer, state, surf_index, None);
+                    });
+
+                let (inner_rect, close_requested) =
+                    ctx.input(|i| (i.viewport().inner_rect, i.viewport().close_requested()));
+                if let Some(rect) = inner_rect {
+                    self.dock_state
+                        .get_window_state_mut(surf_index)
+                        .unwrap()
+                        .set_screen_rect(rect.translate(-origin.to_vec2()));
+                }
+                if close_requested {
+                    self.to_remove.push(TabRemoval::Window(surf_index));
+                }
+            });
+    }
+
+    /// Shows a window surface inside of an [`egui::Window`].
+    fn show_egui_window_surface(
+        &mut self,
+        ui: &Ui,
+        surf_index: SurfaceIndex,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        state: &mut State,
+        fade_style: Option<(&Style, f32, SurfaceIndex)>,
+    ) {
+        // Construct egui window
+        self.reorder_egui_window(ui, surf_index);
+        let id = Self::egui_window_id(self.id, self.dock_state.surface_id(surf_index).unwrap());
+        let bounds = self.window_bounds.unwrap();
+        let open = true;
+        let size_limits = tab_viewer
+            .window_size_bounds(surf_index)
+            .unwrap_or((Vec2::ZERO, Vec2::INFINITY));
+        let window_state = self.dock_state.get_window_state_mut(surf_index).unwrap();
+        let chrome = if window_state.is_borderless() {
+            let (area, size) = window_state.create_area(id, bounds, size_limits);
+            WindowChrome::Panel(area, size)
+        } else {
+            WindowChrome::Window(Box::new(window_state.create_window(
+                id,
+                bounds,
+                size_limits,
+            )))
+        };
 
         // Calculate fading of the window (if any)
         let (fade_factor, fade_style) = match fade_style {
@@ -41,24 +237,9 @@ impl<Tab> DockArea<'_, Tab> {
         };
 
         // Get galley of currently selected node as a window title
-        let title = {
-            let node_id = self.dock_state[surf_index]
-                .focused_leaf()
-                .unwrap_or_else(|| {
-                    for node_index in self.dock_state[surf_index].breadth_first_index_iter() {
-                        if self.dock_state[surf_index][node_index].is_leaf() {
-                            return node_index;
-                        }
-                    }
-                    unreachable!("a window surface should never be empty")
-                });
-            let Node::Leaf { tabs, active, .. } = &mut self.dock_state[surf_index][node_id] else {
-                unreachable!()
-            };
-            tab_viewer
-                .title(&mut tabs[active.0])
-                .color(ui.visuals().widgets.noninteractive.fg_stroke.color)
-        };
+        let title = self
+            .window_title(surf_index, tab_viewer)
+            .color(ui.visuals().widgets.noninteractive.fg_stroke.color);
 
         // Iterate through every node in dock_state[surf_index], and sum up the number of tabs in them
         let mut tab_count = 0;
@@ -69,40 +250,52 @@ impl<Tab> DockArea<'_, Tab> {
         }
 
         // Fade window frame (if necessary)
-        let mut frame = Frame::window(ui.style());
-        if fade_factor != 1.0 {
-            frame.fill = frame.fill.linear_multiply(fade_factor);
-            frame.stroke.color = frame.stroke.color.linear_multiply(fade_factor);
-            frame.shadow.color = frame.shadow.color.linear_multiply(fade_factor);
-        }
-
-        let tab_bar_height = self.style.as_ref().unwrap().tab_bar.height;
-        let minimized = self
+        let opacity = self
             .dock_state
             .get_window_state(surf_index)
             .unwrap()
-            .is_minimized();
-        if minimized {
-            let height = tab_bar_height;
-            window
-                .resizable([true, false])
-                .max_height(height)
-                .min_height(height)
+            .opacity();
+        let window_style = &self.style.as_ref().unwrap().window;
+        let default_frame = match chrome {
+            WindowChrome::Window(_) => Frame::window(ui.style())
+                .fill(window_style.fill)
+                .stroke(window_style.stroke)
+                .rounding(window_style.rounding)
+                .shadow(window_style.shadow),
+            WindowChrome::Panel(..) => Frame::none(),
+        };
+        let mut frame = tab_viewer
+            .window_frame_override(surf_index, &default_frame)
+            .unwrap_or(default_frame);
+        let frame_fade = fade_factor * opacity;
+        if frame_fade != 1.0 {
+            frame.fill = frame.fill.linear_multiply(frame_fade);
+            frame.stroke.color = frame.stroke.color.linear_multiply(frame_fade);
+            frame.shadow.color = frame.shadow.color.linear_multiply(frame_fade);
+        }
+
+        let tab_bar_height = self.style.as_ref().unwrap().tab_bar.height;
+        let window_state = self.dock_state.get_window_state(surf_index).unwrap();
+        let minimized = window_state.is_minimized();
+        let title_bar = (window_state.has_title_bar() || window_style.title_bars) && !minimized;
+        let collapsed_height = if minimized {
+            Some(tab_bar_height)
         } else if self.dock_state[surf_index].is_collapsed() {
-            let height = self.dock_state[surf_index].collapsed_leaf_count() as f32 * tab_bar_height;
-            window
-                .resizable([true, false])
-                .max_height(height)
-                .min_height(height)
+            let mut height =
+                self.dock_state[surf_index].collapsed_leaf_count() as f32 * tab_bar_height;
+            if title_bar {
+                height += tab_bar_height;
+            }
+            Some(height)
         } else {
-            window
-        }
-        .frame(frame)
-        .show(ui.ctx(), |ui| {
+            None
+        };
+        let add_contents = |ui: &mut Ui| {
             // Fade inner ui (if necessary)
             if fade_factor != 1.0 {
                 fade_visuals(ui.visuals_mut(), fade_factor);
             }
+            ui.multiply_opacity(opacity);
             if minimized {
                 self.minimized_body(
                     ui,
@@ -112,15 +305,203 @@ impl<Tab> DockArea<'_, Tab> {
                     tab_count,
                 )
             } else {
+                if title_bar {
+                    self.window_title_bar(
+                        ui,
+                        surf_index,
+                        tab_viewer,
+                        fade_style.map(|(style, _)| style),
+                        title,
+                    );
+                }
                 self.render_nodes(ui, tab_viewer, state, surf_index, fade_style);
             }
-        });
+        };
+        let response = match chrome {
+            WindowChrome::Window(window) => match collapsed_height {
+                Some(height) => (*window)
+                    .resizable([true, false])
+                    .max_height(height)
+                    .min_height(height),
+                None => *window,
+            }
+            .frame(frame)
+            .show(ui.ctx(), add_contents)
+            .map(|response| response.response),
+            WindowChrome::Panel(area, mut size) => {
+                if let Some(height) = collapsed_height {
+                    size.y = height;
+                }
+                let response = area.show(ui.ctx(), |ui| {
+                    frame.show(ui, |ui| {
+                        ui.set_min_size(size);
+                        ui.set_max_size(size);
+                        add_contents(ui);
+                    });
+                });
+                Some(response.response)
+            }
+        };
+
+        if let Some(response) = response {
+            self.surface_layers.push((surf_index, response.layer_id));
+            let to_taskbar = self.is_dropped_outside_bounds(ui, &response);
+            let window_state = self.dock_state.get_window_state_mut(surf_index).unwrap();
+            window_state.set_screen_rect(response.rect);
+            window_state.set_dragged(response.dragged());
+            if to_taskbar {
+                state.taskbar.push(window_state.id());
+            } else if self.window_snapping && !self.locked {
+                self.snap_window(ui, surf_index, &response);
+            }
+        }
 
         if !open {
             self.to_remove.push(TabRemoval::Window(surf_index));
         }
     }
 
+    /// Snaps a window dragged to an edge of the window bounds, showing where it would go while it's dragged.
+    fn snap_window(&mut self, ui: &Ui, surf_index: SurfaceIndex, response: &Response) {
+        let Some(pointer) = ui.ctx().pointer_interact_pos() else {
+            return;
+        };
+        let bounds = self.window_bounds.unwrap();
+        let window_state = self.dock_state.get_window_state_mut(surf_index).unwrap();
+
+        if response.drag_started() {
+            // Restore a maximized window, keeping the pointer over the same part of it.
+            if let Some(restore_rect) = window_state.restore_rect() {
+                let rect = response.rect;
+                let offset = (pointer - rect.min) / rect.size() * restore_rect.size();
+                window_state.restore().set_position(pointer - offset);
+            }
+            return;
+        }
+        if !response.dragged() && !response.drag_stopped() {
+            return;
+        }
+        // Windows dragged outside of the bounds go to the taskbar instead.
+        if self.window_taskbar && !bounds.contains(pointer) {
+            return;
+        }
+
+        let margin = Style::WINDOW_SNAP_MARGIN;
+        let snap = if pointer.x <= bounds.left() + margin {
+            WindowSnap::Dock(Split::Left)
+        } else if pointer.x >= bounds.right() - margin {
+            WindowSnap::Dock(Split::Right)
+        } else if pointer.y <= bounds.top() + margin {
+            WindowSnap::Maximize
+        } else {
+            return;
+        };
+
+        if response.drag_stopped() {
+            match snap {
+                WindowSnap::Dock(split) => self.to_dock.push((surf_index, split)),
+                WindowSnap::Maximize => {
+                    window_state.maximize(bounds);
+                }
+            }
+            return;
+        }
+
+        let style = self.style.as_ref().unwrap();
+        let main_rect = self
+            .dock_state
+            .main_surface()
+            .root_node()
+            .and_then(|node| node.rect())
+            .filter(|rect| rect.is_positive())
+            .unwrap_or(bounds);
+        let center = main_rect.center().x;
+        match snap {
+            WindowSnap::Dock(Split::Left) => draw_drop_rect(
+                Rect::from_min_max(main_rect.min, pos2(center, main_rect.max.y)),
+                ui,
+                style,
+            ),
+            WindowSnap::Dock(_) => draw_drop_rect(
+                Rect::from_min_max(pos2(center, main_rect.min.y), main_rect.max),
+                ui,
+                style,
+            ),
+            WindowSnap::Maximize => draw_window_rect(bounds, ui, style),
+        }
+    }
+
+    /// Shows the title bar of a window above its contents, see
+    /// [`WindowState::set_title_bar`](crate::WindowState::set_title_bar).
+    fn window_title_bar(
+        &mut self,
+        ui: &mut Ui,
+        surf_index: SurfaceIndex,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        fade_style: Option<&Style>,
+        title: WidgetText,
+    ) {
+        let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+        let (rect, _) = ui.allocate_exact_size(
+            vec2(ui.available_width(), style.tab_bar.height),
+            Sense::hover(),
+        );
+        ui.painter()
+            .rect_filled(rect, style.tab_bar.rounding, style.tab_bar.bg_fill);
+        ui.painter().hline(
+            rect.x_range(),
+            rect.bottom(),
+            Stroke::new(
+                ui.ctx().pixels_per_point().recip(),
+                style.tab_bar.hline_color,
+            ),
+        );
+
+        let margin = ui.spacing().item_spacing.x;
+        let ui = &mut ui.new_child(
+            UiBuilder::new()
+                .max_rect(rect.shrink2(vec2(margin, 0.0)))
+                .layout(Layout::right_to_left(Align::Center))
+                .id_salt((surf_index, "window_title_bar")),
+        );
+        tab_viewer.window_title_bar_ui(ui, surf_index);
+        // The title doesn't take clicks, so that the window can be dragged by it.
+        ui.with_layout(Layout::left_to_right(Align::Center), |ui| {
+            ui.add(
+                Label::new(title)
+                    .truncate()
+                    .selectable(false)
+                    .sense(Sense::hover()),
+            );
+        });
+    }
+
+    /// Returns the title of the window given by [`TabViewer::window_title`], or the title of the currently selected
+    /// tab of the window, or of the first leaf if none is focused.
+    pub(super) fn window_title(
+        &mut self,
+        surf_index: SurfaceIndex,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> WidgetText {
+        if let Some(title) = tab_viewer.window_title(surf_index) {
+            return title;
+        }
+        let node_id = self.dock_state[surf_index]
+            .focused_leaf()
+            .unwrap_or_else(|| {
+                for node_index in self.dock_state[surf_index].breadth_first_index_iter() {
+                    if self.dock_state[surf_index][node_index].is_leaf() {
+                        return node_index;
+                    }
+                }
+                unreachable!("a window surface should never be empty")
+            });
+        let Node::Leaf { tabs, active, .. } = &mut self.dock_state[surf_index][node_id] else {
+            unreachable!()
+        };
+        tab_viewer.title(&mut tabs[active.0])
+    }
+
     fn minimized_body(
         &mut self,
         ui: &mut Ui,
@@ -1,6 +1,6 @@
-use egui::{Context, Id, Pos2};
+use egui::{Context, Id, Pos2, Rect, Vec2};
 
-use crate::{Style, SurfaceIndex};
+use crate::{NodeIndex, Style, SurfaceId, SurfaceIndex, TabIndex};
 
 use super::drag_and_drop::{DragData, DragDropState, HoverData};
 
@@ -10,6 +10,27 @@ pub(super) struct State {
     pub last_hover_pos: Option<Pos2>,
     pub dnd: Option<DragDropState>,
     pub window_fade: Option<(f64, SurfaceIndex)>,
+
+    /// Size of the title of the tab being dragged in this area.
+    pub dragged_tab_size: Option<Vec2>,
+
+    /// Sizes of the gaps opened before tabs in front of which the dragged tab would be inserted.
+    pub tab_gaps: Vec<((SurfaceIndex, NodeIndex, TabIndex), f32)>,
+
+    /// Id of the active tab of the focused leaf at the end of the last frame.
+    pub focused_tab: Option<Id>,
+
+    /// The tab whose title a tab is dragged over, and the time since when it's been hovered.
+    pub tab_hover_since: Option<((SurfaceIndex, NodeIndex, TabIndex), f64)>,
+
+    /// The tab bar the dragged tab is glued to, see [`OverlayFeel::tab_bar_glue_distance`](crate::OverlayFeel::tab_bar_glue_distance).
+    pub glued_tab_bar: Option<Rect>,
+
+    /// Whether the dragged tab has left the tab bar it was glued to.
+    pub tab_torn_off: bool,
+
+    /// Ids of the windows minimized into the taskbar, in the order they were minimized.
+    pub taskbar: Vec<SurfaceId>,
 }
 
 impl State {
@@ -20,6 +41,13 @@ impl State {
             last_hover_pos: None,
             dnd: None,
             window_fade: None,
+            dragged_tab_size: None,
+            tab_gaps: Vec::new(),
+            focused_tab: None,
+            tab_hover_since: None,
+            glued_tab_bar: None,
+            tab_torn_off: false,
+            taskbar: Vec::new(),
         })
     }
 
@@ -32,6 +60,39 @@ impl State {
         self.dnd = None;
         self.window_fade = None;
         self.drag_start = None;
+        self.dragged_tab_size = None;
+        self.tab_gaps.clear();
+        self.tab_hover_since = None;
+        self.glued_tab_bar = None;
+        self.tab_torn_off = false;
+    }
+
+    /// Moves the size of the gap before a tab by `step` towards `target`, returning its new size.
+    pub(super) fn step_tab_gap(
+        &mut self,
+        tab: (SurfaceIndex, NodeIndex, TabIndex),
+        target: f32,
+        step: f32,
+    ) -> f32 {
+        let index = self
+            .tab_gaps
+            .iter()
+            .position(|&(gap_tab, _)| gap_tab == tab);
+        let current = index.map_or(0.0, |index| self.tab_gaps[index].1);
+        let size = if current < target {
+            (current + step).min(target)
+        } else {
+            (current - step).max(target)
+        };
+        match (index, size > 0.0) {
+            (Some(index), true) => self.tab_gaps[index].1 = size,
+            (Some(index), false) => {
+                self.tab_gaps.swap_remove(index);
+            }
+            (None, true) => self.tab_gaps.push((tab, size)),
+            (None, false) => {}
+        }
+        size
     }
 
     pub(super) fn set_drag_and_drop(
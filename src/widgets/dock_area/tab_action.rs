@@ -0,0 +1,33 @@
+/// What happens when a tab is clicked in a particular way, see
+/// [`DockArea::tab_middle_click_action`](crate::DockArea::tab_middle_click_action) and
+/// [`DockArea::tab_double_click_action`](crate::DockArea::tab_double_click_action).
+///
+/// # Examples
+///
+/// ```rust
+/// # use egui_dock::{DockArea, DockState, TabAction};
+/// # let mut dock_state = DockState::new(vec![("Notes", false)]);
+/// // Middle-clicking a tab pins it instead of closing it.
+/// let dock_area = DockArea::new(&mut dock_state)
+///     .tab_middle_click_action(TabAction::Custom(Box::new(|tab: &mut (&str, bool)| tab.1 = !tab.1)));
+/// ```
+pub enum TabAction<'tree, Tab> {
+    /// Nothing happens.
+    None,
+
+    /// The tab is closed, as if its close button was clicked.
+    ///
+    /// Tabs which can't be closed, e.g. because [`TabViewer::closeable`](crate::TabViewer::closeable) returns
+    /// `false`, stay open.
+    Close,
+
+    /// The tab is moved into a new window, as if it was ejected from its context menu.
+    Detach,
+
+    /// The window containing the tab is [maximized](crate::WindowState::maximize), or restored if it's already
+    /// maximized. Nothing happens to tabs outside of windows.
+    Maximize,
+
+    /// The given function is called with the tab.
+    Custom(Box<dyn FnMut(&mut Tab) + 'tree>),
+}
@@ -3,8 +3,15 @@ pub mod dock_area;
 
 pub(crate) mod popup;
 
+/// Separator for resizing the parts of a split area.
+pub mod separator;
+
 /// Trait for tab-viewing types.
 pub mod tab_viewer;
 
-pub use dock_area::{AllowedSplits, DockArea};
-pub use tab_viewer::TabViewer;
+pub use dock_area::{
+    AllowedDestinations, AllowedSplits, CloseActivationPolicy, DockArea, DockEvent, DockLayout,
+    DockResponse, GroupId, LayoutDirection, OverlayButtonKind, TabAction,
+};
+pub use separator::{Separator, SeparatorResponse, SplitOrientation};
+pub use tab_viewer::{MoveResponse, OnCloseResponse, TabViewer};
@@ -5,6 +5,29 @@ struct State {
     size: Vec2,
 }
 
+/// Where a popup is shown relative to the widget it belongs to, and how large it is.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct PopupLayout {
+    /// Width of the contents, or [`None`] to make them at least as wide as the widget.
+    pub width: Option<f32>,
+
+    /// Edge of the widget the same edge of the popup is lined up with.
+    pub align: Align,
+
+    /// Height above which the contents are scrolled.
+    pub max_height: Option<f32>,
+}
+
+impl Default for PopupLayout {
+    fn default() -> Self {
+        Self {
+            width: None,
+            align: Align::Min,
+            max_height: None,
+        }
+    }
+}
+
 // This code was taken from the example here: https://github.com/emilk/egui/pull/1653#issuecomment-1133671051.
 // It's needed because `egui`'s `popup_below_widget` currently doesn't respect window edges and will fall outside
 // of them if near the right or bottom edge. It should be replaced with `egui` functions when this is fixed.
@@ -12,10 +35,32 @@ struct State {
 // All credit goes to https://github.com/zicklag.
 
 /// Like `egui::popup_under_widget`, but pops up to the left, so that the popup doesn't go off the screen.
+///
+/// If `close_on_inner_click` is `false`, the popup stays open when it's clicked, e.g. to focus a text field in it.
 pub(crate) fn popup_under_widget<R>(
     ui: &Ui,
     popup_id: Id,
     widget_response: &Response,
+    close_on_inner_click: bool,
+    add_contents: impl FnOnce(&mut Ui) -> R,
+) -> Option<R> {
+    popup_under_widget_with_layout(
+        ui,
+        popup_id,
+        widget_response,
+        close_on_inner_click,
+        PopupLayout::default(),
+        add_contents,
+    )
+}
+
+/// Like [`popup_under_widget`], but lays the popup out as specified by `layout`.
+pub(crate) fn popup_under_widget_with_layout<R>(
+    ui: &Ui,
+    popup_id: Id,
+    widget_response: &Response,
+    close_on_inner_click: bool,
+    layout: PopupLayout,
     add_contents: impl FnOnce(&mut Ui) -> R,
 ) -> Option<R> {
     if !ui.memory(|mem| mem.is_popup_open(popup_id)) {
@@ -32,10 +77,13 @@ pub(crate) fn popup_under_widget<R>(
 
     let mut state = state.unwrap_or_default();
 
-    let rect = Rect {
-        min: widget_response.rect.left_bottom(),
-        max: widget_response.rect.left_bottom() + state.size,
+    let widget_rect = widget_response.rect;
+    let left = match layout.align {
+        Align::Min => widget_rect.left(),
+        Align::Center => widget_rect.center().x - state.size.x / 2.0,
+        Align::Max => widget_rect.right() - state.size.x,
     };
+    let rect = Rect::from_min_size(pos2(left, widget_rect.bottom()), state.size);
     let inner = Area::new(popup_id)
         .order(Order::Foreground)
         .fixed_pos(constrain_window_rect_to_area(ui.ctx(), rect, None).min)
@@ -48,8 +96,19 @@ pub(crate) fn popup_under_widget<R>(
             let result = frame
                 .show(ui, |ui| {
                     ui.with_layout(Layout::top_down_justified(Align::LEFT), |ui| {
-                        ui.set_width(widget_response.rect.width() - frame_margin.sum().x);
-                        add_contents(ui)
+                        match layout.width {
+                            Some(width) => ui.set_width(width),
+                            None => ui.set_width(widget_rect.width() - frame_margin.sum().x),
+                        }
+                        match layout.max_height {
+                            Some(max_height) => {
+                                ScrollArea::vertical()
+                                    .max_height(max_height)
+                                    .show(ui, add_contents)
+                                    .inner
+                            }
+                            None => add_contents(ui),
+                        }
                     })
                     .inner
                 })
@@ -63,7 +122,14 @@ pub(crate) fn popup_under_widget<R>(
 
     ui.data_mut(|d| *d.get_temp_mut_or_default(popup_id) = state);
 
-    if ui.input(|i| i.key_pressed(Key::Escape)) || widget_response.clicked_elsewhere() {
+    let clicked_inside = !close_on_inner_click
+        && ui
+            .input(|i| i.pointer.interact_pos())
+            .zip(ui.ctx().memory(|mem| mem.area_rect(popup_id)))
+            .is_some_and(|(pos, rect)| rect.contains(pos));
+    if ui.input(|i| i.key_pressed(Key::Escape))
+        || (widget_response.clicked_elsewhere() && !clicked_inside)
+    {
         ui.memory_mut(|mem| mem.close_popup());
     }
     Some(inner)
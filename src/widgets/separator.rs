@@ -0,0 +1,294 @@
+use egui::{
+    CursorIcon, EventFilter, Id, Key, Rect, Response, Rounding, Sense, Ui, Vec2, WidgetInfo,
+};
+
+use crate::{utils::map_to_pixel, SeparatorStyle};
+
+/// Orientation of the split made by a [`Separator`], named like the [`Node`](crate::Node)s it's used in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum SplitOrientation {
+    /// The area is split into a left and a right part, like in a [`Node::Horizontal`](crate::Node::Horizontal).
+    Horizontal,
+
+    /// The area is split into a top and a bottom part, like in a [`Node::Vertical`](crate::Node::Vertical).
+    Vertical,
+}
+
+impl SplitOrientation {
+    /// Index of the axis along which the area is split.
+    #[inline(always)]
+    fn axis(self) -> usize {
+        match self {
+            SplitOrientation::Horizontal => 0,
+            SplitOrientation::Vertical => 1,
+        }
+    }
+}
+
+/// The separator shown between nodes of a [`DockArea`](crate::DockArea), for resizing panels outside of it with the
+/// same look and feel.
+///
+/// It can be dragged with the pointer, moved with the arrow keys while holding Shift or Ctrl when it has keyboard
+/// focus, and reset to the middle of the area by double-clicking it.
+///
+/// # Examples
+///
+/// ```rust
+/// # use egui_dock::{Separator, SplitOrientation};
+/// # egui::__run_test_ui(|ui| {
+/// # let mut fraction = 0.3;
+/// let rect = ui.available_rect_before_wrap();
+/// let separator = Separator::new("sidebar_separator", &mut fraction, SplitOrientation::Horizontal)
+///     .min_sizes(100.0, 200.0)
+///     .show(ui, rect);
+/// let [sidebar, content] = separator.rects;
+/// # });
+/// ```
+#[derive(Debug)]
+#[must_use = "You should call .show()"]
+pub struct Separator<'a> {
+    id_salt: Id,
+    fraction: &'a mut f32,
+    orientation: SplitOrientation,
+    style: Option<SeparatorStyle>,
+    limits: [(f32, f32); 2],
+    interactive: bool,
+}
+
+/// What [`Separator::show`] returns.
+#[derive(Debug)]
+pub struct SeparatorResponse {
+    /// The response of the separator. It's marked as [changed](Response::changed) when the user has dragged,
+    /// double-clicked or moved the separator with the arrow keys.
+    pub response: Response,
+
+    /// The parts of the area on either side of the separator: left and right for
+    /// [`SplitOrientation::Horizontal`], top and bottom for [`SplitOrientation::Vertical`].
+    pub rects: [Rect; 2],
+}
+
+impl<'a> Separator<'a> {
+    /// Creates a separator which splits an area by moving the border between its two parts to `fraction` of its size.
+    pub fn new(
+        id_salt: impl std::hash::Hash,
+        fraction: &'a mut f32,
+        orientation: SplitOrientation,
+    ) -> Self {
+        Self {
+            id_salt: Id::new(id_salt),
+            fraction,
+            orientation,
+            style: None,
+            limits: [(0.0, f32::INFINITY); 2],
+            interactive: true,
+        }
+    }
+
+    /// Sets the look of the separator.
+    /// By default it's [`SeparatorStyle::from_egui`] called with the style of the [`Ui`] it's shown in.
+    pub fn style(mut self, style: SeparatorStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Sets the minimum sizes of the parts on either side of the separator, along the split axis.
+    /// They're never smaller than [`SeparatorStyle::min_panel_size`].
+    /// By default it's `0.0` for both.
+    pub fn min_sizes(mut self, first: f32, second: f32) -> Self {
+        self.limits[0].0 = first;
+        self.limits[1].0 = second;
+        self
+    }
+
+    /// Sets the maximum sizes of the parts on either side of the separator, along the split axis.
+    /// By default it's [`f32::INFINITY`] for both.
+    pub fn max_sizes(mut self, first: f32, second: f32) -> Self {
+        self.limits[0].1 = first;
+        self.limits[1].1 = second;
+        self
+    }
+
+    /// Whether the user can move the separator. A separator which isn't interactive is only drawn.
+    /// By default it's `true`.
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
+    /// Draws the separator inside `rect`, lets the user move it and keeps the fraction within the size limits.
+    pub fn show(self, ui: &mut Ui, rect: Rect) -> SeparatorResponse {
+        let Self {
+            id_salt,
+            fraction,
+            orientation,
+            style,
+            limits,
+            interactive,
+        } = self;
+        let style = style.unwrap_or_else(|| SeparatorStyle::from_egui(ui.style()));
+        let axis = orientation.axis();
+        let pixels_per_point = ui.ctx().pixels_per_point();
+        let sense = if interactive {
+            Sense::click_and_drag()
+        } else {
+            Sense::hover()
+        };
+
+        let mut separator = rect;
+        let midpoint = rect.min[axis] + rect.size()[axis] * *fraction;
+        separator.min[axis] = midpoint - style.width * 0.5;
+        separator.max[axis] = midpoint + style.width * 0.5;
+
+        let mut expand = Vec2::ZERO;
+        expand[axis] += style.extra_interact_width / 2.0;
+        let interact_rect = separator.expand2(expand);
+
+        let mut response = ui.interact(interact_rect, ui.make_persistent_id(id_salt), sense);
+        if interactive {
+            response = response.on_hover_and_drag_cursor(match orientation {
+                SplitOrientation::Horizontal => CursorIcon::ResizeHorizontal,
+                SplitOrientation::Vertical => CursorIcon::ResizeVertical,
+            });
+        }
+
+        let should_respond_to_arrow_keys = ui.input(|i| i.modifiers.command || i.modifiers.shift);
+
+        if response.has_focus() {
+            // Prevent the default behaviour of removing focus from the separators when the
+            // arrow keys are pressed
+            ui.memory_mut(|m| {
+                m.set_focus_lock_filter(
+                    response.id,
+                    EventFilter {
+                        horizontal_arrows: should_respond_to_arrow_keys,
+                        vertical_arrows: should_respond_to_arrow_keys,
+                        tab: false,
+                        escape: false,
+                    },
+                )
+            });
+        }
+
+        let arrow_key_offset = if response.has_focus() && should_respond_to_arrow_keys {
+            if ui.input(|i| i.key_pressed(Key::ArrowUp)) {
+                Some(egui::vec2(0., -16.))
+            } else if ui.input(|i| i.key_pressed(Key::ArrowDown)) {
+                Some(egui::vec2(0., 16.))
+            } else if ui.input(|i| i.key_pressed(Key::ArrowLeft)) {
+                Some(egui::vec2(-16., 0.))
+            } else if ui.input(|i| i.key_pressed(Key::ArrowRight)) {
+                Some(egui::vec2(16., 0.))
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        separator.min[axis] =
+            map_to_pixel(midpoint - style.width * 0.5, pixels_per_point, f32::round);
+        separator.max[axis] =
+            map_to_pixel(midpoint + style.width * 0.5, pixels_per_point, f32::round);
+
+        let color = if response.dragged() {
+            style.color_dragged
+        } else if (response.hovered() && sense.interactive()) || response.has_focus() {
+            style.color_hovered
+        } else {
+            style.color_idle
+        };
+
+        ui.painter().rect_filled(separator, Rounding::ZERO, color);
+
+        // Update 'fraction' interaction after drawing separator,
+        // otherwise it may overlap on other separator / bodies when
+        // shrunk fast.
+        if let Some(pos) = response
+            .interact_pointer_pos()
+            .or(arrow_key_offset.map(|v| separator.center() + v))
+        {
+            response.mark_changed();
+            let dim_point = pos[axis];
+            let delta = arrow_key_offset.unwrap_or(response.drag_delta())[axis];
+
+            if (delta > 0. && dim_point > midpoint && dim_point < rect.max[axis])
+                || (delta < 0. && dim_point < midpoint && dim_point > rect.min[axis])
+            {
+                *fraction += delta / rect.size()[axis];
+            }
+        }
+
+        if response.double_clicked() {
+            response.mark_changed();
+            *fraction = 0.5;
+        }
+
+        let [(first_min, first_max), (second_min, second_max)] = limits;
+        *fraction = constrain_fraction(
+            *fraction,
+            rect.size()[axis],
+            style.width,
+            (first_min.max(style.min_panel_size), first_max),
+            (second_min.max(style.min_panel_size), second_max),
+        );
+
+        // Screen readers see the separator as a splitter whose value is the fraction.
+        response.widget_info(|| WidgetInfo::slider(interactive, f64::from(*fraction), ""));
+        #[cfg(feature = "accesskit")]
+        ui.ctx().accesskit_node_builder(response.id, |node| {
+            use egui::accesskit;
+            node.set_role(accesskit::Role::Splitter);
+            // The separator of a horizontal split is a vertical line, and vice versa.
+            node.set_orientation(match orientation {
+                SplitOrientation::Horizontal => accesskit::Orientation::Vertical,
+                SplitOrientation::Vertical => accesskit::Orientation::Horizontal,
+            });
+            node.set_min_numeric_value(0.0);
+            node.set_max_numeric_value(1.0);
+        });
+
+        let midpoint = rect.min[axis] + rect.size()[axis] * *fraction;
+        let mut first = rect;
+        let mut second = rect;
+        first.max[axis] = map_to_pixel(midpoint - style.width * 0.5, pixels_per_point, f32::round);
+        second.min[axis] = map_to_pixel(midpoint + style.width * 0.5, pixels_per_point, f32::round);
+
+        SeparatorResponse {
+            response,
+            rects: [first, second],
+        }
+    }
+}
+
+/// Clamps the `fraction` of a parent node of the given `size` (along its split axis) so that its children stay within
+/// their `(min, max)` size limits. If the limits can't all be satisfied, the minimum sizes take precedence.
+pub(crate) fn constrain_fraction(
+    fraction: f32,
+    size: f32,
+    separator_width: f32,
+    (left_min, left_max): (f32, f32),
+    (right_min, right_max): (f32, f32),
+) -> f32 {
+    if size <= 0.0 {
+        return fraction;
+    }
+    let half_separator = separator_width * 0.5;
+    let lower = (left_min + half_separator).max(size - right_max - half_separator) / size;
+    let upper = (left_max + half_separator).min(size - right_min - half_separator) / size;
+    let fraction = if lower <= upper {
+        fraction.clamp(lower, upper)
+    } else {
+        let lower = (left_min + half_separator) / size;
+        let upper = (size - right_min - half_separator) / size;
+        if lower <= upper {
+            fraction.clamp(lower, upper)
+        } else if left_min + right_min > 0.0 {
+            // Not even the minimum sizes fit, so split the space proportionally to them.
+            left_min / (left_min + right_min)
+        } else {
+            0.5
+        }
+    };
+    fraction.clamp(0.0, 1.0)
+}
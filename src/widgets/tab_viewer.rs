@@ -1,5 +1,10 @@
-use crate::{NodeIndex, SurfaceIndex, TabStyle};
-use egui::{Id, Ui, WidgetText};
+use crate::{
+    AllowedDestinations, AllowedSplits, GroupId, NodeIndex, OverlayButtonKind, Split, Style,
+    SurfaceIndex, TabDestination, TabIndex, TabStyle,
+};
+use egui::{
+    vec2, Frame, Id, Painter, Rect, Rounding, Sense, Stroke, Ui, Vec2, Visuals, WidgetText,
+};
 
 /// Defines how a tab should behave and be rendered inside a [`Tree`](crate::Tree).
 pub trait TabViewer {
@@ -12,6 +17,44 @@ pub trait TabViewer {
     /// Actual tab content.
     fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab);
 
+    /// The smallest size at which the body of a given `_tab` is still usable, or [`None`] if it can be of any size.
+    ///
+    /// Separators can't be dragged in a way that would make the body of any tab in a leaf smaller than this, and the
+    /// surrounding nodes shrink first when the available space gets smaller, like with
+    /// [`Node::set_min_size`](crate::Node::set_min_size).
+    ///
+    /// By default [`None`] is always returned.
+    fn min_body_size(&mut self, _tab: &mut Self::Tab) -> Option<Vec2> {
+        None
+    }
+
+    /// Content shown in place of the tabs of a `_surface` which has none, i.e. an empty main surface or an empty
+    /// [central node](crate::Tree::set_central_node), e.g. a placeholder with buttons for opening new tabs.
+    ///
+    /// By default a faint outline of a dock is drawn in the middle of the `ui`.
+    fn empty_surface_ui(&mut self, ui: &mut Ui, _surface: SurfaceIndex) {
+        let rect = Rect::from_center_size(ui.max_rect().center(), vec2(48.0, 36.0));
+        ui.allocate_rect(ui.max_rect(), Sense::hover());
+        let stroke = Stroke::new(1.0, ui.visuals().weak_text_color());
+        let painter = ui.painter();
+        painter.rect_stroke(rect, Rounding::same(3.0), stroke);
+        let tab_bar = rect.top() + 8.0;
+        let split = rect.left() + rect.width() / 3.0;
+        painter.hline(rect.x_range(), tab_bar, stroke);
+        painter.vline(split, tab_bar..=rect.bottom(), stroke);
+    }
+
+    /// Content shown under the pointer while a `_tab` is being dragged out of its tab bar, in place of its title.
+    ///
+    /// The `_ui` starts at the pointer and is as big as the body of the leaf the tab is dragged from, so that a
+    /// thumbnail of the tab can be shown, e.g. by calling `ui.set_opacity(0.5)` followed by [`ui`](Self::ui).
+    ///
+    /// Returns `true` if a preview has been shown. By default `false` is always returned, in which case the title of
+    /// the tab follows the pointer.
+    fn drag_preview_ui(&mut self, _ui: &mut Ui, _tab: &mut Self::Tab) -> bool {
+        false
+    }
+
     /// Content inside the context menu shown when the tab is right-clicked.
     ///
     /// `_surface` and `_node` specify which [`Surface`](crate::Surface) and [`Node`](crate::Node)
@@ -27,6 +70,10 @@ pub trait TabViewer {
 
     /// Unique ID for this tab.
     ///
+    /// This is how tabs are identified in [`DockEvent`](crate::DockEvent)s and when they're looked up with
+    /// [`DockState::find_tab_by_id`](crate::DockState::find_tab_by_id), so it should stay the same for as long as the
+    /// tab exists.
+    ///
     /// If not implemented, uses tab title text as an ID source.
     fn id(&mut self, tab: &mut Self::Tab) -> Id {
         Id::new(self.title(tab).text())
@@ -35,6 +82,55 @@ pub trait TabViewer {
     /// Called after each tab button is shown, so you can add a tooltip, check for clicks, etc.
     fn on_tab_button(&mut self, _tab: &mut Self::Tab, _response: &egui::Response) {}
 
+    /// This is called when `_tab` becomes the active tab of the focused leaf, either because the user has clicked it
+    /// or its leaf, or because the focus has been moved with e.g.
+    /// [`DockState::set_focused_node_and_surface`](crate::DockState::set_focused_node_and_surface).
+    ///
+    /// Useful for resuming work that only matters while the user is looking at the tab. The call is made at the end
+    /// of [`DockArea::show`](crate::DockArea::show), after the tab's [`ui`](Self::ui), and also for the tab focused
+    /// when the dock area is shown for the first time.
+    fn on_tab_focused(&mut self, _tab: &mut Self::Tab) {}
+
+    /// This is called when `_tab` stops being the active tab of the focused leaf, e.g. because another tab or leaf
+    /// has been focused. Useful for pausing expensive rendering or background work.
+    ///
+    /// Isn't called for tabs which are closed while focused.
+    fn on_tab_unfocused(&mut self, _tab: &mut Self::Tab) {}
+
+    /// This is called right before the [`DockArea`](crate::DockArea) moves `_tab` from `_src` to `_dst`, after it's
+    /// been dropped or ejected into a new window.
+    ///
+    /// Returns whether the move goes ahead, see [`MoveResponse`]. Useful for e.g. making certain tabs always split
+    /// the leaf they're dropped onto instead of being added to it. By default [`MoveResponse::Allow`] is returned.
+    fn on_tab_move(
+        &mut self,
+        _tab: &mut Self::Tab,
+        _src: (SurfaceIndex, NodeIndex, TabIndex),
+        _dst: &TabDestination,
+    ) -> MoveResponse {
+        MoveResponse::Allow
+    }
+
+    /// This is called when the [`DockArea`](crate::DockArea) moves `_tab` into the window `_surface`, either by
+    /// ejecting it into a new window or by dropping it onto a window it wasn't in before.
+    ///
+    /// Useful for changing how the tab behaves while it's detached, e.g. rendering a viewport at the native resolution
+    /// of its window.
+    fn on_tab_detached(&mut self, _tab: &mut Self::Tab, _surface: SurfaceIndex) {}
+
+    /// This is called when the [`DockArea`](crate::DockArea) moves `_tab` out of a window and into the leaf `_node`
+    /// of `_surface`, which is the main surface or one hidden along its edge.
+    fn on_tab_docked(&mut self, _tab: &mut Self::Tab, _surface: SurfaceIndex, _node: NodeIndex) {}
+
+    /// Makes a copy of `_tab` when it's dropped while holding the
+    /// [`DockArea::tab_copy_modifiers`](crate::DockArea::tab_copy_modifiers), e.g. `Some(tab.clone())`.
+    ///
+    /// This requires that [`DockArea::allow_tab_copy`](crate::DockArea::allow_tab_copy) is set to `true`. Returns
+    /// [`None`] if the tab can't be copied, in which case it's moved instead, which is the default.
+    fn clone_tab(&mut self, _tab: &mut Self::Tab) -> Option<Self::Tab> {
+        None
+    }
+
     /// Returns `true` if the user of your app should be able to close a given `_tab`.
     ///
     /// By default `true` is always returned.
@@ -42,14 +138,45 @@ pub trait TabViewer {
         true
     }
 
+    /// Returns `true` if a close button should be shown on the title of a given `_tab`.
+    ///
+    /// Returning `false` only hides the button, the tab can still be closed with a middle click or from its context
+    /// menu as long as it's [`closeable`](Self::closeable).
+    ///
+    /// By default `true` is always returned.
+    fn show_close_button(&mut self, _tab: &mut Self::Tab) -> bool {
+        true
+    }
+
+    /// Returns `true` if the user of your app should be able to drag a given `_tab`, either to reorder it within its
+    /// tab bar or to move it elsewhere. Tabs which can't be dragged can't be ejected into new windows from their
+    /// context menu either.
+    ///
+    /// By default `true` is always returned.
+    fn draggable(&mut self, _tab: &mut Self::Tab) -> bool {
+        true
+    }
+
+    /// Returns `true` if a given `_tab` should always be kept at the start of its leaf, in front of the tabs for which
+    /// this returns `false`. Tabs dropped in front of it are moved behind it.
+    ///
+    /// Together with [`closeable`](Self::closeable) and [`draggable`](Self::draggable) returning `false`, this makes
+    /// a "home" tab, like the pinned start page of a web browser.
+    ///
+    /// By default `false` is always returned.
+    fn stays_first(&mut self, _tab: &mut Self::Tab) -> bool {
+        false
+    }
+
     /// This is called when the `_tab` gets closed by the user.
     ///
-    /// Returns `true` if the tab should close immediately, otherwise `false`.
+    /// Returns what should happen to the tab, see [`OnCloseResponse`].
+    /// By default [`OnCloseResponse::Close`] is returned.
     ///
-    /// **Note**: if `false` is returned, [`ui`](Self::ui) will still be called once more if this
+    /// **Note**: if the tab is closed, [`ui`](Self::ui) will still be called once more if this
     /// tab is active.
-    fn on_close(&mut self, _tab: &mut Self::Tab) -> bool {
-        true
+    fn on_close(&mut self, _tab: &mut Self::Tab) -> OnCloseResponse {
+        OnCloseResponse::Close
     }
 
     /// This is called when the add button is pressed.
@@ -58,11 +185,88 @@ pub trait TabViewer {
     /// [`Node`](crate::Node) this particular add button was pressed.
     fn on_add(&mut self, _surface: SurfaceIndex, _node: NodeIndex) {}
 
+    /// This is called when a split button of a leaf is clicked, see
+    /// [`DockArea::show_split_buttons`](crate::DockArea::show_split_buttons).
+    ///
+    /// Returns the tab put into the new leaf created on the `_split` side of the leaf `_node`, e.g. an empty
+    /// placeholder. If [`None`] is returned, the active tab of the leaf is copied into the new leaf with
+    /// [`clone_tab`](Self::clone_tab), or moved there if it can't be copied and the leaf has other tabs.
+    /// By default [`None`] is returned.
+    fn on_split(
+        &mut self,
+        _surface: SurfaceIndex,
+        _node: NodeIndex,
+        _split: Split,
+    ) -> Option<Self::Tab> {
+        None
+    }
+
     /// Content of the popup under the add button. Useful for selecting what type of tab to add.
     ///
+    /// Returns the size the popup should have, if its contents shouldn't decide it: the `x` is the width of the
+    /// contents, and the `y` is the height above which they're scrolled. It takes effect in the next frame, and
+    /// overrides [`ButtonsStyle::add_tab_popup_width`](crate::ButtonsStyle::add_tab_popup_width) and
+    /// [`ButtonsStyle::add_tab_popup_max_height`](crate::ButtonsStyle::add_tab_popup_max_height).
+    /// By default [`None`] is returned.
+    ///
     /// This requires that [`DockArea::show_add_buttons`](crate::DockArea::show_add_buttons) and
     /// [`DockArea::show_add_popup`](crate::DockArea::show_add_popup) are set to `true`.
-    fn add_popup(&mut self, _ui: &mut Ui, _surface: SurfaceIndex, _node: NodeIndex) {}
+    fn add_popup(
+        &mut self,
+        _ui: &mut Ui,
+        _surface: SurfaceIndex,
+        _node: NodeIndex,
+    ) -> Option<Vec2> {
+        None
+    }
+
+    /// This is called when the empty space of a tab bar, i.e. the part not taken by tabs or buttons, is
+    /// double-clicked. Useful for e.g. adding a new tab, like in web browsers.
+    ///
+    /// `_surface` and `_node` specify which [`Surface`](crate::Surface) and [`Node`](crate::Node)
+    /// this particular tab bar belongs to.
+    fn on_tab_bar_double_click(&mut self, _surface: SurfaceIndex, _node: NodeIndex) {}
+
+    /// Content shown at the end of the tab bar of each leaf, to the left of the close all tabs button,
+    /// e.g. a search box or a button acting on the whole group of tabs.
+    ///
+    /// The `_ui` uses a right-to-left layout (left-to-right if the [`DockArea`](crate::DockArea) uses
+    /// [`LayoutDirection::RightToLeft`](crate::LayoutDirection::RightToLeft)), and the space it takes is taken
+    /// away from the tabs.
+    ///
+    /// `_surface` and `_node` specify which [`Surface`](crate::Surface) and [`Node`](crate::Node)
+    /// this particular tab bar belongs to.
+    fn tab_bar_trailing_ui(&mut self, _ui: &mut Ui, _surface: SurfaceIndex, _node: NodeIndex) {}
+
+    /// Returns `true` if a given `_tab` has unsaved changes, in which case a dot is shown after its title.
+    ///
+    /// By default `false` is always returned.
+    fn is_modified(&mut self, _tab: &mut Self::Tab) -> bool {
+        false
+    }
+
+    /// Text of a badge shown for the `_tab` on the tab bar of its leaf while the leaf is collapsed, e.g. the number
+    /// of unread messages, so that it can be noticed without expanding the leaf.
+    ///
+    /// Collapsed leaves always show how many tabs they contain, next to the badges of their tabs.
+    /// By default `None` is always returned.
+    fn badge(&mut self, _tab: &mut Self::Tab) -> Option<String> {
+        None
+    }
+
+    /// Content shown inside the title of a tab, between its text and its close button, e.g. a dot marking unsaved
+    /// changes, a spinner or a badge count.
+    ///
+    /// The tab is made wide enough to fit it, and widgets added to `_ui` stay clickable without activating the tab.
+    /// On tab bars at the left or right of a leaf, `_ui` uses a top-down layout.
+    fn tab_title_trailing_ui(&mut self, _ui: &mut Ui, _tab: &mut Self::Tab) {}
+
+    /// Content of the tooltip shown while hovering over a tab, if enabled with
+    /// [`DockArea::show_tab_tooltips`](crate::DockArea::show_tab_tooltips), e.g. the path of a file, its status or a
+    /// thumbnail. By default it shows the title of the `tab`.
+    fn tab_tooltip_ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {
+        ui.label(self.title(tab));
+    }
 
     /// This is called every frame after [`ui`](Self::ui) is called, if the `_tab` is active.
     ///
@@ -78,6 +282,93 @@ pub trait TabViewer {
         None
     }
 
+    /// Returns the group a tab belongs to in its leaf, or [`None`] if it isn't in any group.
+    ///
+    /// Leaves containing grouped tabs show a row with a title for each group, see
+    /// [`group_title`](Self::group_title), above their tab bar, or below it if the tab bar is at the bottom. Only the
+    /// tabs in the group of the active tab are shown in the tab bar. Tabs which aren't in any group are put together
+    /// under [`LeafTranslations::ungrouped_tabs`](crate::LeafTranslations::ungrouped_tabs). Groups aren't shown next
+    /// to tab bars on the sides of leaves.
+    ///
+    /// Holding a dragged tab over the title of a group shows that group, so that the tab can be dropped into it,
+    /// see [`set_group`](Self::set_group). By default no tab is in a group.
+    fn group_of(&mut self, _tab: &mut Self::Tab) -> Option<GroupId> {
+        None
+    }
+
+    /// The title of a group of tabs shown in the group row of a leaf. By default it's the name of the group.
+    fn group_title(&mut self, group: &GroupId) -> WidgetText {
+        group.as_str().into()
+    }
+
+    /// Moves a tab into the given group, or out of any group if `_group` is [`None`].
+    ///
+    /// Called when a tab is dropped into a leaf showing groups, with the group which was shown, so that the tab ends
+    /// up in the group it was dropped into. Since groups are returned from [`group_of`](Self::group_of), they're
+    /// stored in the tabs and saved along with them. By default nothing happens.
+    fn set_group(&mut self, _tab: &mut Self::Tab, _group: Option<GroupId>) {}
+
+    /// Changes the [`Visuals`] used for the body of a tab before it's shown, e.g. to give a preview panel light
+    /// visuals while the rest of the application is dark, or to tint the widgets of a tab with an accent color.
+    ///
+    /// `_visuals` starts out as the visuals of the [`Ui`] the [`DockArea`](crate::DockArea) is shown in, and the
+    /// changes apply only to the tab's body, including its scroll bars. The background painted behind the body is
+    /// set with [`tab_style_override`](Self::tab_style_override). By default nothing is changed.
+    fn visuals_override(&mut self, _tab: &mut Self::Tab, _visuals: &mut Visuals) {}
+
+    /// Sets custom style for a window surface, e.g. to make floating tool palettes look different from the
+    /// main docked area.
+    ///
+    /// The returned style is used for everything shown inside the window, including its tab bars and separators.
+    fn window_style_override(
+        &self,
+        _surface: SurfaceIndex,
+        _global_style: &Style,
+    ) -> Option<Style> {
+        None
+    }
+
+    /// Sets the title of a window surface, shown in its title bar (see
+    /// [`WindowState::set_title_bar`](crate::WindowState::set_title_bar)), when it's minimized, and by the operating
+    /// system when it's shown natively.
+    ///
+    /// By default the title of the active tab of the focused leaf of the window is used.
+    fn window_title(&mut self, _surface: SurfaceIndex) -> Option<WidgetText> {
+        None
+    }
+
+    /// Adds widgets to the title bar of a window surface, e.g. buttons to pin the window or to open its settings,
+    /// if it has one (see [`WindowState::set_title_bar`](crate::WindowState::set_title_bar)).
+    ///
+    /// The `ui` lays widgets out from right to left, starting at the right edge of the title bar.
+    fn window_title_bar_ui(&mut self, _ui: &mut Ui, _surface: SurfaceIndex) {}
+
+    /// Sets a custom frame for a window surface in place of `default`, which is derived from the egui style.
+    ///
+    /// Windows shown natively (see [`DockArea::native_windows`](crate::DockArea::native_windows)) don't have a
+    /// frame.
+    fn window_frame_override(&self, _surface: SurfaceIndex, _default: &Frame) -> Option<Frame> {
+        None
+    }
+
+    /// Sets the smallest and the largest size a window surface can be resized to, e.g. so that tool palettes don't
+    /// get too small to use or bigger than their contents.
+    ///
+    /// These are combined with the sizes set with [`WindowState::set_min_size`](crate::WindowState::set_min_size)
+    /// and [`WindowState::set_max_size`](crate::WindowState::set_max_size), keeping the narrower range.
+    fn window_size_bounds(&mut self, _surface: SurfaceIndex) -> Option<(Vec2, Vec2)> {
+        None
+    }
+
+    /// Returns how much of the area of a leaf `_tab` takes when it's dropped onto the given `_split` of it, in range
+    /// `0.0..=1.0`, e.g. `Some(0.25)` for an inspector which should be a narrow column.
+    ///
+    /// Returns [`None`] to use [`OverlayFeel::drop_split_fraction`](crate::OverlayFeel::drop_split_fraction), which
+    /// is the default.
+    fn preferred_split_fraction(&mut self, _tab: &mut Self::Tab, _split: Split) -> Option<f32> {
+        None
+    }
+
     /// Specifies a tab's ability to be shown in a window.
     ///
     /// Returns `false` if this tab should never be turned into a window.
@@ -85,16 +376,99 @@ pub trait TabViewer {
         true
     }
 
+    /// Specifies where a tab can be dropped while it's being dragged, e.g. only into some leaves of the main
+    /// surface.
+    ///
+    /// This is combined with [`allowed_in_windows`](Self::allowed_in_windows) and
+    /// [`DockArea::allowed_splits`](crate::DockArea::allowed_splits). By default the tab can be dropped anywhere.
+    fn allowed_destinations(&mut self, _tab: &mut Self::Tab) -> AllowedDestinations {
+        AllowedDestinations::all()
+    }
+
+    /// Specifies which sides of the leaf at `_node` of `_surface` tabs can be dropped onto to split it, e.g. only
+    /// above and below a central viewport, and nowhere for side panels.
+    ///
+    /// This is combined with [`DockArea::allowed_splits`](crate::DockArea::allowed_splits) and the splits allowed
+    /// by [`allowed_destinations`](Self::allowed_destinations) for the dragged tab. By default all splits are allowed.
+    fn allowed_splits_for_node(
+        &mut self,
+        _surface: SurfaceIndex,
+        _node: NodeIndex,
+    ) -> AllowedSplits {
+        AllowedSplits::All
+    }
+
     /// Whether the tab body will be cleared with the color specified in
     /// [`TabBarStyle::bg_fill`](crate::TabBarStyle::bg_fill).
     fn clear_background(&self, _tab: &Self::Tab) -> bool {
         true
     }
 
+    /// Draws a custom icon of a drop target button shown on the overlay while dragging a tab, replacing the built-in
+    /// one. `_rect` is the area of the button and `_hovered` is `true` if releasing the tab now would drop it there.
+    ///
+    /// Returns `true` if the button has been drawn, or `false` to draw the built-in icon instead, which is the default.
+    ///
+    /// This is only used with [`OverlayType::Widgets`](crate::OverlayType::Widgets).
+    fn overlay_button_ui(
+        &mut self,
+        _painter: &Painter,
+        _rect: Rect,
+        _kind: OverlayButtonKind,
+        _hovered: bool,
+    ) -> bool {
+        false
+    }
+
     /// Returns `true` if the horizontal and vertical scroll bars will be shown for `tab`.
     ///
     /// By default, both scroll bars are shown.
     fn scroll_bars(&self, _tab: &Self::Tab) -> [bool; 2] {
         [true, true]
     }
+
+    /// Returns `true` if [`ui`](Self::ui) should still be called for `_tab` every frame while it isn't shown, because
+    /// another tab is active in its leaf or the leaf is collapsed, e.g. to keep a video playing.
+    ///
+    /// The contents of such a tab are laid out in the body of its leaf, but they're invisible and disabled.
+    /// By default, tabs which aren't shown are skipped.
+    fn keep_alive(&mut self, _tab: &mut Self::Tab) -> bool {
+        false
+    }
+}
+
+/// What happens to a tab the user has moved, returned from [`TabViewer::on_tab_move`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MoveResponse {
+    /// The tab is moved where the user has put it.
+    #[default]
+    Allow,
+
+    /// The tab stays where it was.
+    Cancel,
+
+    /// The tab is moved to the given destination instead. The tab stays where it was if the destination doesn't
+    /// exist.
+    Redirect(TabDestination),
+}
+
+/// What happens to a tab the user wants to close, returned from [`TabViewer::on_close`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum OnCloseResponse {
+    /// The tab is closed.
+    #[default]
+    Close,
+
+    /// The tab stays open and gets focused.
+    Focus,
+
+    /// The tab stays open and nothing else happens.
+    Ignore,
+
+    /// The tab stays open, marked as closing, until the application decides what to do with it by calling
+    /// [`DockState::confirm_close`](crate::DockState::confirm_close) or
+    /// [`DockState::cancel_close`](crate::DockState::cancel_close) with the tab's [`id`](TabViewer::id).
+    ///
+    /// Useful when closing needs to be confirmed by the user, e.g. in an "unsaved changes" dialog.
+    Pending,
 }
@@ -0,0 +1,45 @@
+use egui::{pos2, Ui, WidgetText};
+use egui_dock::{testing::DockHarness, DockState, Edge, NodeIndex, SurfaceIndex, TabViewer};
+
+const MAIN: SurfaceIndex = SurfaceIndex::main();
+
+struct Viewer;
+
+impl TabViewer for Viewer {
+    type Tab = &'static str;
+
+    fn title(&mut self, tab: &mut Self::Tab) -> WidgetText {
+        (*tab).into()
+    }
+
+    fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {
+        ui.label(*tab);
+    }
+}
+
+#[test]
+fn open_leaf_is_not_passed_on_to_reused_index() {
+    let mut dock_state = DockState::new(vec!["main"]);
+    let [_, explorer] =
+        dock_state
+            .main_surface_mut()
+            .split_left(NodeIndex::root(), 0.3, vec!["explorer"]);
+    let explorer = dock_state.set_auto_hide((MAIN, explorer), Edge::Left);
+    let mut harness = DockHarness::new(dock_state, Viewer);
+    harness.run();
+
+    // Open the leaf by clicking its entry in the strip along the left edge.
+    harness.click_at(pos2(12.0, 40.0));
+    assert_eq!(harness.metrics().open_auto_hidden, Some(explorer));
+
+    let dock_state = harness.dock_state_mut();
+    dock_state.dock_auto_hidden(explorer);
+    let [_, outline] =
+        dock_state
+            .main_surface_mut()
+            .split_right(NodeIndex::root(), 0.3, vec!["outline"]);
+    let outline = dock_state.set_auto_hide((MAIN, outline), Edge::Right);
+    assert_eq!(outline, explorer);
+    harness.run();
+    assert_eq!(harness.metrics().open_auto_hidden, None);
+}
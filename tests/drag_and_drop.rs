@@ -0,0 +1,238 @@
+use egui::{pos2, Context, Id, Key, Modifiers, Rect, Sense, Ui, Vec2, WidgetText};
+use egui_dock::{
+    testing::DockHarness, DockState, NodeIndex, OverlayButtonKind, Split, SurfaceIndex, TabIndex,
+    TabViewer,
+};
+
+struct Viewer;
+
+impl TabViewer for Viewer {
+    type Tab = &'static str;
+
+    fn title(&mut self, tab: &mut Self::Tab) -> WidgetText {
+        (*tab).into()
+    }
+
+    fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {
+        ui.label(*tab);
+    }
+
+    fn preferred_split_fraction(&mut self, tab: &mut Self::Tab, _split: Split) -> Option<f32> {
+        (*tab == "inspector").then_some(0.25)
+    }
+}
+
+const MAIN: SurfaceIndex = SurfaceIndex::main();
+
+fn harness(tabs: Vec<&'static str>) -> DockHarness<Viewer> {
+    let mut harness = DockHarness::new(DockState::new(tabs), Viewer);
+    harness.run();
+    harness
+}
+
+fn leaves(harness: &DockHarness<Viewer>) -> Vec<Vec<&'static str>> {
+    harness
+        .leaf_tabs(MAIN)
+        .into_iter()
+        .map(|tabs| tabs.into_iter().copied().collect())
+        .collect()
+}
+
+#[test]
+fn drop_on_split_buttons() {
+    for (split, expected) in [
+        (Split::Left, [["b"], ["a"]]),
+        (Split::Right, [["a"], ["b"]]),
+        (Split::Above, [["b"], ["a"]]),
+        (Split::Below, [["a"], ["b"]]),
+    ] {
+        let mut harness = harness(vec!["a", "b"]);
+        harness.drag_tab_to_overlay_button(
+            (MAIN, NodeIndex::root(), TabIndex(1)),
+            (MAIN, NodeIndex::root()),
+            OverlayButtonKind::Split(split),
+        );
+        assert_eq!(leaves(&harness), expected, "{split:?}");
+    }
+}
+
+#[test]
+fn drop_on_split_takes_preferred_fraction() {
+    for split in [Split::Left, Split::Right, Split::Above, Split::Below] {
+        let mut harness = harness(vec!["a", "inspector"]);
+        harness.drag_tab_to_overlay_button(
+            (MAIN, NodeIndex::root(), TabIndex(1)),
+            (MAIN, NodeIndex::root()),
+            OverlayButtonKind::Split(split),
+        );
+        let (_, node, _) = harness.dock_state().find_tab(&"inspector").unwrap();
+        assert_ne!(node, NodeIndex::root(), "{split:?}");
+
+        let root = harness.node_rect((MAIN, NodeIndex::root()));
+        let dropped = harness.node_rect((MAIN, node));
+        let fraction = if split.is_left_right() {
+            dropped.width() / root.width()
+        } else {
+            dropped.height() / root.height()
+        };
+        assert!((fraction - 0.25).abs() < 0.02, "{split:?}: {fraction}");
+    }
+}
+
+#[test]
+fn drop_with_explode_modifiers_makes_grid() {
+    let mut harness = DockHarness::new(DockState::new(vec!["a", "b", "c"]), Viewer)
+        .dock_area(|dock_area| dock_area.allow_leaf_explode(true));
+    harness
+        .dock_state_mut()
+        .main_surface_mut()
+        .split_right(NodeIndex::root(), 0.5, vec!["d"]);
+    harness.run();
+
+    harness.set_modifiers(Modifiers::ALT);
+    harness.drag_tab_to_overlay_button(
+        (MAIN, NodeIndex::root().right(), TabIndex(0)),
+        (MAIN, NodeIndex::root().left()),
+        OverlayButtonKind::Explode,
+    );
+    assert_eq!(leaves(&harness), [["a"], ["b"], ["c"], ["d"]]);
+    assert!(harness.dock_state().main_surface()[NodeIndex::root()].is_vertical());
+}
+
+#[test]
+fn drop_on_tab_bar_of_other_leaf() {
+    let mut harness = harness(vec!["a", "b"]);
+    harness
+        .dock_state_mut()
+        .main_surface_mut()
+        .split_right(NodeIndex::root(), 0.5, vec!["c"]);
+    harness.run();
+
+    let target = harness.tab_rect((MAIN, NodeIndex::root().right(), TabIndex(0)));
+    harness.drag_tab_to(
+        (MAIN, NodeIndex::root().left(), TabIndex(0)),
+        target.center(),
+    );
+    assert_eq!(leaves(&harness), [vec!["b"], vec!["a", "c"]]);
+}
+
+#[test]
+fn escape_cancels_drag() {
+    let mut harness = harness(vec!["a", "b"]);
+    let from = harness
+        .tab_rect((MAIN, NodeIndex::root(), TabIndex(1)))
+        .center();
+    let to = harness
+        .overlay_button_rect(
+            (MAIN, NodeIndex::root()),
+            OverlayButtonKind::Split(Split::Below),
+        )
+        .center();
+
+    harness.pointer_button(from, egui::PointerButton::Primary, true);
+    harness.step();
+    for step in 1..=30 {
+        harness.pointer_move(from.lerp(to, (step as f32 / 10.0).min(1.0)));
+        harness.step();
+    }
+    harness.press_key(Key::Escape);
+    harness.pointer_button(to, egui::PointerButton::Primary, false);
+    harness.run();
+
+    assert_eq!(leaves(&harness), [vec!["a", "b"]]);
+}
+
+#[test]
+fn close_button_removes_tab() {
+    let mut harness = harness(vec!["a", "b"]);
+    harness.close_tab((MAIN, NodeIndex::root(), TabIndex(0)));
+    assert_eq!(leaves(&harness), [vec!["b"]]);
+}
+
+#[test]
+fn separator_drag_resizes_split() {
+    let mut harness = harness(vec!["a"]);
+    harness
+        .dock_state_mut()
+        .main_surface_mut()
+        .split_right(NodeIndex::root(), 0.5, vec!["b"]);
+    harness.run();
+
+    let width = harness.node_rect((MAIN, NodeIndex::root())).width();
+    harness.drag_separator((MAIN, NodeIndex::root()), Vec2::new(width * 0.25, 0.0));
+    let left = harness.node_rect((MAIN, NodeIndex::root().left())).width();
+    assert!((left / width - 0.75).abs() < 0.02, "{}", left / width);
+}
+
+#[test]
+fn glued_tab_reorders_until_torn_off() {
+    let glued_harness = || {
+        let mut style = egui_dock::Style::from_egui(&egui::Style::default());
+        style.overlay.feel.tab_bar_glue_distance = Some(20.0);
+        let mut harness = DockHarness::new(DockState::new(vec!["a", "b", "c"]), Viewer)
+            .dock_area(move |dock_area| dock_area.style(style.clone()));
+        harness.run();
+        harness
+    };
+
+    // Pointers drifting off the tab bar, but not past the glue distance, still reorder the tab.
+    let mut harness = glued_harness();
+    let target = harness.tab_rect((MAIN, NodeIndex::root(), TabIndex(2)));
+    harness.drag_tab_to(
+        (MAIN, NodeIndex::root(), TabIndex(0)),
+        target.center_bottom() + Vec2::new(0.0, 10.0),
+    );
+    assert_eq!(leaves(&harness), [["b", "a", "c"]]);
+
+    // Past the glue distance the tab is torn off and can be dropped onto the overlay.
+    let mut harness = glued_harness();
+    harness.drag_tab_to_overlay_button(
+        (MAIN, NodeIndex::root(), TabIndex(0)),
+        (MAIN, NodeIndex::root()),
+        OverlayButtonKind::Split(Split::Below),
+    );
+    assert_eq!(leaves(&harness), [vec!["b", "c"], vec!["a"]]);
+}
+
+#[test]
+fn payload_taken_only_by_widgets_shown_before_area() {
+    use std::sync::{Arc, Mutex};
+
+    let target = Rect::from_min_size(pos2(650.0, 450.0), Vec2::splat(100.0));
+    for shown_before in [true, false] {
+        let dropped = Arc::new(Mutex::new(None));
+        let show_target = {
+            let dropped = dropped.clone();
+            move |ctx: &Context| {
+                egui::Area::new(Id::new("drop target"))
+                    .fixed_pos(target.min)
+                    .show(ctx, |ui| {
+                        let (_, response) = ui.allocate_exact_size(target.size(), Sense::hover());
+                        if let Some(tab) = response.dnd_release_payload::<&'static str>() {
+                            *dropped.lock().unwrap() = Some(*tab);
+                        }
+                    });
+            }
+        };
+        let harness = DockHarness::new(DockState::new(vec!["a", "b"]), Viewer)
+            .dock_area(|dock_area| dock_area.external_drag_payload(|tab: &&'static str| *tab));
+        let mut harness = if shown_before {
+            harness.show_before(show_target)
+        } else {
+            harness.show_after(show_target)
+        };
+        harness.run();
+
+        harness.drag_tab_to((MAIN, NodeIndex::root(), TabIndex(1)), target.center());
+
+        let dropped = *dropped.lock().unwrap();
+        if shown_before {
+            assert_eq!(dropped, Some("b"));
+            assert_eq!(leaves(&harness), [["a", "b"]]);
+        } else {
+            assert_eq!(dropped, None);
+            assert_eq!(leaves(&harness), [["a"]]);
+            assert_eq!(harness.dock_state().surfaces_count(), 2);
+        }
+    }
+}
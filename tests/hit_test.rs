@@ -0,0 +1,118 @@
+use egui::{pos2, vec2, Ui, WidgetText};
+use egui_dock::{
+    testing::DockHarness, DockElement, DockState, Edge, NodeIndex, SurfaceIndex, TabIndex,
+    TabViewer,
+};
+
+const MAIN: SurfaceIndex = SurfaceIndex::main();
+
+struct Viewer;
+
+impl TabViewer for Viewer {
+    type Tab = &'static str;
+
+    fn title(&mut self, tab: &mut Self::Tab) -> WidgetText {
+        (*tab).into()
+    }
+
+    fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {
+        ui.label(*tab);
+    }
+}
+
+/// Adds a window with `tab` covering the rect from `min` to `min + 300×300`.
+fn add_window(
+    dock_state: &mut DockState<&'static str>,
+    tab: &'static str,
+    min: egui::Pos2,
+) -> SurfaceIndex {
+    let window = dock_state.add_window(vec![tab]);
+    dock_state
+        .get_window_state_mut(window)
+        .unwrap()
+        .set_position(min)
+        .set_size(vec2(300.0, 300.0));
+    window
+}
+
+fn hit_surface(harness: &DockHarness<Viewer>, pos: egui::Pos2) -> Option<SurfaceIndex> {
+    harness.dock_state().hit_test(pos).map(|hit| hit.surface)
+}
+
+#[test]
+fn windows_are_hit_in_the_order_they_are_stacked() {
+    let mut dock_state = DockState::new(vec!["main"]);
+    let first = add_window(&mut dock_state, "first", pos2(100.0, 100.0));
+    let second = add_window(&mut dock_state, "second", pos2(200.0, 200.0));
+    let mut harness = DockHarness::new(dock_state, Viewer);
+    harness.run();
+
+    let overlap = pos2(350.0, 350.0);
+    assert_eq!(hit_surface(&harness, overlap), Some(second));
+
+    harness
+        .dock_state_mut()
+        .get_window_state_mut(first)
+        .unwrap()
+        .bring_to_front();
+    harness.run();
+    assert_eq!(hit_surface(&harness, overlap), Some(first));
+
+    harness
+        .dock_state_mut()
+        .get_window_state_mut(first)
+        .unwrap()
+        .send_to_back();
+    harness.run();
+    assert_eq!(hit_surface(&harness, overlap), Some(second));
+
+    // Clicking a window raises it.
+    harness.click_at(pos2(150.0, 150.0));
+    assert_eq!(hit_surface(&harness, overlap), Some(first));
+}
+
+#[test]
+fn open_auto_hidden_leaf_is_hit_first() {
+    let mut dock_state = DockState::new(vec!["main"]);
+    let [_, explorer] =
+        dock_state
+            .main_surface_mut()
+            .split_left(NodeIndex::root(), 0.3, vec!["explorer"]);
+    let auto_hidden = dock_state.set_auto_hide((MAIN, explorer), Edge::Left);
+    add_window(&mut dock_state, "window", pos2(50.0, 50.0));
+    let mut harness = DockHarness::new(dock_state, Viewer);
+    harness.run();
+
+    let pos = pos2(100.0, 300.0);
+    assert_ne!(hit_surface(&harness, pos), Some(auto_hidden));
+
+    // Open the leaf by clicking its entry in the strip along the left edge.
+    let strip_entry = pos2(12.0, 40.0);
+    harness.click_at(strip_entry);
+    assert_eq!(harness.metrics().open_auto_hidden, Some(auto_hidden));
+    assert_eq!(hit_surface(&harness, pos), Some(auto_hidden));
+}
+
+#[test]
+fn central_leaf_forgets_removed_tabs() {
+    let mut dock_state = DockState::new(vec!["viewport"]);
+    let [viewport, _] =
+        dock_state
+            .main_surface_mut()
+            .split_left(NodeIndex::root(), 0.2, vec!["inspector"]);
+    dock_state.main_surface_mut().set_central_node(viewport);
+    let mut harness = DockHarness::new(dock_state, Viewer);
+    harness.run();
+    let title = harness.tab_rect((MAIN, viewport, TabIndex(0))).center();
+
+    let tree = harness.dock_state_mut().main_surface_mut();
+    tree.remove_leaf(viewport);
+    tree[viewport].append_tab("scene");
+    let tree = harness.dock_state().main_surface();
+    assert_eq!(tree[viewport].tabs(), Some(&["scene"][..]));
+    assert_eq!(tree[viewport].tab_rect(TabIndex(0)), None);
+    assert_eq!(
+        harness.dock_state().hit_test(title).map(|hit| hit.element),
+        Some(DockElement::TabBar)
+    );
+}
@@ -0,0 +1,87 @@
+use egui::{Id, Ui, WidgetText};
+use egui_dock::{
+    testing::DockHarness, DockArea, DockState, NodeIndex, SurfaceIndex, TabIndex, TabViewer,
+};
+
+const MAIN: SurfaceIndex = SurfaceIndex::main();
+
+struct Inner;
+
+impl TabViewer for Inner {
+    type Tab = &'static str;
+
+    fn title(&mut self, tab: &mut Self::Tab) -> WidgetText {
+        (*tab).into()
+    }
+
+    fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {
+        ui.label(*tab);
+    }
+}
+
+/// Shows another dock area inside the "editor" tab.
+struct Outer {
+    inner: DockState<&'static str>,
+    inner_drag_group: Option<Id>,
+}
+
+impl TabViewer for Outer {
+    type Tab = &'static str;
+
+    fn title(&mut self, tab: &mut Self::Tab) -> WidgetText {
+        (*tab).into()
+    }
+
+    fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {
+        if *tab != "editor" {
+            return;
+        }
+        let mut dock_area = DockArea::new(&mut self.inner).id(Id::new("inner"));
+        if let Some(group) = self.inner_drag_group {
+            dock_area = dock_area.drag_group(group);
+        }
+        dock_area.show_inside(ui, &mut Inner);
+    }
+}
+
+fn harness(inner_drag_group: Option<Id>) -> DockHarness<Outer> {
+    let mut dock_state = DockState::new(vec!["a"]);
+    dock_state
+        .main_surface_mut()
+        .split_right(NodeIndex::root(), 0.5, vec!["editor"]);
+    let outer = Outer {
+        inner: DockState::new(vec!["x"]),
+        inner_drag_group,
+    };
+    let mut harness = DockHarness::new(dock_state, outer)
+        .dock_area(|dock_area| dock_area.id(Id::new("outer")).drag_group(Id::new("group")));
+    harness.run();
+    harness
+}
+
+fn drop_on_editor(harness: &mut DockHarness<Outer>) {
+    let target = harness
+        .node_rect((MAIN, NodeIndex::root().right()))
+        .center();
+    harness.drag_tab_to((MAIN, NodeIndex::root().left(), TabIndex(0)), target);
+}
+
+fn tabs(dock_state: &DockState<&'static str>) -> Vec<&'static str> {
+    dock_state.iter_all_tabs().map(|(_, tab)| *tab).collect()
+}
+
+#[test]
+fn drop_on_nested_area_of_same_drag_group() {
+    let mut harness = harness(Some(Id::new("group")));
+    drop_on_editor(&mut harness);
+    assert_eq!(tabs(harness.dock_state()), ["editor"]);
+    assert_eq!(tabs(&harness.tab_viewer().inner), ["x", "a"]);
+}
+
+#[test]
+fn drop_on_nested_area_of_other_drag_group() {
+    let mut harness = harness(None);
+    drop_on_editor(&mut harness);
+    assert_eq!(tabs(harness.dock_state()), ["editor", "a"]);
+    assert_eq!(tabs(&harness.tab_viewer().inner), ["x"]);
+}